@@ -0,0 +1,59 @@
+#ifndef __ursa__crypto__secp256k1__included__
+#define __ursa__crypto__secp256k1__included__
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+extern int32_t ursa_secp256k1_get_public_key_size(void);
+extern int32_t ursa_secp256k1_get_private_key_size(void);
+extern int32_t ursa_secp256k1_get_signature_size(void);
+extern int32_t ursa_secp256k1_get_recoverable_signature_size(void);
+
+extern int32_t ursa_secp256k1_keypair_new(const struct ByteBuffer* public_key,
+                                      const struct ByteBuffer* private_key,
+                                      const struct ExternError* err);
+
+extern int32_t ursa_secp256k1_keypair_from_seed(const struct ByteBuffer* const seed,
+                                            const struct ByteBuffer* public_key,
+                                            const struct ByteBuffer* private_key,
+                                            const struct ExternError* err);
+
+extern int32_t ursa_secp256k1_get_public_key(const struct ByteBuffer* const private_key,
+                                         const struct ByteBuffer* public_key,
+                                         const struct ExternError* err);
+
+extern int32_t ursa_secp256k1_sign(const struct ByteBuffer* const message,
+                               const struct ByteBuffer* const private_key,
+                               const struct ByteBuffer* signature,
+                               const struct ExternError* err);
+
+extern int32_t ursa_secp256k1_verify(const struct ByteBuffer* const message,
+                                 const struct ByteBuffer* const signature,
+                                 const struct ByteBuffer* const public_key,
+                                 const struct ExternError* err);
+
+extern int32_t ursa_secp256k1_sign_recoverable(const struct ByteBuffer* const message,
+                                               const struct ByteBuffer* const private_key,
+                                               const struct ByteBuffer* signature,
+                                               const struct ExternError* err);
+
+extern int32_t ursa_secp256k1_recover_public_key(const struct ByteBuffer* const message,
+                                                 const struct ByteBuffer* const signature,
+                                                 const struct ByteBuffer* public_key,
+                                                 const struct ExternError* err);
+
+extern int32_t ursa_secp256k1_sign_recoverable_keccak256(const struct ByteBuffer* const message,
+                                                         const struct ByteBuffer* const private_key,
+                                                         const struct ByteBuffer* signature,
+                                                         const struct ExternError* err);
+
+extern int32_t ursa_secp256k1_recover_public_key_keccak256(const struct ByteBuffer* const message,
+                                                           const struct ByteBuffer* const signature,
+                                                           const struct ByteBuffer* public_key,
+                                                           const struct ExternError* err);
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,105 @@
+//! Base64 with padding for PEM and the unpadded URL safe variant for JWK
+
+use {CryptoError, CryptoResult};
+
+const STANDARD: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const URL_SAFE: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+pub fn encode(data: &[u8]) -> String {
+    encode_with(data, STANDARD, true)
+}
+
+pub fn decode(encoded: &str) -> CryptoResult<Vec<u8>> {
+    let unpadded = encoded.trim_end_matches('=');
+    if encoded.len() != unpadded.len() + (4 - unpadded.len() % 4) % 4 {
+        return Err(CryptoError::ParseError(
+            "Invalid Base64 padding".to_string(),
+        ));
+    }
+    decode_with(unpadded, STANDARD)
+}
+
+pub fn encode_url(data: &[u8]) -> String {
+    encode_with(data, URL_SAFE, false)
+}
+
+pub fn decode_url(encoded: &str) -> CryptoResult<Vec<u8>> {
+    decode_with(encoded, URL_SAFE)
+}
+
+fn encode_with(data: &[u8], alphabet: &[u8], pad: bool) -> String {
+    let mut output = String::with_capacity(data.len() / 3 * 4 + 4);
+    for chunk in data.chunks(3) {
+        let mut block = [0u8; 3];
+        block[..chunk.len()].copy_from_slice(chunk);
+        let n = (block[0] as u32) << 16 | (block[1] as u32) << 8 | block[2] as u32;
+        for i in 0..=chunk.len() {
+            output.push(alphabet[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+        }
+        if pad {
+            for _ in chunk.len()..3 {
+                output.push('=');
+            }
+        }
+    }
+    output
+}
+
+fn decode_with(encoded: &str, alphabet: &[u8]) -> CryptoResult<Vec<u8>> {
+    let invalid = || CryptoError::ParseError("Invalid Base64 encoding".to_string());
+    if encoded.len() % 4 == 1 {
+        return Err(invalid());
+    }
+    let mut output = Vec::with_capacity(encoded.len() * 3 / 4);
+    for chunk in encoded.as_bytes().chunks(4) {
+        let mut n = 0u32;
+        for (i, c) in chunk.iter().enumerate() {
+            let value = alphabet.iter().position(|a| a == c).ok_or_else(invalid)? as u32;
+            n |= value << (18 - 6 * i);
+        }
+        let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        let len = chunk.len() - 1;
+        // The bits after the last byte must be zero for the encoding to be canonical
+        if len < 3 && bytes[len] != 0 {
+            return Err(invalid());
+        }
+        output.extend_from_slice(&bytes[..len]);
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_vectors() {
+        // RFC 4648 section 10
+        let vectors = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (data, encoded) in vectors.iter() {
+            assert_eq!(encode(data.as_bytes()), *encoded);
+            assert_eq!(decode(encoded).unwrap(), data.as_bytes());
+            let unpadded = encoded.trim_end_matches('=');
+            assert_eq!(encode_url(data.as_bytes()), unpadded);
+            assert_eq!(decode_url(unpadded).unwrap(), data.as_bytes());
+        }
+        assert_eq!(encode_url(&[0xfb, 0xff]), "-_8");
+        assert_eq!(encode(&[0xfb, 0xff]), "+/8=");
+
+        assert!(decode("Zg=").is_err());
+        assert!(decode("Zg").is_err());
+        assert!(decode("Zh==").is_err());
+        assert!(decode("Z===").is_err());
+        assert!(decode_url("Zg==").is_err());
+        assert!(decode_url("+/8").is_err());
+        assert!(decode_url("Zm9vY").is_err());
+    }
+}
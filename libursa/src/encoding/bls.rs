@@ -0,0 +1,241 @@
+//! BLS12-381 keys in the ZCash serialization format used by JWK and multicodec
+//!
+//! Compressed points are the x-coordinate with three flags in the most significant bits:
+//! compression, the point at infinity and whether y is the larger of y and -y.
+//! G2 coordinates are written as `c1 || c0`. Private keys are 32 byte big-endian scalars.
+
+use amcl_wrapper::constants::MODBYTES;
+use amcl_wrapper::field_elem::FieldElement;
+use amcl_wrapper::group_elem::GroupElement;
+use amcl_wrapper::group_elem_g1::G1;
+use amcl_wrapper::group_elem_g2::G2;
+use keys::{PrivateKey, PublicKey};
+use zeroize::Zeroize;
+
+use {CryptoError, CryptoResult};
+
+pub const G1_COMPRESSED_SIZE: usize = MODBYTES;
+pub const G2_COMPRESSED_SIZE: usize = 2 * MODBYTES;
+pub const SCALAR_SIZE: usize = 32;
+
+const COMPRESSION_FLAG: u8 = 0x80;
+const INFINITY_FLAG: u8 = 0x40;
+const SIGN_FLAG: u8 = 0x20;
+const FLAGS: u8 = COMPRESSION_FLAG | INFINITY_FLAG | SIGN_FLAG;
+
+/// (p - 1) / 2 for the BLS12-381 base field
+const HALF_MODULUS: [u8; MODBYTES] = [
+    0x0d, 0x00, 0x88, 0xf5, 0x1c, 0xbf, 0xf3, 0x4d, 0x25, 0x8d, 0xd3, 0xdb, 0x21, 0xa5, 0xd6, 0x6b,
+    0xb2, 0x3b, 0xa5, 0xc2, 0x79, 0xc2, 0x89, 0x5f, 0xb3, 0x98, 0x69, 0x50, 0x7b, 0x58, 0x7b, 0x12,
+    0x0f, 0x55, 0xff, 0xff, 0x58, 0xa9, 0xff, 0xff, 0xdc, 0xff, 0x7f, 0xff, 0xff, 0xff, 0xd5, 0x55,
+];
+
+// amcl writes uncompressed points as 0x04 || x || y with G2 coordinates as c0 || c1
+// and reads compressed points as 0x02 || x with the sign of y left to be fixed
+
+fn is_larger(y: &[u8]) -> bool {
+    y > &HALF_MODULUS[..]
+}
+
+fn is_larger_fp2(c0: &[u8], c1: &[u8]) -> bool {
+    if c1.iter().all(|b| *b == 0) {
+        is_larger(c0)
+    } else {
+        is_larger(c1)
+    }
+}
+
+pub fn g1_compress(pk: &[u8]) -> CryptoResult<Vec<u8>> {
+    let point = G1::from_bytes(pk)
+        .map_err(|_| CryptoError::ParseError("Invalid BLS12-381 G1 public key".to_string()))?;
+    check_not_identity(point.is_identity())?;
+    let bytes = point.to_bytes(false);
+    let mut output = bytes[1..=MODBYTES].to_vec();
+    output[0] |= COMPRESSION_FLAG;
+    if is_larger(&bytes[MODBYTES + 1..]) {
+        output[0] |= SIGN_FLAG;
+    }
+    Ok(output)
+}
+
+pub fn g1_decompress(data: &[u8]) -> CryptoResult<PublicKey> {
+    let sign = read_flags(data, G1_COMPRESSED_SIZE)?;
+    let mut bytes = vec![0x02];
+    bytes.extend_from_slice(data);
+    bytes[1] &= !FLAGS;
+    let mut point = G1::from_bytes(&bytes).map_err(|_| invalid_point())?;
+    check_not_identity(point.is_identity())?;
+    if is_larger(&point.to_bytes(false)[MODBYTES + 1..]) != sign {
+        point = -point;
+    }
+    Ok(PublicKey(point.to_bytes(false)))
+}
+
+pub fn g2_compress(pk: &[u8]) -> CryptoResult<Vec<u8>> {
+    let point = G2::from_bytes(pk)
+        .map_err(|_| CryptoError::ParseError("Invalid BLS12-381 G2 public key".to_string()))?;
+    check_not_identity(point.is_identity())?;
+    let bytes = point.to_bytes(false);
+    let (x, y) = bytes[1..].split_at(2 * MODBYTES);
+    let mut output = x[MODBYTES..].to_vec();
+    output.extend_from_slice(&x[..MODBYTES]);
+    output[0] |= COMPRESSION_FLAG;
+    if is_larger_fp2(&y[..MODBYTES], &y[MODBYTES..]) {
+        output[0] |= SIGN_FLAG;
+    }
+    Ok(output)
+}
+
+pub fn g2_decompress(data: &[u8]) -> CryptoResult<PublicKey> {
+    let sign = read_flags(data, G2_COMPRESSED_SIZE)?;
+    let mut bytes = vec![0x02];
+    bytes.extend_from_slice(&data[MODBYTES..]);
+    bytes.extend_from_slice(&data[..MODBYTES]);
+    bytes[MODBYTES + 1] &= !FLAGS;
+    let mut point = G2::from_bytes(&bytes).map_err(|_| invalid_point())?;
+    check_not_identity(point.is_identity())?;
+    let uncompressed = point.to_bytes(false);
+    let y = &uncompressed[2 * MODBYTES + 1..];
+    if is_larger_fp2(&y[..MODBYTES], &y[MODBYTES..]) != sign {
+        point = -point;
+    }
+    Ok(PublicKey(point.to_bytes(false)))
+}
+
+pub fn g1_public_key(sk: &PrivateKey) -> CryptoResult<PublicKey> {
+    Ok(PublicKey(
+        (&G1::generator() * &parse_private_key(sk)?).to_bytes(false),
+    ))
+}
+
+pub fn g2_public_key(sk: &PrivateKey) -> CryptoResult<PublicKey> {
+    Ok(PublicKey(
+        (&G2::generator() * &parse_private_key(sk)?).to_bytes(false),
+    ))
+}
+
+/// The 32 byte scalar of a private key of `signatures::bls`
+pub fn private_key_to_scalar(sk: &PrivateKey) -> CryptoResult<Vec<u8>> {
+    let mut bytes = parse_private_key(sk)?.to_bytes();
+    let scalar = bytes[MODBYTES - SCALAR_SIZE..].to_vec();
+    bytes.zeroize();
+    Ok(scalar)
+}
+
+pub fn private_key_from_scalar(scalar: &[u8]) -> CryptoResult<PrivateKey> {
+    if scalar.len() != SCALAR_SIZE {
+        return Err(CryptoError::ParseError(
+            "Invalid BLS12-381 private key length".to_string(),
+        ));
+    }
+    let mut bytes = vec![0u8; MODBYTES - SCALAR_SIZE];
+    bytes.extend_from_slice(scalar);
+    let sk = PrivateKey(bytes);
+    // Values at or above the group order would be reduced so they do not round trip
+    let valid = parse_private_key(&sk)?.to_bytes() == sk.0;
+    if !valid {
+        return Err(CryptoError::ParseError(
+            "Invalid BLS12-381 private key".to_string(),
+        ));
+    }
+    Ok(sk)
+}
+
+fn parse_private_key(sk: &PrivateKey) -> CryptoResult<FieldElement> {
+    let value = FieldElement::from_bytes(&sk[..])
+        .map_err(|_| CryptoError::ParseError("Invalid BLS12-381 private key".to_string()))?;
+    if value.is_zero() {
+        return Err(CryptoError::ParseError(
+            "Invalid BLS12-381 private key".to_string(),
+        ));
+    }
+    Ok(value)
+}
+
+/// Check the flags of a compressed point and return the sign flag
+fn read_flags(data: &[u8], size: usize) -> CryptoResult<bool> {
+    if data.len() != size {
+        return Err(CryptoError::ParseError(
+            "Invalid BLS12-381 public key length".to_string(),
+        ));
+    }
+    if data[0] & COMPRESSION_FLAG == 0 {
+        return Err(CryptoError::ParseError(
+            "BLS12-381 public key is not compressed".to_string(),
+        ));
+    }
+    check_not_identity(data[0] & INFINITY_FLAG != 0)?;
+    Ok(data[0] & SIGN_FLAG != 0)
+}
+
+fn check_not_identity(is_identity: bool) -> CryptoResult<()> {
+    if is_identity {
+        Err(CryptoError::ParseError(
+            "BLS12-381 public key cannot be the point at infinity".to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn invalid_point() -> CryptoError {
+    CryptoError::ParseError("BLS12-381 public key is not on the curve".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The generators in the ZCash format
+    const G1_GENERATOR: &str = "97f1d3a73197d7942695638c4fa9ac0fc3688c4f9774b905a14e3a3f171bac586c55e83ff97a1aeffb3af00adb22c6bb";
+    const G2_GENERATOR: &str = "93e02b6052719f607dacd3a088274f65596bd0d09920b61ab5da61bbdc7f5049334cf11213945d57e5ac7d055d042b7e024aa2b2f08f0a91260805272dc51051c6e47ad4fa403b02b4510b647ae3d1770bac0326a805bbefd48056c8c121bdb8";
+
+    #[test]
+    fn bls_point_compression() {
+        let g1 = G1::generator().to_bytes(false);
+        assert_eq!(hex::encode(g1_compress(&g1).unwrap()), G1_GENERATOR);
+        assert_eq!(
+            g1_decompress(&hex::decode(G1_GENERATOR).unwrap())
+                .unwrap()
+                .0,
+            g1
+        );
+        let g2 = G2::generator().to_bytes(false);
+        assert_eq!(hex::encode(g2_compress(&g2).unwrap()), G2_GENERATOR);
+        assert_eq!(
+            g2_decompress(&hex::decode(G2_GENERATOR).unwrap())
+                .unwrap()
+                .0,
+            g2
+        );
+
+        // Both signs of y
+        for _ in 0..8 {
+            let g1 = G1::random().to_bytes(false);
+            assert_eq!(g1_decompress(&g1_compress(&g1).unwrap()).unwrap().0, g1);
+            let g2 = G2::random().to_bytes(false);
+            assert_eq!(g2_decompress(&g2_compress(&g2).unwrap()).unwrap().0, g2);
+        }
+
+        let mut infinity = vec![0u8; G1_COMPRESSED_SIZE];
+        infinity[0] = COMPRESSION_FLAG | INFINITY_FLAG;
+        assert!(g1_decompress(&infinity).is_err());
+        assert!(g1_compress(&G1::identity().to_bytes(false)).is_err());
+        let mut uncompressed = hex::decode(G1_GENERATOR).unwrap();
+        uncompressed[0] &= !COMPRESSION_FLAG;
+        assert!(g1_decompress(&uncompressed).is_err());
+        assert!(g2_decompress(&hex::decode(G1_GENERATOR).unwrap()).is_err());
+    }
+
+    #[test]
+    fn bls_private_key_scalar() {
+        let sk = PrivateKey(FieldElement::random().to_bytes());
+        let scalar = private_key_to_scalar(&sk).unwrap();
+        assert_eq!(scalar.len(), SCALAR_SIZE);
+        assert_eq!(private_key_from_scalar(&scalar).unwrap(), sk);
+
+        assert!(private_key_from_scalar(&[0u8; SCALAR_SIZE]).is_err());
+        assert!(private_key_from_scalar(&[0xffu8; SCALAR_SIZE]).is_err());
+        assert!(private_key_from_scalar(&[1u8; 31]).is_err());
+    }
+}
@@ -0,0 +1,160 @@
+//! The subset of ASN.1 DER needed for PKCS#8 and SubjectPublicKeyInfo
+
+use {CryptoError, CryptoResult};
+
+pub const INTEGER: u8 = 0x02;
+pub const BIT_STRING: u8 = 0x03;
+pub const OCTET_STRING: u8 = 0x04;
+pub const OBJECT_IDENTIFIER: u8 = 0x06;
+pub const SEQUENCE: u8 = 0x30;
+/// `[0]` constructed, used for PKCS#8 attributes and ECPrivateKey parameters
+pub const CONTEXT_0: u8 = 0xa0;
+/// `[1]` primitive, used for the PKCS#8 v2 public key
+pub const CONTEXT_1: u8 = 0x81;
+/// `[1]` constructed, used for the ECPrivateKey public key
+pub const CONTEXT_1_CONSTRUCTED: u8 = 0xa1;
+
+/// Encode one tag-length-value
+pub fn encode(tag: u8, contents: &[u8]) -> Vec<u8> {
+    let mut output = vec![tag];
+    if contents.len() < 0x80 {
+        output.push(contents.len() as u8);
+    } else {
+        let length = (contents.len() as u32).to_be_bytes();
+        let skip = length.iter().take_while(|b| **b == 0).count();
+        output.push(0x80 | (length.len() - skip) as u8);
+        output.extend_from_slice(&length[skip..]);
+    }
+    output.extend_from_slice(contents);
+    output
+}
+
+pub fn sequence(elements: &[&[u8]]) -> Vec<u8> {
+    encode(SEQUENCE, &elements.concat())
+}
+
+/// A BIT STRING with no unused bits
+pub fn bit_string(contents: &[u8]) -> Vec<u8> {
+    let mut bits = vec![0u8];
+    bits.extend_from_slice(contents);
+    encode(BIT_STRING, &bits)
+}
+
+/// Reads the elements of DER encoded data in order
+pub struct Reader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(data: &'a [u8]) -> Self {
+        Reader { data }
+    }
+
+    /// Read the next element which must have `tag` and return its contents
+    pub fn read(&mut self, tag: u8) -> CryptoResult<&'a [u8]> {
+        self.read_optional(tag)?
+            .ok_or_else(|| malformed(&format!("expected tag 0x{:02x}", tag)))
+    }
+
+    /// Read the next element only if it has `tag`
+    pub fn read_optional(&mut self, tag: u8) -> CryptoResult<Option<&'a [u8]>> {
+        if self.data.first() != Some(&tag) {
+            return Ok(None);
+        }
+        let first = *self
+            .data
+            .get(1)
+            .ok_or_else(|| malformed("missing length"))?;
+        let (length, offset) = if first < 0x80 {
+            (first as usize, 2)
+        } else {
+            let count = (first & 0x7f) as usize;
+            if count == 0 || count > 4 || self.data.len() < 2 + count {
+                return Err(malformed("invalid length"));
+            }
+            let length = self.data[2..2 + count]
+                .iter()
+                .fold(0usize, |l, b| (l << 8) | *b as usize);
+            // DER requires the shortest length encoding
+            if length < 0x80 || self.data[2] == 0 {
+                return Err(malformed("non-minimal length"));
+            }
+            (length, 2 + count)
+        };
+        if self.data.len() - offset < length {
+            return Err(malformed("truncated element"));
+        }
+        let contents = &self.data[offset..offset + length];
+        self.data = &self.data[offset + length..];
+        Ok(Some(contents))
+    }
+
+    /// Read a BIT STRING which must have no unused bits
+    pub fn read_bit_string(&mut self) -> CryptoResult<&'a [u8]> {
+        bit_string_contents(self.read(BIT_STRING)?)
+    }
+
+    /// Read a small non-negative INTEGER
+    pub fn read_version(&mut self) -> CryptoResult<u8> {
+        match self.read(INTEGER)? {
+            [version] if *version < 0x80 => Ok(*version),
+            _ => Err(malformed("unsupported version")),
+        }
+    }
+
+    /// Fail if there is unread data
+    pub fn finish(&self) -> CryptoResult<()> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(malformed("unexpected trailing data"))
+        }
+    }
+}
+
+pub fn bit_string_contents(contents: &[u8]) -> CryptoResult<&[u8]> {
+    match contents.split_first() {
+        Some((0, bits)) => Ok(bits),
+        _ => Err(malformed("invalid bit string")),
+    }
+}
+
+fn malformed(reason: &str) -> CryptoError {
+    CryptoError::ParseError(format!("Malformed DER: {}", reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn der_round_trip() {
+        let short = encode(OCTET_STRING, &[1u8; 3]);
+        assert_eq!(short, vec![0x04, 0x03, 1, 1, 1]);
+        let long = encode(OCTET_STRING, &[2u8; 200]);
+        assert_eq!(&long[..3], &[0x04, 0x81, 200]);
+        let longer = encode(OCTET_STRING, &[3u8; 300]);
+        assert_eq!(&longer[..4], &[0x04, 0x82, 0x01, 0x2c]);
+
+        let data = sequence(&[&short, &long, &longer, &bit_string(&[4u8; 2])]);
+        let mut outer = Reader::new(&data);
+        let mut reader = Reader::new(outer.read(SEQUENCE).unwrap());
+        outer.finish().unwrap();
+        assert_eq!(reader.read(OCTET_STRING).unwrap(), &[1u8; 3]);
+        assert_eq!(reader.read_optional(INTEGER).unwrap(), None);
+        assert_eq!(reader.read(OCTET_STRING).unwrap(), &[2u8; 200][..]);
+        assert_eq!(reader.read(OCTET_STRING).unwrap(), &[3u8; 300][..]);
+        assert!(reader.finish().is_err());
+        assert_eq!(reader.read_bit_string().unwrap(), &[4u8; 2]);
+        reader.finish().unwrap();
+
+        assert!(Reader::new(&[0x04, 0x81, 0x05, 1, 2, 3, 4, 5])
+            .read(OCTET_STRING)
+            .is_err());
+        assert!(Reader::new(&[0x04, 0x05, 1, 2]).read(OCTET_STRING).is_err());
+        assert!(Reader::new(&[0x04, 0x80]).read(OCTET_STRING).is_err());
+        assert!(Reader::new(&[0x03, 0x02, 0x01, 0xff])
+            .read_bit_string()
+            .is_err());
+    }
+}
@@ -0,0 +1,358 @@
+//! JSON Web Keys
+//!
+//! Ed25519 and X25519 keys use the `OKP` key type of RFC 8037 and secp256k1 keys the
+//! `EC` key type with the `secp256k1` curve of RFC 8812. BLS12-381 keys use the `OKP`
+//! key type with the `Bls12381G1` and `Bls12381G2` curves where `x` is the compressed
+//! point and `d` the 32 byte scalar.
+
+use super::{
+    base64, bls, ed25519_private_key, ed25519_public_key, ed25519_seed,
+    public_key_from_private_key, secp256k1_private_key, secp256k1_public_key, x25519_public_key,
+    x25519_secret, KeyType,
+};
+use keys::{PrivateKey, PublicKey};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use zeroize::Zeroize;
+
+use {CryptoError, CryptoResult};
+
+const OCTET_KEY_PAIR: &str = "OKP";
+const ELLIPTIC_CURVE: &str = "EC";
+const CRV_ED25519: &str = "Ed25519";
+const CRV_X25519: &str = "X25519";
+const CRV_SECP256K1: &str = "secp256k1";
+const CRV_BLS12381_G1: &str = "Bls12381G1";
+const CRV_BLS12381_G2: &str = "Bls12381G2";
+const SECP256K1_COORDINATE_SIZE: usize = 32;
+
+/// A public or private key as a JWK. Members other than these are ignored when parsing.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    pub crv: String,
+    pub x: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub d: Option<String>,
+}
+
+impl Jwk {
+    pub fn from_public_key(key_type: KeyType, pk: &PublicKey) -> CryptoResult<Self> {
+        let (kty, crv) = match key_type {
+            KeyType::Ed25519 => (OCTET_KEY_PAIR, CRV_ED25519),
+            KeyType::X25519 => (OCTET_KEY_PAIR, CRV_X25519),
+            KeyType::Secp256k1 => (ELLIPTIC_CURVE, CRV_SECP256K1),
+            KeyType::Bls12381G1 => (OCTET_KEY_PAIR, CRV_BLS12381_G1),
+            KeyType::Bls12381G2 => (OCTET_KEY_PAIR, CRV_BLS12381_G2),
+        };
+        let (x, y) = match key_type {
+            KeyType::Ed25519 => (ed25519_public_key(&pk[..])?[..].to_vec(), None),
+            KeyType::X25519 => (x25519_public_key(&pk[..])?[..].to_vec(), None),
+            KeyType::Secp256k1 => {
+                let point = secp256k1_public_key(&pk[..])?.serialize();
+                let (x, y) = point[1..].split_at(SECP256K1_COORDINATE_SIZE);
+                (x.to_vec(), Some(base64::encode_url(y)))
+            }
+            KeyType::Bls12381G1 => (bls::g1_compress(&pk[..])?, None),
+            KeyType::Bls12381G2 => (bls::g2_compress(&pk[..])?, None),
+        };
+        Ok(Jwk {
+            kty: kty.to_string(),
+            crv: crv.to_string(),
+            x: base64::encode_url(&x),
+            y,
+            d: None,
+        })
+    }
+
+    /// The JWK of a private key which also holds its public key
+    pub fn from_private_key(key_type: KeyType, sk: &PrivateKey) -> CryptoResult<Self> {
+        let mut jwk = Jwk::from_public_key(key_type, &public_key_from_private_key(key_type, sk)?)?;
+        let mut d = match key_type {
+            KeyType::Ed25519 => ed25519_seed(sk)?.to_vec(),
+            KeyType::X25519 => x25519_secret(&sk[..])?.to_vec(),
+            KeyType::Secp256k1 => sk.0.clone(),
+            KeyType::Bls12381G1 | KeyType::Bls12381G2 => bls::private_key_to_scalar(sk)?,
+        };
+        jwk.d = Some(base64::encode_url(&d));
+        d.zeroize();
+        Ok(jwk)
+    }
+
+    pub fn from_json(json: &str) -> CryptoResult<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| CryptoError::ParseError(format!("Invalid JWK: {}", e)))
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("JWK members are strings")
+    }
+
+    /// The same key without the private part
+    pub fn to_public(&self) -> Self {
+        let mut jwk = self.clone();
+        if let Some(mut d) = jwk.d.take() {
+            d.zeroize();
+        }
+        jwk
+    }
+
+    pub fn is_private(&self) -> bool {
+        self.d.is_some()
+    }
+
+    pub fn key_type(&self) -> CryptoResult<KeyType> {
+        let key_type = match (self.kty.as_str(), self.crv.as_str()) {
+            (OCTET_KEY_PAIR, CRV_ED25519) => KeyType::Ed25519,
+            (OCTET_KEY_PAIR, CRV_X25519) => KeyType::X25519,
+            (ELLIPTIC_CURVE, CRV_SECP256K1) => KeyType::Secp256k1,
+            (OCTET_KEY_PAIR, crv) if crv.eq_ignore_ascii_case(CRV_BLS12381_G1) => {
+                KeyType::Bls12381G1
+            }
+            (OCTET_KEY_PAIR, crv) if crv.eq_ignore_ascii_case(CRV_BLS12381_G2) => {
+                KeyType::Bls12381G2
+            }
+            (kty, crv) => {
+                return Err(CryptoError::NoSuchAlgorithm(format!(
+                    "Unsupported JWK key type '{}' with curve '{}'",
+                    kty, crv
+                )))
+            }
+        };
+        Ok(key_type)
+    }
+
+    pub fn to_public_key(&self) -> CryptoResult<(KeyType, PublicKey)> {
+        let key_type = self.key_type()?;
+        let x = decode_member("x", &self.x)?;
+        if key_type != KeyType::Secp256k1 && self.y.is_some() {
+            return Err(CryptoError::ParseError(
+                "Unexpected JWK member 'y'".to_string(),
+            ));
+        }
+        let pk = match key_type {
+            KeyType::Ed25519 => ed25519_public_key(&x)?,
+            KeyType::X25519 => x25519_public_key(&x)?,
+            KeyType::Secp256k1 => {
+                let y = match self.y {
+                    Some(ref y) => decode_member("y", y)?,
+                    None => {
+                        return Err(CryptoError::ParseError(
+                            "Missing JWK member 'y'".to_string(),
+                        ))
+                    }
+                };
+                if x.len() != SECP256K1_COORDINATE_SIZE || y.len() != SECP256K1_COORDINATE_SIZE {
+                    return Err(CryptoError::ParseError(
+                        "Invalid secp256k1 JWK coordinate length".to_string(),
+                    ));
+                }
+                let mut point = vec![0x04];
+                point.extend_from_slice(&x);
+                point.extend_from_slice(&y);
+                PublicKey(
+                    secp256k1_public_key(&point)?
+                        .serialize_compressed()
+                        .to_vec(),
+                )
+            }
+            KeyType::Bls12381G1 => bls::g1_decompress(&x)?,
+            KeyType::Bls12381G2 => bls::g2_decompress(&x)?,
+        };
+        Ok((key_type, pk))
+    }
+
+    /// The private key after checking it matches the public key
+    pub fn to_private_key(&self) -> CryptoResult<(KeyType, PrivateKey)> {
+        let (key_type, pk) = self.to_public_key()?;
+        let mut d = match self.d {
+            Some(ref d) => decode_member("d", d)?,
+            None => {
+                return Err(CryptoError::ParseError(
+                    "JWK does not contain a private key".to_string(),
+                ))
+            }
+        };
+        let sk = match key_type {
+            KeyType::Ed25519 => ed25519_private_key(&d),
+            KeyType::X25519 => x25519_secret(&d).map(|d| PrivateKey(d.to_vec())),
+            KeyType::Secp256k1 => secp256k1_private_key(&d),
+            KeyType::Bls12381G1 | KeyType::Bls12381G2 => bls::private_key_from_scalar(&d),
+        };
+        d.zeroize();
+        let sk = sk?;
+        if public_key_from_private_key(key_type, &sk)? != pk {
+            return Err(CryptoError::ParseError(
+                "JWK private key does not match the public key".to_string(),
+            ));
+        }
+        Ok((key_type, sk))
+    }
+}
+
+impl fmt::Debug for Jwk {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Jwk")
+            .field("kty", &self.kty)
+            .field("crv", &self.crv)
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("d", &self.d.as_ref().map(|_| "..."))
+            .finish()
+    }
+}
+
+impl Drop for Jwk {
+    fn drop(&mut self) {
+        if let Some(ref mut d) = self.d {
+            d.zeroize();
+        }
+    }
+}
+
+fn decode_member(name: &str, value: &str) -> CryptoResult<Vec<u8>> {
+    base64::decode_url(value)
+        .map_err(|_| CryptoError::ParseError(format!("Invalid JWK member '{}'", name)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::KeyType::*;
+    use super::*;
+    use kex::x25519::X25519Sha256;
+    use kex::KeyExchangeScheme;
+    use openssl::bn::{BigNum, BigNumContext, BigNumRef};
+    use openssl::ec::{EcGroup, EcKey};
+    use openssl::nid::Nid;
+    use signatures::bls::{normal, small};
+    use signatures::secp256k1::EcdsaSecp256k1Sha256;
+    use signatures::SignatureScheme;
+
+    // RFC 8037 appendix A.1
+    const ED25519_JWK: &str = r#"{"kty":"OKP","crv":"Ed25519",
+        "d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A",
+        "x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#;
+
+    #[test]
+    fn jwk_rfc8037() {
+        let jwk = Jwk::from_json(ED25519_JWK).unwrap();
+        assert!(jwk.is_private());
+        let (key_type, sk) = jwk.to_private_key().unwrap();
+        assert_eq!(key_type, Ed25519);
+        assert_eq!(
+            hex::encode(&sk[..]),
+            "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60\
+             d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a"
+        );
+        assert_eq!(Jwk::from_private_key(key_type, &sk).unwrap(), jwk);
+        assert_eq!(Jwk::from_json(&jwk.to_json()).unwrap(), jwk);
+        let public = jwk.to_public();
+        assert!(!public.is_private());
+        assert_eq!(
+            public.to_json(),
+            r#"{"kty":"OKP","crv":"Ed25519","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#
+        );
+        assert_eq!(
+            public.to_public_key().unwrap(),
+            (Ed25519, PublicKey(sk[32..].to_vec()))
+        );
+        assert!(public.to_private_key().is_err());
+
+        // RFC 7748 section 6.1 keys of Alice
+        let sk = PrivateKey(
+            hex::decode("77076d0a7318a57d3c16c17251b26645df4c2f87ebc0992ab177fba51db92c2a")
+                .unwrap(),
+        );
+        let jwk = Jwk::from_private_key(X25519, &sk).unwrap();
+        assert_eq!(jwk.x, "hSDwCYkwp1R0i33ctD73Wg2_Og0mOBr066SpjqqbTmo");
+        assert_eq!(jwk.to_private_key().unwrap(), (X25519, sk));
+        let (pk, sk) = X25519Sha256::new().keypair(None).unwrap();
+        let jwk = Jwk::from_private_key(X25519, &sk).unwrap();
+        assert_eq!(jwk.to_public_key().unwrap(), (X25519, pk));
+    }
+
+    #[test]
+    fn jwk_secp256k1() {
+        // Build the JWK the way JOSE libraries do from the OpenSSL key components
+        let group = EcGroup::from_curve_name(Nid::SECP256K1).unwrap();
+        let ec_key = EcKey::generate(&group).unwrap();
+        let mut ctx = BigNumContext::new().unwrap();
+        let mut x = BigNum::new().unwrap();
+        let mut y = BigNum::new().unwrap();
+        ec_key
+            .public_key()
+            .affine_coordinates_gfp(&group, &mut x, &mut y, &mut ctx)
+            .unwrap();
+        let coordinate = |n: &BigNumRef| base64::encode_url(&n.to_vec_padded(32).unwrap());
+        let json = format!(
+            r#"{{"kty":"EC","crv":"secp256k1","x":"{}","y":"{}","d":"{}","kid":"1","use":"sig"}}"#,
+            coordinate(&x),
+            coordinate(&y),
+            coordinate(ec_key.private_key())
+        );
+        let jwk = Jwk::from_json(&json).unwrap();
+        let (key_type, sk) = jwk.to_private_key().unwrap();
+        assert_eq!(key_type, Secp256k1);
+        assert_eq!(Jwk::from_private_key(Secp256k1, &sk).unwrap(), jwk);
+
+        let scheme = EcdsaSecp256k1Sha256::new();
+        let (pk, sk) = scheme.keypair(None).unwrap();
+        let jwk = Jwk::from_private_key(Secp256k1, &sk).unwrap();
+        assert_eq!(jwk.to_public_key().unwrap(), (Secp256k1, pk));
+        assert_eq!(jwk.to_private_key().unwrap(), (Secp256k1, sk));
+
+        let mut missing_y = jwk.to_public();
+        missing_y.y = None;
+        assert!(missing_y.to_public_key().is_err());
+        let mut wrong_curve = jwk.to_public();
+        wrong_curve.crv = "P-256".to_string();
+        assert!(wrong_curve.to_public_key().is_err());
+    }
+
+    #[test]
+    fn jwk_bls() {
+        let (pk, sk) = normal::Bls::new().keypair(None).unwrap();
+        let jwk = Jwk::from_private_key(Bls12381G1, &sk).unwrap();
+        assert_eq!(jwk.crv, "Bls12381G1");
+        assert_eq!(base64::decode_url(&jwk.x).unwrap().len(), 48);
+        assert_eq!(jwk.to_public_key().unwrap(), (Bls12381G1, pk));
+        assert_eq!(
+            jwk.to_private_key().unwrap(),
+            (Bls12381G1, PrivateKey(sk.0.clone()))
+        );
+
+        let (pk, sk) = small::Bls::new().keypair(None).unwrap();
+        let jwk = Jwk::from_private_key(Bls12381G2, &sk).unwrap();
+        assert_eq!(base64::decode_url(&jwk.x).unwrap().len(), 96);
+        assert_eq!(jwk.to_public_key().unwrap(), (Bls12381G2, pk));
+        assert_eq!(jwk.to_private_key().unwrap(), (Bls12381G2, sk));
+
+        let mut upper_case = jwk.to_public();
+        upper_case.crv = "BLS12381G2".to_string();
+        assert_eq!(upper_case.key_type().unwrap(), Bls12381G2);
+        upper_case.crv = "Bls12381G1".to_string();
+        assert!(upper_case.to_public_key().is_err());
+    }
+
+    #[test]
+    fn jwk_invalid() {
+        let jwk = Jwk::from_json(ED25519_JWK).unwrap();
+        let (_, other) = X25519Sha256::new().keypair(None).unwrap();
+        let mut mismatched = jwk.to_public();
+        mismatched.d = Some(base64::encode_url(&other[..]));
+        assert!(mismatched.to_private_key().is_err());
+        assert!(Jwk::from_json(r#"{"kty":"OKP","crv":"Ed25519"}"#).is_err());
+        assert!(Jwk::from_json("[]").is_err());
+        let mut padded = jwk.to_public();
+        padded.x.push('=');
+        assert!(padded.to_public_key().is_err());
+        let mut with_y = jwk.to_public();
+        with_y.y = Some(jwk.x.clone());
+        assert!(with_y.to_public_key().is_err());
+        let mut rsa = jwk.to_public();
+        rsa.kty = "RSA".to_string();
+        assert!(rsa.key_type().is_err());
+    }
+}
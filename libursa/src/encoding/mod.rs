@@ -0,0 +1,144 @@
+//! Standard encodings for the keys used by the signature and key exchange schemes
+//!
+//! `PublicKey` and `PrivateKey` are raw bytes in the encoding of the scheme that made them.
+//! The functions here convert them to and from containers that record the key type and
+//! that other libraries understand.
+//!
+//! * `pkcs8` for private keys and `spki` for public keys as DER or PEM. These are the
+//!   `PRIVATE KEY` and `PUBLIC KEY` formats of OpenSSL
+//!   (see <https://tools.ietf.org/html/rfc5958>, <https://tools.ietf.org/html/rfc5480>
+//!   and <https://tools.ietf.org/html/rfc8410>).
+//! * `jwk` for JSON Web Keys
+//!   (see <https://tools.ietf.org/html/rfc7517>, <https://tools.ietf.org/html/rfc8037>
+//!   and <https://tools.ietf.org/html/rfc8812>).
+//! * `multibase` for public keys as multicodec values in base58btc and `did:key` identifiers
+//!   (see <https://w3c-ccg.github.io/did-method-key/>).
+//!
+//! BLS12-381 keys have no registered PKCS#8 or SubjectPublicKeyInfo algorithm so they can
+//! only be encoded as JWK and multibase. Their public keys use the compressed point format
+//! of the ZCash BLS12-381 serialization in both.
+
+use ed25519_dalek::{PublicKey as DalekPublicKey, SecretKey as DalekSecretKey};
+use keys::{PrivateKey, PublicKey};
+use rustlibsecp256k1;
+use x25519_dalek::{PublicKey as X25519PublicKey, StaticSecret};
+use zeroize::Zeroize;
+
+use {CryptoError, CryptoResult};
+
+mod base64;
+mod bls;
+mod der;
+pub mod jwk;
+pub mod multibase;
+pub mod pem;
+pub mod pkcs8;
+pub mod spki;
+
+/// The algorithm a key belongs to
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum KeyType {
+    /// Keys of `Ed25519Sha512`. Private keys are the 64 byte `seed || public key`
+    Ed25519,
+    /// Keys of `X25519Sha256`
+    X25519,
+    /// Keys of `EcdsaSecp256k1Sha256`. Public keys are 33 byte compressed points
+    Secp256k1,
+    /// Keys of `signatures::bls::normal` with the public key in G1
+    Bls12381G1,
+    /// Keys of `signatures::bls::small` with the public key in G2
+    Bls12381G2,
+}
+
+/// Compute the public key for `sk`
+pub fn public_key_from_private_key(key_type: KeyType, sk: &PrivateKey) -> CryptoResult<PublicKey> {
+    match key_type {
+        KeyType::Ed25519 => Ok(PublicKey(
+            ed25519_private_key(ed25519_seed(sk)?)?[ED25519_SEED_SIZE..].to_vec(),
+        )),
+        KeyType::X25519 => {
+            let secret =
+                StaticSecret::from(*array_ref!(x25519_secret(&sk[..])?, 0, X25519_KEY_SIZE));
+            Ok(PublicKey(
+                X25519PublicKey::from(&secret).as_bytes().to_vec(),
+            ))
+        }
+        KeyType::Secp256k1 => Ok(PublicKey(
+            rustlibsecp256k1::PublicKey::from_secret_key(&secp256k1_secret_key(&sk[..])?)
+                .serialize_compressed()
+                .to_vec(),
+        )),
+        KeyType::Bls12381G1 => bls::g1_public_key(sk),
+        KeyType::Bls12381G2 => bls::g2_public_key(sk),
+    }
+}
+
+const ED25519_SEED_SIZE: usize = 32;
+const ED25519_PUBLIC_KEY_SIZE: usize = 32;
+const X25519_KEY_SIZE: usize = 32;
+const SECP256K1_PRIVATE_KEY_SIZE: usize = 32;
+
+fn ed25519_seed(sk: &PrivateKey) -> CryptoResult<&[u8]> {
+    if sk.len() != ED25519_SEED_SIZE + ED25519_PUBLIC_KEY_SIZE {
+        return Err(CryptoError::ParseError(
+            "Invalid Ed25519 private key length".to_string(),
+        ));
+    }
+    Ok(&sk[..ED25519_SEED_SIZE])
+}
+
+/// Expand an RFC 8032 seed to the `seed || public key` form used by `Ed25519Sha512`
+fn ed25519_private_key(seed: &[u8]) -> CryptoResult<PrivateKey> {
+    let secret = DalekSecretKey::from_bytes(seed)
+        .map_err(|_| CryptoError::ParseError("Invalid Ed25519 private key".to_string()))?;
+    let mut sk = seed.to_vec();
+    sk.extend_from_slice(DalekPublicKey::from(&secret).as_bytes());
+    Ok(PrivateKey(sk))
+}
+
+fn ed25519_public_key(pk: &[u8]) -> CryptoResult<PublicKey> {
+    DalekPublicKey::from_bytes(pk)
+        .map(|pk| PublicKey(pk.to_bytes().to_vec()))
+        .map_err(|_| CryptoError::ParseError("Invalid Ed25519 public key".to_string()))
+}
+
+fn x25519_secret(sk: &[u8]) -> CryptoResult<&[u8]> {
+    if sk.len() != X25519_KEY_SIZE {
+        return Err(CryptoError::ParseError(
+            "Invalid X25519 private key length".to_string(),
+        ));
+    }
+    Ok(sk)
+}
+
+fn x25519_public_key(pk: &[u8]) -> CryptoResult<PublicKey> {
+    if pk.len() != X25519_KEY_SIZE {
+        return Err(CryptoError::ParseError(
+            "Invalid X25519 public key length".to_string(),
+        ));
+    }
+    Ok(PublicKey(pk.to_vec()))
+}
+
+fn secp256k1_secret_key(sk: &[u8]) -> CryptoResult<rustlibsecp256k1::SecretKey> {
+    if sk.len() != SECP256K1_PRIVATE_KEY_SIZE {
+        return Err(CryptoError::ParseError(
+            "Invalid secp256k1 private key length".to_string(),
+        ));
+    }
+    rustlibsecp256k1::SecretKey::parse_slice(sk)
+        .map_err(|_| CryptoError::ParseError("Invalid secp256k1 private key".to_string()))
+}
+
+/// Parse a compressed or uncompressed secp256k1 point
+fn secp256k1_public_key(pk: &[u8]) -> CryptoResult<rustlibsecp256k1::PublicKey> {
+    rustlibsecp256k1::PublicKey::parse_slice(pk, None)
+        .map_err(|_| CryptoError::ParseError("Invalid secp256k1 public key".to_string()))
+}
+
+fn secp256k1_private_key(sk: &[u8]) -> CryptoResult<PrivateKey> {
+    let mut secret = secp256k1_secret_key(sk)?.serialize();
+    let sk = PrivateKey(secret.to_vec());
+    secret.zeroize();
+    Ok(sk)
+}
@@ -0,0 +1,191 @@
+//! Public keys as multicodec values in base58btc multibase and `did:key` identifiers
+//!
+//! The multicodec values are the compressed points for secp256k1 and BLS12-381.
+
+use super::{bls, ed25519_public_key, secp256k1_public_key, x25519_public_key, KeyType};
+use keys::PublicKey;
+use utils::base58;
+
+use {CryptoError, CryptoResult};
+
+/// The multibase prefix of base58btc
+pub const BASE58_BTC: char = 'z';
+pub const DID_KEY_PREFIX: &str = "did:key:";
+
+// Multicodec codes as unsigned varints
+const ED25519_PUB: [u8; 2] = [0xed, 0x01];
+const X25519_PUB: [u8; 2] = [0xec, 0x01];
+const SECP256K1_PUB: [u8; 2] = [0xe7, 0x01];
+const BLS12381_G1_PUB: [u8; 2] = [0xea, 0x01];
+const BLS12381_G2_PUB: [u8; 2] = [0xeb, 0x01];
+
+const SECP256K1_COMPRESSED_SIZE: usize = 33;
+
+const CODECS: [(KeyType, [u8; 2]); 5] = [
+    (KeyType::Ed25519, ED25519_PUB),
+    (KeyType::X25519, X25519_PUB),
+    (KeyType::Secp256k1, SECP256K1_PUB),
+    (KeyType::Bls12381G1, BLS12381_G1_PUB),
+    (KeyType::Bls12381G2, BLS12381_G2_PUB),
+];
+
+pub fn encode(key_type: KeyType, pk: &PublicKey) -> CryptoResult<String> {
+    let key = match key_type {
+        KeyType::Ed25519 => ed25519_public_key(&pk[..])?[..].to_vec(),
+        KeyType::X25519 => x25519_public_key(&pk[..])?[..].to_vec(),
+        KeyType::Secp256k1 => secp256k1_public_key(&pk[..])?
+            .serialize_compressed()
+            .to_vec(),
+        KeyType::Bls12381G1 => bls::g1_compress(&pk[..])?,
+        KeyType::Bls12381G2 => bls::g2_compress(&pk[..])?,
+    };
+    let (_, codec) = CODECS
+        .iter()
+        .find(|(t, _)| *t == key_type)
+        .expect("every key type has a multicodec");
+    let mut data = codec.to_vec();
+    data.extend_from_slice(&key);
+    let mut output = BASE58_BTC.to_string();
+    output.push_str(&base58::encode(&data));
+    Ok(output)
+}
+
+pub fn decode(encoded: &str) -> CryptoResult<(KeyType, PublicKey)> {
+    if !encoded.starts_with(BASE58_BTC) {
+        return Err(CryptoError::ParseError(
+            "Only base58btc multibase is supported".to_string(),
+        ));
+    }
+    let data = base58::decode(&encoded[1..])?;
+    let (key_type, key) = CODECS
+        .iter()
+        .find(|(_, codec)| data.starts_with(codec))
+        .map(|(key_type, codec)| (*key_type, &data[codec.len()..]))
+        .ok_or_else(|| CryptoError::NoSuchAlgorithm("Unsupported multicodec".to_string()))?;
+    let pk = match key_type {
+        KeyType::Ed25519 => ed25519_public_key(key)?,
+        KeyType::X25519 => x25519_public_key(key)?,
+        KeyType::Secp256k1 => {
+            if key.len() != SECP256K1_COMPRESSED_SIZE {
+                return Err(CryptoError::ParseError(
+                    "secp256k1 multicodec keys must be compressed".to_string(),
+                ));
+            }
+            secp256k1_public_key(key)?;
+            PublicKey(key.to_vec())
+        }
+        KeyType::Bls12381G1 => bls::g1_decompress(key)?,
+        KeyType::Bls12381G2 => bls::g2_decompress(key)?,
+    };
+    Ok((key_type, pk))
+}
+
+pub fn to_did_key(key_type: KeyType, pk: &PublicKey) -> CryptoResult<String> {
+    Ok(format!("{}{}", DID_KEY_PREFIX, encode(key_type, pk)?))
+}
+
+/// Parse a `did:key` identifier. A fragment such as the verification method id is ignored.
+pub fn from_did_key(did: &str) -> CryptoResult<(KeyType, PublicKey)> {
+    if !did.starts_with(DID_KEY_PREFIX) {
+        return Err(CryptoError::ParseError("Not a did:key".to_string()));
+    }
+    let encoded = did[DID_KEY_PREFIX.len()..]
+        .split('#')
+        .next()
+        .unwrap_or_default();
+    decode(encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::KeyType::*;
+    use super::*;
+    use amcl_wrapper::group_elem::GroupElement;
+    use amcl_wrapper::group_elem_g1::G1;
+    use kex::x25519::X25519Sha256;
+    use kex::KeyExchangeScheme;
+    use signatures::bls::{normal, small};
+    use signatures::ed25519::Ed25519Sha512;
+    use signatures::secp256k1::EcdsaSecp256k1Sha256;
+    use signatures::SignatureScheme;
+
+    // Examples from the did:key specification
+    const DID_KEYS: [(KeyType, &str); 4] = [
+        (Ed25519, "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp"),
+        (X25519, "did:key:z6LSeu9HkTHSfLLeUs2nnzUSNedgDUevfNQgQjQC23ZCit6F"),
+        (Secp256k1, "did:key:zQ3shokFTS3brHcDQrn82RUDfCZESWL1ZdCEJwekUDPQiYBme"),
+        (Bls12381G2, "did:key:zUC7K4ndUaGZgV7Cp2yJy6JtMoUHY6u7tkcSYUvPrEidqBmLCTLmi6d5WvwnUqejscAkERJ3bfjEiSYtdPkRSE8kSa11hFBr4sTgnbZ95SJj19PN2jdvJjyzpSZgxkyyxNnBNnY"),
+    ];
+
+    #[test]
+    fn did_key_vectors() {
+        for (key_type, did) in DID_KEYS.iter() {
+            let (decoded_type, pk) = from_did_key(did).unwrap();
+            assert_eq!(decoded_type, *key_type);
+            assert_eq!(to_did_key(*key_type, &pk).unwrap(), *did);
+            let fragment = format!("{}#{}", did, &did[DID_KEY_PREFIX.len()..]);
+            assert_eq!(from_did_key(&fragment).unwrap(), (*key_type, pk));
+        }
+
+        // RFC 8032 test 1 and the BLS12-381 G1 generator
+        let pk = PublicKey(
+            hex::decode("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a")
+                .unwrap(),
+        );
+        assert_eq!(
+            encode(Ed25519, &pk).unwrap(),
+            "z6MktwupdmLXVVqTzCw4i46r4uGyosGXRnR3XjN4Zq7oMMsw"
+        );
+        assert_eq!(
+            encode(Bls12381G1, &PublicKey(G1::generator().to_bytes(false))).unwrap(),
+            "z3tEFUdV4D3tCMG6Fr1deVvt32DCS1Y4SxDGoELedXaMUdTdr5FfZvBnbK9bWMhAGj3RHk"
+        );
+    }
+
+    #[test]
+    fn multibase_round_trip() {
+        let keys = [
+            (Ed25519, Ed25519Sha512::new().keypair(None).unwrap().0),
+            (X25519, X25519Sha256::new().keypair(None).unwrap().0),
+            (
+                Secp256k1,
+                EcdsaSecp256k1Sha256::new().keypair(None).unwrap().0,
+            ),
+            (Bls12381G1, normal::Bls::new().keypair(None).unwrap().0),
+            (Bls12381G2, small::Bls::new().keypair(None).unwrap().0),
+        ];
+        for (key_type, pk) in keys.iter() {
+            let encoded = encode(*key_type, pk).unwrap();
+            assert!(encoded.starts_with(BASE58_BTC));
+            assert_eq!(
+                decode(&encoded).unwrap(),
+                (*key_type, PublicKey(pk.0.clone()))
+            );
+            let did = to_did_key(*key_type, pk).unwrap();
+            assert_eq!(
+                from_did_key(&did).unwrap(),
+                (*key_type, PublicKey(pk.0.clone()))
+            );
+        }
+    }
+
+    #[test]
+    fn multibase_invalid() {
+        let (_, did) = DID_KEYS[0];
+        let encoded = &did[DID_KEY_PREFIX.len()..];
+        assert!(from_did_key(encoded).is_err());
+        assert!(from_did_key("did:web:example.com").is_err());
+        assert!(decode(&encoded.replace('z', "f")).is_err());
+        assert!(decode(&encoded[..encoded.len() - 1]).is_err());
+        // Multicodec ed25519-priv
+        let private = format!("z{}", base58::encode(&[0x80, 0x26, 1, 2, 3]));
+        assert!(decode(&private).is_err());
+        let uncompressed = EcdsaSecp256k1Sha256::new().keypair(None).unwrap().0;
+        let point = rustlibsecp256k1::PublicKey::parse_slice(&uncompressed[..], None)
+            .unwrap()
+            .serialize();
+        let mut data = SECP256K1_PUB.to_vec();
+        data.extend_from_slice(&point);
+        assert!(decode(&format!("z{}", base58::encode(&data))).is_err());
+    }
+}
@@ -0,0 +1,63 @@
+//! PEM armor around DER data (see <https://tools.ietf.org/html/rfc7468>)
+
+use super::base64;
+
+use {CryptoError, CryptoResult};
+
+const LINE_LENGTH: usize = 64;
+
+/// Wrap `der` in `-----BEGIN label-----` and `-----END label-----` lines
+pub fn encode(label: &str, der: &[u8]) -> String {
+    let encoded = base64::encode(der);
+    let mut output = format!("-----BEGIN {}-----\n", label);
+    for line in encoded.as_bytes().chunks(LINE_LENGTH) {
+        output.push_str(std::str::from_utf8(line).expect("Base64 is ASCII"));
+        output.push('\n');
+    }
+    output.push_str(&format!("-----END {}-----\n", label));
+    output
+}
+
+/// Extract the DER data of the first block with `label`.
+/// Text around the block and whitespace inside it are ignored.
+pub fn decode(label: &str, pem: &str) -> CryptoResult<Vec<u8>> {
+    let begin = format!("-----BEGIN {}-----", label);
+    let end = format!("-----END {}-----", label);
+    let start = pem
+        .find(&begin)
+        .ok_or_else(|| CryptoError::ParseError(format!("Missing {} PEM header", label)))?
+        + begin.len();
+    let length = pem[start..]
+        .find(&end)
+        .ok_or_else(|| CryptoError::ParseError(format!("Missing {} PEM footer", label)))?;
+    let encoded: String = pem[start..start + length]
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect();
+    base64::decode(&encoded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pem_round_trip() {
+        let der = (0..100u8).collect::<Vec<u8>>();
+        let pem = encode("TEST", &der);
+        let lines: Vec<&str> = pem.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "-----BEGIN TEST-----");
+        assert_eq!(lines[1].len(), LINE_LENGTH);
+        assert_eq!(lines[4], "-----END TEST-----");
+        assert_eq!(decode("TEST", &pem).unwrap(), der);
+        assert_eq!(
+            decode("TEST", &format!("comment\r\n{}", pem.replace('\n', "\r\n"))).unwrap(),
+            der
+        );
+
+        assert!(decode("OTHER", &pem).is_err());
+        assert!(decode("TEST", &lines[..4].join("\n")).is_err());
+        assert!(decode("TEST", &pem.replace("AAE", "A*E")).is_err());
+    }
+}
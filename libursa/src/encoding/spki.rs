@@ -0,0 +1,199 @@
+//! SubjectPublicKeyInfo public keys as DER or PEM with the `PUBLIC KEY` label
+//!
+//! secp256k1 keys are written as uncompressed points like OpenSSL does but compressed
+//! points are read as well.
+
+use super::der::{self, Reader};
+use super::{ed25519_public_key, pem, secp256k1_public_key, x25519_public_key, KeyType};
+use keys::PublicKey;
+
+use {CryptoError, CryptoResult};
+
+pub const PEM_LABEL: &str = "PUBLIC KEY";
+
+/// id-Ed25519 1.3.101.112
+pub(super) const ED25519_OID: &[u8] = &[0x2b, 0x65, 0x70];
+/// id-X25519 1.3.101.110
+pub(super) const X25519_OID: &[u8] = &[0x2b, 0x65, 0x6e];
+/// id-ecPublicKey 1.2.840.10045.2.1
+pub(super) const EC_PUBLIC_KEY_OID: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+/// secp256k1 1.3.132.0.10
+pub(super) const SECP256K1_OID: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+pub fn to_der(key_type: KeyType, pk: &PublicKey) -> CryptoResult<Vec<u8>> {
+    let algorithm = algorithm_identifier(key_type)?;
+    let key = match key_type {
+        KeyType::Ed25519 => ed25519_public_key(&pk[..])?[..].to_vec(),
+        KeyType::X25519 => x25519_public_key(&pk[..])?[..].to_vec(),
+        KeyType::Secp256k1 => secp256k1_public_key(&pk[..])?.serialize().to_vec(),
+        KeyType::Bls12381G1 | KeyType::Bls12381G2 => unreachable!(),
+    };
+    Ok(der::sequence(&[&algorithm, &der::bit_string(&key)]))
+}
+
+pub fn from_der(data: &[u8]) -> CryptoResult<(KeyType, PublicKey)> {
+    let mut outer = Reader::new(data);
+    let mut info = Reader::new(outer.read(der::SEQUENCE)?);
+    outer.finish()?;
+    let key_type = read_algorithm_identifier(&mut info)?;
+    let key = info.read_bit_string()?;
+    info.finish()?;
+
+    let pk = match key_type {
+        KeyType::Ed25519 => ed25519_public_key(key)?,
+        KeyType::X25519 => x25519_public_key(key)?,
+        KeyType::Secp256k1 => PublicKey(secp256k1_public_key(key)?.serialize_compressed().to_vec()),
+        KeyType::Bls12381G1 | KeyType::Bls12381G2 => unreachable!(),
+    };
+    Ok((key_type, pk))
+}
+
+pub fn to_pem(key_type: KeyType, pk: &PublicKey) -> CryptoResult<String> {
+    Ok(pem::encode(PEM_LABEL, &to_der(key_type, pk)?))
+}
+
+pub fn from_pem(data: &str) -> CryptoResult<(KeyType, PublicKey)> {
+    from_der(&pem::decode(PEM_LABEL, data)?)
+}
+
+/// The AlgorithmIdentifier shared by PKCS#8 and SubjectPublicKeyInfo
+pub(super) fn algorithm_identifier(key_type: KeyType) -> CryptoResult<Vec<u8>> {
+    match key_type {
+        KeyType::Ed25519 => Ok(der::sequence(&[&der::encode(
+            der::OBJECT_IDENTIFIER,
+            ED25519_OID,
+        )])),
+        KeyType::X25519 => Ok(der::sequence(&[&der::encode(
+            der::OBJECT_IDENTIFIER,
+            X25519_OID,
+        )])),
+        KeyType::Secp256k1 => Ok(der::sequence(&[
+            &der::encode(der::OBJECT_IDENTIFIER, EC_PUBLIC_KEY_OID),
+            &der::encode(der::OBJECT_IDENTIFIER, SECP256K1_OID),
+        ])),
+        KeyType::Bls12381G1 | KeyType::Bls12381G2 => Err(CryptoError::NoSuchAlgorithm(
+            "BLS12-381 keys have no PKCS#8 or SubjectPublicKeyInfo encoding".to_string(),
+        )),
+    }
+}
+
+pub(super) fn read_algorithm_identifier(reader: &mut Reader) -> CryptoResult<KeyType> {
+    let mut algorithm = Reader::new(reader.read(der::SEQUENCE)?);
+    let oid = algorithm.read(der::OBJECT_IDENTIFIER)?;
+    let key_type = if oid == ED25519_OID {
+        KeyType::Ed25519
+    } else if oid == X25519_OID {
+        KeyType::X25519
+    } else if oid == EC_PUBLIC_KEY_OID {
+        if algorithm.read(der::OBJECT_IDENTIFIER)? != SECP256K1_OID {
+            return Err(CryptoError::NoSuchAlgorithm(
+                "Only the secp256k1 curve is supported".to_string(),
+            ));
+        }
+        KeyType::Secp256k1
+    } else {
+        return Err(CryptoError::NoSuchAlgorithm(
+            "Unsupported key algorithm".to_string(),
+        ));
+    };
+    // RFC 8410 requires the parameters to be absent
+    algorithm.finish()?;
+    Ok(key_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::KeyType::*;
+    use super::*;
+    use openssl::ec::{EcGroup, EcKey, PointConversionForm};
+    use openssl::nid::Nid;
+    use openssl::pkey::PKey;
+    use std::str;
+
+    // RFC 8410 section 10.1
+    const ED25519_SPKI: &str = "-----BEGIN PUBLIC KEY-----
+MCowBQYDK2VwAyEAGb9ECWmEzf6FQbrBZ9w7lshQhqowtrbLDFw4rXAxZuE=
+-----END PUBLIC KEY-----
+";
+
+    #[test]
+    fn spki_rfc8410() {
+        let (key_type, pk) = from_pem(ED25519_SPKI).unwrap();
+        assert_eq!(key_type, Ed25519);
+        assert_eq!(
+            hex::encode(&pk[..]),
+            "19bf44096984cdfe8541bac167dc3b96c85086aa30b6b6cb0c5c38ad703166e1"
+        );
+        assert_eq!(to_pem(key_type, &pk).unwrap(), ED25519_SPKI);
+    }
+
+    #[test]
+    fn spki_openssl_round_trip() {
+        let group = EcGroup::from_curve_name(Nid::SECP256K1).unwrap();
+        let ec_key = EcKey::generate(&group).unwrap();
+        let mut ctx = openssl::bn::BigNumContext::new().unwrap();
+        let compressed = ec_key
+            .public_key()
+            .to_bytes(&group, PointConversionForm::COMPRESSED, &mut ctx)
+            .unwrap();
+        let keys = [
+            (Ed25519, PKey::generate_ed25519().unwrap()),
+            (X25519, PKey::generate_x25519().unwrap()),
+            (Secp256k1, PKey::from_ec_key(ec_key).unwrap()),
+        ];
+        for (key_type, pkey) in keys.iter() {
+            let der = pkey.public_key_to_der().unwrap();
+            let (decoded_type, pk) = from_der(&der).unwrap();
+            assert_eq!(decoded_type, *key_type);
+            assert_eq!(to_der(*key_type, &pk).unwrap(), der);
+            if *key_type == Secp256k1 {
+                assert_eq!(pk.0, compressed);
+            } else {
+                assert_eq!(pkey.raw_public_key().unwrap(), pk.0);
+            }
+
+            let pem = to_pem(*key_type, &pk).unwrap();
+            assert_eq!(pem.as_bytes(), &pkey.public_key_to_pem().unwrap()[..]);
+            let reparsed = PKey::public_key_from_pem(pem.as_bytes()).unwrap();
+            assert!(reparsed.public_eq(pkey));
+        }
+
+        // Compressed points are read as well
+        let compressed_der = der::sequence(&[
+            &algorithm_identifier(Secp256k1).unwrap(),
+            &der::bit_string(&compressed),
+        ]);
+        assert_eq!(
+            from_der(&compressed_der).unwrap(),
+            (Secp256k1, PublicKey(compressed.clone()))
+        );
+        let mut invalid = compressed.clone();
+        invalid[0] = 0x05;
+        let invalid_der = der::sequence(&[
+            &algorithm_identifier(Secp256k1).unwrap(),
+            &der::bit_string(&invalid),
+        ]);
+        assert!(from_der(&invalid_der).is_err());
+    }
+
+    #[test]
+    fn spki_invalid() {
+        assert!(to_der(X25519, &PublicKey(vec![1u8; 31])).is_err());
+        assert!(to_der(Secp256k1, &PublicKey(vec![5u8; 33])).is_err());
+        assert!(to_der(Bls12381G2, &PublicKey(vec![1u8; 193])).is_err());
+        let (_, pk) = from_pem(ED25519_SPKI).unwrap();
+        let der = to_der(Ed25519, &pk).unwrap();
+        // Parameters must be absent
+        let with_parameters = der::sequence(&[
+            &der::sequence(&[
+                &der::encode(der::OBJECT_IDENTIFIER, ED25519_OID),
+                &der::encode(0x05, &[]),
+            ]),
+            &der::bit_string(&pk[..]),
+        ]);
+        assert!(from_der(&with_parameters).is_err());
+        let mut unused_bits = der.clone();
+        unused_bits[11] = 1;
+        assert!(from_der(&unused_bits).is_err());
+    }
+}
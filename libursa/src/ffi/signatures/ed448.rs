@@ -0,0 +1,287 @@
+// FFI functions to the ed448 signatures
+//
+// These are used the same way as the `ursa_ed25519_*` functions, see ed25519.rs
+// for an example of calling them from C.
+
+use super::super::ByteArray;
+use keys::{KeyGenOption, PrivateKey, PublicKey};
+use signatures::ed448;
+use signatures::prelude::*;
+
+use ffi_support::{ByteBuffer, ErrorCode, ExternError};
+
+pub mod ed448_error_codes {
+    pub const KEYPAIR_ERROR: i32 = 1;
+    pub const SIGNING_ERROR: i32 = 2;
+    pub const VERIFY_ERROR: i32 = 3;
+    pub const INVALID_PARAM1: i32 = 4;
+    pub const INVALID_PARAM2: i32 = 5;
+}
+
+/// Return the number of bytes in an Ed448 public key - 57 bytes
+#[no_mangle]
+pub extern "C" fn ursa_ed448_get_public_key_size() -> i32 {
+    ed448::PUBLIC_KEY_SIZE as i32
+}
+
+/// Return the number of bytes in an Ed448 private key - 114 bytes
+#[no_mangle]
+pub extern "C" fn ursa_ed448_get_private_key_size() -> i32 {
+    ed448::PRIVATE_KEY_SIZE as i32
+}
+
+/// Return the number of bytes in an Ed448 signature 114 bytes
+#[no_mangle]
+pub extern "C" fn ursa_ed448_get_signature_size() -> i32 {
+    ed448::SIGNATURE_SIZE as i32
+}
+
+/// Create a new keypair.
+/// Caller will need to call `ursa_bytebuffer_free` on `public_key` and `private_key`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_ed448_keypair_new(
+    public_key: &mut ByteBuffer,
+    private_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_ed448_keypair_gen(None, public_key, Some(private_key), err)
+}
+
+/// Create a new keypair from a seed.
+/// Caller will need to call `ursa_bytebuffer_free` on `public_key` and `private_key`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_ed448_keypair_from_seed(
+    seed: &ByteArray,
+    public_key: &mut ByteBuffer,
+    private_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_ed448_keypair_gen(
+        Some(KeyGenOption::UseSeed(seed.to_vec())),
+        public_key,
+        Some(private_key),
+        err,
+    )
+}
+
+/// Get a public key from a private key.
+/// Caller will need to call `ursa_bytebuffer_free` on `public_key`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_ed448_get_public_key(
+    private_key: &ByteArray,
+    public_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_ed448_keypair_gen(
+        Some(KeyGenOption::FromSecretKey(PrivateKey(
+            private_key.to_vec(),
+        ))),
+        public_key,
+        None,
+        err,
+    )
+}
+
+/// Sign a message
+/// Caller will need to call `ursa_bytebuffer_free` on `signature`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_ed448_sign(
+    message: &ByteArray,
+    private_key: &ByteArray,
+    signature: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let scheme = Ed448::new();
+    let sk = PrivateKey(private_key.to_vec());
+
+    match scheme.sign(message.to_vec().as_slice(), &sk) {
+        Ok(sig) => {
+            *err = ExternError::success();
+            *signature = ByteBuffer::from_vec(sig);
+            1
+        }
+        Err(e) => {
+            *err = ExternError::new_error(
+                ErrorCode::new(ed448_error_codes::SIGNING_ERROR),
+                e.to_string(),
+            );
+            0
+        }
+    }
+}
+
+/// Verify a signature over a message
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_ed448_verify(
+    message: &ByteArray,
+    signature: &ByteArray,
+    public_key: &ByteArray,
+    err: &mut ExternError,
+) -> i32 {
+    let scheme = Ed448::new();
+    let pk = PublicKey(public_key.to_vec());
+
+    match scheme.verify(
+        message.to_vec().as_slice(),
+        signature.to_vec().as_slice(),
+        &pk,
+    ) {
+        Ok(b) => {
+            if b {
+                *err = ExternError::success();
+                1
+            } else {
+                0
+            }
+        }
+        Err(e) => {
+            *err = ExternError::new_error(
+                ErrorCode::new(ed448_error_codes::VERIFY_ERROR),
+                e.to_string(),
+            );
+            0
+        }
+    }
+}
+
+fn ursa_ed448_keypair_gen(
+    option: Option<KeyGenOption>,
+    public_key: &mut ByteBuffer,
+    private_key: Option<&mut ByteBuffer>,
+    err: &mut ExternError,
+) -> i32 {
+    let scheme = Ed448::new();
+    match scheme.keypair(option) {
+        Ok((pk, sk)) => {
+            *err = ExternError::success();
+            *public_key = ByteBuffer::from_vec(pk.0.to_vec());
+            if let Some(s) = private_key {
+                *s = ByteBuffer::from_vec(sk.0.to_vec());
+            }
+            1
+        }
+        Err(e) => {
+            *err = ExternError::new_error(
+                ErrorCode::new(ed448_error_codes::KEYPAIR_ERROR),
+                e.to_string(),
+            );
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ffi_keypair() {
+        let mut public_key = ByteBuffer::new_with_size(ursa_ed448_get_public_key_size() as usize);
+        let mut private_key = ByteBuffer::new_with_size(ursa_ed448_get_private_key_size() as usize);
+        let mut error = ExternError::success();
+        let res = ursa_ed448_keypair_new(&mut public_key, &mut private_key, &mut error);
+
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        let pk = public_key.into_vec();
+        let sk = private_key.into_vec();
+
+        assert_eq!(pk.len(), ed448::PUBLIC_KEY_SIZE);
+        assert_eq!(sk.len(), ed448::PRIVATE_KEY_SIZE);
+        assert!(!pk.iter().all(|b| *b == 0u8));
+        assert!(!sk.iter().all(|b| *b == 0u8));
+
+        let mut public_key = ByteBuffer::new_with_size(ursa_ed448_get_public_key_size() as usize);
+        let mut private_key = ByteBuffer::new_with_size(ursa_ed448_get_private_key_size() as usize);
+        let seed = vec![1u8; 32];
+        let seed_wrapper = ByteArray::from(&seed);
+        let res = ursa_ed448_keypair_from_seed(
+            &seed_wrapper,
+            &mut public_key,
+            &mut private_key,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        let pk = public_key.into_vec();
+        let sk = private_key.into_vec();
+        assert_eq!(&sk[ed448::SEED_SIZE..], pk.as_slice());
+
+        let mut public_key = ByteBuffer::new_with_size(ursa_ed448_get_public_key_size() as usize);
+        let sk_wrapper = ByteArray::from(&sk);
+        let res = ursa_ed448_get_public_key(&sk_wrapper, &mut public_key, &mut error);
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        assert_eq!(pk, public_key.into_vec());
+
+        let mut public_key = ByteBuffer::new_with_size(ursa_ed448_get_public_key_size() as usize);
+        let mut private_key = ByteBuffer::new_with_size(ursa_ed448_get_private_key_size() as usize);
+        let pk_wrapper = ByteArray::from(&pk[..32]);
+        let res = ursa_ed448_get_public_key(&pk_wrapper, &mut public_key, &mut error);
+        assert_eq!(res, 0);
+        assert_eq!(
+            error.get_message().into_string(),
+            "KeyGenError(Keypair must be 114 bytes in length)".to_string()
+        );
+        let seed = ByteArray::default();
+        let res =
+            ursa_ed448_keypair_from_seed(&seed, &mut public_key, &mut private_key, &mut error);
+        assert_eq!(res, 1);
+        public_key.destroy();
+        private_key.destroy();
+    }
+
+    #[test]
+    fn ffi_ed448_sign() {
+        let mut public_key = ByteBuffer::new_with_size(ursa_ed448_get_public_key_size() as usize);
+        let mut private_key = ByteBuffer::new_with_size(ursa_ed448_get_private_key_size() as usize);
+        let mut error = ExternError::success();
+        let seed = vec![1u8; 32];
+        let seed_wrapper = ByteArray::from(&seed);
+        let res = ursa_ed448_keypair_from_seed(
+            &seed_wrapper,
+            &mut public_key,
+            &mut private_key,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        let pk = public_key.into_vec();
+        let sk = private_key.into_vec();
+
+        let mut signature = ByteBuffer::new_with_size(ursa_ed448_get_signature_size() as usize);
+        let message = b"Wepa! This is a message that should be signed.";
+        let message_wrapper = ByteArray::from(&message[..]);
+        let sk_wrapper = ByteArray::from(&sk);
+
+        let res = ursa_ed448_sign(&message_wrapper, &sk_wrapper, &mut signature, &mut error);
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+
+        let sig = signature.into_vec();
+        assert_eq!(sig.len(), ed448::SIGNATURE_SIZE);
+        let sig_wrapper = ByteArray::from(&sig);
+        let pk_wrapper = ByteArray::from(&pk);
+        let res = ursa_ed448_verify(&message_wrapper, &sig_wrapper, &pk_wrapper, &mut error);
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+
+        let other_message = ByteArray::from(&b"Wepa! This is another message."[..]);
+        let res = ursa_ed448_verify(&other_message, &sig_wrapper, &pk_wrapper, &mut error);
+        assert_eq!(res, 0);
+    }
+}
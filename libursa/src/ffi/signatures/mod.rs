@@ -1 +1,3 @@
 pub mod ed25519;
+pub mod ed448;
+pub mod secp256k1;
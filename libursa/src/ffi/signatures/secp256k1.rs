@@ -0,0 +1,454 @@
+// FFI functions to the secp256k1 ECDSA signatures
+//
+// These are used the same way as the `ursa_ed25519_*` functions, see ed25519.rs
+// for an example of calling them from C. The recoverable functions produce and
+// consume 65 byte `r || s || v` signatures, the `_keccak256` variants hash the
+// message with Keccak-256 like Ethereum instead of SHA-256.
+
+use super::super::ByteArray;
+use keys::{KeyGenOption, PrivateKey, PublicKey};
+use sha2::digest::generic_array::typenum::U32;
+use sha2::{Digest, Sha256};
+use sha3::Keccak256;
+use signatures::prelude::*;
+use signatures::secp256k1;
+
+use ffi_support::{ByteBuffer, ErrorCode, ExternError};
+
+pub mod secp256k1_error_codes {
+    pub const KEYPAIR_ERROR: i32 = 1;
+    pub const SIGNING_ERROR: i32 = 2;
+    pub const VERIFY_ERROR: i32 = 3;
+    pub const INVALID_PARAM1: i32 = 4;
+    pub const INVALID_PARAM2: i32 = 5;
+    pub const RECOVERY_ERROR: i32 = 6;
+}
+
+/// Return the number of bytes in an secp256k1 compressed public key - 33 bytes
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_get_public_key_size() -> i32 {
+    secp256k1::PUBLIC_KEY_SIZE as i32
+}
+
+/// Return the number of bytes in an secp256k1 private key - 32 bytes
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_get_private_key_size() -> i32 {
+    secp256k1::PRIVATE_KEY_SIZE as i32
+}
+
+/// Return the number of bytes in an secp256k1 compact signature - 64 bytes
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_get_signature_size() -> i32 {
+    secp256k1::SIGNATURE_SIZE as i32
+}
+
+/// Return the number of bytes in a secp256k1 recoverable signature - 65 bytes
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_get_recoverable_signature_size() -> i32 {
+    secp256k1::RECOVERABLE_SIGNATURE_SIZE as i32
+}
+
+/// Create a new keypair.
+/// Caller will need to call `ursa_bytebuffer_free` on `public_key` and `private_key`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_keypair_new(
+    public_key: &mut ByteBuffer,
+    private_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_secp256k1_keypair_gen(None, public_key, Some(private_key), err)
+}
+
+/// Create a new keypair from a seed.
+/// Caller will need to call `ursa_bytebuffer_free` on `public_key` and `private_key`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_keypair_from_seed(
+    seed: &ByteArray,
+    public_key: &mut ByteBuffer,
+    private_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_secp256k1_keypair_gen(
+        Some(KeyGenOption::UseSeed(seed.to_vec())),
+        public_key,
+        Some(private_key),
+        err,
+    )
+}
+
+/// Get a public key from a private key.
+/// Caller will need to call `ursa_bytebuffer_free` on `public_key`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_get_public_key(
+    private_key: &ByteArray,
+    public_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_secp256k1_keypair_gen(
+        Some(KeyGenOption::FromSecretKey(PrivateKey(
+            private_key.to_vec(),
+        ))),
+        public_key,
+        None,
+        err,
+    )
+}
+
+/// Sign a message
+/// Caller will need to call `ursa_bytebuffer_free` on `signature`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_sign(
+    message: &ByteArray,
+    private_key: &ByteArray,
+    signature: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let scheme = EcdsaSecp256k1Sha256::new();
+    let sk = PrivateKey(private_key.to_vec());
+
+    match scheme.sign(message.to_vec().as_slice(), &sk) {
+        Ok(sig) => {
+            *err = ExternError::success();
+            *signature = ByteBuffer::from_vec(sig);
+            1
+        }
+        Err(e) => {
+            *err = ExternError::new_error(
+                ErrorCode::new(secp256k1_error_codes::SIGNING_ERROR),
+                e.to_string(),
+            );
+            0
+        }
+    }
+}
+
+/// Verify a signature over a message
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_verify(
+    message: &ByteArray,
+    signature: &ByteArray,
+    public_key: &ByteArray,
+    err: &mut ExternError,
+) -> i32 {
+    let scheme = EcdsaSecp256k1Sha256::new();
+    let pk = PublicKey(public_key.to_vec());
+
+    match scheme.verify(
+        message.to_vec().as_slice(),
+        signature.to_vec().as_slice(),
+        &pk,
+    ) {
+        Ok(b) => {
+            if b {
+                *err = ExternError::success();
+                1
+            } else {
+                0
+            }
+        }
+        Err(e) => {
+            *err = ExternError::new_error(
+                ErrorCode::new(secp256k1_error_codes::VERIFY_ERROR),
+                e.to_string(),
+            );
+            0
+        }
+    }
+}
+
+/// Sign a message and append the recovery id
+/// Caller will need to call `ursa_bytebuffer_free` on `signature`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_sign_recoverable(
+    message: &ByteArray,
+    private_key: &ByteArray,
+    signature: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_secp256k1_sign_recoverable_with_digest::<Sha256>(message, private_key, signature, err)
+}
+
+/// Sign a message hashed with Keccak-256 as in Ethereum and append the recovery id
+/// Caller will need to call `ursa_bytebuffer_free` on `signature`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_sign_recoverable_keccak256(
+    message: &ByteArray,
+    private_key: &ByteArray,
+    signature: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_secp256k1_sign_recoverable_with_digest::<Keccak256>(message, private_key, signature, err)
+}
+
+/// Recover the signer's compressed public key from a recoverable signature
+/// Caller will need to call `ursa_bytebuffer_free` on `public_key`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_recover_public_key(
+    message: &ByteArray,
+    signature: &ByteArray,
+    public_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_secp256k1_recover_public_key_with_digest::<Sha256>(message, signature, public_key, err)
+}
+
+/// Recover the signer's compressed public key from a recoverable signature
+/// over a message hashed with Keccak-256 as in Ethereum
+/// Caller will need to call `ursa_bytebuffer_free` on `public_key`
+/// to free the memory.
+/// If an error occurs, caller will need to call `ursa_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn ursa_secp256k1_recover_public_key_keccak256(
+    message: &ByteArray,
+    signature: &ByteArray,
+    public_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    ursa_secp256k1_recover_public_key_with_digest::<Keccak256>(message, signature, public_key, err)
+}
+
+fn ursa_secp256k1_sign_recoverable_with_digest<D>(
+    message: &ByteArray,
+    private_key: &ByteArray,
+    signature: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32
+where
+    D: Digest<OutputSize = U32>,
+{
+    let scheme = EcdsaSecp256k1Sha256::new();
+    let sk = PrivateKey(private_key.to_vec());
+
+    match scheme.sign_recoverable_with_digest::<D>(message.to_vec().as_slice(), &sk) {
+        Ok(sig) => {
+            *err = ExternError::success();
+            *signature = ByteBuffer::from_vec(sig);
+            1
+        }
+        Err(e) => {
+            *err = ExternError::new_error(
+                ErrorCode::new(secp256k1_error_codes::SIGNING_ERROR),
+                e.to_string(),
+            );
+            0
+        }
+    }
+}
+
+fn ursa_secp256k1_recover_public_key_with_digest<D>(
+    message: &ByteArray,
+    signature: &ByteArray,
+    public_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32
+where
+    D: Digest<OutputSize = U32>,
+{
+    let scheme = EcdsaSecp256k1Sha256::new();
+
+    match scheme.recover_public_key_with_digest::<D>(
+        message.to_vec().as_slice(),
+        signature.to_vec().as_slice(),
+    ) {
+        Ok(pk) => {
+            *err = ExternError::success();
+            *public_key = ByteBuffer::from_vec(pk.0.to_vec());
+            1
+        }
+        Err(e) => {
+            *err = ExternError::new_error(
+                ErrorCode::new(secp256k1_error_codes::RECOVERY_ERROR),
+                e.to_string(),
+            );
+            0
+        }
+    }
+}
+
+fn ursa_secp256k1_keypair_gen(
+    option: Option<KeyGenOption>,
+    public_key: &mut ByteBuffer,
+    private_key: Option<&mut ByteBuffer>,
+    err: &mut ExternError,
+) -> i32 {
+    let scheme = EcdsaSecp256k1Sha256::new();
+    match scheme.keypair(option) {
+        Ok((pk, sk)) => {
+            *err = ExternError::success();
+            *public_key = ByteBuffer::from_vec(pk.0.to_vec());
+            if let Some(s) = private_key {
+                *s = ByteBuffer::from_vec(sk.0.to_vec());
+            }
+            1
+        }
+        Err(e) => {
+            *err = ExternError::new_error(
+                ErrorCode::new(secp256k1_error_codes::KEYPAIR_ERROR),
+                e.to_string(),
+            );
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ffi_keypair() {
+        let mut public_key =
+            ByteBuffer::new_with_size(ursa_secp256k1_get_public_key_size() as usize);
+        let mut private_key =
+            ByteBuffer::new_with_size(ursa_secp256k1_get_private_key_size() as usize);
+        let mut error = ExternError::success();
+        let res = ursa_secp256k1_keypair_new(&mut public_key, &mut private_key, &mut error);
+
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        let pk = public_key.into_vec();
+        let sk = private_key.into_vec();
+
+        assert_eq!(pk.len(), secp256k1::PUBLIC_KEY_SIZE);
+        assert_eq!(sk.len(), secp256k1::PRIVATE_KEY_SIZE);
+
+        let mut public_key =
+            ByteBuffer::new_with_size(ursa_secp256k1_get_public_key_size() as usize);
+        let sk_wrapper = ByteArray::from(&sk);
+        let res = ursa_secp256k1_get_public_key(&sk_wrapper, &mut public_key, &mut error);
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        assert_eq!(pk, public_key.into_vec());
+
+        let mut public_key =
+            ByteBuffer::new_with_size(ursa_secp256k1_get_public_key_size() as usize);
+        let mut private_key =
+            ByteBuffer::new_with_size(ursa_secp256k1_get_private_key_size() as usize);
+        let seed = vec![1u8; 32];
+        let seed_wrapper = ByteArray::from(&seed);
+        let res = ursa_secp256k1_keypair_from_seed(
+            &seed_wrapper,
+            &mut public_key,
+            &mut private_key,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        public_key.destroy();
+        private_key.destroy();
+    }
+
+    #[test]
+    fn ffi_secp256k1_recoverable() {
+        let mut public_key =
+            ByteBuffer::new_with_size(ursa_secp256k1_get_public_key_size() as usize);
+        let mut private_key =
+            ByteBuffer::new_with_size(ursa_secp256k1_get_private_key_size() as usize);
+        let mut error = ExternError::success();
+        let res = ursa_secp256k1_keypair_new(&mut public_key, &mut private_key, &mut error);
+        assert_eq!(res, 1);
+        let pk = public_key.into_vec();
+        let sk = private_key.into_vec();
+
+        let mut signature =
+            ByteBuffer::new_with_size(ursa_secp256k1_get_recoverable_signature_size() as usize);
+        let message = b"Wepa! This is a message that should be signed.";
+        let message_wrapper = ByteArray::from(&message[..]);
+        let sk_wrapper = ByteArray::from(&sk);
+        let res = ursa_secp256k1_sign_recoverable(
+            &message_wrapper,
+            &sk_wrapper,
+            &mut signature,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        let sig = signature.into_vec();
+        assert_eq!(sig.len(), secp256k1::RECOVERABLE_SIGNATURE_SIZE);
+
+        let compact_wrapper = ByteArray::from(&sig[..secp256k1::SIGNATURE_SIZE]);
+        let pk_wrapper = ByteArray::from(&pk);
+        let res =
+            ursa_secp256k1_verify(&message_wrapper, &compact_wrapper, &pk_wrapper, &mut error);
+        assert_eq!(res, 1);
+
+        let mut public_key =
+            ByteBuffer::new_with_size(ursa_secp256k1_get_public_key_size() as usize);
+        let sig_wrapper = ByteArray::from(&sig);
+        let res = ursa_secp256k1_recover_public_key(
+            &message_wrapper,
+            &sig_wrapper,
+            &mut public_key,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        assert_eq!(pk, public_key.into_vec());
+
+        let mut public_key = ByteBuffer::default();
+        let res = ursa_secp256k1_recover_public_key(
+            &message_wrapper,
+            &compact_wrapper,
+            &mut public_key,
+            &mut error,
+        );
+        assert_eq!(res, 0);
+        assert_eq!(
+            error.get_code(),
+            ErrorCode::new(secp256k1_error_codes::RECOVERY_ERROR)
+        );
+
+        // Keccak-256 signatures only recover the key with Keccak-256
+        let mut signature = ByteBuffer::default();
+        let res = ursa_secp256k1_sign_recoverable_keccak256(
+            &message_wrapper,
+            &sk_wrapper,
+            &mut signature,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        let sig = signature.into_vec();
+        let sig_wrapper = ByteArray::from(&sig);
+        let mut public_key = ByteBuffer::default();
+        let res = ursa_secp256k1_recover_public_key_keccak256(
+            &message_wrapper,
+            &sig_wrapper,
+            &mut public_key,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        assert_eq!(pk, public_key.into_vec());
+        let mut public_key = ByteBuffer::default();
+        let res = ursa_secp256k1_recover_public_key(
+            &message_wrapper,
+            &sig_wrapper,
+            &mut public_key,
+            &mut error,
+        );
+        assert!(res == 0 || public_key.into_vec() != pk);
+    }
+}
@@ -0,0 +1,702 @@
+//! BIP-32 hierarchical deterministic secp256k1 keys
+//!
+//! The private keys are the 32 byte scalars and the public keys the 33 byte
+//! compressed points used by `EcdsaSecp256k1Sha256` and `SchnorrSecp256k1`.
+//! Extended keys serialize to the mainnet `xprv` and `xpub` Base58Check strings.
+
+use super::{hmac_sha512, DerivationPath};
+use keys::{PrivateKey, PublicKey};
+use rustlibsecp256k1;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use utils::base58;
+use zeroize::Zeroize;
+
+use {CryptoError, CryptoResult};
+
+pub const CHAIN_CODE_SIZE: usize = 32;
+pub const PRIVATE_KEY_SIZE: usize = 32;
+pub const PUBLIC_KEY_SIZE: usize = 33;
+/// The size of a serialized extended key before Base58Check encoding
+pub const EXTENDED_KEY_SIZE: usize = 78;
+pub const MIN_SEED_SIZE: usize = 16;
+pub const MAX_SEED_SIZE: usize = 64;
+
+const XPRV_VERSION: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+const XPUB_VERSION: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+const MASTER_KEY: &[u8] = b"Bitcoin seed";
+
+/// A private key with the chain code needed to derive its children
+#[derive(Clone, PartialEq, Eq)]
+pub struct ExtendedPrivateKey {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; CHAIN_CODE_SIZE],
+    private_key: PrivateKey,
+}
+
+/// A public key with the chain code needed to derive its non-hardened children
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedPublicKey {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; CHAIN_CODE_SIZE],
+    public_key: PublicKey,
+}
+
+impl ExtendedPrivateKey {
+    /// Generate the master key from a seed of 16 to 64 bytes
+    pub fn new_master(seed: &[u8]) -> CryptoResult<Self> {
+        if seed.len() < MIN_SEED_SIZE || seed.len() > MAX_SEED_SIZE {
+            return Err(CryptoError::KeyGenError(format!(
+                "Seed must be between {} and {} bytes in length",
+                MIN_SEED_SIZE, MAX_SEED_SIZE
+            )));
+        }
+        let mut i = hmac_sha512(MASTER_KEY, &[seed]);
+        let result = parse_secret_key(&i[..32]).map(|sk| ExtendedPrivateKey {
+            depth: 0,
+            parent_fingerprint: [0u8; 4],
+            child_number: 0,
+            chain_code: *array_ref!(i, 32, CHAIN_CODE_SIZE),
+            private_key: PrivateKey(sk.serialize().to_vec()),
+        });
+        i.zeroize();
+        result
+    }
+
+    /// Derive the child at `index`. Indices from `HARDENED_OFFSET` upwards are hardened.
+    pub fn derive_child(&self, index: u32) -> CryptoResult<Self> {
+        if self.depth == u8::MAX {
+            return Err(CryptoError::KeyGenError(
+                "Maximum derivation depth reached".to_string(),
+            ));
+        }
+        let mut sk = parse_secret_key(&self.private_key[..])?;
+        let mut i = if DerivationPath::is_hardened(index) {
+            let mut data = [0u8; 1 + PRIVATE_KEY_SIZE];
+            data[1..].copy_from_slice(&self.private_key[..]);
+            let i = hmac_sha512(&self.chain_code, &[&data, &index.to_be_bytes()]);
+            data.zeroize();
+            i
+        } else {
+            let pk = rustlibsecp256k1::PublicKey::from_secret_key(&sk);
+            hmac_sha512(
+                &self.chain_code,
+                &[&pk.serialize_compressed(), &index.to_be_bytes()],
+            )
+        };
+        let result = parse_secret_key(&i[..32]).and_then(|tweak| {
+            sk.tweak_add_assign(&tweak).map_err(|_| invalid_child())?;
+            Ok(ExtendedPrivateKey {
+                depth: self.depth + 1,
+                parent_fingerprint: self.fingerprint(),
+                child_number: index,
+                chain_code: *array_ref!(i, 32, CHAIN_CODE_SIZE),
+                private_key: PrivateKey(sk.serialize().to_vec()),
+            })
+        });
+        i.zeroize();
+        result
+    }
+
+    /// Derive each index of `path` in turn starting from this key
+    pub fn derive_path(&self, path: &DerivationPath) -> CryptoResult<Self> {
+        path.indices()
+            .iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    pub fn extended_public_key(&self) -> ExtendedPublicKey {
+        ExtendedPublicKey {
+            depth: self.depth,
+            parent_fingerprint: self.parent_fingerprint,
+            child_number: self.child_number,
+            chain_code: self.chain_code,
+            public_key: self.public_key(),
+        }
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        let sk = parse_secret_key(&self.private_key[..]).expect("private key is always valid");
+        PublicKey(
+            rustlibsecp256k1::PublicKey::from_secret_key(&sk)
+                .serialize_compressed()
+                .to_vec(),
+        )
+    }
+
+    pub fn private_key(&self) -> &PrivateKey {
+        &self.private_key
+    }
+
+    pub fn chain_code(&self) -> &[u8; CHAIN_CODE_SIZE] {
+        &self.chain_code
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// The first 4 bytes of HASH160 of the public key
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.public_key())
+    }
+
+    /// Encode as a Base58Check `xprv` string
+    pub fn to_xprv(&self) -> String {
+        let mut data = [0u8; 1 + PRIVATE_KEY_SIZE];
+        data[1..].copy_from_slice(&self.private_key[..]);
+        let mut payload = serialize(
+            XPRV_VERSION,
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &data,
+        );
+        data.zeroize();
+        let encoded = base58::encode_check(&payload);
+        payload.zeroize();
+        encoded
+    }
+
+    /// Decode a Base58Check `xprv` string
+    pub fn from_xprv(xprv: &str) -> CryptoResult<Self> {
+        let mut payload = base58::decode_check(xprv)?;
+        let result = deserialize(&payload, XPRV_VERSION).and_then(|fields| {
+            let (depth, parent_fingerprint, child_number, chain_code, key) = fields;
+            if key[0] != 0 {
+                return Err(CryptoError::ParseError(
+                    "Invalid extended private key".to_string(),
+                ));
+            }
+            let sk = parse_secret_key(&key[1..])?;
+            Ok(ExtendedPrivateKey {
+                depth,
+                parent_fingerprint,
+                child_number,
+                chain_code,
+                private_key: PrivateKey(sk.serialize().to_vec()),
+            })
+        });
+        payload.zeroize();
+        result
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("depth", &self.depth)
+            .field("parent_fingerprint", &self.parent_fingerprint)
+            .field("child_number", &self.child_number)
+            .field("chain_code", &"...")
+            .field("private_key", &"...")
+            .finish()
+    }
+}
+
+impl Zeroize for ExtendedPrivateKey {
+    fn zeroize(&mut self) {
+        self.chain_code.zeroize();
+        self.private_key.zeroize();
+    }
+}
+
+impl Drop for ExtendedPrivateKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ExtendedPublicKey {
+    /// Derive the non-hardened child at `index`
+    pub fn derive_child(&self, index: u32) -> CryptoResult<Self> {
+        if DerivationPath::is_hardened(index) {
+            return Err(CryptoError::KeyGenError(
+                "Hardened children cannot be derived from a public key".to_string(),
+            ));
+        }
+        if self.depth == u8::MAX {
+            return Err(CryptoError::KeyGenError(
+                "Maximum derivation depth reached".to_string(),
+            ));
+        }
+        let i = hmac_sha512(
+            &self.chain_code,
+            &[&self.public_key[..], &index.to_be_bytes()],
+        );
+        let tweak = parse_secret_key(&i[..32])?;
+        let mut pk = parse_public_key(&self.public_key[..])?;
+        pk.tweak_add_assign(&tweak).map_err(|_| invalid_child())?;
+        Ok(ExtendedPublicKey {
+            depth: self.depth + 1,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code: *array_ref!(i, 32, CHAIN_CODE_SIZE),
+            public_key: PublicKey(pk.serialize_compressed().to_vec()),
+        })
+    }
+
+    /// Derive each index of `path` in turn starting from this key
+    pub fn derive_path(&self, path: &DerivationPath) -> CryptoResult<Self> {
+        path.indices()
+            .iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    pub fn chain_code(&self) -> &[u8; CHAIN_CODE_SIZE] {
+        &self.chain_code
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    pub fn parent_fingerprint(&self) -> [u8; 4] {
+        self.parent_fingerprint
+    }
+
+    /// The first 4 bytes of HASH160 of the public key
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint(&self.public_key)
+    }
+
+    /// Encode as a Base58Check `xpub` string
+    pub fn to_xpub(&self) -> String {
+        base58::encode_check(&serialize(
+            XPUB_VERSION,
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &self.public_key[..],
+        ))
+    }
+
+    /// Decode a Base58Check `xpub` string
+    pub fn from_xpub(xpub: &str) -> CryptoResult<Self> {
+        let payload = base58::decode_check(xpub)?;
+        let (depth, parent_fingerprint, child_number, chain_code, key) =
+            deserialize(&payload, XPUB_VERSION)?;
+        let pk = parse_public_key(key)?;
+        Ok(ExtendedPublicKey {
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+            public_key: PublicKey(pk.serialize_compressed().to_vec()),
+        })
+    }
+}
+
+fn invalid_child() -> CryptoError {
+    CryptoError::KeyGenError("Derived key is invalid, use the next index".to_string())
+}
+
+fn parse_secret_key(data: &[u8]) -> CryptoResult<rustlibsecp256k1::SecretKey> {
+    if data.len() != PRIVATE_KEY_SIZE {
+        return Err(CryptoError::ParseError(format!(
+            "Private key must be {} bytes in length",
+            PRIVATE_KEY_SIZE
+        )));
+    }
+    rustlibsecp256k1::SecretKey::parse(array_ref!(data, 0, PRIVATE_KEY_SIZE))
+        .map_err(|_| invalid_child())
+}
+
+fn parse_public_key(data: &[u8]) -> CryptoResult<rustlibsecp256k1::PublicKey> {
+    if data.len() != PUBLIC_KEY_SIZE {
+        return Err(CryptoError::ParseError(format!(
+            "Public key must be {} bytes in length",
+            PUBLIC_KEY_SIZE
+        )));
+    }
+    rustlibsecp256k1::PublicKey::parse_compressed(array_ref!(data, 0, PUBLIC_KEY_SIZE))
+        .map_err(|e| CryptoError::ParseError(format!("{:?}", e)))
+}
+
+fn fingerprint(pk: &PublicKey) -> [u8; 4] {
+    let hash = ripemd160(Sha256::digest(&pk[..]).as_slice());
+    *array_ref!(hash, 0, 4)
+}
+
+fn serialize(
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: &[u8; CHAIN_CODE_SIZE],
+    key: &[u8],
+) -> Vec<u8> {
+    let mut output = Vec::with_capacity(EXTENDED_KEY_SIZE);
+    output.extend_from_slice(&version);
+    output.push(depth);
+    output.extend_from_slice(&parent_fingerprint);
+    output.extend_from_slice(&child_number.to_be_bytes());
+    output.extend_from_slice(chain_code);
+    output.extend_from_slice(key);
+    output
+}
+
+/// Depth, parent fingerprint, child number, chain code and key data
+type KeyFields<'a> = (u8, [u8; 4], u32, [u8; CHAIN_CODE_SIZE], &'a [u8]);
+
+fn deserialize(payload: &[u8], version: [u8; 4]) -> CryptoResult<KeyFields<'_>> {
+    if payload.len() != EXTENDED_KEY_SIZE {
+        return Err(CryptoError::ParseError(format!(
+            "Extended key must be {} bytes in length",
+            EXTENDED_KEY_SIZE
+        )));
+    }
+    if payload[..4] != version {
+        return Err(CryptoError::ParseError(
+            "Unexpected extended key version".to_string(),
+        ));
+    }
+    let depth = payload[4];
+    let parent_fingerprint = *array_ref!(payload, 5, 4);
+    let child_number = u32::from_be_bytes(*array_ref!(payload, 9, 4));
+    if depth == 0 && (parent_fingerprint != [0u8; 4] || child_number != 0) {
+        return Err(CryptoError::ParseError(
+            "Master key with a parent fingerprint or child number".to_string(),
+        ));
+    }
+    let chain_code = *array_ref!(payload, 13, CHAIN_CODE_SIZE);
+    Ok((
+        depth,
+        parent_fingerprint,
+        child_number,
+        chain_code,
+        &payload[45..],
+    ))
+}
+
+/// RIPEMD-160 as used in HASH160 for key fingerprints
+fn ripemd160(data: &[u8]) -> [u8; 20] {
+    const R_LEFT: [usize; 80] = [
+        0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9,
+        5, 2, 14, 11, 8, 3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, 1, 9, 11, 10, 0, 8,
+        12, 4, 13, 3, 7, 15, 14, 5, 6, 2, 4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+    ];
+    const R_RIGHT: [usize; 80] = [
+        5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, 6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8,
+        12, 4, 9, 1, 2, 15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, 8, 6, 4, 1, 3, 11,
+        15, 0, 5, 12, 2, 13, 9, 7, 10, 14, 12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+    ];
+    const S_LEFT: [u32; 80] = [
+        11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, 7, 6, 8, 13, 11, 9, 7, 15, 7, 12,
+        15, 9, 11, 7, 13, 12, 11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, 11, 12, 14,
+        15, 14, 15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, 9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11,
+        8, 5, 6,
+    ];
+    const S_RIGHT: [u32; 80] = [
+        8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, 9, 13, 15, 7, 12, 8, 9, 11, 7, 7,
+        12, 7, 6, 15, 13, 11, 9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, 15, 5, 8, 11,
+        14, 14, 6, 14, 6, 9, 12, 9, 12, 5, 15, 8, 8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13,
+        11, 11,
+    ];
+    const K_LEFT: [u32; 5] = [
+        0x0000_0000,
+        0x5a82_7999,
+        0x6ed9_eba1,
+        0x8f1b_bcdc,
+        0xa953_fd4e,
+    ];
+    const K_RIGHT: [u32; 5] = [
+        0x50a2_8be6,
+        0x5c4d_d124,
+        0x6d70_3ef3,
+        0x7a6d_76e9,
+        0x0000_0000,
+    ];
+
+    fn f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+        match round {
+            0 => x ^ y ^ z,
+            1 => (x & y) | (!x & z),
+            2 => (x | !y) ^ z,
+            3 => (x & z) | (y & !z),
+            _ => x ^ (y | !z),
+        }
+    }
+
+    let mut h: [u32; 5] = [
+        0x6745_2301,
+        0xefcd_ab89,
+        0x98ba_dcfe,
+        0x1032_5476,
+        0xc3d2_e1f0,
+    ];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_le_bytes());
+
+    for block in message.chunks(64) {
+        let mut x = [0u32; 16];
+        for (i, word) in block.chunks(4).enumerate() {
+            x[i] = u32::from_le_bytes(*array_ref!(word, 0, 4));
+        }
+        let (mut al, mut bl, mut cl, mut dl, mut el) = (h[0], h[1], h[2], h[3], h[4]);
+        let (mut ar, mut br, mut cr, mut dr, mut er) = (h[0], h[1], h[2], h[3], h[4]);
+        for j in 0..80 {
+            let round = j / 16;
+            let t = al
+                .wrapping_add(f(round, bl, cl, dl))
+                .wrapping_add(x[R_LEFT[j]])
+                .wrapping_add(K_LEFT[round])
+                .rotate_left(S_LEFT[j])
+                .wrapping_add(el);
+            al = el;
+            el = dl;
+            dl = cl.rotate_left(10);
+            cl = bl;
+            bl = t;
+            let t = ar
+                .wrapping_add(f(4 - round, br, cr, dr))
+                .wrapping_add(x[R_RIGHT[j]])
+                .wrapping_add(K_RIGHT[round])
+                .rotate_left(S_RIGHT[j])
+                .wrapping_add(er);
+            ar = er;
+            er = dr;
+            dr = cr.rotate_left(10);
+            cr = br;
+            br = t;
+        }
+        let t = h[1].wrapping_add(cl).wrapping_add(dr);
+        h[1] = h[2].wrapping_add(dl).wrapping_add(er);
+        h[2] = h[3].wrapping_add(el).wrapping_add(ar);
+        h[3] = h[4].wrapping_add(al).wrapping_add(br);
+        h[4] = h[0].wrapping_add(bl).wrapping_add(cr);
+        h[0] = t;
+    }
+
+    let mut output = [0u8; 20];
+    for (i, word) in h.iter().enumerate() {
+        output[i * 4..(i + 1) * 4].copy_from_slice(&word.to_le_bytes());
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hd::HARDENED_OFFSET;
+    use keys::KeyGenOption;
+    use signatures::secp256k1::EcdsaSecp256k1Sha256;
+    use signatures::SignatureScheme;
+
+    // BIP-32 test vector 1
+    const SEED_1: &str = "000102030405060708090a0b0c0d0e0f";
+    const VECTOR_1: [(&str, &str, &str); 6] = [
+        ("m", "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8", "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi"),
+        ("m/0H", "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw", "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7"),
+        ("m/0H/1", "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ", "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs"),
+        ("m/0H/1/2H", "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5", "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM"),
+        ("m/0H/1/2H/2", "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV", "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334"),
+        ("m/0H/1/2H/2/1000000000", "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy", "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76"),
+    ];
+
+    // BIP-32 test vector 2
+    const SEED_2: &str = "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542";
+    const VECTOR_2: [(&str, &str, &str); 6] = [
+        ("m", "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB", "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U"),
+        ("m/0", "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH", "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt"),
+        ("m/0/2147483647H", "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a", "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9"),
+        ("m/0/2147483647H/1", "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon", "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef"),
+        ("m/0/2147483647H/1/2147483646H", "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL", "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc"),
+        ("m/0/2147483647H/1/2147483646H/2", "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt", "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j"),
+    ];
+
+    // BIP-32 test vector 3
+    const SEED_3: &str = "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be";
+    const VECTOR_3: [(&str, &str, &str); 2] = [
+        ("m", "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13", "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6"),
+        ("m/0H", "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y", "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L"),
+    ];
+
+    // BIP-32 test vector 4
+    const SEED_4: &str = "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678";
+    const VECTOR_4: [(&str, &str, &str); 3] = [
+        ("m", "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa", "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv"),
+        ("m/0H", "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m", "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G"),
+        ("m/0H/1H", "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt", "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1"),
+    ];
+
+    // BIP-32 test vector 5, extended keys that must be rejected
+    const VECTOR_5_XPUB: [&str; 7] = [
+        // public key version with private key data
+        "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm",
+        // invalid public key prefixes 04 and 01
+        "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn",
+        "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4",
+        // zero depth with a non-zero parent fingerprint
+        "xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ",
+        // zero depth with a non-zero index
+        "xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8",
+        // unknown version
+        "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHPmHJiEDXkTiJTVV9rHEBUem2mwVbbNfvT2MTcAqj3nesx8uBf9",
+        // public key not on the curve
+        "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY",
+    ];
+    const VECTOR_5_XPRV: [&str; 9] = [
+        // private key version with public key data
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGTQQD3dC4H2D5GBj7vWvSQaaBv5cxi9gafk7NF3pnBju6dwKvH",
+        // invalid private key prefixes 04 and 01
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGpWnsj83BHtEy5Zt8CcDr1UiRXuWCmTQLxEK9vbz5gPstX92JQ",
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fEQ3Qen6J",
+        // zero depth with a non-zero parent fingerprint
+        "xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv",
+        // zero depth with a non-zero index
+        "xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN",
+        // unknown version
+        "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHGMQzT7ayAmfo4z3gY5KfbrZWZ6St24UVf2Qgo6oujFktLHdHY4",
+        // private keys 0 and n are out of range
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzF93Y5wvzdUayhgkkFoicQZcP3y52uPPxFnfoLZB21Teqt1VvEHx",
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD5SDKr24z3aiUvKr9bJpdrcLg1y3G",
+        // invalid checksum
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHL",
+    ];
+
+    #[test]
+    fn ripemd160_vectors() {
+        assert_eq!(
+            hex::encode(ripemd160(b"")),
+            "9c1185a5c5e9fc54612808977ee8f548b2258d31"
+        );
+        assert_eq!(
+            hex::encode(ripemd160(b"abc")),
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"
+        );
+        assert_eq!(
+            hex::encode(ripemd160(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "12a053384a9c0c88e405a06c27dcf49ada62eb2b"
+        );
+    }
+
+    fn check_vector(seed: &str, vector: &[(&str, &str, &str)]) {
+        let master = ExtendedPrivateKey::new_master(&hex::decode(seed).unwrap()).unwrap();
+        for (path, xpub, xprv) in vector.iter() {
+            let path = path.parse::<DerivationPath>().unwrap();
+            let key = master.derive_path(&path).unwrap();
+            assert_eq!(key.to_xprv(), *xprv);
+            assert_eq!(key.extended_public_key().to_xpub(), *xpub);
+            assert_eq!(ExtendedPrivateKey::from_xprv(xprv).unwrap(), key);
+            assert_eq!(
+                ExtendedPublicKey::from_xpub(xpub).unwrap(),
+                key.extended_public_key()
+            );
+        }
+    }
+
+    #[test]
+    fn bip32_test_vector_1() {
+        check_vector(SEED_1, &VECTOR_1);
+    }
+
+    #[test]
+    fn bip32_test_vector_2() {
+        check_vector(SEED_2, &VECTOR_2);
+    }
+
+    #[test]
+    fn bip32_test_vector_3() {
+        // Retention of leading zeros in the private key
+        check_vector(SEED_3, &VECTOR_3);
+    }
+
+    #[test]
+    fn bip32_test_vector_4() {
+        // Retention of leading zeros in hardened derivation
+        check_vector(SEED_4, &VECTOR_4);
+    }
+
+    #[test]
+    fn bip32_test_vector_5() {
+        // Invalid extended keys
+        for xpub in VECTOR_5_XPUB.iter() {
+            assert!(ExtendedPublicKey::from_xpub(xpub).is_err());
+        }
+        for xprv in VECTOR_5_XPRV.iter() {
+            assert!(ExtendedPrivateKey::from_xprv(xprv).is_err());
+        }
+    }
+
+    #[test]
+    fn bip32_public_derivation() {
+        let master = ExtendedPrivateKey::new_master(&[7u8; 32]).unwrap();
+        let account = master.derive_path(&"m/44'/0'/0'".parse().unwrap()).unwrap();
+        let path = "m/0/5".parse::<DerivationPath>().unwrap();
+        let child = account.derive_path(&path).unwrap();
+        let child_pub = account.extended_public_key().derive_path(&path).unwrap();
+        assert_eq!(child.extended_public_key(), child_pub);
+        assert_eq!(child.depth(), 5);
+        assert_eq!(child.child_number(), 5);
+
+        assert!(account
+            .extended_public_key()
+            .derive_child(HARDENED_OFFSET)
+            .is_err());
+
+        let scheme = EcdsaSecp256k1Sha256::new();
+        let (pk, _) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(
+                child.private_key().clone(),
+            )))
+            .unwrap();
+        assert_eq!(&pk, child_pub.public_key());
+        let signature = scheme.sign(b"hd message", child.private_key()).unwrap();
+        assert!(scheme
+            .verify(b"hd message", &signature, child_pub.public_key())
+            .unwrap());
+    }
+
+    #[test]
+    fn bip32_invalid_input() {
+        assert!(ExtendedPrivateKey::new_master(&[1u8; 15]).is_err());
+        assert!(ExtendedPrivateKey::new_master(&[1u8; 65]).is_err());
+
+        let (_, _, xprv) = VECTOR_1[1];
+        let (_, xpub, _) = VECTOR_1[1];
+        assert!(ExtendedPrivateKey::from_xprv(xpub).is_err());
+        assert!(ExtendedPublicKey::from_xpub(xprv).is_err());
+        let mut corrupted = xprv.to_string();
+        corrupted.pop();
+        corrupted.push('1');
+        assert!(ExtendedPrivateKey::from_xprv(&corrupted).is_err());
+        assert!(ExtendedPrivateKey::from_xprv("xprv0OIl").is_err());
+    }
+
+    #[test]
+    fn bip32_secrets_redacted() {
+        let master = ExtendedPrivateKey::new_master(&hex::decode(SEED_1).unwrap()).unwrap();
+        let debug = format!("{:?}", master);
+        assert!(!debug.contains(&hex::encode(&master.private_key()[..])));
+        assert!(!debug.contains(&hex::encode(master.chain_code())));
+    }
+}
@@ -0,0 +1,308 @@
+//! BIP-39 mnemonic phrases with the English wordlist
+//!
+//! A `Mnemonic` encodes 128 to 256 bits of entropy plus a checksum as 12 to 24 words.
+//! `to_seed` stretches the phrase and an optional passphrase with PBKDF2-HMAC-SHA512
+//! into a 64 byte seed for `KeyGenOption::UseSeed` or `bip32::ExtendedPrivateKey::new_master`.
+//! A different passphrase gives a different, equally valid, seed.
+
+use super::hmac_sha512;
+use keys::KeyGenOption;
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use unicode_normalization::UnicodeNormalization;
+use zeroize::Zeroize;
+
+use {CryptoError, CryptoResult};
+
+pub const SEED_SIZE: usize = 64;
+pub const MIN_WORDS: usize = 12;
+pub const MAX_WORDS: usize = 24;
+
+const WORDLIST: &str = include_str!("wordlists/english.txt");
+const PBKDF2_ROUNDS: usize = 2048;
+const BITS_PER_WORD: usize = 11;
+
+/// A mnemonic phrase whose words and checksum are known to be valid
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    phrase: String,
+    entropy: Vec<u8>,
+}
+
+impl Mnemonic {
+    /// Generate a random mnemonic with 12, 15, 18, 21 or 24 words
+    pub fn generate(word_count: usize) -> CryptoResult<Self> {
+        if !is_valid_word_count(word_count) {
+            return Err(CryptoError::KeyGenError(
+                "Mnemonic must have 12, 15, 18, 21 or 24 words".to_string(),
+            ));
+        }
+        let mut entropy = vec![0u8; word_count * 4 / 3];
+        OsRng.fill_bytes(&mut entropy);
+        let mnemonic = Self::from_entropy(&entropy);
+        entropy.zeroize();
+        mnemonic
+    }
+
+    /// Encode 16, 20, 24, 28 or 32 bytes of entropy
+    pub fn from_entropy(entropy: &[u8]) -> CryptoResult<Self> {
+        if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+            return Err(CryptoError::KeyGenError(
+                "Entropy must be 16, 20, 24, 28 or 32 bytes in length".to_string(),
+            ));
+        }
+        let checksum = Sha256::digest(entropy);
+        let mut bits = entropy.to_vec();
+        bits.push(checksum[0]);
+
+        let words = wordlist();
+        let word_count = entropy.len() * 3 / 4;
+        let phrase = (0..word_count)
+            .map(|i| words[bit_index(&bits, i * BITS_PER_WORD)])
+            .collect::<Vec<&str>>()
+            .join(" ");
+        bits.zeroize();
+        Ok(Mnemonic {
+            phrase,
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    /// Parse a phrase checking every word is in the wordlist and the checksum matches.
+    /// Words may be separated by any whitespace.
+    pub fn from_phrase(phrase: &str) -> CryptoResult<Self> {
+        let mut normalized = phrase.nfkd().collect::<String>();
+        let result = Self::parse_normalized(&normalized);
+        normalized.zeroize();
+        result
+    }
+
+    pub fn phrase(&self) -> &str {
+        self.phrase.as_str()
+    }
+
+    pub fn entropy(&self) -> &[u8] {
+        self.entropy.as_slice()
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.entropy.len() * 3 / 4
+    }
+
+    /// Derive the 64 byte seed. Use an empty `passphrase` if there is none.
+    pub fn to_seed(&self, passphrase: &str) -> Vec<u8> {
+        let mut salt = "mnemonic".to_string();
+        salt.extend(passphrase.nfkd());
+        let seed = pbkdf2_hmac_sha512(self.phrase.as_bytes(), salt.as_bytes());
+        salt.zeroize();
+        seed
+    }
+
+    /// The seed as a key generation option for any `SignatureScheme` or `KeyExchangeScheme`
+    pub fn to_key_gen_option(&self, passphrase: &str) -> KeyGenOption {
+        KeyGenOption::UseSeed(self.to_seed(passphrase))
+    }
+
+    fn parse_normalized(phrase: &str) -> CryptoResult<Self> {
+        let input = phrase.split_whitespace().collect::<Vec<&str>>();
+        if !is_valid_word_count(input.len()) {
+            return Err(CryptoError::ParseError(
+                "Mnemonic must have 12, 15, 18, 21 or 24 words".to_string(),
+            ));
+        }
+        let words = wordlist();
+        let mut bits = vec![0u8; input.len() * BITS_PER_WORD / 8 + 1];
+        for (i, word) in input.iter().enumerate() {
+            let index = words.binary_search(word).map_err(|_| {
+                CryptoError::ParseError(format!("Unknown mnemonic word '{}'", word))
+            })?;
+            for b in 0..BITS_PER_WORD {
+                if index & (1 << (BITS_PER_WORD - 1 - b)) != 0 {
+                    let bit = i * BITS_PER_WORD + b;
+                    bits[bit / 8] |= 0x80 >> (bit % 8);
+                }
+            }
+        }
+        let entropy_size = input.len() * 4 / 3;
+        let checksum_bits = entropy_size / 4;
+        let checksum = Sha256::digest(&bits[..entropy_size]);
+        let mask = 0xffu8 << (8 - checksum_bits);
+        let valid = (checksum[0] & mask) == (bits[entropy_size] & mask);
+        let entropy = bits[..entropy_size].to_vec();
+        bits.zeroize();
+        if !valid {
+            return Err(CryptoError::ParseError(
+                "Invalid mnemonic checksum".to_string(),
+            ));
+        }
+        Ok(Mnemonic {
+            phrase: input.join(" "),
+            entropy,
+        })
+    }
+}
+
+impl Drop for Mnemonic {
+    fn drop(&mut self) {
+        self.phrase.zeroize();
+        self.entropy.zeroize();
+    }
+}
+
+fn is_valid_word_count(word_count: usize) -> bool {
+    matches!(word_count, 12 | 15 | 18 | 21 | 24)
+}
+
+fn wordlist() -> Vec<&'static str> {
+    WORDLIST.lines().collect()
+}
+
+/// The 11 bit big-endian number starting at bit `start` of `bits`
+fn bit_index(bits: &[u8], start: usize) -> usize {
+    (0..BITS_PER_WORD).fold(0, |acc, i| {
+        let bit = start + i;
+        (acc << 1) | ((bits[bit / 8] >> (7 - bit % 8)) & 1) as usize
+    })
+}
+
+/// PBKDF2 with HMAC-SHA512 for a single 64 byte output block
+fn pbkdf2_hmac_sha512(password: &[u8], salt: &[u8]) -> Vec<u8> {
+    let mut u = hmac_sha512(password, &[salt, &1u32.to_be_bytes()]);
+    let mut output = u;
+    for _ in 1..PBKDF2_ROUNDS {
+        u = hmac_sha512(password, &[&u]);
+        for (o, b) in output.iter_mut().zip(u.iter()) {
+            *o ^= b;
+        }
+    }
+    u.zeroize();
+    let seed = output.to_vec();
+    output.zeroize();
+    seed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hd::bip32::ExtendedPrivateKey;
+    use kex::x25519::X25519Sha256;
+    use kex::KeyExchangeScheme;
+    use signatures::ed25519::Ed25519Sha512;
+    use signatures::secp256k1::EcdsaSecp256k1Sha256;
+    use signatures::SignatureScheme;
+
+    // Test vectors from https://github.com/trezor/python-mnemonic with passphrase "TREZOR"
+    const VECTORS: [(&str, &str, &str); 4] = [
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+        (
+            "9e885d952ad362caeb4efe34a8e91bd2",
+            "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+            "274ddc525802f7c828d8ef7ddbcdc5304e87ac3535913611fbbfa986d0c9e5476c91689f9c8a54fd55bd38606aa6a8595ad213d4c9c9f9aca3fb217069a41028",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+        ),
+    ];
+
+    #[test]
+    fn bip39_test_vectors() {
+        assert_eq!(wordlist().len(), 2048);
+        for (entropy, phrase, seed) in VECTORS.iter() {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(mnemonic.phrase(), *phrase);
+            assert_eq!(hex::encode(mnemonic.to_seed("TREZOR")), *seed);
+
+            let parsed = Mnemonic::from_phrase(phrase).unwrap();
+            assert_eq!(hex::encode(parsed.entropy()), *entropy);
+            assert!(parsed == mnemonic);
+        }
+    }
+
+    #[test]
+    fn bip39_generate() {
+        for word_count in &[12, 15, 18, 21, 24] {
+            let mnemonic = Mnemonic::generate(*word_count).unwrap();
+            assert_eq!(mnemonic.word_count(), *word_count);
+            assert_eq!(mnemonic.phrase().split(' ').count(), *word_count);
+            let parsed = Mnemonic::from_phrase(mnemonic.phrase()).unwrap();
+            assert_eq!(parsed.entropy(), mnemonic.entropy());
+        }
+        assert!(Mnemonic::generate(13).is_err());
+        assert!(Mnemonic::generate(27).is_err());
+        assert!(Mnemonic::from_entropy(&[0u8; 15]).is_err());
+    }
+
+    #[test]
+    fn bip39_invalid_phrase() {
+        let (_, phrase, _) = VECTORS[1];
+        let spaced = format!("  {}\n", phrase.replace(" ", "\t "));
+        assert_eq!(Mnemonic::from_phrase(&spaced).unwrap().phrase(), phrase);
+
+        // Wrong checksum
+        assert!(Mnemonic::from_phrase(&phrase.replace("yellow", "year")).is_err());
+        // Unknown word
+        assert!(Mnemonic::from_phrase(&phrase.replace("legal", "legals")).is_err());
+        // Wrong number of words
+        assert!(Mnemonic::from_phrase("abandon abandon abandon").is_err());
+        assert!(Mnemonic::from_phrase("").is_err());
+    }
+
+    #[test]
+    fn bip39_passphrase_normalization() {
+        let mnemonic = Mnemonic::from_phrase(VECTORS[0].1).unwrap();
+        assert_eq!(
+            mnemonic.to_seed("caf\u{e9}"),
+            mnemonic.to_seed("cafe\u{301}")
+        );
+        assert_ne!(mnemonic.to_seed(""), mnemonic.to_seed("TREZOR"));
+    }
+
+    #[test]
+    fn bip39_key_generation() {
+        let mnemonic = Mnemonic::generate(24).unwrap();
+        let restored = Mnemonic::from_phrase(mnemonic.phrase()).unwrap();
+
+        let scheme = Ed25519Sha512::new();
+        let (pk, _) = scheme
+            .keypair(Some(mnemonic.to_key_gen_option("")))
+            .unwrap();
+        let (pk1, _) = scheme
+            .keypair(Some(restored.to_key_gen_option("")))
+            .unwrap();
+        assert_eq!(pk, pk1);
+
+        let scheme = EcdsaSecp256k1Sha256::new();
+        let (pk, _) = scheme
+            .keypair(Some(mnemonic.to_key_gen_option("secret")))
+            .unwrap();
+        let (pk1, _) = scheme
+            .keypair(Some(restored.to_key_gen_option("secret")))
+            .unwrap();
+        assert_eq!(pk, pk1);
+
+        let scheme = X25519Sha256::new();
+        let (pk, _) = scheme
+            .keypair(Some(mnemonic.to_key_gen_option("")))
+            .unwrap();
+        let (pk1, _) = scheme
+            .keypair(Some(restored.to_key_gen_option("")))
+            .unwrap();
+        assert_eq!(pk, pk1);
+
+        let master = ExtendedPrivateKey::new_master(&mnemonic.to_seed("")).unwrap();
+        let restored_master = ExtendedPrivateKey::new_master(&restored.to_seed("")).unwrap();
+        assert_eq!(master, restored_master);
+    }
+}
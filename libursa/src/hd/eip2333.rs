@@ -0,0 +1,158 @@
+//! EIP-2333 hierarchical deterministic BLS12-381 keys
+//!
+//! Every child is derived through a Lamport public key of its parent so there is no
+//! public derivation and no chain code. The private keys are in the encoding used by
+//! `signatures::bls` and can be passed to its `keypair` with `KeyGenOption::FromSecretKey`.
+//! Paths for validator keys are defined in EIP-2334, `m/12381/3600/0/0/0` for example.
+
+use super::DerivationPath;
+use amcl_wrapper::field_elem::FieldElement;
+use keys::PrivateKey;
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use {CryptoError, CryptoResult};
+
+pub const MIN_SEED_SIZE: usize = 32;
+
+/// Output length of HKDF_mod_r
+const L: usize = 48;
+const LAMPORT_CHUNKS: usize = 255;
+const DIGEST_SIZE: usize = 32;
+
+/// Derive the master private key from a seed of at least 32 bytes
+pub fn derive_master_secret_key(seed: &[u8]) -> CryptoResult<PrivateKey> {
+    if seed.len() < MIN_SEED_SIZE {
+        return Err(CryptoError::KeyGenError(format!(
+            "Seed must be at least {} bytes in length",
+            MIN_SEED_SIZE
+        )));
+    }
+    Ok(PrivateKey(hkdf_mod_r(seed)?.to_bytes()))
+}
+
+/// Derive the child of `parent` at `index`
+pub fn derive_child_secret_key(parent: &PrivateKey, index: u32) -> CryptoResult<PrivateKey> {
+    let parent = FieldElement::from_bytes(&parent[..])
+        .map_err(|_| CryptoError::ParseError("Failed to parse private key.".to_string()))?;
+    let lamport_pk = parent_sk_to_lamport_pk(&parent, index)?;
+    Ok(PrivateKey(hkdf_mod_r(&lamport_pk)?.to_bytes()))
+}
+
+/// Derive the master key from `seed` followed by each index of `path`
+pub fn derive_secret_key(seed: &[u8], path: &DerivationPath) -> CryptoResult<PrivateKey> {
+    path.indices()
+        .iter()
+        .try_fold(derive_master_secret_key(seed)?, |sk, index| {
+            derive_child_secret_key(&sk, *index)
+        })
+}
+
+fn hkdf_mod_r(ikm: &[u8]) -> CryptoResult<FieldElement> {
+    let mut salt = Sha256::digest(b"BLS-SIG-KEYGEN-SALT-");
+    let mut input = ikm.to_vec();
+    input.push(0); // IKM || I2OSP(0, 1)
+    let info = [0u8, L as u8]; // key_info || I2OSP(L, 2)
+    let mut okm = [0u8; L];
+    loop {
+        hkdf::Hkdf::<Sha256>::new(Some(salt.as_slice()), &input)
+            .expand(&info, &mut okm)
+            .map_err(|err| {
+                CryptoError::KeyGenError(format!("Failed to generate keypair: {}", err))
+            })?;
+        let sk = FieldElement::from_bytes(&okm)
+            .map_err(|_| CryptoError::KeyGenError("Failed to generate keypair".to_string()))?;
+        if !sk.is_zero() {
+            input.zeroize();
+            okm.zeroize();
+            return Ok(sk);
+        }
+        salt = Sha256::digest(salt.as_slice());
+    }
+}
+
+fn parent_sk_to_lamport_pk(parent: &FieldElement, index: u32) -> CryptoResult<Vec<u8>> {
+    let salt = index.to_be_bytes();
+    let mut bytes = parent.to_bytes();
+    let mut ikm = [0u8; DIGEST_SIZE];
+    ikm.copy_from_slice(&bytes[bytes.len() - DIGEST_SIZE..]);
+    bytes.zeroize();
+
+    let mut lamport_pk = Sha256::new();
+    for _ in 0..2 {
+        let mut lamport_sk = [0u8; LAMPORT_CHUNKS * DIGEST_SIZE];
+        hkdf::Hkdf::<Sha256>::new(Some(&salt[..]), &ikm)
+            .expand(&[], &mut lamport_sk[..])
+            .map_err(|err| {
+                CryptoError::KeyGenError(format!("Failed to generate keypair: {}", err))
+            })?;
+        for chunk in lamport_sk.chunks(DIGEST_SIZE) {
+            lamport_pk.input(Sha256::digest(chunk).as_slice());
+        }
+        lamport_sk.zeroize();
+        for b in ikm.iter_mut() {
+            *b = !*b;
+        }
+    }
+    ikm.zeroize();
+    Ok(lamport_pk.result().to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use keys::KeyGenOption;
+    use signatures::bls::normal::Bls;
+    use signatures::SignatureScheme;
+
+    // EIP-2333 test cases as (seed, master_SK, child_index, child_SK)
+    const VECTORS: [(&str, &str, u32, &str); 3] = [
+        (
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            "0d7359d57963ab8fbbde1852dcf553fedbc31f464d80ee7d40ae683122b45070",
+            0,
+            "2d18bd6c14e6d15bf8b5085c9b74f3daae3b03cc2014770a599d8c1539e50f8e",
+        ),
+        (
+            "3141592653589793238462643383279502884197169399375105820974944592",
+            "41c9e07822b092a93fd6797396338c3ada4170cc81829fdfce6b5d34bd5e7ec7",
+            3141592653,
+            "384843fad5f3d777ea39de3e47a8f999ae91f89e42bffa993d91d9782d152a0f",
+        ),
+        (
+            "0099ff991111002299dd7744ee3355bbdd8844115566cc55663355668888cc00",
+            "3cfa341ab3910a7d00d933d8f7c4fe87c91798a0397421d6b19fd5b815132e80",
+            4294967295,
+            "40e86285582f35b28821340f6a53b448588efa575bc4d88c32ef8567b8d9479b",
+        ),
+    ];
+
+    #[test]
+    fn eip2333_test_vectors() {
+        for (seed, master, index, child) in VECTORS.iter() {
+            let seed = hex::decode(seed).unwrap();
+            let master_sk = derive_master_secret_key(&seed).unwrap();
+            assert_eq!(hex::encode(&master_sk[master_sk.len() - 32..]), *master);
+            let child_sk = derive_child_secret_key(&master_sk, *index).unwrap();
+            assert_eq!(hex::encode(&child_sk[child_sk.len() - 32..]), *child);
+            assert_eq!(
+                derive_secret_key(&seed, &DerivationPath::new(vec![*index])).unwrap(),
+                child_sk
+            );
+        }
+    }
+
+    #[test]
+    fn eip2333_bls_keys() {
+        let sk = derive_secret_key(&[5u8; 32], &"m/12381/3600/0/0/0".parse().unwrap()).unwrap();
+        let scheme = Bls::new();
+        let (pk, sk) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(sk)))
+            .unwrap();
+        let signature = scheme.sign(b"hd message", &sk).unwrap();
+        assert!(scheme.verify(b"hd message", &signature, &pk).unwrap());
+
+        assert!(derive_master_secret_key(&[5u8; 31]).is_err());
+        assert!(derive_child_secret_key(&PrivateKey(vec![1u8; 4]), 0).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! Hierarchical deterministic key derivation
+//!
+//! Every key in a wallet can be derived from one master seed along a `DerivationPath`
+//! like `m/44'/0'/0'/0/1`.
+//!
+//! * `bip39` encodes the entropy behind a master seed as a mnemonic phrase that can be
+//!   written down as a backup
+//!   (see <https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki>).
+//! * `bip32` derives secp256k1 keys for `EcdsaSecp256k1Sha256` with hardened and
+//!   non-hardened children and encodes them as xprv/xpub strings
+//!   (see <https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki>).
+//! * `slip10` derives Ed25519 keys for `Ed25519Sha512`. Only hardened children exist
+//!   (see <https://github.com/satoshilabs/slips/blob/master/slip-0010.md>).
+//! * `eip2333` derives BLS12-381 keys for `signatures::bls`
+//!   (see <https://eips.ethereum.org/EIPS/eip-2333>).
+
+use std::fmt;
+use std::str::FromStr;
+
+use CryptoError;
+
+#[cfg(feature = "bip32")]
+pub mod bip32;
+#[cfg(feature = "bip39")]
+pub mod bip39;
+#[cfg(feature = "eip2333")]
+pub mod eip2333;
+#[cfg(feature = "slip10")]
+pub mod slip10;
+
+/// Child indices at or above this value are hardened
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// A list of child indices starting from the master key.
+///
+/// Parsed from strings like `m/44'/60'/0'/0/0` where a trailing `'`, `h` or `H`
+/// marks a hardened index.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    pub fn new(indices: Vec<u32>) -> Self {
+        DerivationPath(indices)
+    }
+
+    pub fn indices(&self) -> &[u32] {
+        self.0.as_slice()
+    }
+
+    pub fn is_hardened(index: u32) -> bool {
+        index >= HARDENED_OFFSET
+    }
+}
+
+impl From<Vec<u32>> for DerivationPath {
+    fn from(indices: Vec<u32>) -> Self {
+        DerivationPath(indices)
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split('/');
+        if parts.next() != Some("m") {
+            return Err(CryptoError::ParseError(
+                "Derivation path must start with 'm'".to_string(),
+            ));
+        }
+        let mut indices = Vec::new();
+        for part in parts {
+            let (number, offset) =
+                if part.ends_with('\'') || part.ends_with('h') || part.ends_with('H') {
+                    (&part[..part.len() - 1], HARDENED_OFFSET)
+                } else {
+                    (part, 0)
+                };
+            let index = number
+                .parse::<u32>()
+                .ok()
+                .filter(|i| *i < HARDENED_OFFSET && !number.starts_with('+'))
+                .ok_or_else(|| {
+                    CryptoError::ParseError(format!("Invalid derivation path index '{}'", part))
+                })?;
+            indices.push(index + offset);
+        }
+        Ok(DerivationPath(indices))
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.0 {
+            if DerivationPath::is_hardened(*index) {
+                write!(f, "/{}'", index - HARDENED_OFFSET)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(any(feature = "bip32", feature = "bip39", feature = "slip10"))]
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> [u8; 64] {
+    use hmac::{Hmac, Mac};
+    use sha2::Sha512;
+
+    let mut mac = Hmac::<Sha512>::new_varkey(key).expect("HMAC accepts keys of any size");
+    for d in data {
+        mac.input(d);
+    }
+    let mut output = [0u8; 64];
+    output.copy_from_slice(mac.result().code().as_slice());
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derivation_path() {
+        let path = "m/44'/60'/0h/0/1".parse::<DerivationPath>().unwrap();
+        assert_eq!(
+            path.indices(),
+            &[
+                44 + HARDENED_OFFSET,
+                60 + HARDENED_OFFSET,
+                HARDENED_OFFSET,
+                0,
+                1
+            ]
+        );
+        assert_eq!(path.to_string(), "m/44'/60'/0'/0/1");
+        assert_eq!(
+            "m".parse::<DerivationPath>().unwrap(),
+            DerivationPath::default()
+        );
+
+        assert!("".parse::<DerivationPath>().is_err());
+        assert!("44'/0'".parse::<DerivationPath>().is_err());
+        assert!("m/".parse::<DerivationPath>().is_err());
+        assert!("m/x".parse::<DerivationPath>().is_err());
+        assert!("m/+1".parse::<DerivationPath>().is_err());
+        assert!("m/2147483648".parse::<DerivationPath>().is_err());
+        assert!("m/1''".parse::<DerivationPath>().is_err());
+    }
+}
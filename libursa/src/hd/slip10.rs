@@ -0,0 +1,196 @@
+//! SLIP-10 hierarchical deterministic Ed25519 keys
+//!
+//! Ed25519 only supports hardened derivation so every index in a path is hardened,
+//! `m/44'/501'/0'` for example. The private keys are the 64 byte `seed || public key`
+//! encoding used by `Ed25519Sha512`.
+
+use super::{hmac_sha512, DerivationPath};
+use ed25519_dalek::{PublicKey as DalekPublicKey, SecretKey as DalekSecretKey};
+use keys::{PrivateKey, PublicKey};
+use std::fmt;
+use zeroize::Zeroize;
+
+use {CryptoError, CryptoResult};
+
+pub const CHAIN_CODE_SIZE: usize = 32;
+pub const SEED_SIZE: usize = 32;
+pub const MIN_SEED_SIZE: usize = 16;
+pub const MAX_SEED_SIZE: usize = 64;
+
+const MASTER_KEY: &[u8] = b"ed25519 seed";
+
+/// An Ed25519 private key with the chain code needed to derive its children
+#[derive(Clone, PartialEq, Eq)]
+pub struct Ed25519ExtendedPrivateKey {
+    depth: u8,
+    child_number: u32,
+    chain_code: [u8; CHAIN_CODE_SIZE],
+    seed: [u8; SEED_SIZE],
+}
+
+impl Ed25519ExtendedPrivateKey {
+    /// Generate the master key from a seed of 16 to 64 bytes
+    pub fn new_master(seed: &[u8]) -> CryptoResult<Self> {
+        if seed.len() < MIN_SEED_SIZE || seed.len() > MAX_SEED_SIZE {
+            return Err(CryptoError::KeyGenError(format!(
+                "Seed must be between {} and {} bytes in length",
+                MIN_SEED_SIZE, MAX_SEED_SIZE
+            )));
+        }
+        Ok(Self::from_hmac(0, 0, hmac_sha512(MASTER_KEY, &[seed])))
+    }
+
+    /// Derive the child at `index` which must be hardened
+    pub fn derive_child(&self, index: u32) -> CryptoResult<Self> {
+        if !DerivationPath::is_hardened(index) {
+            return Err(CryptoError::KeyGenError(
+                "Ed25519 only supports hardened derivation".to_string(),
+            ));
+        }
+        if self.depth == u8::MAX {
+            return Err(CryptoError::KeyGenError(
+                "Maximum derivation depth reached".to_string(),
+            ));
+        }
+        let mut data = [0u8; 1 + SEED_SIZE];
+        data[1..].copy_from_slice(&self.seed);
+        let i = hmac_sha512(&self.chain_code, &[&data, &index.to_be_bytes()]);
+        data.zeroize();
+        Ok(Self::from_hmac(self.depth + 1, index, i))
+    }
+
+    /// Derive each index of `path` in turn starting from this key
+    pub fn derive_path(&self, path: &DerivationPath) -> CryptoResult<Self> {
+        path.indices()
+            .iter()
+            .try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    /// The private key in the encoding used by `Ed25519Sha512`
+    pub fn private_key(&self) -> PrivateKey {
+        let mut sk = self.seed.to_vec();
+        sk.extend_from_slice(&self.public_key()[..]);
+        PrivateKey(sk)
+    }
+
+    pub fn public_key(&self) -> PublicKey {
+        let secret = DalekSecretKey::from_bytes(&self.seed).expect("seed is always 32 bytes");
+        PublicKey(DalekPublicKey::from(&secret).to_bytes().to_vec())
+    }
+
+    pub fn chain_code(&self) -> &[u8; CHAIN_CODE_SIZE] {
+        &self.chain_code
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn child_number(&self) -> u32 {
+        self.child_number
+    }
+
+    fn from_hmac(depth: u8, child_number: u32, mut i: [u8; 64]) -> Self {
+        let key = Ed25519ExtendedPrivateKey {
+            depth,
+            child_number,
+            chain_code: *array_ref!(i, 32, CHAIN_CODE_SIZE),
+            seed: *array_ref!(i, 0, SEED_SIZE),
+        };
+        i.zeroize();
+        key
+    }
+}
+
+impl fmt::Debug for Ed25519ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Ed25519ExtendedPrivateKey")
+            .field("depth", &self.depth)
+            .field("child_number", &self.child_number)
+            .field("chain_code", &"...")
+            .field("seed", &"...")
+            .finish()
+    }
+}
+
+impl Zeroize for Ed25519ExtendedPrivateKey {
+    fn zeroize(&mut self) {
+        self.chain_code.zeroize();
+        self.seed.zeroize();
+    }
+}
+
+impl Drop for Ed25519ExtendedPrivateKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use keys::KeyGenOption;
+    use signatures::ed25519::Ed25519Sha512;
+    use signatures::SignatureScheme;
+
+    // SLIP-10 test vector 1 for ed25519
+    const SEED_1: &str = "000102030405060708090a0b0c0d0e0f";
+    const VECTOR_1: [(&str, &str, &str, &str); 4] = [
+        (
+            "m",
+            "90046a93de5380a72b5e45010748567d5ea02bbf6522f979e05c0d8d8ca9fffb",
+            "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7",
+            "a4b2856bfec510abab89753fac1ac0e1112364e7d250545963f135f2a33188ed",
+        ),
+        (
+            "m/0H",
+            "8b59aa11380b624e81507a27fedda59fea6d0b779a778918a2fd3590e16e9c69",
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3",
+            "8c8a13df77a28f3445213a0f432fde644acaa215fc72dcdf300d5efaa85d350c",
+        ),
+        (
+            "m/0H/1H",
+            "a320425f77d1b5c2505a6b1b27382b37368ee640e3557c315416801243552f14",
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2",
+            "1932a5270f335bed617d5b935c80aedb1a35bd9fc1e31acafd5372c30f5c1187",
+        ),
+        (
+            "m/0H/1H/2H",
+            "2e69929e00b5ab250f49c3fb1c12f252de4fed2c1db88387094a0f8c4c9ccd6c",
+            "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9",
+            "ae98736566d30ed0e9d2f4486a64bc95740d89c7db33f52121f8ea8f76ff0fc1",
+        ),
+    ];
+
+    #[test]
+    fn slip10_test_vector_1() {
+        let master = Ed25519ExtendedPrivateKey::new_master(&hex::decode(SEED_1).unwrap()).unwrap();
+        for (path, chain_code, seed, public_key) in VECTOR_1.iter() {
+            let key = master.derive_path(&path.parse().unwrap()).unwrap();
+            assert_eq!(hex::encode(key.chain_code()), *chain_code);
+            assert_eq!(hex::encode(&key.private_key()[..SEED_SIZE]), *seed);
+            assert_eq!(hex::encode(&key.public_key()[..]), *public_key);
+        }
+    }
+
+    #[test]
+    fn slip10_ed25519_keys() {
+        let master = Ed25519ExtendedPrivateKey::new_master(&[3u8; 32]).unwrap();
+        let key = master
+            .derive_path(&"m/44'/501'/0'".parse().unwrap())
+            .unwrap();
+        assert_eq!(key.depth(), 3);
+
+        let scheme = Ed25519Sha512::new();
+        let (pk, sk) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(key.private_key())))
+            .unwrap();
+        assert_eq!(pk, key.public_key());
+        let signature = scheme.sign(b"hd message", &sk).unwrap();
+        assert!(scheme.verify(b"hd message", &signature, &pk).unwrap());
+
+        assert!(master.derive_child(0).is_err());
+        assert!(master.derive_path(&"m/0'/1".parse().unwrap()).is_err());
+        assert!(Ed25519ExtendedPrivateKey::new_master(&[1u8; 8]).is_err());
+    }
+}
@@ -32,8 +32,12 @@ pub trait KeyExchangeScheme {
     fn private_key_size() -> usize;
 }
 
+#[cfg(any(feature = "ecdh_nist", feature = "ecdh_nist_native"))]
+pub mod nist;
 #[cfg(any(feature = "x25519", feature = "x25519_asm"))]
 pub mod x25519;
+#[cfg(feature = "x448")]
+pub mod x448;
 
 #[cfg(any(
     feature = "ecdh_secp256k1",
@@ -0,0 +1,324 @@
+//! Elliptic curve Diffie-Hellman on the NIST curves from FIPS 186-4, P-256 in `p256`
+//! and P-384 in `p384`.
+//!
+//! Public keys are compressed SEC1 points but uncompressed points are accepted as remote keys.
+//! Like the other key exchange schemes the shared secret is a hash of the x-coordinate of
+//! the shared point, `Z` in NIST SP 800-56A, with SHA-256 for P-256 and SHA-384 for P-384.
+//! The `ecdh_nist` feature is implemented in Rust with AMCL and `ecdh_nist_native` with OpenSSL.
+
+macro_rules! ecdh_nist_impl {
+    ($name:ident, $size:expr, $amcl_curve:ident, $nid:ident, $digest:ident) => {
+        use super::super::KeyExchangeScheme;
+        use keys::{KeyGenOption, PrivateKey, PublicKey, SessionKey};
+        use rand::rngs::OsRng;
+        use rand::{RngCore, SeedableRng};
+        use rand_chacha::ChaChaRng;
+        use sha2::Digest;
+        use zeroize::Zeroize;
+
+        use CryptoError;
+
+        pub const PRIVATE_KEY_SIZE: usize = $size;
+        pub const PUBLIC_KEY_SIZE: usize = $size + 1;
+        pub const PUBLIC_UNCOMPRESSED_KEY_SIZE: usize = 2 * $size + 1;
+        pub const SHARED_SECRET_SIZE: usize = $size;
+
+        pub struct $name(ecdh_impl::EcdhImpl);
+
+        impl KeyExchangeScheme for $name {
+            fn new() -> Self {
+                $name(ecdh_impl::EcdhImpl::new())
+            }
+
+            fn keypair(
+                &self,
+                option: Option<KeyGenOption>,
+            ) -> Result<(PublicKey, PrivateKey), CryptoError> {
+                let mut s = [0u8; PRIVATE_KEY_SIZE];
+                match option {
+                    Some(mut o) => match o {
+                        KeyGenOption::UseSeed(ref mut seed) => {
+                            if seed.len() < 32 {
+                                return Err(CryptoError::KeyGenError(
+                                    "Seed must be at least 32 bytes".to_string(),
+                                ));
+                            }
+                            let mut rng = ChaChaRng::from_seed(*array_ref!(seed.as_slice(), 0, 32));
+                            seed.zeroize();
+                            rng.fill_bytes(&mut s);
+                        }
+                        KeyGenOption::FromSecretKey(ref sk) => {
+                            let pk = self.0.public_key(sk)?;
+                            return Ok((pk, PrivateKey(sk[..].to_vec())));
+                        }
+                    },
+                    None => OsRng.fill_bytes(&mut s),
+                };
+                let mut sk = PrivateKey(sha2::$digest::digest(&s).to_vec());
+                s.zeroize();
+                // Hash again in the unlikely case the digest is not less than the group order
+                loop {
+                    if let Ok(pk) = self.0.public_key(&sk) {
+                        return Ok((pk, sk));
+                    }
+                    sk = PrivateKey(sha2::$digest::digest(&sk[..]).to_vec());
+                }
+            }
+
+            fn compute_shared_secret(
+                &self,
+                local_private_key: &PrivateKey,
+                remote_public_key: &PublicKey,
+            ) -> Result<SessionKey, CryptoError> {
+                let mut z = self
+                    .0
+                    .compute_shared_secret(local_private_key, remote_public_key)?;
+                let hash = sha2::$digest::digest(&z[..]);
+                z.zeroize();
+                Ok(SessionKey(hash.to_vec()))
+            }
+
+            fn public_key_size() -> usize {
+                PUBLIC_KEY_SIZE
+            }
+            fn private_key_size() -> usize {
+                PRIVATE_KEY_SIZE
+            }
+            fn shared_secret_size() -> usize {
+                SHARED_SECRET_SIZE
+            }
+        }
+
+        #[cfg(feature = "ecdh_nist_native")]
+        mod ecdh_impl {
+            use super::*;
+            use openssl::bn::{BigNum, BigNumContext};
+            use openssl::ec::{EcGroup, EcPoint, PointConversionForm};
+            use openssl::nid::Nid;
+
+            pub struct EcdhImpl(EcGroup);
+
+            impl EcdhImpl {
+                pub fn new() -> Self {
+                    EcdhImpl(EcGroup::from_curve_name(Nid::$nid).unwrap())
+                }
+                pub fn public_key(&self, sk: &PrivateKey) -> Result<PublicKey, CryptoError> {
+                    let s = self.secret(sk)?;
+                    let mut ctx = BigNumContext::new().map_err(general_error)?;
+                    let mut point = EcPoint::new(&self.0).map_err(general_error)?;
+                    point
+                        .mul_generator(&self.0, &s, &ctx)
+                        .map_err(general_error)?;
+                    let pk = point
+                        .to_bytes(&self.0, PointConversionForm::COMPRESSED, &mut ctx)
+                        .map_err(general_error)?;
+                    Ok(PublicKey(pk))
+                }
+                pub fn compute_shared_secret(
+                    &self,
+                    local_private_key: &PrivateKey,
+                    remote_public_key: &PublicKey,
+                ) -> Result<SessionKey, CryptoError> {
+                    let s = self.secret(local_private_key)?;
+                    let len = remote_public_key.len();
+                    if len != PUBLIC_KEY_SIZE && len != PUBLIC_UNCOMPRESSED_KEY_SIZE {
+                        return Err(CryptoError::ParseError("Invalid key length".to_string()));
+                    }
+                    let mut ctx = BigNumContext::new().map_err(general_error)?;
+                    let remote = EcPoint::from_bytes(&self.0, &remote_public_key[..], &mut ctx)
+                        .map_err(|e| CryptoError::ParseError(e.to_string()))?;
+                    let mut shared = EcPoint::new(&self.0).map_err(general_error)?;
+                    shared
+                        .mul(&self.0, &remote, &s, &ctx)
+                        .map_err(general_error)?;
+                    let mut x = BigNum::new().map_err(general_error)?;
+                    let mut y = BigNum::new().map_err(general_error)?;
+                    shared
+                        .affine_coordinates_gfp(&self.0, &mut x, &mut y, &mut ctx)
+                        .map_err(general_error)?;
+                    Ok(SessionKey(x.to_vec_padded($size).map_err(general_error)?))
+                }
+
+                fn secret(&self, sk: &PrivateKey) -> Result<BigNum, CryptoError> {
+                    let mut ctx = BigNumContext::new().map_err(general_error)?;
+                    let mut order = BigNum::new().map_err(general_error)?;
+                    self.0.order(&mut order, &mut ctx).map_err(general_error)?;
+                    let s = BigNum::from_slice(&sk[..]).map_err(general_error)?;
+                    if sk.len() != PRIVATE_KEY_SIZE || s.num_bits() == 0 || s >= order {
+                        return Err(CryptoError::ParseError(
+                            "Invalid private key provided".to_string(),
+                        ));
+                    }
+                    Ok(s)
+                }
+            }
+
+            fn general_error(e: openssl::error::ErrorStack) -> CryptoError {
+                CryptoError::GeneralError(e.to_string())
+            }
+        }
+
+        #[cfg(feature = "ecdh_nist")]
+        mod ecdh_impl {
+            use super::*;
+            use amcl::$amcl_curve::{big::BIG, ecdh, ecp::ECP, rom::CURVE_ORDER};
+
+            pub struct EcdhImpl;
+
+            impl EcdhImpl {
+                pub fn new() -> Self {
+                    EcdhImpl
+                }
+                pub fn public_key(&self, sk: &PrivateKey) -> Result<PublicKey, CryptoError> {
+                    let s = secret(sk)?;
+                    let mut pk = [0u8; PUBLIC_KEY_SIZE];
+                    ECP::generator().mul(&s).tobytes(&mut pk, true);
+                    Ok(PublicKey(pk.to_vec()))
+                }
+                pub fn compute_shared_secret(
+                    &self,
+                    local_private_key: &PrivateKey,
+                    remote_public_key: &PublicKey,
+                ) -> Result<SessionKey, CryptoError> {
+                    secret(local_private_key)?;
+                    let valid = match remote_public_key.len() {
+                        PUBLIC_KEY_SIZE => {
+                            remote_public_key[0] == 0x02 || remote_public_key[0] == 0x03
+                        }
+                        PUBLIC_UNCOMPRESSED_KEY_SIZE => remote_public_key[0] == 0x04,
+                        _ => false,
+                    };
+                    if !valid {
+                        return Err(CryptoError::ParseError("Invalid key length".to_string()));
+                    }
+                    // Points that are not on the curve are infinity
+                    if ECP::frombytes(&remote_public_key[..]).is_infinity() {
+                        return Err(CryptoError::ParseError(
+                            "Invalid public key provided".to_string(),
+                        ));
+                    }
+                    let mut z = [0u8; SHARED_SECRET_SIZE];
+                    if ecdh::ecpsvdp_dh(&local_private_key[..], &remote_public_key[..], &mut z) != 0
+                    {
+                        return Err(CryptoError::GeneralError(
+                            "Unable to compute the shared secret".to_string(),
+                        ));
+                    }
+                    Ok(SessionKey(z.to_vec()))
+                }
+            }
+
+            fn secret(sk: &PrivateKey) -> Result<BIG, CryptoError> {
+                if sk.len() != PRIVATE_KEY_SIZE {
+                    return Err(CryptoError::ParseError(
+                        "Invalid private key length".to_string(),
+                    ));
+                }
+                let s = BIG::frombytes(&sk[..]);
+                if s.iszilch() || BIG::comp(&s, &BIG::new_ints(&CURVE_ORDER)) >= 0 {
+                    return Err(CryptoError::ParseError(
+                        "Invalid private key provided".to_string(),
+                    ));
+                }
+                Ok(s)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+macro_rules! ecdh_nist_tests_impl {
+    ($name:ident, $nid:ident, $digest:ident) => {
+        use openssl::derive::Deriver;
+        use openssl::ec::{EcGroup, EcKey};
+        use openssl::nid::Nid;
+        use openssl::pkey::PKey;
+
+        #[test]
+        fn key_exchange() {
+            let scheme = $name::new();
+            let (pk, sk) = scheme.keypair(None).unwrap();
+            assert_eq!(pk.len(), PUBLIC_KEY_SIZE);
+            assert_eq!(sk.len(), PRIVATE_KEY_SIZE);
+            let (pk1, sk1) = scheme.keypair(None).unwrap();
+            let secret = scheme.compute_shared_secret(&sk, &pk1).unwrap();
+            assert_eq!(secret.len(), SHARED_SECRET_SIZE);
+            assert_eq!(secret, scheme.compute_shared_secret(&sk1, &pk).unwrap());
+
+            let (pk2, sk2) = scheme
+                .keypair(Some(KeyGenOption::FromSecretKey(sk.clone())))
+                .unwrap();
+            assert_eq!(pk2, pk);
+            assert_eq!(sk2, sk);
+
+            let seed = vec![5u8; 32];
+            let (pk3, _) = scheme
+                .keypair(Some(KeyGenOption::UseSeed(seed.clone())))
+                .unwrap();
+            let (pk4, _) = scheme.keypair(Some(KeyGenOption::UseSeed(seed))).unwrap();
+            assert_eq!(pk3, pk4);
+
+            assert!(scheme
+                .keypair(Some(KeyGenOption::FromSecretKey(PrivateKey(vec![
+                    0u8;
+                    PRIVATE_KEY_SIZE
+                ]))))
+                .is_err());
+            assert!(scheme
+                .compute_shared_secret(&sk, &PublicKey(pk1[1..].to_vec()))
+                .is_err());
+        }
+
+        #[test]
+        fn openssl_compatibility() {
+            let scheme = $name::new();
+            let (pk, sk) = scheme.keypair(None).unwrap();
+
+            let group = EcGroup::from_curve_name(Nid::$nid).unwrap();
+            let key = EcKey::generate(&group).unwrap();
+            let mut ctx = openssl::bn::BigNumContext::new().unwrap();
+            let uncompressed = key
+                .public_key()
+                .to_bytes(
+                    &group,
+                    openssl::ec::PointConversionForm::UNCOMPRESSED,
+                    &mut ctx,
+                )
+                .unwrap();
+            let remote = PKey::from_ec_key(key).unwrap();
+
+            let point = openssl::ec::EcPoint::from_bytes(&group, &pk[..], &mut ctx).unwrap();
+            let local = PKey::from_ec_key(EcKey::from_public_key(&group, &point).unwrap()).unwrap();
+            let mut deriver = Deriver::new(&remote).unwrap();
+            deriver.set_peer(&local).unwrap();
+            let expected = sha2::$digest::digest(&deriver.derive_to_vec().unwrap());
+
+            let secret = scheme
+                .compute_shared_secret(&sk, &PublicKey(uncompressed))
+                .unwrap();
+            assert_eq!(secret[..], expected[..]);
+        }
+    };
+}
+
+pub mod p256 {
+    ecdh_nist_impl!(EcdhP256Sha256, 32, nist256, X9_62_PRIME256V1, Sha256);
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        ecdh_nist_tests_impl!(EcdhP256Sha256, X9_62_PRIME256V1, Sha256);
+    }
+}
+
+pub mod p384 {
+    ecdh_nist_impl!(EcdhP384Sha384, 48, nist384, SECP384R1, Sha384);
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        ecdh_nist_tests_impl!(EcdhP384Sha384, SECP384R1, Sha384);
+    }
+}
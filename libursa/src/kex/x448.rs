@@ -0,0 +1,280 @@
+//! Diffie-Hellman on Curve448 as specified in RFC 7748
+//! (see <https://tools.ietf.org/html/rfc7748#section-5>).
+//!
+//! Keys are 56 byte little-endian encodings and private keys are stored clamped.
+//! The shared secret is the SHA-512 hash of the u-coordinate of the shared point,
+//! like `X25519Sha256` hashes its output with SHA-256.
+
+use super::*;
+use amcl::arch::Chunk;
+use amcl::goldilocks::big::BIG;
+use amcl::goldilocks::fp::FP;
+use amcl::goldilocks::rom;
+use rand::rngs::OsRng;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use sha2::Digest;
+use zeroize::Zeroize;
+
+use CryptoError;
+
+pub const PRIVATE_KEY_SIZE: usize = 56;
+pub const PUBLIC_KEY_SIZE: usize = 56;
+pub const SHARED_SECRET_SIZE: usize = 64;
+
+/// (A - 2) / 4 for Curve448
+const A24: isize = 39081;
+
+pub struct X448Sha512;
+
+impl KeyExchangeScheme for X448Sha512 {
+    fn new() -> Self {
+        Self
+    }
+
+    fn keypair(
+        &self,
+        option: Option<KeyGenOption>,
+    ) -> Result<(PublicKey, PrivateKey), CryptoError> {
+        let mut sk = [0u8; PRIVATE_KEY_SIZE];
+        match option {
+            Some(mut o) => match o {
+                KeyGenOption::UseSeed(ref mut s) => {
+                    let hash = sha2::Sha256::digest(s.as_slice());
+                    s.zeroize();
+                    let mut rng = ChaChaRng::from_seed(*array_ref!(hash.as_slice(), 0, 32));
+                    rng.fill_bytes(&mut sk);
+                }
+                KeyGenOption::FromSecretKey(ref s) => {
+                    sk.copy_from_slice(&parse_key(&s[..], "Private")?[..]);
+                }
+            },
+            None => OsRng.fill_bytes(&mut sk),
+        };
+        clamp(&mut sk);
+        let mut base = [0u8; PUBLIC_KEY_SIZE];
+        base[0] = 5;
+        let pk = x448(&sk, &base);
+        let private = PrivateKey(sk.to_vec());
+        sk.zeroize();
+        Ok((PublicKey(pk.to_vec()), private))
+    }
+
+    fn compute_shared_secret(
+        &self,
+        local_private_key: &PrivateKey,
+        remote_public_key: &PublicKey,
+    ) -> Result<SessionKey, CryptoError> {
+        let mut sk = parse_key(&local_private_key[..], "Private")?;
+        let pk = parse_key(&remote_public_key[..], "Public")?;
+        let mut shared_secret = x448(&sk, &pk);
+        sk.zeroize();
+        if shared_secret.iter().all(|b| *b == 0) {
+            return Err(CryptoError::GeneralError(
+                "Public key is a low order point".to_string(),
+            ));
+        }
+        let hash = sha2::Sha512::digest(&shared_secret);
+        shared_secret.zeroize();
+        Ok(SessionKey(hash.to_vec()))
+    }
+
+    fn public_key_size() -> usize {
+        PUBLIC_KEY_SIZE
+    }
+    fn private_key_size() -> usize {
+        PRIVATE_KEY_SIZE
+    }
+    fn shared_secret_size() -> usize {
+        SHARED_SECRET_SIZE
+    }
+}
+
+fn parse_key(key: &[u8], name: &str) -> Result<[u8; 56], CryptoError> {
+    if key.len() != 56 {
+        return Err(CryptoError::ParseError(format!(
+            "{} key must be 56 bytes in length",
+            name
+        )));
+    }
+    Ok(*array_ref!(key, 0, 56))
+}
+
+fn clamp(k: &mut [u8; 56]) {
+    k[0] &= 252;
+    k[55] |= 128;
+}
+
+/// The X448 function from RFC 7748 section 5 using the Montgomery ladder
+fn x448(k: &[u8; 56], u: &[u8; 56]) -> [u8; 56] {
+    let mut scalar = *k;
+    clamp(&mut scalar);
+
+    let mut u_big = BIG::new();
+    for b in u.iter().rev() {
+        u_big.fshl(8);
+        u_big.w[0] += *b as Chunk;
+    }
+    let p = BIG::new_ints(&rom::MODULUS);
+    if BIG::comp(&u_big, &p) >= 0 {
+        u_big.sub(&p);
+        u_big.norm();
+    }
+
+    let x_1 = FP::new_big(&u_big);
+    let mut x_2 = FP::new_int(1);
+    let mut z_2 = FP::new();
+    let mut x_3 = x_1;
+    let mut z_3 = FP::new_int(1);
+    let mut swap = 0isize;
+    for t in (0..448).rev() {
+        let k_t = ((scalar[t / 8] >> (t % 8)) & 1) as isize;
+        swap ^= k_t;
+        x_2.cswap(&mut x_3, swap);
+        z_2.cswap(&mut z_3, swap);
+        swap = k_t;
+
+        let mut a = x_2;
+        a.add(&z_2);
+        let mut aa = a;
+        aa.sqr();
+        let mut b = x_2;
+        b.sub(&z_2);
+        let mut bb = b;
+        bb.sqr();
+        let mut e = aa;
+        e.sub(&bb);
+        let mut c = x_3;
+        c.add(&z_3);
+        let mut d = x_3;
+        d.sub(&z_3);
+        let mut da = d;
+        da.mul(&a);
+        let mut cb = c;
+        cb.mul(&b);
+
+        x_3 = da;
+        x_3.add(&cb);
+        x_3.sqr();
+        z_3 = da;
+        z_3.sub(&cb);
+        z_3.sqr();
+        z_3.mul(&x_1);
+        x_2 = aa;
+        x_2.mul(&bb);
+        z_2 = e;
+        z_2.imul(A24);
+        z_2.add(&aa);
+        z_2.mul(&e);
+    }
+    x_2.cswap(&mut x_3, swap);
+    z_2.cswap(&mut z_3, swap);
+    scalar.zeroize();
+
+    z_2.inverse();
+    x_2.mul(&z_2);
+    x_2.reduce();
+    let mut out = [0u8; 56];
+    x_2.redc().tobytes(&mut out);
+    out.reverse();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::derive::Deriver;
+    use openssl::pkey::{Id, PKey};
+
+    // RFC 7748 section 6.2
+    const ALICE_PRIVATE_KEY: &str = "9a8f4925d1519f5775cf46b04b5800d4ee9ee8bae8bc5565d498c28dd9c9baf574a9419744897391006382a6f127ab1d9ac2d8c0a598726b";
+    const ALICE_PUBLIC_KEY: &str = "9b08f7cc31b7e3e67d22d5aea121074a273bd2b83de09c63faa73d2c22c5d9bbc836647241d953d40c5b12da88120d53177f80e532c41fa0";
+    const BOB_PRIVATE_KEY: &str = "1c306a7ac2a0e2e0990b294470cba339e6453772b075811d8fad0d1d6927c120bb5ee8972b0d3e21374c9c921b09d1b0366f10b65173992d";
+    const BOB_PUBLIC_KEY: &str = "3eb7a829b0cd20f5bcfc0b599b6feccf6da4627107bdb0d4f345b43027d8b972fc3e34fb4232a13ca706dcb57aec3dae07bdc1c67bf33609";
+    const SHARED_SECRET: &str = "07fff4181ac6cc95ec1c16a94a0f74d12da232ce40a77552281d282bb60c0b56fd2464c335543936521c24403085d59a449a5037514a879d";
+
+    #[test]
+    fn x448_function() {
+        // RFC 7748 section 5.2
+        let k = hex::decode("3d262fddf9ec8e88495266fea19a34d28882acef045104d0d1aae121700a779c984c24f8cdd78fbff44943eba368f54b29259a4f1c600ad3").unwrap();
+        let u = hex::decode("06fce640fa3487bfda5f6cf2d5263f8aad88334cbd07437f020f08f9814dc031ddbdc38c19c6da2583fa5429db94ada18aa7a7fb4ef8a086").unwrap();
+        assert_eq!(
+            hex::encode(&x448(array_ref!(k, 0, 56), array_ref!(u, 0, 56))[..]),
+            "ce3e4ff95a60dc6697da1db1d85e6afbdf79b50a2412d7546d5f239fe14fbaadeb445fc66a01b0779d98223961111e21766282f73dd96b6f"
+        );
+
+        let mut k = [0u8; 56];
+        k[0] = 5;
+        let u = k;
+        assert_eq!(
+            hex::encode(&x448(&k, &u)[..]),
+            "3f482c8a9f19b01e6c46ee9711d9dc14fd4bf67af30765c2ae2b846a4d23a8cd0db897086239492caf350b51f833868b9bc2b3bca9cf4113"
+        );
+    }
+
+    #[test]
+    fn key_exchange() {
+        let scheme = X448Sha512::new();
+        let alice = PrivateKey(hex::decode(ALICE_PRIVATE_KEY).unwrap());
+        let (pk, sk) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(alice.clone())))
+            .unwrap();
+        assert_eq!(hex::encode(&pk[..]), ALICE_PUBLIC_KEY);
+        let (pk1, sk1) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(sk.clone())))
+            .unwrap();
+        assert_eq!(pk1, pk);
+        assert_eq!(sk1, sk);
+
+        let bob = PrivateKey(hex::decode(BOB_PRIVATE_KEY).unwrap());
+        let (bob_pk, _) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(bob.clone())))
+            .unwrap();
+        assert_eq!(hex::encode(&bob_pk[..]), BOB_PUBLIC_KEY);
+        let expected = sha2::Sha512::digest(&hex::decode(SHARED_SECRET).unwrap());
+        let secret = scheme.compute_shared_secret(&alice, &bob_pk).unwrap();
+        assert_eq!(secret.len(), SHARED_SECRET_SIZE);
+        assert_eq!(secret[..], expected[..]);
+        let secret = scheme.compute_shared_secret(&bob, &pk).unwrap();
+        assert_eq!(secret[..], expected[..]);
+
+        let (pk, sk) = scheme.keypair(None).unwrap();
+        let (pk1, sk1) = scheme
+            .keypair(Some(KeyGenOption::UseSeed(vec![1u8; 32])))
+            .unwrap();
+        assert_eq!(
+            scheme.compute_shared_secret(&sk, &pk1).unwrap(),
+            scheme.compute_shared_secret(&sk1, &pk).unwrap()
+        );
+        let (pk2, _) = scheme
+            .keypair(Some(KeyGenOption::UseSeed(vec![1u8; 32])))
+            .unwrap();
+        assert_eq!(pk1, pk2);
+
+        assert!(scheme
+            .compute_shared_secret(&sk, &PublicKey(vec![0u8; PUBLIC_KEY_SIZE]))
+            .is_err());
+        assert!(scheme
+            .compute_shared_secret(&sk, &PublicKey(vec![5u8; 32]))
+            .is_err());
+        assert!(scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(PrivateKey(vec![1u8; 32]))))
+            .is_err());
+    }
+
+    #[test]
+    fn openssl_compatibility() {
+        let scheme = X448Sha512::new();
+        let (pk, sk) = scheme.keypair(None).unwrap();
+        let remote = PKey::generate_x448().unwrap();
+        let remote_pk = PublicKey(remote.raw_public_key().unwrap());
+
+        let local = PKey::public_key_from_raw_bytes(&pk[..], Id::X448).unwrap();
+        let mut deriver = Deriver::new(&remote).unwrap();
+        deriver.set_peer(&local).unwrap();
+        let expected = sha2::Sha512::digest(&deriver.derive_to_vec().unwrap());
+
+        let secret = scheme.compute_shared_secret(&sk, &remote_pk).unwrap();
+        assert_eq!(secret[..], expected[..]);
+    }
+}
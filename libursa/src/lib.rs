@@ -6,6 +6,10 @@ compile_error!("Cannot compile both features 'ecdsa_sepc256k1' and 'ecdsa_secp25
 compile_error!("Cannot compile both features 'ecdsa_sepc256k1' and 'ecdsa_secp256k1_asm'");
 #[cfg(all(feature = "ecdsa_secp256k1_native", feature = "ecdsa_secp256k1_asm"))]
 compile_error!("Cannot compile both features 'ecdsa_sepc256k1_native' and 'ecdsa_secp256k1_asm'");
+#[cfg(all(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+compile_error!("Cannot compile both features 'ecdsa_nist' and 'ecdsa_nist_native'");
+#[cfg(all(feature = "ecdh_nist", feature = "ecdh_nist_native"))]
+compile_error!("Cannot compile both features 'ecdh_nist' and 'ecdh_nist_native'");
 #[cfg(all(feature = "ed25519", feature = "ed25519_asm"))]
 compile_error!("Cannot compile both features 'ed25519' and 'ed25519_asm'");
 #[cfg(all(feature = "cl", feature = "cl_native"))]
@@ -87,7 +91,7 @@ extern crate rustlibsecp256k1;
 extern crate secp256k1 as libsecp256k1;
 #[cfg(feature = "serde")]
 extern crate serde;
-#[cfg(any(test, feature = "ffi"))]
+#[cfg(any(test, feature = "ffi", feature = "key_encoding"))]
 #[cfg_attr(
     any(
         feature = "cl",
@@ -109,26 +113,41 @@ extern crate curve25519_dalek;
 extern crate hex;
 #[cfg(feature = "js-sys")]
 extern crate js_sys;
+#[cfg(feature = "unicode-normalization")]
+extern crate unicode_normalization;
 #[cfg(feature = "wasm-bindgen")]
 extern crate wasm_bindgen;
 #[cfg(feature = "x25519-dalek")]
 extern crate x25519_dalek;
 
 #[cfg(any(
+    feature = "bip32",
+    feature = "bip39",
     feature = "bls_bls12381",
     feature = "cl",
     feature = "cl_native",
+    feature = "ecdh_nist",
+    feature = "ecdh_nist_native",
     feature = "ecdh_secp256k1",
     feature = "ecdh_secp256k1_native",
     feature = "ecdh_secp256k1_asm",
+    feature = "ecdsa_nist",
+    feature = "ecdsa_nist_native",
     feature = "ecdsa_secp256k1",
     feature = "ecdsa_secp256k1_native",
     feature = "ecdsa_secp256k1_asm",
     feature = "ed25519",
     feature = "ed25519_asm",
+    feature = "ed448",
+    feature = "eip2333",
     feature = "ffi",
+    feature = "frost",
+    feature = "key_encoding",
+    feature = "schnorr_secp256k1",
+    feature = "slip10",
     feature = "x25519",
     feature = "x25519_asm",
+    feature = "x448",
     feature = "wasm"
 ))]
 #[macro_use]
@@ -144,6 +163,8 @@ pub mod bn;
 pub mod bn;
 #[cfg(any(feature = "cl", feature = "cl_native"))]
 pub mod cl;
+#[cfg(feature = "key_encoding")]
+pub mod encoding;
 #[cfg(any(
     feature = "aescbc",
     feature = "aescbc_native",
@@ -171,25 +192,48 @@ pub mod ffi;
 #[cfg(any(feature = "blake2", feature = "sha2", feature = "sha3"))]
 pub mod hash;
 #[cfg(any(
+    feature = "bip32",
+    feature = "bip39",
+    feature = "eip2333",
+    feature = "slip10"
+))]
+pub mod hd;
+#[cfg(any(
+    feature = "ecdh_nist",
+    feature = "ecdh_nist_native",
     feature = "ecdh_secp256k1",
     feature = "ecdh_secp256k1_native",
     feature = "ecdh_secp256k1_asm",
     feature = "x25519",
-    feature = "x25519_asm"
+    feature = "x25519_asm",
+    feature = "x448"
 ))]
 pub mod kex;
 #[cfg(any(
+    feature = "bip32",
+    feature = "bip39",
     feature = "bls_bls12381",
+    feature = "ecdh_nist",
+    feature = "ecdh_nist_native",
     feature = "ecdh_secp256k1",
     feature = "ecdh_secp256k1_native",
     feature = "ecdh_secp256k1_asm",
+    feature = "ecdsa_nist",
+    feature = "ecdsa_nist_native",
     feature = "ecdsa_secp256k1",
     feature = "ecdsa_secp256k1_native",
     feature = "ecdsa_secp256k1_asm",
     feature = "ed25519",
     feature = "ed25519_asm",
+    feature = "ed448",
+    feature = "eip2333",
+    feature = "frost",
+    feature = "key_encoding",
+    feature = "schnorr_secp256k1",
+    feature = "slip10",
     feature = "x25519",
     feature = "x25519_asm",
+    feature = "x448",
     feature = "wasm"
 ))]
 pub mod keys;
@@ -201,15 +245,24 @@ pub mod keys;
 ))]
 #[path = "pair/amcl.rs"]
 pub mod pair;
-#[cfg(any(feature = "sharing", feature = "sharing_native"))]
+#[cfg(any(
+    feature = "bls_bls12381",
+    feature = "sharing",
+    feature = "sharing_native"
+))]
 pub mod sharing;
 #[cfg(any(
     feature = "ed25519",
     feature = "ed25519_asm",
+    feature = "ed448",
+    feature = "ecdsa_nist",
+    feature = "ecdsa_nist_native",
     feature = "ecdsa_secp256k1",
     feature = "ecdsa_secp256k1_native",
     feature = "ecdsa_secp256k1_asm",
-    feature = "bls_bls12381"
+    feature = "bls_bls12381",
+    feature = "frost",
+    feature = "schnorr_secp256k1"
 ))]
 pub mod signatures;
 #[cfg(feature = "wasm")]
@@ -0,0 +1,808 @@
+//! Implements the distributed key generation protocol of Gennaro, Jarecki, Krawczyk and Rabin
+//! (see <https://link.springer.com/article/10.1007/s00145-006-0347-3>)
+//! over the BLS12-381 scalar field with commitments in G1.
+//!
+//! Every party deals a random secret with `pedersen` verifiable secret sharing. The joint
+//! secret key is the sum of the secrets of the qualified dealers and the share of each party
+//! is the sum of the shares it received, so nobody learns the joint secret key. The group
+//! public key is extracted with Feldman commitments once the qualified dealers are fixed,
+//! reconstructing the secret of any dealer whose Feldman commitments are inconsistent.
+//!
+//! Each round is a state that consumes the messages of the previous round, keyed by the
+//! identifier of the sender, and returns the next state and the messages to send. A party
+//! keeps the messages it sends to itself so they don't need to be delivered.
+//! `DealerShare`s from `Round1::new` must be sent to each party over a private authenticated
+//! channel. All other messages are broadcast and must reach every party unchanged.
+//!
+//! 1. `Round1::new` deals the secret of the party
+//! 2. `Round1::round2` verifies the received shares and complains about dealers
+//! 3. `Round2::round3` answers complaints and commits to the secret polynomial
+//! 4. `Round3::round4` fixes the qualified dealers and complains about their commitments
+//! 5. `Round4::round5` reveals the shares of dealers with valid complaints
+//! 6. `Round5::complete` outputs the key share, group public key and Feldman verifier
+
+use amcl_wrapper::{field_elem::FieldElement, group_elem::GroupElement, group_elem_g1::G1};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+use super::feldman::FeldmanVerifier;
+use super::pedersen::{split_secret_with_polynomial, PedersenVerifier};
+use super::vss::{evaluate_commitments, interpolate_polynomial, Share};
+use {CryptoError, CryptoResult};
+
+/// A share from a dealer with the blinding share to verify it
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DealerShare {
+    /// The share of the dealer's secret
+    pub secret_share: Share,
+    /// The share of the dealer's blinding value
+    pub blinding_share: Share,
+}
+
+impl DealerShare {
+    fn verify(&self, verifier: &PedersenVerifier, identifier: u8) -> bool {
+        self.secret_share.identifier == identifier
+            && verifier.verify_share(&self.secret_share, &self.blinding_share)
+    }
+}
+
+/// The Pedersen commitments of a dealer
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Round1Broadcast {
+    /// Commitments to the coefficients of the secret and blinding polynomials
+    pub commitments: Vec<G1>,
+}
+
+/// Complaints about dealers whose share was missing or didn't verify
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Round2Broadcast {
+    /// The identifiers of the dealers
+    pub complaints: BTreeSet<u8>,
+}
+
+/// Answers to complaints about a dealer and its Feldman commitments
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Round3Broadcast {
+    /// The share for each party that complained about the dealer
+    pub answers: BTreeMap<u8, DealerShare>,
+    /// `generator * a_i` for each coefficient `a_i` of the secret polynomial
+    pub commitments: Vec<G1>,
+}
+
+/// Complaints about qualified dealers whose Feldman commitments are inconsistent
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Round4Broadcast {
+    /// The share received from each dealer as evidence
+    pub complaints: BTreeMap<u8, DealerShare>,
+}
+
+/// The shares of dealers with valid complaints against their Feldman commitments
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Round5Broadcast {
+    /// The share received from each dealer
+    pub shares: BTreeMap<u8, DealerShare>,
+}
+
+/// The output of the protocol for a party
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct DkgResult {
+    /// The share of the joint secret key
+    pub secret_share: Share,
+    /// `generator * secret key`
+    pub public_key: G1,
+    /// The identifiers of the dealers whose secrets make up the joint secret key
+    pub qualified: BTreeSet<u8>,
+    /// The sum of the Feldman commitments of the qualified dealers, which verifies
+    /// the share of every party and is needed to refresh or reshare the key
+    pub verifier: FeldmanVerifier,
+}
+
+#[derive(Debug, Clone)]
+struct Parameters {
+    id: u8,
+    threshold: u8,
+    total: u8,
+    generator: G1,
+    blinder: G1,
+}
+
+impl Parameters {
+    /// The messages from each party including the one this party sent to itself
+    fn inbox<T: Clone>(&self, messages: &BTreeMap<u8, T>, own: &T) -> BTreeMap<u8, T> {
+        let mut inbox: BTreeMap<u8, T> = messages
+            .iter()
+            .filter(|(sender, _)| **sender != 0 && **sender <= self.total)
+            .map(|(sender, m)| (*sender, m.clone()))
+            .collect();
+        inbox.insert(self.id, own.clone());
+        inbox
+    }
+
+    fn feldman_matches(&self, commitments: Option<&Vec<G1>>, share: &Share) -> bool {
+        commitments
+            .filter(|c| evaluate_commitments(c, share.identifier) == &self.generator * &share.value)
+            .is_some()
+    }
+}
+
+/// Waiting for the messages from `Round1::new`
+#[derive(Debug, Clone)]
+pub struct Round1 {
+    params: Parameters,
+    broadcast: Round1Broadcast,
+    dealt: BTreeMap<u8, DealerShare>,
+    feldman: Vec<G1>,
+}
+
+impl Round1 {
+    /// Start the protocol as party `id` of `total` parties, which `threshold` are required
+    /// to use the joint secret key. Party identifiers are [1, total].
+    /// Nobody may know the discrete log of `blinder` with respect to `generator`.
+    ///
+    /// Returns the state, the message to broadcast and the share to send to each party.
+    pub fn new(
+        id: u8,
+        threshold: u8,
+        total: u8,
+        generator: &G1,
+        blinder: &G1,
+    ) -> CryptoResult<(Self, Round1Broadcast, BTreeMap<u8, DealerShare>)> {
+        if id == 0 || id > total {
+            return Err(CryptoError::GeneralError(
+                "id must be in [1, total]".to_string(),
+            ));
+        }
+        let (res, polynomial) = split_secret_with_polynomial(
+            &FieldElement::random(),
+            threshold,
+            total,
+            generator,
+            blinder,
+        )?;
+        let feldman = polynomial
+            .coefficients()
+            .iter()
+            .map(|a| generator * a)
+            .collect();
+        let dealt: BTreeMap<u8, DealerShare> = res
+            .secret_shares
+            .into_iter()
+            .zip(res.blinding_shares)
+            .map(|(secret_share, blinding_share)| {
+                (
+                    secret_share.identifier,
+                    DealerShare {
+                        secret_share,
+                        blinding_share,
+                    },
+                )
+            })
+            .collect();
+        let outgoing = dealt
+            .iter()
+            .filter(|(i, _)| **i != id)
+            .map(|(i, s)| (*i, s.clone()))
+            .collect();
+        let broadcast = Round1Broadcast {
+            commitments: res.verifier.commitments,
+        };
+        let state = Round1 {
+            params: Parameters {
+                id,
+                threshold,
+                total,
+                generator: generator.clone(),
+                blinder: blinder.clone(),
+            },
+            broadcast: broadcast.clone(),
+            dealt,
+            feldman,
+        };
+        Ok((state, broadcast, outgoing))
+    }
+
+    /// Verify the `shares` received from each dealer against its `broadcasts`.
+    /// Dealers that didn't broadcast are disqualified.
+    pub fn round2(
+        self,
+        broadcasts: &BTreeMap<u8, Round1Broadcast>,
+        shares: &BTreeMap<u8, DealerShare>,
+    ) -> CryptoResult<(Round2, Round2Broadcast)> {
+        let params = self.params;
+        let mut verifiers = BTreeMap::new();
+        let mut received = BTreeMap::new();
+        let mut complaints = BTreeSet::new();
+        for (dealer, broadcast) in params.inbox(broadcasts, &self.broadcast) {
+            if broadcast.commitments.len() != params.threshold as usize {
+                continue;
+            }
+            let verifier = PedersenVerifier {
+                generator: params.generator.clone(),
+                blinder: params.blinder.clone(),
+                commitments: broadcast.commitments,
+            };
+            let share = if dealer == params.id {
+                self.dealt.get(&dealer)
+            } else {
+                shares.get(&dealer)
+            };
+            match share {
+                Some(s) if s.verify(&verifier, params.id) => {
+                    received.insert(dealer, s.clone());
+                }
+                _ => {
+                    complaints.insert(dealer);
+                }
+            }
+            verifiers.insert(dealer, verifier);
+        }
+        let broadcast = Round2Broadcast { complaints };
+        let state = Round2 {
+            params,
+            verifiers,
+            received,
+            broadcast: broadcast.clone(),
+            dealt: self.dealt,
+            feldman: self.feldman,
+        };
+        Ok((state, broadcast))
+    }
+}
+
+/// Waiting for the messages from `Round1::round2`
+#[derive(Debug, Clone)]
+pub struct Round2 {
+    params: Parameters,
+    verifiers: BTreeMap<u8, PedersenVerifier>,
+    received: BTreeMap<u8, DealerShare>,
+    broadcast: Round2Broadcast,
+    dealt: BTreeMap<u8, DealerShare>,
+    feldman: Vec<G1>,
+}
+
+impl Round2 {
+    /// Answer the `broadcasts` complaining about this party with the share sent to the
+    /// complainer and commit to the secret polynomial
+    pub fn round3(
+        self,
+        broadcasts: &BTreeMap<u8, Round2Broadcast>,
+    ) -> CryptoResult<(Round3, Round3Broadcast)> {
+        let params = self.params;
+        let dealt = self.dealt;
+        let mut complaints: BTreeMap<u8, BTreeSet<u8>> = BTreeMap::new();
+        for (complainer, broadcast) in params.inbox(broadcasts, &self.broadcast) {
+            for dealer in broadcast.complaints {
+                complaints.entry(dealer).or_default().insert(complainer);
+            }
+        }
+        let answers = complaints
+            .get(&params.id)
+            .into_iter()
+            .flatten()
+            .filter_map(|i| dealt.get(i).map(|s| (*i, s.clone())))
+            .collect();
+        let broadcast = Round3Broadcast {
+            answers,
+            commitments: self.feldman,
+        };
+        let state = Round3 {
+            params,
+            verifiers: self.verifiers,
+            received: self.received,
+            complaints,
+            broadcast: broadcast.clone(),
+        };
+        Ok((state, broadcast))
+    }
+}
+
+/// Waiting for the messages from `Round2::round3`
+#[derive(Debug, Clone)]
+pub struct Round3 {
+    params: Parameters,
+    verifiers: BTreeMap<u8, PedersenVerifier>,
+    received: BTreeMap<u8, DealerShare>,
+    complaints: BTreeMap<u8, BTreeSet<u8>>,
+    broadcast: Round3Broadcast,
+}
+
+impl Round3 {
+    /// Disqualify dealers with a threshold of complaints or an invalid answer to one.
+    /// Then check the Feldman commitments in `broadcasts` of the qualified dealers.
+    pub fn round4(
+        self,
+        broadcasts: &BTreeMap<u8, Round3Broadcast>,
+    ) -> CryptoResult<(Round4, Round4Broadcast)> {
+        let params = self.params;
+        let inbox = params.inbox(broadcasts, &self.broadcast);
+        let mut received = self.received;
+        let mut qualified = BTreeSet::new();
+        let none = BTreeSet::new();
+        for (dealer, verifier) in &self.verifiers {
+            let complainers = self.complaints.get(dealer).unwrap_or(&none);
+            if complainers.len() >= params.threshold as usize {
+                continue;
+            }
+            let answers = inbox.get(dealer).map(|b| &b.answers);
+            let answered = complainers.iter().all(|c| {
+                answers
+                    .and_then(|a| a.get(c))
+                    .filter(|s| s.verify(verifier, *c))
+                    .is_some()
+            });
+            if !answered {
+                continue;
+            }
+            if complainers.contains(&params.id) {
+                received.insert(*dealer, answers.unwrap()[&params.id].clone());
+            }
+            qualified.insert(*dealer);
+        }
+        if !qualified.contains(&params.id) {
+            return Err(CryptoError::GeneralError(
+                "This party was disqualified".to_string(),
+            ));
+        }
+        if qualified.len() < params.threshold as usize {
+            return Err(CryptoError::GeneralError(
+                "Fewer than threshold parties qualified".to_string(),
+            ));
+        }
+
+        let feldman: BTreeMap<u8, Vec<G1>> = qualified
+            .iter()
+            .filter_map(|dealer| inbox.get(dealer).map(|b| (*dealer, b.commitments.clone())))
+            .filter(|(_, c)| c.len() == params.threshold as usize)
+            .collect();
+        let complaints = qualified
+            .iter()
+            .filter(|dealer| {
+                !params.feldman_matches(feldman.get(dealer), &received[dealer].secret_share)
+            })
+            .map(|dealer| (*dealer, received[dealer].clone()))
+            .collect();
+        let broadcast = Round4Broadcast { complaints };
+        let state = Round4 {
+            params,
+            verifiers: self.verifiers,
+            received,
+            qualified,
+            feldman,
+            broadcast: broadcast.clone(),
+        };
+        Ok((state, broadcast))
+    }
+}
+
+/// Waiting for the messages from `Round3::round4`
+#[derive(Debug, Clone)]
+pub struct Round4 {
+    params: Parameters,
+    verifiers: BTreeMap<u8, PedersenVerifier>,
+    received: BTreeMap<u8, DealerShare>,
+    qualified: BTreeSet<u8>,
+    feldman: BTreeMap<u8, Vec<G1>>,
+    broadcast: Round4Broadcast,
+}
+
+impl Round4 {
+    /// Check the complaints in `broadcasts` and reveal the shares of each qualified dealer
+    /// with a share that verifies against its Pedersen commitments but not its Feldman ones
+    pub fn round5(
+        self,
+        broadcasts: &BTreeMap<u8, Round4Broadcast>,
+    ) -> CryptoResult<(Round5, Round5Broadcast)> {
+        let params = self.params;
+        let received = self.received;
+        let mut disputed = BTreeSet::new();
+        for (complainer, broadcast) in params.inbox(broadcasts, &self.broadcast) {
+            for (dealer, evidence) in broadcast.complaints {
+                let valid = self.qualified.contains(&dealer)
+                    && evidence.verify(&self.verifiers[&dealer], complainer)
+                    && !params.feldman_matches(self.feldman.get(&dealer), &evidence.secret_share);
+                if valid {
+                    disputed.insert(dealer);
+                }
+            }
+        }
+        let shares = disputed
+            .iter()
+            .map(|dealer| (*dealer, received[dealer].clone()))
+            .collect();
+        let broadcast = Round5Broadcast { shares };
+        let state = Round5 {
+            params,
+            verifiers: self.verifiers,
+            received,
+            qualified: self.qualified,
+            feldman: self.feldman,
+            disputed,
+            broadcast: broadcast.clone(),
+        };
+        Ok((state, broadcast))
+    }
+}
+
+/// Waiting for the messages from `Round4::round5`
+#[derive(Debug, Clone)]
+pub struct Round5 {
+    params: Parameters,
+    verifiers: BTreeMap<u8, PedersenVerifier>,
+    received: BTreeMap<u8, DealerShare>,
+    qualified: BTreeSet<u8>,
+    feldman: BTreeMap<u8, Vec<G1>>,
+    disputed: BTreeSet<u8>,
+    broadcast: Round5Broadcast,
+}
+
+impl Round5 {
+    /// Reconstruct the secrets of the disputed dealers from the shares in `broadcasts`
+    /// and output the key share and group public key
+    pub fn complete(self, broadcasts: &BTreeMap<u8, Round5Broadcast>) -> CryptoResult<DkgResult> {
+        let params = self.params;
+        let inbox = params.inbox(broadcasts, &self.broadcast);
+        let mut commitments = vec![G1::identity(); params.threshold as usize];
+        let mut secret = FieldElement::zero();
+        for dealer in &self.qualified {
+            if self.disputed.contains(dealer) {
+                let verifier = &self.verifiers[dealer];
+                let shares: Vec<Share> = inbox
+                    .iter()
+                    .filter_map(|(sender, b)| {
+                        b.shares
+                            .get(dealer)
+                            .filter(|s| s.verify(verifier, *sender))
+                            .map(|s| s.secret_share.clone())
+                    })
+                    .collect();
+                if shares.len() < params.threshold as usize {
+                    return Err(CryptoError::GeneralError(format!(
+                        "Not enough shares to reconstruct the secret of dealer {}",
+                        dealer
+                    )));
+                }
+                let polynomial = interpolate_polynomial(&shares[..params.threshold as usize])?;
+                for (c, a) in commitments.iter_mut().zip(polynomial.coefficients().iter()) {
+                    *c += &params.generator * a;
+                }
+            } else {
+                for (c, d) in commitments.iter_mut().zip(&self.feldman[dealer]) {
+                    *c += d;
+                }
+            }
+            secret += &self.received[dealer].secret_share.value;
+        }
+        Ok(DkgResult {
+            secret_share: Share {
+                identifier: params.id,
+                value: secret,
+            },
+            public_key: commitments[0].clone(),
+            qualified: self.qualified,
+            verifier: FeldmanVerifier {
+                generator: params.generator,
+                commitments,
+            },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sharing::reshare::{combine_refreshes, refresh_dealing};
+    use sharing::vss::combine_shares;
+
+    const THRESHOLD: u8 = 3;
+    const TOTAL: u8 = 5;
+
+    type Inbox<T> = BTreeMap<u8, T>;
+
+    fn generators() -> (G1, G1) {
+        (G1::generator(), G1::from_msg_hash(b"dkg test blinder"))
+    }
+
+    fn deal() -> (
+        BTreeMap<u8, Round1>,
+        Inbox<Round1Broadcast>,
+        Inbox<BTreeMap<u8, DealerShare>>,
+    ) {
+        let (g, h) = generators();
+        let mut states = BTreeMap::new();
+        let mut broadcasts = BTreeMap::new();
+        let mut shares = BTreeMap::new();
+        for id in 1..=TOTAL {
+            let (state, broadcast, outgoing) = Round1::new(id, THRESHOLD, TOTAL, &g, &h).unwrap();
+            states.insert(id, state);
+            broadcasts.insert(id, broadcast);
+            shares.insert(id, outgoing);
+        }
+        (states, broadcasts, shares)
+    }
+
+    fn round2(
+        states: BTreeMap<u8, Round1>,
+        broadcasts: &Inbox<Round1Broadcast>,
+        shares: &Inbox<BTreeMap<u8, DealerShare>>,
+    ) -> (BTreeMap<u8, Round2>, Inbox<Round2Broadcast>) {
+        let mut next = BTreeMap::new();
+        let mut out = BTreeMap::new();
+        for (id, state) in states {
+            let received = shares
+                .iter()
+                .filter_map(|(dealer, s)| s.get(&id).map(|s| (*dealer, s.clone())))
+                .collect();
+            let (state, broadcast) = state.round2(broadcasts, &received).unwrap();
+            next.insert(id, state);
+            out.insert(id, broadcast);
+        }
+        (next, out)
+    }
+
+    macro_rules! broadcast_round {
+        ($name:ident, $state:ident, $inbox:ident, $next:ident, $out:ident, $method:ident) => {
+            fn $name(
+                states: BTreeMap<u8, $state>,
+                broadcasts: &Inbox<$inbox>,
+            ) -> (BTreeMap<u8, $next>, Inbox<$out>) {
+                let mut next = BTreeMap::new();
+                let mut out = BTreeMap::new();
+                for (id, state) in states {
+                    let (state, broadcast) = state.$method(broadcasts).unwrap();
+                    next.insert(id, state);
+                    out.insert(id, broadcast);
+                }
+                (next, out)
+            }
+        };
+    }
+
+    broadcast_round!(
+        round3,
+        Round2,
+        Round2Broadcast,
+        Round3,
+        Round3Broadcast,
+        round3
+    );
+    broadcast_round!(
+        round4,
+        Round3,
+        Round3Broadcast,
+        Round4,
+        Round4Broadcast,
+        round4
+    );
+    broadcast_round!(
+        round5,
+        Round4,
+        Round4Broadcast,
+        Round5,
+        Round5Broadcast,
+        round5
+    );
+
+    fn complete(
+        states: BTreeMap<u8, Round5>,
+        broadcasts: &Inbox<Round5Broadcast>,
+    ) -> Vec<DkgResult> {
+        states
+            .into_iter()
+            .map(|(_, state)| state.complete(broadcasts).unwrap())
+            .collect()
+    }
+
+    fn run_from_round3(
+        states: BTreeMap<u8, Round3>,
+        broadcasts: &Inbox<Round3Broadcast>,
+    ) -> Vec<DkgResult> {
+        let (states, broadcasts) = round4(states, broadcasts);
+        let (states, broadcasts) = round5(states, &broadcasts);
+        complete(states, &broadcasts)
+    }
+
+    /// All parties agree and any threshold of shares is a sharing of the secret key
+    fn check_results(results: &[DkgResult], qualified: &[u8]) {
+        let (g, _) = generators();
+        let qualified: BTreeSet<u8> = qualified.iter().copied().collect();
+        for res in results {
+            assert_eq!(res.public_key, results[0].public_key);
+            assert_eq!(res.qualified, qualified);
+            assert_eq!(res.verifier, results[0].verifier);
+            assert_eq!(res.verifier.commitments[0], res.public_key);
+            assert!(res.verifier.verify_share(&res.secret_share));
+        }
+        let shares: Vec<Share> = results.iter().map(|r| r.secret_share.clone()).collect();
+        for window in shares.windows(THRESHOLD as usize) {
+            let secret = combine_shares(window).unwrap();
+            assert_eq!(&g * &secret, results[0].public_key);
+        }
+        let secret = combine_shares(&shares[..(THRESHOLD - 1) as usize]).unwrap();
+        assert_ne!(&g * &secret, results[0].public_key);
+    }
+
+    #[test]
+    fn invalid_args() {
+        let (g, h) = generators();
+        assert!(Round1::new(0, 2, 3, &g, &h).is_err());
+        assert!(Round1::new(4, 2, 3, &g, &h).is_err());
+        assert!(Round1::new(1, 4, 3, &g, &h).is_err());
+        assert!(Round1::new(1, 1, 3, &g, &h).is_err());
+        assert!(Round1::new(1, 2, 3, &g, &g).is_err());
+    }
+
+    #[test]
+    fn honest_parties() {
+        let (states, broadcasts, shares) = deal();
+        let (states, broadcasts) = round2(states, &broadcasts, &shares);
+        assert!(broadcasts.values().all(|b| b.complaints.is_empty()));
+        let (states, broadcasts) = round3(states, &broadcasts);
+        assert!(broadcasts.values().all(|b| b.answers.is_empty()));
+        let (states, broadcasts) = round4(states, &broadcasts);
+        assert!(broadcasts.values().all(|b| b.complaints.is_empty()));
+        let (states, broadcasts) = round5(states, &broadcasts);
+        assert!(broadcasts.values().all(|b| b.shares.is_empty()));
+        let results = complete(states, &broadcasts);
+        check_results(&results, &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn refresh_result() {
+        let (states, broadcasts, shares) = deal();
+        let (states, broadcasts) = round2(states, &broadcasts, &shares);
+        let (states, broadcasts) = round3(states, &broadcasts);
+        let results = run_from_round3(states, &broadcasts);
+
+        let mut dealings = BTreeMap::new();
+        for res in &results {
+            let dealing = refresh_dealing(&res.verifier, TOTAL).unwrap();
+            dealings.insert(res.secret_share.identifier, dealing);
+        }
+        let refreshed: Vec<(FeldmanVerifier, Share)> = results
+            .iter()
+            .map(|res| {
+                let id = res.secret_share.identifier;
+                let received = dealings
+                    .iter()
+                    .map(|(dealer, (v, s))| (*dealer, (v.clone(), s[id as usize - 1].clone())))
+                    .collect();
+                combine_refreshes(&res.verifier, &res.secret_share, &received).unwrap()
+            })
+            .collect();
+        for (verifier, share) in &refreshed {
+            assert_eq!(*verifier, refreshed[0].0);
+            assert!(verifier.verify_share(share));
+        }
+        let shares: Vec<Share> = refreshed.iter().map(|(_, s)| s.clone()).collect();
+        let secret = refreshed[0].0.combine_shares(&shares[1..4]).unwrap();
+        assert_eq!(&G1::generator() * &secret, results[0].public_key);
+    }
+
+    #[test]
+    fn answered_complaint() {
+        let (states, broadcasts, mut shares) = deal();
+        // Dealer 1 sends a bad share to party 2
+        let bad = shares.get_mut(&1).unwrap().get_mut(&2).unwrap();
+        bad.secret_share.value = bad.secret_share.value.clone() + FieldElement::one();
+        let (states, broadcasts) = round2(states, &broadcasts, &shares);
+        assert_eq!(broadcasts[&2].complaints, [1].iter().copied().collect());
+        let (states, broadcasts) = round3(states, &broadcasts);
+        assert_eq!(broadcasts[&1].answers.len(), 1);
+        let results = run_from_round3(states, &broadcasts);
+        check_results(&results, &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn unanswered_complaint() {
+        let (states, broadcasts, mut shares) = deal();
+        // Dealer 1 doesn't send a share to party 2, then gives a bad answer
+        shares.get_mut(&1).unwrap().remove(&2);
+        let (mut states, broadcasts) = round2(states, &broadcasts, &shares);
+        states.remove(&1);
+        let (states, mut broadcasts) = round3(states, &broadcasts);
+        let mut answer = states[&2].received.values().next().unwrap().clone();
+        answer.blinding_share.value = FieldElement::random();
+        let mut dealer = Round3Broadcast {
+            answers: BTreeMap::new(),
+            commitments: Vec::new(),
+        };
+        dealer.answers.insert(2, answer);
+        broadcasts.insert(1, dealer);
+        let results = run_from_round3(states, &broadcasts);
+        check_results(&results, &[2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn threshold_complaints() {
+        let (states, broadcasts, mut shares) = deal();
+        // Dealer 5 sends bad shares to a threshold of parties then answers correctly
+        for id in 1..=THRESHOLD {
+            let bad = shares.get_mut(&5).unwrap().get_mut(&id).unwrap();
+            bad.blinding_share.value = FieldElement::random();
+        }
+        let (states, broadcasts) = round2(states, &broadcasts, &shares);
+        let (mut states, broadcasts) = round3(states, &broadcasts);
+
+        // The dealer finds itself disqualified
+        let dealer = states.remove(&5).unwrap();
+        assert!(dealer.round4(&broadcasts).is_err());
+
+        let results = run_from_round3(states, &broadcasts);
+        check_results(&results, &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn missing_dealer() {
+        let (mut states, mut broadcasts, mut shares) = deal();
+        // Party 4 deals nothing
+        broadcasts.remove(&4);
+        shares.remove(&4);
+        states.remove(&4);
+        let (states, broadcasts) = round2(states, &broadcasts, &shares);
+        assert!(broadcasts.values().all(|b| b.complaints.is_empty()));
+        let (states, broadcasts) = round3(states, &broadcasts);
+        let results = run_from_round3(states, &broadcasts);
+        assert_eq!(results.len(), 4);
+        let (g, _) = generators();
+        for res in &results {
+            assert_eq!(res.public_key, results[0].public_key);
+            assert_eq!(res.qualified, [1, 2, 3, 5].iter().copied().collect());
+        }
+        let shares: Vec<Share> = results.iter().map(|r| r.secret_share.clone()).collect();
+        let secret = combine_shares(&shares[1..]).unwrap();
+        assert_eq!(&g * &secret, results[0].public_key);
+    }
+
+    #[test]
+    fn inconsistent_feldman_commitments() {
+        let (states, broadcasts, shares) = deal();
+        let (states, broadcasts) = round2(states, &broadcasts, &shares);
+        let (states, mut broadcasts) = round3(states, &broadcasts);
+        // Dealer 3 commits to a different secret to bias the public key
+        let (g, _) = generators();
+        broadcasts.get_mut(&3).unwrap().commitments[0] = g.clone();
+
+        let (states, broadcasts) = round4(states, &broadcasts);
+        for (id, b) in &broadcasts {
+            if *id == 3 {
+                assert!(b.complaints.is_empty());
+            } else {
+                assert!(b.complaints.contains_key(&3));
+            }
+        }
+        let (states, broadcasts) = round5(states, &broadcasts);
+        for (id, b) in &broadcasts {
+            assert_eq!(b.shares.contains_key(&3), *id != 3);
+        }
+        let results = complete(states, &broadcasts);
+        check_results(&results, &[1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn false_feldman_complaint() {
+        let (states, broadcasts, shares) = deal();
+        let (states, broadcasts) = round2(states, &broadcasts, &shares);
+        let (states, broadcasts) = round3(states, &broadcasts);
+        let (states, mut broadcasts) = round4(states, &broadcasts);
+        // Party 2 complains about dealer 1 with a share that doesn't verify
+        let mut evidence = states[&2].received[&1].clone();
+        evidence.secret_share.value = FieldElement::random();
+        broadcasts
+            .get_mut(&2)
+            .unwrap()
+            .complaints
+            .insert(1, evidence);
+        let (states, broadcasts) = round5(states, &broadcasts);
+        assert!(broadcasts.values().all(|b| b.shares.is_empty()));
+        let results = complete(states, &broadcasts);
+        check_results(&results, &[1, 2, 3, 4, 5]);
+    }
+}
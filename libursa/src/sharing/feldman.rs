@@ -0,0 +1,157 @@
+//! Implements Feldman's verifiable secret sharing scheme
+//! (see <https://www.cs.umd.edu/~gasarch/TOPICS/secretsharing/feldmanVSS.pdf>)
+//! over the BLS12-381 scalar field with commitments in G1.
+//!
+//! The dealer publishes a commitment to each coefficient of the sharing polynomial
+//! so every holder can check its share is a point on the same polynomial.
+//! The first commitment is `generator * secret`, so the secret is only
+//! computationally hidden. Use `pedersen` when that is not acceptable.
+
+use amcl_wrapper::{field_elem::FieldElement, group_elem::GroupElement, group_elem_g1::G1};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::vss::{
+    check_threshold, combine_shares as interpolate, evaluate_commitments, evaluate_shares,
+    random_polynomial, Share,
+};
+use {CryptoError, CryptoResult};
+
+/// The commitments to the polynomial coefficients used to verify shares
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeldmanVerifier {
+    /// The generator for the commitments
+    pub generator: G1,
+    /// `generator * a_i` for each coefficient `a_i`.
+    /// The first is the commitment to the secret.
+    pub commitments: Vec<G1>,
+}
+
+impl FeldmanVerifier {
+    /// Check that `share` is consistent with the commitments from the dealer
+    pub fn verify_share(&self, share: &Share) -> bool {
+        share.identifier != 0
+            && !self.commitments.is_empty()
+            && evaluate_commitments(&self.commitments, share.identifier)
+                == &self.generator * &share.value
+    }
+
+    /// The number of shares required to reconstruct the secret
+    pub fn threshold(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// Verify each of at least a threshold set of `shares`, then reconstruct the secret
+    pub fn combine_shares(&self, shares: &[Share]) -> CryptoResult<FieldElement> {
+        check_threshold(shares.len(), &self.commitments)?;
+        if let Some(share) = shares.iter().find(|s| !self.verify_share(s)) {
+            return Err(CryptoError::GeneralError(format!(
+                "Share {} is not consistent with the commitments",
+                share.identifier
+            )));
+        }
+        interpolate(shares)
+    }
+}
+
+/// Take a `secret` and generate `total` shares, which `threshold` is required to
+/// reconstruct the `secret`, and the commitments with `generator` to verify them.
+/// The `threshold` and `total` must be at least 2.
+pub fn split_secret(
+    secret: &FieldElement,
+    threshold: u8,
+    total: u8,
+    generator: &G1,
+) -> CryptoResult<(FeldmanVerifier, Vec<Share>)> {
+    if generator.is_identity() {
+        return Err(CryptoError::GeneralError(
+            "generator cannot be the identity".to_string(),
+        ));
+    }
+    let polynomial = random_polynomial(secret, threshold, total)?;
+    let commitments = polynomial
+        .coefficients()
+        .iter()
+        .map(|a| generator * a)
+        .collect();
+    let verifier = FeldmanVerifier {
+        generator: generator.clone(),
+        commitments,
+    };
+    Ok((verifier, evaluate_shares(&polynomial, total)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_invalid_args() {
+        let g = G1::generator();
+        let secret = FieldElement::random();
+        assert!(split_secret(&secret, 0, 0, &g).is_err());
+        assert!(split_secret(&secret, 3, 2, &g).is_err());
+        assert!(split_secret(&secret, 1, 10, &g).is_err());
+        assert!(split_secret(&secret, 2, 3, &G1::identity()).is_err());
+    }
+
+    #[test]
+    fn verify_and_combine() {
+        let g = G1::generator();
+        let secret = FieldElement::random();
+        let (verifier, shares) = split_secret(&secret, 3, 5, &g).unwrap();
+        assert_eq!(verifier.threshold(), 3);
+        assert_eq!(verifier.commitments[0], &g * &secret);
+        for share in &shares {
+            assert!(verifier.verify_share(share));
+        }
+
+        for i in 0..5 {
+            for j in (i + 1)..5 {
+                for k in (j + 1)..5 {
+                    let parts = [shares[i].clone(), shares[j].clone(), shares[k].clone()];
+                    assert_eq!(verifier.combine_shares(&parts).unwrap(), secret);
+                }
+            }
+        }
+        assert!(verifier.combine_shares(&shares[..2]).is_err());
+    }
+
+    #[test]
+    fn corrupted_share() {
+        let g = G1::generator();
+        let secret = FieldElement::random();
+        let (verifier, mut shares) = split_secret(&secret, 2, 3, &g).unwrap();
+
+        shares[1].value = shares[1].value.clone() + FieldElement::one();
+        assert!(!verifier.verify_share(&shares[1]));
+        assert!(verifier.combine_shares(&shares).is_err());
+        assert_eq!(
+            verifier
+                .combine_shares(&[shares[0].clone(), shares[2].clone()])
+                .unwrap(),
+            secret
+        );
+
+        // A share moved to another identifier
+        let mut moved = shares[0].clone();
+        moved.identifier = 2;
+        assert!(!verifier.verify_share(&moved));
+        moved.identifier = 0;
+        assert!(!verifier.verify_share(&moved));
+    }
+
+    #[test]
+    fn cheating_dealer() {
+        let g = G1::generator();
+        let secret = FieldElement::random();
+        let (mut verifier, shares) = split_secret(&secret, 2, 3, &g).unwrap();
+
+        // Commitments for another polynomial
+        let (other, _) = split_secret(&secret, 2, 3, &g).unwrap();
+        verifier.commitments[1] = other.commitments[1].clone();
+        assert!(shares.iter().all(|s| !verifier.verify_share(s)));
+        assert!(verifier.combine_shares(&shares).is_err());
+    }
+}
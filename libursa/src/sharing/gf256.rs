@@ -0,0 +1,314 @@
+//! Implements Shamir's secret sharing scheme byte by byte over GF(2^8)
+//! like SLIP-39 (see <https://github.com/satoshilabs/slips/blob/master/slip-0039.md>)
+//! and `ssss` (see <http://point-at-infinity.org/ssss/>).
+//!
+//! Unlike `shamir` there is no field to pick and secrets can have any length.
+//! Each byte of the secret is shared with its own polynomial so a share is the
+//! same length as the secret plus the checksum and identifier. The field has 255
+//! non-zero elements so there can be at most 255 shares.
+//!
+//! The first bytes of the SHA-256 digest of the secret are shared with it and checked
+//! when combining, which detects shares that are corrupted, from different secrets
+//! or fewer than the threshold. The arithmetic doesn't branch on or index by secret data.
+
+use rand::{rngs::OsRng, RngCore};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use zeroize::Zeroize;
+
+use {CryptoError, CryptoResult};
+
+/// The number of checksum bytes added to each share
+pub const CHECKSUM_SIZE: usize = 4;
+
+/// Represents a share created from a split
+#[derive(Debug, Clone, PartialEq)]
+pub struct Share {
+    /// x-coordinate
+    pub identifier: u8,
+    /// y-coordinate of the polynomial for each byte of the secret and checksum
+    pub value: Vec<u8>,
+}
+
+impl Share {
+    /// Return byte representation of this share: value || identifier
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = self.value.clone();
+        output.push(self.identifier);
+        output
+    }
+
+    /// Convert a share from bytes, assumes the last byte is the identifier
+    pub fn from_bytes<B: AsRef<[u8]>>(data: B) -> CryptoResult<Self> {
+        let data = data.as_ref();
+        if data.len() < CHECKSUM_SIZE + 2 {
+            return Err(CryptoError::ParseError("Invalid share length".to_string()));
+        }
+        Ok(Self {
+            identifier: data[data.len() - 1],
+            value: data[..(data.len() - 1)].to_vec(),
+        })
+    }
+}
+
+/// Take a `secret` and generate `total` shares, which `threshold` is required to
+/// reconstruct the `secret`. The `threshold` and `total` must be at least 2.
+pub fn split_secret<B: AsRef<[u8]>>(
+    secret: B,
+    threshold: u8,
+    total: u8,
+) -> CryptoResult<Vec<Share>> {
+    if total < threshold {
+        return Err(CryptoError::GeneralError(
+            "total cannot be less than the threshold".to_string(),
+        ));
+    }
+    if threshold < 2 {
+        return Err(CryptoError::GeneralError(
+            "threshold must be at least 2".to_string(),
+        ));
+    }
+    let secret = secret.as_ref();
+    if secret.is_empty() {
+        return Err(CryptoError::GeneralError(
+            "secret cannot be empty".to_string(),
+        ));
+    }
+    let mut intercepts = secret.to_vec();
+    intercepts.extend_from_slice(&checksum(secret));
+
+    // The random coefficients of degree 1 to threshold - 1 for each byte
+    let degree = (threshold - 1) as usize;
+    let mut coefficients = vec![0u8; intercepts.len() * degree];
+    OsRng.fill_bytes(&mut coefficients);
+
+    // x coordinates are incremental from [1, total+1). 0 is reserved for the secret.
+    let shares = (1..=total)
+        .map(|identifier| Share {
+            identifier,
+            value: intercepts
+                .iter()
+                .zip(coefficients.chunks(degree))
+                .map(|(a_0, a)| {
+                    // Horner's Method
+                    let out = a
+                        .iter()
+                        .rev()
+                        .fold(0u8, |out, a_i| mul(out, identifier) ^ a_i);
+                    mul(out, identifier) ^ a_0
+                })
+                .collect(),
+        })
+        .collect();
+    intercepts.zeroize();
+    coefficients.zeroize();
+    Ok(shares)
+}
+
+/// Reconstruct a secret from at least a threshold set of `shares`
+pub fn combine_shares<B: AsRef<[Share]>>(shares: B) -> CryptoResult<Vec<u8>> {
+    let shares = shares.as_ref();
+    if shares.len() < 2 {
+        return Err(CryptoError::GeneralError(
+            "Less than two shares cannot be used to reconstruct the secret".to_string(),
+        ));
+    }
+    let length = shares[0].value.len();
+    if length <= CHECKSUM_SIZE {
+        return Err(CryptoError::GeneralError(
+            "Share value is too short".to_string(),
+        ));
+    }
+    let mut dups = BTreeSet::new();
+    for share in shares {
+        if share.identifier == 0 {
+            return Err(CryptoError::GeneralError(
+                "Share must have a non-zero identifier".to_string(),
+            ));
+        }
+        if !dups.insert(share.identifier) {
+            return Err(CryptoError::GeneralError(
+                "Duplicate shares cannot be used to reconstruct the secret".to_string(),
+            ));
+        }
+        if share.value.len() != length {
+            return Err(CryptoError::GeneralError(
+                "Shares must have the same length".to_string(),
+            ));
+        }
+    }
+
+    // Lagrange coefficients at 0: x_m / (x_m - x_j) * ...
+    // Subtraction is xor in GF(2^8)
+    let basis: Vec<u8> = shares
+        .iter()
+        .map(|s_j| {
+            shares
+                .iter()
+                .filter(|s_m| s_m.identifier != s_j.identifier)
+                .fold(1u8, |l, s_m| {
+                    mul(l, mul(s_m.identifier, inv(s_m.identifier ^ s_j.identifier)))
+                })
+        })
+        .collect();
+    let mut secret: Vec<u8> = (0..length)
+        .map(|i| {
+            shares
+                .iter()
+                .zip(basis.iter())
+                .fold(0u8, |out, (s, l)| out ^ mul(s.value[i], *l))
+        })
+        .collect();
+
+    let expected = checksum(&secret[..(length - CHECKSUM_SIZE)]);
+    let diff = expected
+        .iter()
+        .zip(secret[(length - CHECKSUM_SIZE)..].iter())
+        .fold(0u8, |d, (a, b)| d | (a ^ b));
+    if diff != 0 {
+        secret.zeroize();
+        return Err(CryptoError::GeneralError(
+            "Invalid checksum, the shares are corrupted, mixed or too few".to_string(),
+        ));
+    }
+    secret.truncate(length - CHECKSUM_SIZE);
+    Ok(secret)
+}
+
+fn checksum(secret: &[u8]) -> [u8; CHECKSUM_SIZE] {
+    let digest = Sha256::digest(secret);
+    let mut output = [0u8; CHECKSUM_SIZE];
+    output.copy_from_slice(&digest[..CHECKSUM_SIZE]);
+    output
+}
+
+/// Multiplication modulo the AES polynomial x^8 + x^4 + x^3 + x + 1
+fn mul(a: u8, b: u8) -> u8 {
+    let mut a = a;
+    let mut b = b;
+    let mut out = 0u8;
+    for _ in 0..8 {
+        // Masks instead of branches to run in constant time
+        out ^= a & (b & 1).wrapping_neg();
+        let carry = (a >> 7).wrapping_neg();
+        a = (a << 1) ^ (carry & 0x1b);
+        b >>= 1;
+    }
+    out
+}
+
+/// Multiplicative inverse a^254, 0 maps to 0
+fn inv(a: u8) -> u8 {
+    let mut square = mul(a, a);
+    let mut out = square;
+    for _ in 0..6 {
+        square = mul(square, square);
+        out = mul(out, square);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_arithmetic() {
+        // FIPS 197 section 4.2
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        assert_eq!(inv(0), 0);
+        for a in 1..=255u8 {
+            assert_eq!(mul(a, 1), a);
+            assert_eq!(mul(a, 0), 0);
+            assert_eq!(mul(a, inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn split_invalid_args() {
+        assert!(split_secret(b"test", 0, 0).is_err());
+        assert!(split_secret(b"test", 3, 2).is_err());
+        assert!(split_secret(b"test", 1, 10).is_err());
+        assert!(split_secret(b"", 2, 3).is_err());
+    }
+
+    #[test]
+    fn combine_invalid() {
+        let shares = split_secret(b"test", 2, 3).unwrap();
+        assert!(combine_shares(&shares[..1]).is_err());
+        assert!(combine_shares(&[shares[0].clone(), shares[0].clone()]).is_err());
+
+        let mut zero = shares[1].clone();
+        zero.identifier = 0;
+        assert!(combine_shares(&[shares[0].clone(), zero]).is_err());
+
+        let mut short = shares[1].clone();
+        short.value.pop();
+        assert!(combine_shares(&[shares[0].clone(), short]).is_err());
+        let mut empty = shares.clone();
+        for s in empty.iter_mut() {
+            s.value.truncate(CHECKSUM_SIZE);
+        }
+        assert!(combine_shares(&empty).is_err());
+    }
+
+    #[test]
+    fn combine_all_combinations() {
+        let secret = b"a secret that is longer than any field element of a curve";
+        let shares = split_secret(&secret[..], 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        for share in &shares {
+            assert_eq!(share.value.len(), secret.len() + CHECKSUM_SIZE);
+        }
+        for i in 0..5 {
+            for j in (i + 1)..5 {
+                for k in (j + 1)..5 {
+                    let parts = [shares[i].clone(), shares[j].clone(), shares[k].clone()];
+                    assert_eq!(combine_shares(&parts).unwrap(), secret.to_vec());
+                }
+            }
+        }
+        assert_eq!(combine_shares(&shares).unwrap(), secret.to_vec());
+    }
+
+    #[test]
+    fn secret_lengths() {
+        for length in &[1, 16, 32, 33, 64, 1000] {
+            let secret: Vec<u8> = (0..*length).map(|i| i as u8).collect();
+            let shares = split_secret(&secret, 2, 255).unwrap();
+            assert_eq!(shares[254].identifier, 255);
+            assert_eq!(combine_shares(&shares[200..202]).unwrap(), secret);
+        }
+    }
+
+    #[test]
+    fn checksum_detects_invalid_shares() {
+        let secret = b"the same length!";
+        let shares = split_secret(&secret[..], 3, 5).unwrap();
+
+        // Fewer than the threshold
+        assert!(combine_shares(&shares[..2]).is_err());
+
+        // Corrupted share
+        let mut corrupted = shares[..3].to_vec();
+        corrupted[2].value[0] ^= 1;
+        assert!(combine_shares(&corrupted).is_err());
+
+        // Shares of a different secret with the same length
+        let other = split_secret(b"other of length!", 3, 5).unwrap();
+        let mixed = [shares[0].clone(), shares[1].clone(), other[2].clone()];
+        assert!(combine_shares(&mixed).is_err());
+    }
+
+    #[test]
+    fn share_serialization() {
+        let shares = split_secret(b"test", 2, 3).unwrap();
+        for share in &shares {
+            let bytes = share.to_bytes();
+            assert_eq!(bytes.len(), 4 + CHECKSUM_SIZE + 1);
+            assert_eq!(Share::from_bytes(&bytes).unwrap(), *share);
+        }
+        assert!(Share::from_bytes(&[1u8; CHECKSUM_SIZE + 1]).is_err());
+    }
+}
@@ -1 +1,9 @@
+pub mod dkg;
+pub mod feldman;
+#[cfg(any(feature = "sharing", feature = "sharing_native"))]
+pub mod gf256;
+pub mod pedersen;
+pub mod reshare;
+#[cfg(any(feature = "sharing", feature = "sharing_native"))]
 pub mod shamir;
+pub mod vss;
@@ -0,0 +1,246 @@
+//! Implements Pedersen's verifiable secret sharing scheme
+//! (see <https://www.cs.cornell.edu/courses/cs754/2001fa/129.PDF>)
+//! over the BLS12-381 scalar field with commitments in G1.
+//!
+//! The dealer shares the secret and a random blinding value with two polynomials
+//! and publishes Pedersen commitments to their coefficients. Unlike `feldman` the
+//! commitments are perfectly hiding so they reveal nothing about the secret.
+//! Each holder receives a secret share and a blinding share to verify it.
+
+use amcl_wrapper::{
+    field_elem::FieldElement, group_elem::GroupElement, group_elem_g1::G1,
+    univar_poly::UnivarPolynomial,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use super::vss::{
+    check_threshold, combine_shares as interpolate, evaluate_commitments, evaluate_shares,
+    random_polynomial, Share,
+};
+use {CryptoError, CryptoResult};
+
+/// The commitments to the polynomial coefficients used to verify shares
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct PedersenVerifier {
+    /// The generator for the secret polynomial
+    pub generator: G1,
+    /// The generator for the blinding polynomial
+    pub blinder: G1,
+    /// `generator * a_i + blinder * b_i` for each pair of coefficients `a_i` and `b_i`.
+    /// The first is the commitment to the secret.
+    pub commitments: Vec<G1>,
+}
+
+/// The result of a split
+#[derive(Debug, Clone)]
+pub struct PedersenResult {
+    /// The random value committed with the secret
+    pub blinding: FieldElement,
+    /// The commitments to verify the shares
+    pub verifier: PedersenVerifier,
+    /// The shares of the secret
+    pub secret_shares: Vec<Share>,
+    /// The shares of `blinding`, one for each secret share
+    pub blinding_shares: Vec<Share>,
+}
+
+impl PedersenVerifier {
+    /// Check that `share` and its `blinding_share` are consistent with the commitments
+    /// from the dealer
+    pub fn verify_share(&self, share: &Share, blinding_share: &Share) -> bool {
+        share.identifier != 0
+            && share.identifier == blinding_share.identifier
+            && !self.commitments.is_empty()
+            && evaluate_commitments(&self.commitments, share.identifier)
+                == self.generator.binary_scalar_mul(
+                    &self.blinder,
+                    &share.value,
+                    &blinding_share.value,
+                )
+    }
+
+    /// The number of shares required to reconstruct the secret
+    pub fn threshold(&self) -> usize {
+        self.commitments.len()
+    }
+
+    /// Verify each of at least a threshold set of `shares` with the matching
+    /// `blinding_shares`, then reconstruct the secret
+    pub fn combine_shares(
+        &self,
+        shares: &[Share],
+        blinding_shares: &[Share],
+    ) -> CryptoResult<FieldElement> {
+        if shares.len() != blinding_shares.len() {
+            return Err(CryptoError::GeneralError(
+                "Each share requires a blinding share".to_string(),
+            ));
+        }
+        check_threshold(shares.len(), &self.commitments)?;
+        if let Some((share, _)) = shares
+            .iter()
+            .zip(blinding_shares.iter())
+            .find(|(s, b)| !self.verify_share(s, b))
+        {
+            return Err(CryptoError::GeneralError(format!(
+                "Share {} is not consistent with the commitments",
+                share.identifier
+            )));
+        }
+        interpolate(shares)
+    }
+}
+
+/// Take a `secret` and generate `total` shares, which `threshold` is required to
+/// reconstruct the `secret`, and the commitments with `generator` and `blinder` to
+/// verify them. The `threshold` and `total` must be at least 2.
+///
+/// Nobody may know the discrete log of `blinder` with respect to `generator`,
+/// otherwise the dealer can open the commitments to a different secret.
+/// Use a hash to curve like `G1::from_msg_hash` to create it.
+pub fn split_secret(
+    secret: &FieldElement,
+    threshold: u8,
+    total: u8,
+    generator: &G1,
+    blinder: &G1,
+) -> CryptoResult<PedersenResult> {
+    split_secret_with_polynomial(secret, threshold, total, generator, blinder).map(|(res, _)| res)
+}
+
+/// `split_secret` that also returns the secret polynomial
+pub(crate) fn split_secret_with_polynomial(
+    secret: &FieldElement,
+    threshold: u8,
+    total: u8,
+    generator: &G1,
+    blinder: &G1,
+) -> CryptoResult<(PedersenResult, UnivarPolynomial)> {
+    if generator.is_identity() || blinder.is_identity() || generator == blinder {
+        return Err(CryptoError::GeneralError(
+            "generator and blinder must be distinct and not the identity".to_string(),
+        ));
+    }
+    let blinding = FieldElement::random();
+    let secret_polynomial = random_polynomial(secret, threshold, total)?;
+    let blinding_polynomial = random_polynomial(&blinding, threshold, total)?;
+    let commitments = secret_polynomial
+        .coefficients()
+        .iter()
+        .zip(blinding_polynomial.coefficients().iter())
+        .map(|(a, b)| generator.binary_scalar_mul(blinder, a, b))
+        .collect();
+    let res = PedersenResult {
+        blinding,
+        verifier: PedersenVerifier {
+            generator: generator.clone(),
+            blinder: blinder.clone(),
+            commitments,
+        },
+        secret_shares: evaluate_shares(&secret_polynomial, total),
+        blinding_shares: evaluate_shares(&blinding_polynomial, total),
+    };
+    Ok((res, secret_polynomial))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn generators() -> (G1, G1) {
+        (
+            G1::generator(),
+            G1::from_msg_hash(b"pedersen vss test blinder"),
+        )
+    }
+
+    #[test]
+    fn split_invalid_args() {
+        let (g, h) = generators();
+        let secret = FieldElement::random();
+        assert!(split_secret(&secret, 0, 0, &g, &h).is_err());
+        assert!(split_secret(&secret, 3, 2, &g, &h).is_err());
+        assert!(split_secret(&secret, 1, 10, &g, &h).is_err());
+        assert!(split_secret(&secret, 2, 3, &g, &g).is_err());
+        assert!(split_secret(&secret, 2, 3, &g, &G1::identity()).is_err());
+    }
+
+    #[test]
+    fn verify_and_combine() {
+        let (g, h) = generators();
+        let secret = FieldElement::random();
+        let res = split_secret(&secret, 3, 5, &g, &h).unwrap();
+        assert_eq!(res.verifier.threshold(), 3);
+        assert_eq!(
+            res.verifier.commitments[0],
+            g.binary_scalar_mul(&h, &secret, &res.blinding)
+        );
+        for (s, b) in res.secret_shares.iter().zip(res.blinding_shares.iter()) {
+            assert!(res.verifier.verify_share(s, b));
+        }
+
+        assert_eq!(
+            res.verifier
+                .combine_shares(&res.secret_shares[2..], &res.blinding_shares[2..])
+                .unwrap(),
+            secret
+        );
+        assert_eq!(
+            res.verifier
+                .combine_shares(&res.secret_shares, &res.blinding_shares)
+                .unwrap(),
+            secret
+        );
+        assert!(res
+            .verifier
+            .combine_shares(&res.secret_shares[..2], &res.blinding_shares[..2])
+            .is_err());
+        assert!(res
+            .verifier
+            .combine_shares(&res.secret_shares, &res.blinding_shares[1..])
+            .is_err());
+    }
+
+    #[test]
+    fn corrupted_share() {
+        let (g, h) = generators();
+        let secret = FieldElement::random();
+        let mut res = split_secret(&secret, 2, 3, &g, &h).unwrap();
+
+        res.secret_shares[0].value = res.secret_shares[0].value.clone() + FieldElement::one();
+        assert!(!res
+            .verifier
+            .verify_share(&res.secret_shares[0], &res.blinding_shares[0]));
+        assert!(res
+            .verifier
+            .combine_shares(&res.secret_shares, &res.blinding_shares)
+            .is_err());
+
+        // Mismatched blinding share
+        assert!(!res
+            .verifier
+            .verify_share(&res.secret_shares[1], &res.blinding_shares[2]));
+        assert!(res
+            .verifier
+            .combine_shares(&res.secret_shares[1..], &res.blinding_shares[1..])
+            .is_ok());
+    }
+
+    #[test]
+    fn cheating_dealer() {
+        let (g, h) = generators();
+        let secret = FieldElement::random();
+        let mut res = split_secret(&secret, 2, 3, &g, &h).unwrap();
+
+        // Commitment to another secret
+        res.verifier.commitments[0] =
+            g.binary_scalar_mul(&h, &FieldElement::random(), &res.blinding);
+        assert!(res
+            .secret_shares
+            .iter()
+            .zip(res.blinding_shares.iter())
+            .all(|(s, b)| !res.verifier.verify_share(s, b)));
+    }
+}
@@ -0,0 +1,307 @@
+//! Implements proactive share refresh as described by Herzberg, Jarecki, Krawczyk and Yung
+//! (see <https://link.springer.com/chapter/10.1007/3-540-44750-4_27>)
+//! and resharing to a new committee as described by Desmedt and Jajodia
+//! (see <https://cs.gmu.edu/media/techreports/ISSE-TR-97-01.pdf>)
+//! for secrets shared with `feldman` verifiable secret sharing.
+//!
+//! Neither protocol reconstructs the secret. To refresh, every holder deals a sharing of zero
+//! with `refresh_dealing` and adds the shares it receives to its own with `combine_refreshes`.
+//! Old shares can't be combined with new ones so shares leaked before a refresh are useless.
+//! To reshare, at least a threshold of old holders deal a sharing of their share to the new
+//! committee with `reshare` and each new holder interpolates what it receives with
+//! `combine_reshares`. Both output a new `FeldmanVerifier` that is the same for every holder.
+//!
+//! Dealt shares must be sent over private authenticated channels and the verifiers broadcast.
+//! A holder rejects all dealings if any of them is invalid so the holders must agree on
+//! which dealers to exclude, e.g. by broadcasting complaints, then combine again.
+
+use amcl_wrapper::{field_elem::FieldElement, group_elem::GroupElement, group_elem_g1::G1};
+use std::collections::BTreeMap;
+
+use super::feldman::{split_secret, FeldmanVerifier};
+use super::vss::{evaluate_commitments, lagrange_coefficients, Share};
+use {CryptoError, CryptoResult};
+
+/// Deal a sharing of zero to the `total` holders of a secret shared with `verifier`.
+/// Returns the verifier to broadcast and a share for each holder.
+pub fn refresh_dealing(
+    verifier: &FeldmanVerifier,
+    total: u8,
+) -> CryptoResult<(FeldmanVerifier, Vec<Share>)> {
+    split_secret(
+        &FieldElement::zero(),
+        verifier.threshold() as u8,
+        total,
+        &verifier.generator,
+    )
+}
+
+/// Add the shares of zero in `dealings` from each dealer to `share`, which is consistent
+/// with `verifier`. Returns the new verifier and share.
+pub fn combine_refreshes(
+    verifier: &FeldmanVerifier,
+    share: &Share,
+    dealings: &BTreeMap<u8, (FeldmanVerifier, Share)>,
+) -> CryptoResult<(FeldmanVerifier, Share)> {
+    check_share(verifier, share)?;
+    if dealings.is_empty() {
+        return Err(CryptoError::GeneralError(
+            "At least one dealing is required to refresh a share".to_string(),
+        ));
+    }
+    let mut commitments = verifier.commitments.clone();
+    let mut value = share.value.clone();
+    for (dealer, (dealing, dealt)) in dealings {
+        let valid = dealing.generator == verifier.generator
+            && dealing.threshold() == verifier.threshold()
+            && dealing.commitments[0].is_identity()
+            && dealt.identifier == share.identifier
+            && dealing.verify_share(dealt);
+        if !valid {
+            return Err(invalid_dealing(*dealer));
+        }
+        for (c, d) in commitments.iter_mut().zip(dealing.commitments.iter()) {
+            *c += d;
+        }
+        value += &dealt.value;
+    }
+    Ok((
+        FeldmanVerifier {
+            generator: verifier.generator.clone(),
+            commitments,
+        },
+        Share {
+            identifier: share.identifier,
+            value,
+        },
+    ))
+}
+
+/// Deal a sharing of `share`, which is consistent with `verifier`, to a new committee of
+/// `new_total` holders, which `new_threshold` are required to reconstruct the secret.
+/// Returns the verifier to broadcast and a share for each new holder.
+pub fn reshare(
+    verifier: &FeldmanVerifier,
+    share: &Share,
+    new_threshold: u8,
+    new_total: u8,
+) -> CryptoResult<(FeldmanVerifier, Vec<Share>)> {
+    check_share(verifier, share)?;
+    split_secret(&share.value, new_threshold, new_total, &verifier.generator)
+}
+
+/// Combine the `dealings` from at least a threshold of the holders of a secret shared with
+/// `verifier`, keyed by their identifiers. Returns the verifier and share of the new committee.
+pub fn combine_reshares(
+    verifier: &FeldmanVerifier,
+    dealings: &BTreeMap<u8, (FeldmanVerifier, Share)>,
+) -> CryptoResult<(FeldmanVerifier, Share)> {
+    if dealings.len() < verifier.threshold() {
+        return Err(CryptoError::GeneralError(
+            "Less than threshold dealings cannot be used to reshare the secret".to_string(),
+        ));
+    }
+    let (first, first_share) = dealings.values().next().unwrap();
+    for (dealer, (dealing, dealt)) in dealings {
+        // The dealer must share its old share which is committed in `verifier`
+        let valid = *dealer != 0
+            && !dealing.commitments.is_empty()
+            && dealing.generator == verifier.generator
+            && dealing.threshold() == first.threshold()
+            && dealing.commitments[0] == evaluate_commitments(&verifier.commitments, *dealer)
+            && dealt.identifier == first_share.identifier
+            && dealing.verify_share(dealt);
+        if !valid {
+            return Err(invalid_dealing(*dealer));
+        }
+    }
+
+    let identifiers: Vec<u8> = dealings.keys().copied().collect();
+    let coefficients = lagrange_coefficients(&identifiers)?;
+    let mut commitments = vec![G1::identity(); first.threshold()];
+    let mut value = FieldElement::zero();
+    for ((dealing, dealt), l) in dealings.values().zip(coefficients.iter()) {
+        for (c, d) in commitments.iter_mut().zip(dealing.commitments.iter()) {
+            *c += d * l;
+        }
+        value += &dealt.value * l;
+    }
+    Ok((
+        FeldmanVerifier {
+            generator: verifier.generator.clone(),
+            commitments,
+        },
+        Share {
+            identifier: first_share.identifier,
+            value,
+        },
+    ))
+}
+
+fn check_share(verifier: &FeldmanVerifier, share: &Share) -> CryptoResult<()> {
+    if verifier.verify_share(share) {
+        Ok(())
+    } else {
+        Err(CryptoError::GeneralError(
+            "Share is not consistent with the commitments".to_string(),
+        ))
+    }
+}
+
+fn invalid_dealing(dealer: u8) -> CryptoError {
+    CryptoError::GeneralError(format!(
+        "Dealing from {} is not consistent with the commitments",
+        dealer
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::vss::combine_shares;
+    use super::*;
+
+    /// Send the share for each holder from `dealings`
+    fn deliver(
+        dealings: &BTreeMap<u8, (FeldmanVerifier, Vec<Share>)>,
+        holder: u8,
+    ) -> BTreeMap<u8, (FeldmanVerifier, Share)> {
+        dealings
+            .iter()
+            .map(|(dealer, (v, shares))| {
+                (*dealer, (v.clone(), shares[(holder - 1) as usize].clone()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn refresh() {
+        let g = G1::generator();
+        let secret = FieldElement::random();
+        let (verifier, shares) = split_secret(&secret, 3, 5, &g).unwrap();
+        let dealings: BTreeMap<_, _> = shares
+            .iter()
+            .map(|s| (s.identifier, refresh_dealing(&verifier, 5).unwrap()))
+            .collect();
+
+        let mut refreshed = Vec::new();
+        for share in &shares {
+            let (new_verifier, new_share) =
+                combine_refreshes(&verifier, share, &deliver(&dealings, share.identifier)).unwrap();
+            assert_eq!(new_verifier.commitments[0], verifier.commitments[0]);
+            assert_ne!(new_verifier, verifier);
+            assert!(new_verifier.verify_share(&new_share));
+            assert_ne!(new_share, *share);
+            refreshed.push((new_verifier, new_share));
+        }
+        let new_verifier = refreshed[0].0.clone();
+        let new_shares: Vec<Share> = refreshed
+            .into_iter()
+            .map(|(v, s)| {
+                assert_eq!(v, new_verifier);
+                s
+            })
+            .collect();
+        assert_eq!(
+            new_verifier.combine_shares(&new_shares[2..]).unwrap(),
+            secret
+        );
+
+        // Old and new shares don't mix
+        let mixed = [shares[0].clone(), shares[1].clone(), new_shares[2].clone()];
+        assert_ne!(combine_shares(&mixed).unwrap(), secret);
+    }
+
+    #[test]
+    fn refresh_invalid_dealing() {
+        let g = G1::generator();
+        let secret = FieldElement::random();
+        let (verifier, shares) = split_secret(&secret, 2, 3, &g).unwrap();
+        assert!(combine_refreshes(&verifier, &shares[0], &BTreeMap::new()).is_err());
+
+        // A dealing that changes the secret
+        let mut dealings = BTreeMap::new();
+        dealings.insert(2, split_secret(&FieldElement::one(), 2, 3, &g).unwrap());
+        assert!(combine_refreshes(&verifier, &shares[0], &deliver(&dealings, 1)).is_err());
+
+        // A dealing that changes the threshold
+        dealings.insert(2, refresh_dealing(&verifier, 3).unwrap());
+        dealings.insert(3, split_secret(&FieldElement::zero(), 3, 3, &g).unwrap());
+        assert!(combine_refreshes(&verifier, &shares[0], &deliver(&dealings, 1)).is_err());
+
+        // A share for another holder
+        dealings.remove(&3);
+        assert!(combine_refreshes(&verifier, &shares[0], &deliver(&dealings, 1)).is_ok());
+        assert!(combine_refreshes(&verifier, &shares[0], &deliver(&dealings, 2)).is_err());
+    }
+
+    #[test]
+    fn reshare_to_new_committee() {
+        let g = G1::generator();
+        let secret = FieldElement::random();
+        let (verifier, shares) = split_secret(&secret, 3, 5, &g).unwrap();
+
+        for (threshold, total) in [(2, 3), (4, 7)].iter() {
+            // Any threshold of the old committee can reshare
+            let dealings: BTreeMap<_, _> = shares[1..4]
+                .iter()
+                .map(|s| {
+                    let dealing = reshare(&verifier, s, *threshold, *total).unwrap();
+                    (s.identifier, dealing)
+                })
+                .collect();
+            let mut new_verifiers = Vec::new();
+            let mut new_shares = Vec::new();
+            for holder in 1..=*total {
+                let (v, s) = combine_reshares(&verifier, &deliver(&dealings, holder)).unwrap();
+                assert_eq!(s.identifier, holder);
+                assert!(v.verify_share(&s));
+                new_verifiers.push(v);
+                new_shares.push(s);
+            }
+            let new_verifier = new_verifiers[0].clone();
+            assert!(new_verifiers.iter().all(|v| *v == new_verifier));
+            assert_eq!(new_verifier.threshold(), *threshold as usize);
+            assert_eq!(new_verifier.commitments[0], verifier.commitments[0]);
+            assert_eq!(new_verifier.combine_shares(&new_shares).unwrap(), secret);
+            let t = *threshold as usize;
+            assert_eq!(
+                new_verifier.combine_shares(&new_shares[..t]).unwrap(),
+                secret
+            );
+            assert!(new_verifier.combine_shares(&new_shares[..t - 1]).is_err());
+        }
+    }
+
+    #[test]
+    fn reshare_invalid_dealing() {
+        let g = G1::generator();
+        let secret = FieldElement::random();
+        let (verifier, shares) = split_secret(&secret, 2, 3, &g).unwrap();
+
+        let mut bad = shares[0].clone();
+        bad.value = FieldElement::random();
+        assert!(reshare(&verifier, &bad, 2, 3).is_err());
+        assert!(reshare(&verifier, &shares[0], 1, 3).is_err());
+
+        let mut dealings = BTreeMap::new();
+        dealings.insert(1, reshare(&verifier, &shares[0], 2, 3).unwrap());
+        assert!(combine_reshares(&verifier, &deliver(&dealings, 1)).is_err());
+
+        // A dealer that shares something other than its share
+        dealings.insert(2, split_secret(&FieldElement::random(), 2, 3, &g).unwrap());
+        assert!(combine_reshares(&verifier, &deliver(&dealings, 1)).is_err());
+
+        // A dealer that claims the identifier of another
+        dealings.insert(2, reshare(&verifier, &shares[2], 2, 3).unwrap());
+        assert!(combine_reshares(&verifier, &deliver(&dealings, 1)).is_err());
+
+        // Dealings with different thresholds
+        dealings.insert(3, reshare(&verifier, &shares[2], 3, 3).unwrap());
+        dealings.remove(&2);
+        assert!(combine_reshares(&verifier, &deliver(&dealings, 1)).is_err());
+
+        dealings.insert(3, reshare(&verifier, &shares[2], 2, 3).unwrap());
+        let (new_verifier, _) = combine_reshares(&verifier, &deliver(&dealings, 1)).unwrap();
+        assert_eq!(new_verifier.commitments[0], verifier.commitments[0]);
+    }
+}
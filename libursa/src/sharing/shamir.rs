@@ -6,7 +6,10 @@
 //! or adept secret sharing as described by Phillip Rogaway
 //! (see <https://eprint.iacr.org/2020/800>
 //!
-//! Future work would be to use pedersen commitments or reed-solomon
+//! See `feldman` and `pedersen` for verifiable secret sharing over the
+//! BLS12-381 scalar field and `reshare` to refresh or move `feldman` shares
+//! without reconstructing the secret. See `gf256` to share secrets of any
+//! length without picking a field. Future work would be to use reed-solomon
 //! codes to check for corrupted shares.
 
 use bn::BigNumber;
@@ -0,0 +1,231 @@
+//! Shares and polynomials over the BLS12-381 scalar field used by the
+//! verifiable secret sharing schemes in `feldman` and `pedersen` and
+//! by threshold BLS signatures.
+
+use amcl_wrapper::{
+    constants::MODBYTES, field_elem::FieldElement, group_elem::GroupElement, group_elem_g1::G1,
+    univar_poly::UnivarPolynomial,
+};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+
+use {CryptoError, CryptoResult};
+
+/// The size of a share in bytes
+pub const SHARE_SIZE: usize = MODBYTES + 1;
+
+/// Represents a share created from a split
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Share {
+    /// x-coordinate
+    pub identifier: u8,
+    /// y-coordinate
+    pub value: FieldElement,
+}
+
+impl Share {
+    /// Return byte representation of this share: value || identifier
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = self.value.to_bytes();
+        output.push(self.identifier);
+        output
+    }
+
+    /// Convert a share from bytes, assumes the last byte is the identifier
+    pub fn from_bytes<B: AsRef<[u8]>>(data: B) -> CryptoResult<Self> {
+        let data = data.as_ref();
+        if data.len() != SHARE_SIZE {
+            return Err(CryptoError::ParseError("Invalid share length".to_string()));
+        }
+        let value = FieldElement::from_bytes(&data[..MODBYTES])
+            .map_err(|e| CryptoError::ParseError(format!("{:?}", e)))?;
+        Ok(Self {
+            identifier: data[MODBYTES],
+            value,
+        })
+    }
+}
+
+/// Reconstruct a secret from at least a threshold set of `shares`.
+/// The shares are not checked, use the verifier of the sharing scheme
+/// to detect corrupted shares.
+pub fn combine_shares(shares: &[Share]) -> CryptoResult<FieldElement> {
+    let identifiers: Vec<u8> = shares.iter().map(|s| s.identifier).collect();
+    let coefficients = lagrange_coefficients(&identifiers)?;
+    Ok(shares
+        .iter()
+        .zip(coefficients.iter())
+        .fold(FieldElement::zero(), |secret, (s, l)| secret + &s.value * l))
+}
+
+/// Lagrange coefficients at 0 for the x-coordinates `identifiers`
+pub(crate) fn lagrange_coefficients(identifiers: &[u8]) -> CryptoResult<Vec<FieldElement>> {
+    if identifiers.len() < 2 {
+        return Err(CryptoError::GeneralError(
+            "Less than two shares cannot be combined".to_string(),
+        ));
+    }
+    let x_coordinates = x_coordinates(identifiers)?;
+    Ok(x_coordinates
+        .iter()
+        .map(|x_j| {
+            // x_m / (x_m - x_j) * ...
+            let mut num = FieldElement::one();
+            let mut denom = FieldElement::one();
+            for x_m in x_coordinates.iter().filter(|x_m| *x_m != x_j) {
+                num = num * x_m;
+                denom = denom * (x_m - x_j);
+            }
+            num * denom.inverse()
+        })
+        .collect())
+}
+
+/// Interpolate the polynomial of degree `shares.len() - 1` that passes through `shares`
+pub(crate) fn interpolate_polynomial(shares: &[Share]) -> CryptoResult<UnivarPolynomial> {
+    let identifiers: Vec<u8> = shares.iter().map(|s| s.identifier).collect();
+    let x_coordinates = x_coordinates(&identifiers)?;
+    let mut polynomial = UnivarPolynomial::new_constant(FieldElement::zero());
+    for (x_j, share) in x_coordinates.iter().zip(shares) {
+        // value * (x - x_m) / (x_j - x_m) * ...
+        let others: Vec<FieldElement> = x_coordinates
+            .iter()
+            .filter(|x_m| *x_m != x_j)
+            .cloned()
+            .collect();
+        let denom = others
+            .iter()
+            .fold(FieldElement::one(), |d, x_m| d * (x_j - x_m));
+        let basis = UnivarPolynomial::new_with_roots(&others)
+            .multiply_by_constant(&(&share.value * &denom.inverse()));
+        polynomial = UnivarPolynomial::sum(&polynomial, &basis);
+    }
+    Ok(polynomial)
+}
+
+/// Check the identifiers are non-zero and distinct and map them to field elements
+fn x_coordinates(identifiers: &[u8]) -> CryptoResult<Vec<FieldElement>> {
+    let mut dups = BTreeSet::new();
+    for identifier in identifiers {
+        if *identifier == 0 {
+            return Err(CryptoError::GeneralError(
+                "Share must have a non-zero identifier".to_string(),
+            ));
+        }
+        if !dups.insert(*identifier) {
+            return Err(CryptoError::GeneralError(
+                "Duplicate shares cannot be combined".to_string(),
+            ));
+        }
+    }
+    Ok(identifiers.iter().map(|i| FieldElement::from(*i)).collect())
+}
+
+/// Check the arguments to a split and construct a random polynomial
+/// of degree `threshold - 1` with `intercept`
+pub(crate) fn random_polynomial(
+    intercept: &FieldElement,
+    threshold: u8,
+    total: u8,
+) -> CryptoResult<UnivarPolynomial> {
+    if total < threshold {
+        return Err(CryptoError::GeneralError(
+            "total cannot be less than the threshold".to_string(),
+        ));
+    }
+    if threshold < 2 {
+        return Err(CryptoError::GeneralError(
+            "threshold must be at least 2".to_string(),
+        ));
+    }
+    let mut polynomial = UnivarPolynomial::random((threshold - 1) as usize);
+    polynomial.0[0] = intercept.clone();
+    Ok(polynomial)
+}
+
+/// Evaluate `polynomial` at the x-coordinates [1, total]
+pub(crate) fn evaluate_shares(polynomial: &UnivarPolynomial, total: u8) -> Vec<Share> {
+    // x coordinates are incremental from [1, total+1). 0 is reserved for the secret.
+    (1..=total)
+        .map(|identifier| Share {
+            identifier,
+            value: polynomial.eval(&FieldElement::from(identifier)),
+        })
+        .collect()
+}
+
+/// Evaluate the polynomial in the exponent with coefficients `commitments` at `identifier`
+pub(crate) fn evaluate_commitments(commitments: &[G1], identifier: u8) -> G1 {
+    // Horner's Method in the exponent
+    let x = FieldElement::from(identifier);
+    commitments
+        .iter()
+        .rev()
+        .fold(G1::identity(), |out, c| &out * &x + c)
+}
+
+/// Check there are enough shares to reconstruct a secret shared with `commitments`
+pub(crate) fn check_threshold(shares: usize, commitments: &[G1]) -> CryptoResult<()> {
+    if shares < commitments.len() {
+        Err(CryptoError::GeneralError(
+            "Less than threshold shares cannot be used to reconstruct the secret".to_string(),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn share_serialization() {
+        let share = Share {
+            identifier: 3,
+            value: FieldElement::random(),
+        };
+        let bytes = share.to_bytes();
+        assert_eq!(bytes.len(), SHARE_SIZE);
+        assert_eq!(Share::from_bytes(&bytes).unwrap(), share);
+        assert!(Share::from_bytes(&bytes[1..]).is_err());
+    }
+
+    #[test]
+    fn combine_invalid() {
+        let share = |identifier| Share {
+            identifier,
+            value: FieldElement::random(),
+        };
+        assert!(combine_shares(&[]).is_err());
+        assert!(combine_shares(&[share(1)]).is_err());
+        assert!(combine_shares(&[share(0), share(1)]).is_err());
+        assert!(combine_shares(&[share(1), share(1)]).is_err());
+    }
+
+    #[test]
+    fn polynomial_set_intercept() {
+        let intercept = FieldElement::from(42u8);
+        let polynomial = random_polynomial(&intercept, 3, 5).unwrap();
+        assert_eq!(polynomial.degree(), 2);
+        assert_eq!(polynomial.eval(&FieldElement::zero()), intercept);
+        assert!(random_polynomial(&intercept, 3, 2).is_err());
+        assert!(random_polynomial(&intercept, 1, 2).is_err());
+
+        let shares = evaluate_shares(&polynomial, 5);
+        assert_eq!(combine_shares(&shares[1..4]).unwrap(), intercept);
+        assert_eq!(combine_shares(&shares).unwrap(), intercept);
+        assert_ne!(combine_shares(&shares[..2]).unwrap(), intercept);
+    }
+
+    #[test]
+    fn polynomial_interpolation() {
+        let polynomial = random_polynomial(&FieldElement::random(), 3, 5).unwrap();
+        let shares = evaluate_shares(&polynomial, 5);
+        let interpolated = interpolate_polynomial(&shares[2..]).unwrap();
+        assert_eq!(interpolated.coefficients(), polynomial.coefficients());
+        assert!(interpolate_polynomial(&[shares[0].clone(), shares[0].clone()]).is_err());
+    }
+}
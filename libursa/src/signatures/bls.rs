@@ -15,6 +15,7 @@ use keys::{KeyGenOption, PrivateKey as UrsaPrivateKey, PublicKey as UrsaPublicKe
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use sha2::Sha256;
+use sharing::vss::{evaluate_shares, lagrange_coefficients, random_polynomial, Share};
 
 use CryptoError;
 
@@ -23,6 +24,28 @@ pub const PRIVATE_KEY_SIZE: usize = MODBYTES;
 /// instead of wrapping it as a private field
 pub type PrivateKey = FieldElement;
 
+/// A Shamir share of a `PrivateKey` over the curve order used for threshold signing.
+/// Any `threshold` holders can sign with `Signature::new_share` and combine their
+/// signatures into one that verifies under the `PublicKey` of the shared `PrivateKey`.
+/// Shares from the verifiable secret sharing and DKG schemes in `sharing` can be used directly.
+pub type PrivateKeyShare = Share;
+
+/// Split `sk` into `total` shares which `threshold` are required to sign.
+/// The `threshold` and `total` must be at least 2. Share identifiers are [1, total].
+pub fn split_private_key(
+    sk: &PrivateKey,
+    threshold: u8,
+    total: u8,
+) -> Result<Vec<PrivateKeyShare>, CryptoError> {
+    if sk.is_zero() {
+        return Err(CryptoError::GeneralError(
+            "private key cannot be zero".to_string(),
+        ));
+    }
+    let polynomial = random_polynomial(sk, threshold, total)?;
+    Ok(evaluate_shares(&polynomial, total))
+}
+
 macro_rules! bls_impl {
     ($pk_size:expr, $sig_size:expr, $pk_group:ident, $sig_group:ident, $ate_2_pairing_is_one:ident, $set_pairs:ident) => {
         pub const PUBLIC_KEY_SIZE: usize = $pk_size;
@@ -353,6 +376,71 @@ macro_rules! bls_impl {
             }
         }
 
+        /// Signature over a message by the holder of a `PrivateKeyShare`.
+        /// It verifies under the `PublicKey` of the key share and any `threshold`
+        /// of them combine with `Signature::from_shares`.
+        #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+        #[derive(Debug, Clone)]
+        pub struct SignatureShare {
+            /// Identifier of the private key share
+            pub identifier: u8,
+            /// Signature by the private key share
+            pub signature: Signature,
+        }
+
+        impl SignatureShare {
+            /// Return byte representation of this share: signature || identifier
+            pub fn to_bytes(&self) -> Vec<u8> {
+                let mut output = self.signature.to_bytes();
+                output.push(self.identifier);
+                output
+            }
+
+            /// Convert a share from bytes, assumes the last byte is the identifier
+            pub fn from_bytes(bytes: &[u8]) -> Result<Self, CryptoError> {
+                if bytes.is_empty() {
+                    return Err(CryptoError::ParseError(
+                        "Invalid signature share length".to_string(),
+                    ));
+                }
+                let (signature, identifier) = bytes.split_at(bytes.len() - 1);
+                Ok(SignatureShare {
+                    identifier: identifier[0],
+                    signature: Signature::from_bytes(signature)?,
+                })
+            }
+        }
+
+        impl Signature {
+            /// Sign with a share of a threshold private key
+            pub fn new_share<A: AsRef<[u8]>>(
+                message: A,
+                context: Option<&'static [u8]>,
+                share: &PrivateKeyShare,
+            ) -> SignatureShare {
+                SignatureShare {
+                    identifier: share.identifier,
+                    signature: Signature::new(message, context, &share.value),
+                }
+            }
+
+            /// Combine signature shares with Lagrange interpolation in the exponent.
+            /// The result only verifies under the shared `PublicKey` if the shares
+            /// are valid and there are at least as many as the threshold.
+            pub fn from_shares(shares: &[SignatureShare]) -> Result<Self, CryptoError> {
+                let identifiers: Vec<u8> = shares.iter().map(|s| s.identifier).collect();
+                let coefficients = lagrange_coefficients(identifiers.as_slice())?;
+                Ok(Signature(
+                    shares
+                        .iter()
+                        .zip(coefficients.iter())
+                        .fold(SignatureGroup::identity(), |sig, (s, l)| {
+                            sig + &s.signature.0 * l
+                        }),
+                ))
+            }
+        }
+
         /// Proof of possession for BLS verification key.
         /// Used as another form of rogue key mitigation
         /// where signers are known entities in a group.
@@ -691,6 +779,98 @@ macro_rules! bls_tests_impl {
                 ));
             }
 
+            #[test]
+            fn threshold_signature() {
+                let g = Generator::generator();
+                let (pk, sk) = generate(&g);
+                let shares = split_private_key(&sk, 3, 5).unwrap();
+                let sig_shares: Vec<SignatureShare> = shares
+                    .iter()
+                    .map(|s| Signature::new_share(&MESSAGE_1[..], None, s))
+                    .collect();
+
+                // Each share verifies under the public key of its private key share
+                for (share, sig_share) in shares.iter().zip(sig_shares.iter()) {
+                    let share_pk = PublicKey::new(&share.value, &g);
+                    assert!(sig_share
+                        .signature
+                        .verify(&MESSAGE_1[..], None, &share_pk, &g));
+                    assert!(!sig_share.signature.verify(&MESSAGE_1[..], None, &pk, &g));
+                }
+
+                for i in 0..5 {
+                    for j in (i + 1)..5 {
+                        for k in (j + 1)..5 {
+                            let parts = [
+                                sig_shares[i].clone(),
+                                sig_shares[k].clone(),
+                                sig_shares[j].clone(),
+                            ];
+                            let sig = Signature::from_shares(&parts).unwrap();
+                            assert!(sig.verify(&MESSAGE_1[..], None, &pk, &g));
+                            assert!(!sig.verify(&MESSAGE_2[..], None, &pk, &g));
+                        }
+                    }
+                }
+
+                // All shares also interpolate the same signature
+                let sig = Signature::from_shares(&sig_shares).unwrap();
+                assert!(sig.verify(&MESSAGE_1[..], None, &pk, &g));
+
+                // Below the threshold
+                let sig = Signature::from_shares(&sig_shares[..2]).unwrap();
+                assert!(!sig.verify(&MESSAGE_1[..], None, &pk, &g));
+
+                // A share for another message
+                let mut parts = sig_shares[..3].to_vec();
+                parts[0] = Signature::new_share(&MESSAGE_2[..], None, &shares[0]);
+                let sig = Signature::from_shares(&parts).unwrap();
+                assert!(!sig.verify(&MESSAGE_1[..], None, &pk, &g));
+            }
+
+            #[test]
+            fn threshold_invalid_args() {
+                let g = Generator::generator();
+                let (_, sk) = generate(&g);
+                assert!(split_private_key(&sk, 3, 2).is_err());
+                assert!(split_private_key(&sk, 1, 3).is_err());
+                assert!(split_private_key(&PrivateKey::zero(), 2, 3).is_err());
+
+                let shares = split_private_key(&sk, 2, 3).unwrap();
+                let sig_shares: Vec<SignatureShare> = shares
+                    .iter()
+                    .map(|s| Signature::new_share(&MESSAGE_1[..], None, s))
+                    .collect();
+                assert!(Signature::from_shares(&sig_shares[..1]).is_err());
+                let dup = [sig_shares[0].clone(), sig_shares[0].clone()];
+                assert!(Signature::from_shares(&dup).is_err());
+                let mut zero = sig_shares[..2].to_vec();
+                zero[1].identifier = 0;
+                assert!(Signature::from_shares(&zero).is_err());
+            }
+
+            #[test]
+            fn threshold_share_serialization() {
+                let g = Generator::generator();
+                let (pk, sk) = generate(&g);
+                let shares = split_private_key(&sk, 2, 3).unwrap();
+                let share = PrivateKeyShare::from_bytes(&shares[2].to_bytes()).unwrap();
+                assert_eq!(share, shares[2]);
+                assert!(PrivateKeyShare::from_bytes(&shares[2].to_bytes()[1..]).is_err());
+
+                let sig_shares: Vec<SignatureShare> = shares[1..]
+                    .iter()
+                    .map(|s| {
+                        let bytes = Signature::new_share(&MESSAGE_1[..], None, s).to_bytes();
+                        assert_eq!(bytes.len(), SIGNATURE_SIZE + 1);
+                        SignatureShare::from_bytes(&bytes).unwrap()
+                    })
+                    .collect();
+                let sig = Signature::from_shares(&sig_shares).unwrap();
+                assert!(sig.verify(&MESSAGE_1[..], None, &pk, &g));
+                assert!(SignatureShare::from_bytes(&[]).is_err());
+            }
+
             #[test]
             fn multi_signature_verification() {
                 const KEY_COUNT: usize = 10;
@@ -740,8 +920,9 @@ pub mod prelude {
             AggregatedSignature as SmallAggregatedSignature, Generator as SmallGenerator,
             ProofOfPossession as SmallProofOfPossession, PublicKey as SmallPublicKey,
             Signature as SmallSignature, SignatureGroup as SmallSignatureGroup,
+            SignatureShare as SmallSignatureShare,
         },
-        PrivateKey,
+        split_private_key, PrivateKey, PrivateKeyShare,
     };
 }
 
@@ -1,22 +1,32 @@
 pub const ALGORITHM_NAME: &str = "ED25519_SHA2_512";
 
 use super::{KeyGenOption, SignatureScheme};
+use curve25519_dalek::constants::{ED25519_BASEPOINT_POINT, ED25519_BASEPOINT_TABLE};
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use curve25519_dalek::traits::{IsIdentity, VartimeMultiscalarMul};
 #[cfg(any(feature = "x25519", feature = "x25519_asm"))]
 use ed25519_dalek::SecretKey as SK;
-use ed25519_dalek::{Keypair, PublicKey as PK, Signature};
+use ed25519_dalek::{ExpandedSecretKey, Keypair, PublicKey as PK, Signature};
 pub use ed25519_dalek::{
     EXPANDED_SECRET_KEY_LENGTH as PRIVATE_KEY_SIZE, PUBLIC_KEY_LENGTH as PUBLIC_KEY_SIZE,
     SIGNATURE_LENGTH as SIGNATURE_SIZE,
 };
 use keys::{PrivateKey, PublicKey};
 use rand::rngs::OsRng;
-use rand::SeedableRng;
+use rand::{RngCore, SeedableRng};
 use rand_chacha::ChaChaRng;
-use sha2::Digest;
+use sha2::digest::generic_array::typenum::U64;
+use sha2::{Digest, Sha512};
 use zeroize::Zeroize;
 
 use CryptoError;
 
+/// The domain separation prefix of dom2 from RFC 8032
+const DOM2_PREFIX: &[u8] = b"SigEd25519 no Ed25519 collisions";
+/// The longest context allowed by Ed25519ctx and Ed25519ph
+pub const MAX_CONTEXT_SIZE: usize = 255;
+
 pub struct Ed25519Sha512;
 
 #[cfg(any(feature = "x25519", feature = "x25519_asm"))]
@@ -105,6 +115,244 @@ impl Ed25519Sha512 {
     }
 }
 
+impl Ed25519Sha512 {
+    /// Verify many signatures at once using a random linear combination of the
+    /// verification equations. This is much faster than checking each signature
+    /// but only reports whether all of them are valid.
+    ///
+    /// The combined equation is multiplied by the cofactor so the result does not depend
+    /// on the random coefficients. It accepts every signature that `verify` accepts, and also
+    /// signatures where R or the public key has a small order component or R is not canonically
+    /// encoded, which `verify` rejects and honest signers never produce.
+    /// Returns false if any signature is invalid and an error if the inputs are malformed.
+    ///
+    /// # Example
+    /// ```
+    /// use ursa::signatures::ed25519::Ed25519Sha512;
+    /// use ursa::signatures::SignatureScheme;
+    ///
+    /// let scheme = Ed25519Sha512::new();
+    /// let (pk, sk) = scheme.keypair(None).unwrap();
+    /// let sig = scheme.sign(b"message", &sk).unwrap();
+    /// let result = scheme.verify_batch(&[b"message"], &[&sig], &[pk]);
+    /// assert!(result.unwrap());
+    /// ```
+    pub fn verify_batch(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[PublicKey],
+    ) -> Result<bool, CryptoError> {
+        if messages.len() != signatures.len() || messages.len() != public_keys.len() {
+            return Err(CryptoError::GeneralError(
+                "Messages, signatures and public keys must have the same length".to_string(),
+            ));
+        }
+        let mut rng = OsRng;
+        let mut scalars = Vec::with_capacity(2 * messages.len() + 1);
+        let mut points = Vec::with_capacity(2 * messages.len() + 1);
+        let mut b_coefficient = Scalar::zero();
+        for ((message, signature), pk) in messages
+            .iter()
+            .zip(signatures.iter())
+            .zip(public_keys.iter())
+        {
+            PK::from_bytes(&pk[..]).map_err(|e| CryptoError::ParseError(e.to_string()))?;
+            Signature::from_bytes(signature).map_err(|e| CryptoError::ParseError(e.to_string()))?;
+            let a = CompressedEdwardsY::from_slice(&pk[..])
+                .decompress()
+                .ok_or_else(|| CryptoError::ParseError("Invalid public key".to_string()))?;
+            let r = match CompressedEdwardsY::from_slice(&signature[..32]).decompress() {
+                Some(r) => r,
+                None => return Ok(false),
+            };
+            // The same scalar as `Signature::from_bytes`
+            let s = Scalar::from_bits(*array_ref!(signature, 32, 32));
+            let k = Scalar::from_hash(
+                Sha512::new()
+                    .chain(&signature[..32])
+                    .chain(&pk[..])
+                    .chain(message),
+            );
+            let mut z = [0u8; 32];
+            rng.fill_bytes(&mut z[..16]);
+            let z = Scalar::from_bits(z);
+
+            b_coefficient -= z * s;
+            scalars.push(z);
+            points.push(r);
+            scalars.push(z * k);
+            points.push(a);
+        }
+        // 8 * (sum(z_i * R_i) + sum(z_i * k_i * A_i) - sum(z_i * s_i) * B) = 0
+        scalars.push(b_coefficient);
+        points.push(ED25519_BASEPOINT_POINT);
+        Ok(EdwardsPoint::vartime_multiscalar_mul(scalars, points)
+            .mul_by_cofactor()
+            .is_identity())
+    }
+
+    /// Sign a message that has already been fed into a SHA-512 `Digest`
+    /// as specified for Ed25519ph in RFC 8032.
+    ///
+    /// # Example
+    /// ```
+    /// use ursa::sha2::{Digest, Sha512};
+    /// use ursa::signatures::ed25519::Ed25519Sha512;
+    /// use ursa::signatures::SignatureScheme;
+    ///
+    /// let scheme = Ed25519Sha512::new();
+    /// let (pk, sk) = scheme.keypair(None).unwrap();
+    /// let mut hasher = Sha512::new();
+    /// hasher.input(b"a very long ");
+    /// hasher.input(b"message");
+    /// let sig = scheme.sign_prehashed(hasher.clone(), None, &sk).unwrap();
+    /// assert!(scheme.verify_prehashed(hasher, None, &sig, &pk).unwrap());
+    /// ```
+    pub fn sign_prehashed<D>(
+        &self,
+        prehashed_message: D,
+        context: Option<&[u8]>,
+        sk: &PrivateKey,
+    ) -> Result<Vec<u8>, CryptoError>
+    where
+        D: Digest<OutputSize = U64>,
+    {
+        check_context(context.unwrap_or(b""))?;
+        let kp =
+            Keypair::from_bytes(&sk[..]).map_err(|e| CryptoError::KeyGenError(e.to_string()))?;
+        Ok(kp
+            .sign_prehashed(prehashed_message, context)
+            .to_bytes()
+            .to_vec())
+    }
+
+    /// Verify an Ed25519ph signature over a message that has already been
+    /// fed into a SHA-512 `Digest`.
+    pub fn verify_prehashed<D>(
+        &self,
+        prehashed_message: D,
+        context: Option<&[u8]>,
+        signature: &[u8],
+        pk: &PublicKey,
+    ) -> Result<bool, CryptoError>
+    where
+        D: Digest<OutputSize = U64>,
+    {
+        check_context(context.unwrap_or(b""))?;
+        let p = PK::from_bytes(&pk[..]).map_err(|e| CryptoError::ParseError(e.to_string()))?;
+        let s =
+            Signature::from_bytes(signature).map_err(|e| CryptoError::ParseError(e.to_string()))?;
+        p.verify_prehashed(prehashed_message, context, &s)
+            .map_err(|e| CryptoError::SigningError(e.to_string()))?;
+        Ok(true)
+    }
+
+    /// Sign a message with a context string as specified for Ed25519ctx in RFC 8032.
+    /// The context must be between 1 and 255 bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use ursa::signatures::ed25519::Ed25519Sha512;
+    /// use ursa::signatures::SignatureScheme;
+    ///
+    /// let scheme = Ed25519Sha512::new();
+    /// let (pk, sk) = scheme.keypair(None).unwrap();
+    /// let sig = scheme.sign_with_context(b"message", b"context", &sk).unwrap();
+    /// assert!(scheme.verify_with_context(b"message", b"context", &sig, &pk).unwrap());
+    /// ```
+    pub fn sign_with_context(
+        &self,
+        message: &[u8],
+        context: &[u8],
+        sk: &PrivateKey,
+    ) -> Result<Vec<u8>, CryptoError> {
+        check_ctx_context(context)?;
+        let kp =
+            Keypair::from_bytes(&sk[..]).map_err(|e| CryptoError::KeyGenError(e.to_string()))?;
+        let mut expanded = ExpandedSecretKey::from(&kp.secret).to_bytes();
+        let mut a = Scalar::from_bits(*array_ref!(expanded, 0, 32));
+        let mut r = Scalar::from_hash(dom2(context).chain(&expanded[32..]).chain(message));
+        expanded.zeroize();
+        let big_r = (&r * &ED25519_BASEPOINT_TABLE).compress();
+        let k = Scalar::from_hash(
+            dom2(context)
+                .chain(big_r.as_bytes())
+                .chain(kp.public.as_bytes())
+                .chain(message),
+        );
+        let s = k * a + r;
+        a.zeroize();
+        r.zeroize();
+
+        let mut signature = big_r.as_bytes().to_vec();
+        signature.extend_from_slice(s.as_bytes());
+        Ok(signature)
+    }
+
+    /// Verify an Ed25519ctx signature made with `sign_with_context`.
+    pub fn verify_with_context(
+        &self,
+        message: &[u8],
+        context: &[u8],
+        signature: &[u8],
+        pk: &PublicKey,
+    ) -> Result<bool, CryptoError> {
+        check_ctx_context(context)?;
+        PK::from_bytes(&pk[..]).map_err(|e| CryptoError::ParseError(e.to_string()))?;
+        Signature::from_bytes(signature).map_err(|e| CryptoError::ParseError(e.to_string()))?;
+        let a = CompressedEdwardsY::from_slice(&pk[..])
+            .decompress()
+            .ok_or_else(|| CryptoError::ParseError("Invalid public key".to_string()))?;
+        let big_r = CompressedEdwardsY::from_slice(&signature[..32]);
+        let s = Scalar::from_canonical_bytes(*array_ref!(signature, 32, 32)).ok_or_else(|| {
+            CryptoError::SigningError("Signature scalar is not canonical".to_string())
+        })?;
+        let k = Scalar::from_hash(
+            dom2(context)
+                .chain(big_r.as_bytes())
+                .chain(&pk[..])
+                .chain(message),
+        );
+        let check = EdwardsPoint::vartime_double_scalar_mul_basepoint(&k, &-a, &s);
+        if check.compress() != big_r {
+            return Err(CryptoError::SigningError(
+                "Verification equation was not satisfied".to_string(),
+            ));
+        }
+        Ok(true)
+    }
+}
+
+/// Starts a SHA-512 hash with dom2(0, context) for Ed25519ctx
+fn dom2(context: &[u8]) -> Sha512 {
+    Sha512::new()
+        .chain(DOM2_PREFIX)
+        .chain([0u8])
+        .chain([context.len() as u8])
+        .chain(context)
+}
+
+fn check_context(context: &[u8]) -> Result<(), CryptoError> {
+    if context.len() > MAX_CONTEXT_SIZE {
+        return Err(CryptoError::ParseError(format!(
+            "Context must be at most {} bytes",
+            MAX_CONTEXT_SIZE
+        )));
+    }
+    Ok(())
+}
+
+/// Unlike Ed25519ph, Ed25519ctx requires a non-empty context
+fn check_ctx_context(context: &[u8]) -> Result<(), CryptoError> {
+    if context.is_empty() {
+        return Err(CryptoError::ParseError(
+            "Context must not be empty".to_string(),
+        ));
+    }
+    check_context(context)
+}
+
 impl SignatureScheme for Ed25519Sha512 {
     fn new() -> Self {
         Self
@@ -168,6 +416,8 @@ mod test {
     use self::Ed25519Sha512;
     use super::super::{SignatureScheme, Signer};
     use super::*;
+    use curve25519_dalek::constants::EIGHT_TORSION;
+    use ed25519_dalek::SecretKey;
     use keys::{KeyGenOption, PrivateKey, PublicKey};
     use libsodium_ffi as ffi;
 
@@ -308,4 +558,140 @@ mod test {
         assert_eq!(pk.0, test_pk);
         assert_eq!(sk.0, test_sk);
     }
+
+    #[test]
+    fn ed25519_verify_batch() {
+        let scheme = Ed25519Sha512::new();
+        let mut messages = Vec::new();
+        let mut signatures = Vec::new();
+        let mut public_keys = Vec::new();
+        for i in 0..16u8 {
+            let (p, s) = scheme.keypair(None).unwrap();
+            let message = vec![i; i as usize + 1];
+            signatures.push(scheme.sign(&message, &s).unwrap());
+            messages.push(message);
+            public_keys.push(p);
+        }
+        let m = messages
+            .iter()
+            .map(|m| m.as_slice())
+            .collect::<Vec<&[u8]>>();
+        let sigs = signatures
+            .iter()
+            .map(|s| s.as_slice())
+            .collect::<Vec<&[u8]>>();
+        assert!(scheme.verify_batch(&m, &sigs, &public_keys).unwrap());
+        assert!(scheme.verify_batch(&[], &[], &[]).unwrap());
+
+        let mut tampered = m.clone();
+        tampered[3] = MESSAGE_1;
+        assert!(!scheme.verify_batch(&tampered, &sigs, &public_keys).unwrap());
+        let mut swapped = sigs.clone();
+        swapped.swap(0, 1);
+        assert!(!scheme.verify_batch(&m, &swapped, &public_keys).unwrap());
+        assert!(scheme.verify_batch(&m[1..], &sigs, &public_keys).is_err());
+    }
+
+    #[test]
+    fn ed25519_verify_batch_torsion() {
+        // Add a point of order 8 to R so the signature only satisfies the cofactored equation
+        let scheme = Ed25519Sha512::new();
+        let (p, s) = scheme.keypair(None).unwrap();
+        let expanded =
+            ExpandedSecretKey::from(&SecretKey::from_bytes(&s[..32]).unwrap()).to_bytes();
+        let a = Scalar::from_bits(*array_ref!(expanded, 0, 32));
+        let r = Scalar::from_hash(Sha512::new().chain(MESSAGE_1));
+        let big_r = (&r * &ED25519_BASEPOINT_TABLE + EIGHT_TORSION[1]).compress();
+        let k = Scalar::from_hash(
+            Sha512::new()
+                .chain(big_r.as_bytes())
+                .chain(&p[..])
+                .chain(MESSAGE_1),
+        );
+        let mut sig = big_r.as_bytes().to_vec();
+        sig.extend_from_slice((r + k * a).as_bytes());
+
+        assert!(scheme.verify(MESSAGE_1, &sig, &p).is_err());
+        // Without the cofactor this would fail for 7 in 8 random coefficients
+        for _ in 0..16 {
+            assert!(scheme
+                .verify_batch(&[MESSAGE_1], &[&sig], &[p.clone()])
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn ed25519ph_sign() {
+        // RFC 8032 section 7.3
+        let sk = PrivateKey(hex::decode("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf").unwrap());
+        let pk = PublicKey(
+            hex::decode("ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf")
+                .unwrap(),
+        );
+        let expected = "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae4131f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406";
+        let scheme = Ed25519Sha512::new();
+
+        let sig = scheme
+            .sign_prehashed(Sha512::new().chain(b"abc"), None, &sk)
+            .unwrap();
+        assert_eq!(hex::encode(&sig), expected);
+        let mut hasher = Sha512::new();
+        hasher.input(b"a");
+        hasher.input(b"bc");
+        assert!(scheme.verify_prehashed(hasher, None, &sig, &pk).unwrap());
+        assert!(scheme
+            .verify_prehashed(Sha512::new().chain(b"abd"), None, &sig, &pk)
+            .is_err());
+        assert!(scheme
+            .verify_prehashed(Sha512::new().chain(b"abc"), Some(b"foo"), &sig, &pk)
+            .is_err());
+        // Ed25519ph signatures are not plain Ed25519 signatures
+        assert!(scheme.verify(b"abc", &sig, &pk).is_err());
+
+        let sig = scheme
+            .sign_prehashed(Sha512::new().chain(b"abc"), Some(b"foo"), &sk)
+            .unwrap();
+        assert!(scheme
+            .verify_prehashed(Sha512::new().chain(b"abc"), Some(b"foo"), &sig, &pk)
+            .unwrap());
+        assert!(scheme
+            .sign_prehashed(Sha512::new(), Some(&[0u8; 256]), &sk)
+            .is_err());
+    }
+
+    #[test]
+    fn ed25519ctx_sign() {
+        // RFC 8032 section 7.2
+        let sk = PrivateKey(hex::decode("0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292").unwrap());
+        let pk = PublicKey(
+            hex::decode("dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292")
+                .unwrap(),
+        );
+        let message = hex::decode("f726936d19c800494e3fdaff20b276a8").unwrap();
+        let scheme = Ed25519Sha512::new();
+
+        let sig = scheme.sign_with_context(&message, b"foo", &sk).unwrap();
+        assert_eq!(hex::encode(&sig), "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d");
+        assert!(scheme
+            .verify_with_context(&message, b"foo", &sig, &pk)
+            .unwrap());
+        let sig = scheme.sign_with_context(&message, b"bar", &sk).unwrap();
+        assert_eq!(hex::encode(&sig), "fc60d5872fc46b3aa69f8b5b4351d5808f92bcc044606db097abab6dbcb1aee3216c48e8b3b66431b5b186d1d28f8ee15a5ca2df6668346291c2043d4eb3e90d");
+        assert!(scheme
+            .verify_with_context(&message, b"foo", &sig, &pk)
+            .is_err());
+        assert!(scheme.verify(&message, &sig, &pk).is_err());
+
+        assert!(scheme.sign_with_context(&message, b"", &sk).is_err());
+        assert!(scheme
+            .sign_with_context(&message, &[0u8; 256], &sk)
+            .is_err());
+        let sig = scheme.sign(&message, &sk).unwrap();
+        assert!(scheme
+            .verify_with_context(&message, b"", &sig, &pk)
+            .is_err());
+        assert!(scheme
+            .verify_with_context(&message, b"foo", &sig, &pk)
+            .is_err());
+    }
 }
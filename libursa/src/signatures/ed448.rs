@@ -0,0 +1,394 @@
+//! Ed448 signatures as specified in RFC 8032
+//! (see <https://tools.ietf.org/html/rfc8032#section-5.2>).
+//!
+//! Public keys and the two halves of a signature are 57 byte little-endian encodings.
+//! Private keys are the 57 byte seed followed by the public key, the same layout
+//! as `Ed25519Sha512` keys. Signatures use an empty context.
+
+use super::{KeyGenOption, SignatureScheme};
+use amcl::arch::Chunk;
+use amcl::goldilocks::big::BIG;
+use amcl::goldilocks::dbig::DBIG;
+use amcl::goldilocks::ecp::ECP;
+use amcl::goldilocks::fp::FP;
+use amcl::goldilocks::rom;
+use amcl::sha3::{SHA3, SHAKE256};
+use keys::{PrivateKey, PublicKey};
+use rand::rngs::OsRng;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use sha2::Digest;
+use zeroize::Zeroize;
+
+use CryptoError;
+
+pub const PRIVATE_KEY_SIZE: usize = 114;
+pub const PUBLIC_KEY_SIZE: usize = 57;
+pub const SIGNATURE_SIZE: usize = 114;
+pub const ALGORITHM_NAME: &str = "ED448_SHAKE256";
+
+/// The size of the secret seed at the start of a private key
+pub const SEED_SIZE: usize = 57;
+
+/// dom4(0, "") from RFC 8032
+const DOM4: &[u8] = b"SigEd448\x00\x00";
+const BASE_X: &str = "4f1970c66bed0ded221d15a622bf36da9e146570470f1767ea6de324a3d3a46412ae1af72ab66511433b80e18b00938e2626a82bc70cc05e";
+const BASE_Y: &str = "693f46716eb6bc248876203756c9c7624bea73736ca3984087789c1e05a0c2d73ad3ff1ce67c39c4fdbd132c4ed7c8ad9808795bf230fa14";
+
+pub struct Ed448;
+
+impl SignatureScheme for Ed448 {
+    fn new() -> Self {
+        Self
+    }
+    fn keypair(
+        &self,
+        option: Option<KeyGenOption>,
+    ) -> Result<(PublicKey, PrivateKey), CryptoError> {
+        let mut seed = [0u8; SEED_SIZE];
+        match option {
+            Some(mut o) => match o {
+                KeyGenOption::UseSeed(ref mut s) => {
+                    let hash = sha2::Sha256::digest(s.as_slice());
+                    s.zeroize();
+                    let mut rng = ChaChaRng::from_seed(*array_ref!(hash.as_slice(), 0, 32));
+                    rng.fill_bytes(&mut seed);
+                }
+                KeyGenOption::FromSecretKey(ref s) => {
+                    seed.copy_from_slice(&parse_private_key(&s[..])?[..]);
+                }
+            },
+            None => OsRng.fill_bytes(&mut seed),
+        };
+        let (_, _, pk) = expand_seed(&seed);
+        let mut sk = seed.to_vec();
+        sk.extend_from_slice(&pk);
+        seed.zeroize();
+        Ok((PublicKey(pk.to_vec()), PrivateKey(sk)))
+    }
+    fn sign(&self, message: &[u8], sk: &PrivateKey) -> Result<Vec<u8>, CryptoError> {
+        let seed = parse_private_key(&sk[..])?;
+        let (s, mut prefix, pk) = expand_seed(&seed);
+        let l = BIG::new_ints(&rom::CURVE_ORDER);
+        let r = scalar_from_hash(&shake256(&[DOM4, &prefix, message]));
+        prefix.zeroize();
+        let big_r = encode_point(&base_point().mul(&r));
+        let k = scalar_from_hash(&shake256(&[DOM4, &big_r, &pk, message]));
+        let mut big_s = BIG::modmul(&k, &s, &l);
+        big_s.add(&r);
+        big_s.norm();
+        big_s.rmod(&l);
+
+        let mut signature = big_r.to_vec();
+        signature.extend_from_slice(&big_to_le(&big_s));
+        Ok(signature)
+    }
+    fn verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        pk: &PublicKey,
+    ) -> Result<bool, CryptoError> {
+        if pk.len() != PUBLIC_KEY_SIZE {
+            return Err(CryptoError::ParseError(format!(
+                "Public key must be {} bytes in length",
+                PUBLIC_KEY_SIZE
+            )));
+        }
+        if signature.len() != SIGNATURE_SIZE {
+            return Err(CryptoError::ParseError(format!(
+                "Signature must be {} bytes in length",
+                SIGNATURE_SIZE
+            )));
+        }
+        let a = decode_point(&pk[..])
+            .ok_or_else(|| CryptoError::ParseError("Invalid public key".to_string()))?;
+        let r = match decode_point(&signature[..PUBLIC_KEY_SIZE]) {
+            Some(r) => r,
+            None => return Ok(false),
+        };
+        let l = BIG::new_ints(&rom::CURVE_ORDER);
+        let s = big_from_le(&signature[PUBLIC_KEY_SIZE..]);
+        if signature[SIGNATURE_SIZE - 1] != 0 || BIG::comp(&s, &l) >= 0 {
+            return Ok(false);
+        }
+        let k = scalar_from_hash(&shake256(&[
+            DOM4,
+            &signature[..PUBLIC_KEY_SIZE],
+            &pk[..],
+            message,
+        ]));
+        // [4][S]B = [4]R + [4][k]A
+        let mut lhs = base_point().mul(&s);
+        let mut rhs = a.mul(&k);
+        rhs.add(&r);
+        for _ in 0..2 {
+            lhs.dbl();
+            rhs.dbl();
+        }
+        Ok(lhs.equals(&rhs))
+    }
+    fn signature_size() -> usize {
+        SIGNATURE_SIZE
+    }
+    fn private_key_size() -> usize {
+        PRIVATE_KEY_SIZE
+    }
+    fn public_key_size() -> usize {
+        PUBLIC_KEY_SIZE
+    }
+}
+
+/// Returns the seed from either a bare 57 byte seed or a full private key.
+/// The public key half of a full private key must match the seed.
+fn parse_private_key(sk: &[u8]) -> Result<[u8; SEED_SIZE], CryptoError> {
+    if sk.len() != SEED_SIZE && sk.len() != PRIVATE_KEY_SIZE {
+        return Err(CryptoError::KeyGenError(format!(
+            "Keypair must be {} bytes in length",
+            PRIVATE_KEY_SIZE
+        )));
+    }
+    let mut seed = [0u8; SEED_SIZE];
+    seed.copy_from_slice(&sk[..SEED_SIZE]);
+    if sk.len() == PRIVATE_KEY_SIZE {
+        let (_, _, pk) = expand_seed(&seed);
+        if pk[..] != sk[SEED_SIZE..] {
+            return Err(CryptoError::KeyGenError(
+                "Public key does not match the secret seed".to_string(),
+            ));
+        }
+    }
+    Ok(seed)
+}
+
+/// Hashes the seed into the clamped secret scalar, the nonce prefix and the encoded public key.
+fn expand_seed(seed: &[u8; SEED_SIZE]) -> (BIG, [u8; 57], [u8; PUBLIC_KEY_SIZE]) {
+    let mut h = shake256(&[seed]);
+    h[0] &= 0xfc;
+    h[55] |= 0x80;
+    h[56] = 0;
+    let s = big_from_le(&h[..57]);
+    let mut prefix = [0u8; 57];
+    prefix.copy_from_slice(&h[57..]);
+    h.zeroize();
+    let pk = encode_point(&base_point().mul(&s));
+    (s, prefix, pk)
+}
+
+fn shake256(parts: &[&[u8]]) -> [u8; 114] {
+    let mut sha3 = SHA3::new(SHAKE256);
+    for part in parts {
+        for b in part.iter() {
+            sha3.process(*b);
+        }
+    }
+    let mut out = [0u8; 114];
+    sha3.shake(&mut out, 114);
+    out
+}
+
+fn base_point() -> ECP {
+    let x = BIG::frombytes(&hex::decode(BASE_X).unwrap());
+    let y = BIG::frombytes(&hex::decode(BASE_Y).unwrap());
+    ECP::new_bigs(&x, &y)
+}
+
+/// Reduces a 114 byte little-endian hash output modulo the group order
+fn scalar_from_hash(h: &[u8; 114]) -> BIG {
+    let mut d = DBIG::new();
+    for b in h.iter().rev() {
+        d.shl(8);
+        d.w[0] += *b as Chunk;
+    }
+    d.dmod(&BIG::new_ints(&rom::CURVE_ORDER))
+}
+
+fn big_from_le(bytes: &[u8]) -> BIG {
+    let mut b = BIG::new();
+    for byte in bytes.iter().rev() {
+        b.fshl(8);
+        b.w[0] += *byte as Chunk;
+    }
+    b
+}
+
+fn big_to_le(b: &BIG) -> [u8; 57] {
+    let mut out = [0u8; 57];
+    let mut c = BIG::new_copy(b);
+    c.tobytes(&mut out[..56]);
+    out[..56].reverse();
+    out
+}
+
+fn encode_point(p: &ECP) -> [u8; PUBLIC_KEY_SIZE] {
+    let mut out = big_to_le(&p.gety());
+    out[56] |= (p.getx().parity() as u8) << 7;
+    out
+}
+
+/// Decodes a point as described in RFC 8032 section 5.2.3
+fn decode_point(bytes: &[u8]) -> Option<ECP> {
+    if bytes[56] & 0x7f != 0 {
+        return None;
+    }
+    let x_0 = (bytes[56] >> 7) as isize;
+    let y = big_from_le(&bytes[..56]);
+    if BIG::comp(&y, &BIG::new_ints(&rom::MODULUS)) >= 0 {
+        return None;
+    }
+    let one = FP::new_int(1);
+    let mut y2 = FP::new_big(&y);
+    y2.sqr();
+    // x^2 = (y^2 - 1) / (d * y^2 - 1)
+    let mut u = FP::new_copy(&y2);
+    u.sub(&one);
+    let mut v = FP::new_copy(&y2);
+    v.imul(rom::CURVE_B_I);
+    v.sub(&one);
+    v.inverse();
+    u.mul(&v);
+    let mut x = u.sqrt();
+    let mut x2 = FP::new_copy(&x);
+    x2.sqr();
+    if !x2.equals(&u) {
+        return None;
+    }
+    x.reduce();
+    if x.iszilch() && x_0 == 1 {
+        return None;
+    }
+    if x.redc().parity() != x_0 {
+        x.neg();
+        x.reduce();
+    }
+    Some(ECP::new_bigs(&x.redc(), &y))
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::{SignatureScheme, Signer};
+    use super::*;
+    use keys::{KeyGenOption, PrivateKey, PublicKey};
+    use openssl::pkey::{Id, PKey};
+    use openssl::sign::{Signer as OpenSslSigner, Verifier as OpenSslVerifier};
+
+    // RFC 8032 section 7.4
+    const SEED_1: &str = "6c82a562cb808d10d632be89c8513ebf6c929f34ddfa8c9f63c9960ef6e348a3528c8a3fcc2f044e39a3fc5b94492f8f032e7549a20098f95b";
+    const PUBLIC_KEY_1: &str = "5fd7449b59b461fd2ce787ec616ad46a1da1342485a70e1f8a0ea75d80e96778edf124769b46c7061bd6783df1e50f6cd1fa1abeafe8256180";
+    const SIGNATURE_1: &str = "533a37f6bbe457251f023c0d88f976ae2dfb504a843e34d2074fd823d41a591f2b233f034f628281f2fd7a22ddd47d7828c59bd0a21bfd3980ff0d2028d4b18a9df63e006c5d1c2d345b925d8dc00b4104852db99ac5c7cdda8530a113a0f4dbb61149f05a7363268c71d95808ff2e652600";
+    const SEED_2: &str = "c4eab05d357007c632f3dbb48489924d552b08fe0c353a0d4a1f00acda2c463afbea67c5e8d2877c5e3bc397a659949ef8021e954e0a12274e";
+    const PUBLIC_KEY_2: &str = "43ba28f430cdff456ae531545f7ecd0ac834a55d9358c0372bfa0c6c6798c0866aea01eb00742802b8438ea4cb82169c235160627b4c3a9480";
+    const MESSAGE_2: &str = "03";
+    const SIGNATURE_2: &str = "26b8f91727bd62897af15e41eb43c377efb9c610d48f2335cb0bd0087810f4352541b143c4b981b7e18f62de8ccdf633fc1bf037ab7cd779805e0dbcc0aae1cbcee1afb2e027df36bc04dcecbf154336c19f0af7e0a6472905e799f1953d2a0ff3348ab21aa4adafd1d234441cf807c03a00";
+
+    const MESSAGE: &[u8] = b"This is a dummy message for use with tests";
+
+    #[test]
+    fn ed448_load_keys() {
+        let scheme = Ed448::new();
+        let seed = PrivateKey(hex::decode(SEED_1).unwrap());
+        let (p, s) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(seed)))
+            .unwrap();
+        assert_eq!(p, PublicKey(hex::decode(PUBLIC_KEY_1).unwrap()));
+        assert_eq!(s.len(), PRIVATE_KEY_SIZE);
+        assert_eq!(hex::encode(&s[..SEED_SIZE]), SEED_1);
+        assert_eq!(hex::encode(&s[SEED_SIZE..]), PUBLIC_KEY_1);
+
+        let (p1, s1) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(s.clone())))
+            .unwrap();
+        assert_eq!(p1, p);
+        assert_eq!(s1, s);
+
+        let (p2, s2) = scheme
+            .keypair(Some(KeyGenOption::UseSeed(vec![1u8; 32])))
+            .unwrap();
+        let (p3, s3) = scheme
+            .keypair(Some(KeyGenOption::UseSeed(vec![1u8; 32])))
+            .unwrap();
+        assert_eq!(p2, p3);
+        assert_eq!(s2, s3);
+        assert_eq!(p2.len(), PUBLIC_KEY_SIZE);
+    }
+
+    #[test]
+    fn ed448_invalid_keys() {
+        let scheme = Ed448::new();
+        assert!(scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(PrivateKey(vec![1u8; 32]))))
+            .is_err());
+        let mut sk = hex::decode(SEED_1).unwrap();
+        sk.extend_from_slice(&hex::decode(PUBLIC_KEY_2).unwrap());
+        assert!(scheme.sign(MESSAGE, &PrivateKey(sk)).is_err());
+
+        let sig = hex::decode(SIGNATURE_1).unwrap();
+        assert!(scheme.verify(b"", &sig, &PublicKey(vec![1u8; 32])).is_err());
+        // y >= p
+        assert!(scheme
+            .verify(b"", &sig, &PublicKey(vec![0xffu8; PUBLIC_KEY_SIZE]))
+            .is_err());
+    }
+
+    #[test]
+    fn ed448_verify() {
+        let scheme = Ed448::new();
+        let p1 = PublicKey(hex::decode(PUBLIC_KEY_1).unwrap());
+        let sig1 = hex::decode(SIGNATURE_1).unwrap();
+        assert!(scheme.verify(b"", &sig1, &p1).unwrap());
+        assert!(!scheme.verify(b"a", &sig1, &p1).unwrap());
+
+        let p2 = PublicKey(hex::decode(PUBLIC_KEY_2).unwrap());
+        let message2 = hex::decode(MESSAGE_2).unwrap();
+        let sig2 = hex::decode(SIGNATURE_2).unwrap();
+        assert!(scheme.verify(&message2, &sig2, &p2).unwrap());
+        assert!(!scheme.verify(&message2, &sig1, &p2).unwrap());
+
+        // S >= L
+        let mut sig = sig2.clone();
+        sig[PUBLIC_KEY_SIZE..].copy_from_slice(&[0xffu8; 57]);
+        sig[SIGNATURE_SIZE - 1] = 0;
+        assert!(!scheme.verify(&message2, &sig, &p2).unwrap());
+    }
+
+    #[test]
+    fn ed448_sign() {
+        let scheme = Ed448::new();
+        let seed = PrivateKey(hex::decode(SEED_1).unwrap());
+        let sig = scheme.sign(b"", &seed).unwrap();
+        assert_eq!(hex::encode(&sig), SIGNATURE_1);
+
+        let (_, s) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(PrivateKey(
+                hex::decode(SEED_2).unwrap(),
+            ))))
+            .unwrap();
+        let message2 = hex::decode(MESSAGE_2).unwrap();
+        let sig = scheme.sign(&message2, &s).unwrap();
+        assert_eq!(sig.len(), SIGNATURE_SIZE);
+        assert_eq!(hex::encode(&sig), SIGNATURE_2);
+
+        let (p, s) = scheme.keypair(None).unwrap();
+        let signer = Signer::new(&scheme, &s);
+        let sig = signer.sign(MESSAGE).unwrap();
+        assert!(scheme.verify(MESSAGE, &sig, &p).unwrap());
+        assert_eq!(signer.get_public_key().unwrap(), p);
+    }
+
+    #[test]
+    fn ed448_openssl_compatibility() {
+        let scheme = Ed448::new();
+        let (p, s) = scheme.keypair(None).unwrap();
+
+        let key = PKey::private_key_from_raw_bytes(&s[..SEED_SIZE], Id::ED448).unwrap();
+        assert_eq!(key.raw_public_key().unwrap(), p.0);
+
+        let mut signer = OpenSslSigner::new_without_digest(&key).unwrap();
+        let sig = signer.sign_oneshot_to_vec(MESSAGE).unwrap();
+        assert!(scheme.verify(MESSAGE, &sig, &p).unwrap());
+
+        let sig = scheme.sign(MESSAGE, &s).unwrap();
+        let mut verifier = OpenSslVerifier::new_without_digest(&key).unwrap();
+        assert!(verifier.verify_oneshot(&sig, MESSAGE).unwrap());
+    }
+}
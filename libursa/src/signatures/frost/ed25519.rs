@@ -0,0 +1,312 @@
+//! FROST(Ed25519, SHA-512) from RFC 9591 section 6.1.
+//! Signatures verify with `Ed25519Sha512::verify`.
+
+use curve25519_dalek::{
+    constants::ED25519_BASEPOINT_TABLE,
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+    traits::{Identity, IsIdentity},
+};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha512};
+use zeroize::Zeroize;
+
+use super::Ciphersuite;
+use keys::{PrivateKey, PublicKey};
+use {CryptoError, CryptoResult};
+
+const CONTEXT_STRING: &[u8] = b"FROST-ED25519-SHA512-v1";
+
+/// FROST for Ed25519 signatures
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrostEd25519Sha512;
+
+impl FrostEd25519Sha512 {
+    fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+        let mut hasher = Sha512::new();
+        for part in parts {
+            hasher.input(part);
+        }
+        let mut wide = [0u8; 64];
+        wide.copy_from_slice(hasher.result().as_slice());
+        Scalar::from_bytes_mod_order_wide(&wide)
+    }
+
+    fn hash(label: &[u8], m: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha512::new();
+        hasher.input(CONTEXT_STRING);
+        hasher.input(label);
+        hasher.input(m);
+        hasher.result().to_vec()
+    }
+}
+
+impl Ciphersuite for FrostEd25519Sha512 {
+    type Scalar = Scalar;
+    type Element = EdwardsPoint;
+
+    const SCALAR_SIZE: usize = 32;
+    const ELEMENT_SIZE: usize = 32;
+    const ORDER: &'static str = "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
+    const SCALAR_LITTLE_ENDIAN: bool = true;
+
+    fn scalar_from_u8(value: u8) -> Scalar {
+        Scalar::from(value)
+    }
+
+    fn scalar_add(a: &Scalar, b: &Scalar) -> Scalar {
+        a + b
+    }
+
+    fn scalar_mul(a: &Scalar, b: &Scalar) -> Scalar {
+        a * b
+    }
+
+    fn scalar_neg(a: &Scalar) -> Scalar {
+        -a
+    }
+
+    fn scalar_invert(a: &Scalar) -> Scalar {
+        a.invert()
+    }
+
+    fn random_scalar() -> Scalar {
+        Scalar::random(&mut OsRng)
+    }
+
+    fn zeroize_scalar(a: &mut Scalar) {
+        a.zeroize();
+    }
+
+    fn serialize_scalar(a: &Scalar) -> Vec<u8> {
+        a.to_bytes().to_vec()
+    }
+
+    fn deserialize_scalar(data: &[u8]) -> CryptoResult<Scalar> {
+        if data.len() != Self::SCALAR_SIZE {
+            return Err(CryptoError::ParseError("Invalid scalar length".to_string()));
+        }
+        Scalar::from_canonical_bytes(*array_ref!(data, 0, 32))
+            .ok_or_else(|| CryptoError::ParseError("Invalid scalar".to_string()))
+    }
+
+    fn identity() -> EdwardsPoint {
+        EdwardsPoint::identity()
+    }
+
+    fn is_identity(a: &EdwardsPoint) -> bool {
+        a.is_identity()
+    }
+
+    fn base_mul(a: &Scalar) -> EdwardsPoint {
+        &ED25519_BASEPOINT_TABLE * a
+    }
+
+    fn element_add(a: &EdwardsPoint, b: &EdwardsPoint) -> EdwardsPoint {
+        a + b
+    }
+
+    fn element_mul(a: &EdwardsPoint, b: &Scalar) -> EdwardsPoint {
+        a * b
+    }
+
+    fn element_neg(a: &EdwardsPoint) -> EdwardsPoint {
+        -a
+    }
+
+    fn serialize_element(a: &EdwardsPoint) -> Vec<u8> {
+        a.compress().to_bytes().to_vec()
+    }
+
+    fn deserialize_element(data: &[u8]) -> CryptoResult<EdwardsPoint> {
+        if data.len() != Self::ELEMENT_SIZE {
+            return Err(CryptoError::ParseError(
+                "Invalid element length".to_string(),
+            ));
+        }
+        match CompressedEdwardsY::from_slice(data).decompress() {
+            Some(p) if !p.is_identity() && p.is_torsion_free() => Ok(p),
+            _ => Err(CryptoError::ParseError("Invalid element".to_string())),
+        }
+    }
+
+    fn secret_scalar(sk: &PrivateKey) -> CryptoResult<Scalar> {
+        // The private key is seed || public key
+        if sk.len() != 64 {
+            return Err(CryptoError::ParseError(
+                "Invalid private key provided".to_string(),
+            ));
+        }
+        let hash = Sha512::digest(&sk[..32]);
+        let mut bytes = *array_ref!(hash.as_slice(), 0, 32);
+        bytes[0] &= 248;
+        bytes[31] &= 127;
+        bytes[31] |= 64;
+        Ok(Scalar::from_bytes_mod_order(bytes))
+    }
+
+    fn public_key(a: &EdwardsPoint) -> PublicKey {
+        PublicKey(Self::serialize_element(a))
+    }
+
+    fn h1(m: &[u8]) -> Scalar {
+        Self::hash_to_scalar(&[CONTEXT_STRING, b"rho", m])
+    }
+
+    fn challenge(r: &EdwardsPoint, public_key: &EdwardsPoint, message: &[u8]) -> Scalar {
+        Self::hash_to_scalar(&[
+            r.compress().as_bytes(),
+            public_key.compress().as_bytes(),
+            message,
+        ])
+    }
+
+    fn h3(m: &[u8]) -> Scalar {
+        Self::hash_to_scalar(&[CONTEXT_STRING, b"nonce", m])
+    }
+
+    fn h4(m: &[u8]) -> Vec<u8> {
+        Self::hash(b"msg", m)
+    }
+
+    fn h5(m: &[u8]) -> Vec<u8> {
+        Self::hash(b"com", m)
+    }
+
+    fn serialize_signature(r: &EdwardsPoint, z: &Scalar) -> Vec<u8> {
+        let mut output = Self::serialize_element(r);
+        output.extend_from_slice(z.as_bytes());
+        output
+    }
+}
+
+#[cfg(all(test, any(feature = "ed25519", feature = "ed25519_asm")))]
+mod tests {
+    use super::super::{aggregate, commit_with_randomness, sign, KeyShare, PublicKeyPackage};
+    use super::*;
+    use keys::KeyGenOption;
+    use signatures::{ed25519::Ed25519Sha512, SignatureScheme};
+
+    fn private_key() -> PrivateKey {
+        Ed25519Sha512::new().keypair(None).unwrap().1
+    }
+
+    fn public_key(sk: &PrivateKey) -> PublicKey {
+        let option = KeyGenOption::FromSecretKey(sk.clone());
+        Ed25519Sha512::new().keypair(Some(option)).unwrap().0
+    }
+
+    fn verify(message: &[u8], signature: &[u8], pk: &PublicKey) -> bool {
+        Ed25519Sha512::new()
+            .verify(message, signature, pk)
+            .unwrap_or(false)
+    }
+
+    #[test]
+    fn rfc9591_test_vectors() {
+        // Appendix E.1
+        let decode = |s: &str| hex::decode(s).unwrap();
+        let scalar = |s: &str| FrostEd25519Sha512::deserialize_scalar(&decode(s)).unwrap();
+        let group_secret_key =
+            scalar("7b1c33d3f5291d85de664833beb1ad469f7fb6025a0ec78b3a790c6e13a98304");
+        let group_public_key =
+            decode("15d21ccd7ee42959562fc8aa63224c8851fb3ec85a3faf66040d380fb9738673");
+        let coefficient =
+            scalar("178199860edd8c62f5212ee91eff1295d0d670ab4ed4506866bae57e7030b204");
+        assert_eq!(
+            FrostEd25519Sha512::serialize_element(&FrostEd25519Sha512::base_mul(&group_secret_key)),
+            group_public_key
+        );
+        let participant_shares = [
+            "929dcc590407aae7d388761cddb0c0db6f5627aea8e217f4a033f2ec83d93509",
+            "a91e66e012e4364ac9aaa405fcafd370402d9859f7b6685c07eed76bf409e80d",
+            "d3cb090a075eb154e82fdb4b3cb507f110040905468bb9c46da8bdea643a9a02",
+        ];
+        for (i, share) in participant_shares.iter().enumerate() {
+            let x = Scalar::from(i as u8 + 1);
+            assert_eq!(group_secret_key + coefficient * x, scalar(share));
+        }
+
+        let message = decode("74657374");
+        let signers = [
+            (
+                1,
+                participant_shares[0],
+                "0fd2e39e111cdc266f6c0f4d0fd45c947761f1f5d3cb583dfcb9bbaf8d4c9fec",
+                "69cd85f631d5f7f2721ed5e40519b1366f340a87c2f6856363dbdcda348a7501",
+                "812d6104142944d5a55924de6d49940956206909f2acaeedecda2b726e630407",
+                "b1110165fc2334149750b28dd813a39244f315cff14d4e89e6142f262ed83301",
+                "b5aa8ab305882a6fc69cbee9327e5a45e54c08af61ae77cb8207be3d2ce13de3",
+                "67e98ab55aa310c3120418e5050c9cf76cf387cb20ac9e4b6fdb6f82a469f932",
+                "001719ab5a53ee1a12095cd088fd149702c0720ce5fd2f29dbecf24b7281b603",
+            ),
+            (
+                3,
+                participant_shares[2],
+                "86d64a260059e495d0fb4fcc17ea3da7452391baa494d4b00321098ed2a0062f",
+                "13e6b25afb2eba51716a9a7d44130c0dbae0004a9ef8d7b5550c8a0e07c61775",
+                "c256de65476204095ebdc01bd11dc10e57b36bc96284595b8215222374f99c0e",
+                "243d71944d929063bc51205714ae3c2218bd3451d0214dfb5aeec2a90c35180d",
+                "cfbdb165bd8aad6eb79deb8d287bcc0ab6658ae57fdcc98ed12c0669e90aec91",
+                "7487bc41a6e712eea2f2af24681b58b1cf1da278ea11fe4e8b78398965f13552",
+                "bd86125de990acc5e1f13781d8e32c03a9bbd4c53539bbc106058bfd14326007",
+            ),
+        ];
+        let mut key_shares = Vec::new();
+        let mut nonces = Vec::new();
+        let mut commitments = Vec::new();
+        let mut verifying_shares = std::collections::BTreeMap::new();
+        for (
+            id,
+            share,
+            hiding_randomness,
+            binding_randomness,
+            hiding,
+            binding,
+            hiding_commitment,
+            binding_commitment,
+            _,
+        ) in signers.iter()
+        {
+            let key_share =
+                KeyShare::<FrostEd25519Sha512>::new(*id, &decode(share), &group_public_key)
+                    .unwrap();
+            let (n, c) = commit_with_randomness(
+                &key_share,
+                &decode(hiding_randomness),
+                &decode(binding_randomness),
+            );
+            assert_eq!(n.hiding, scalar(hiding));
+            assert_eq!(n.binding, scalar(binding));
+            assert_eq!(
+                c.to_bytes(),
+                [
+                    &[*id][..],
+                    &decode(hiding_commitment),
+                    &decode(binding_commitment)
+                ]
+                .concat()
+            );
+            verifying_shares.insert(*id, key_share.verifying_share());
+            key_shares.push(key_share);
+            nonces.push(n);
+            commitments.push(c);
+        }
+        let package = PublicKeyPackage::new(&verifying_shares, &group_public_key, 2).unwrap();
+        let shares: Vec<_> = key_shares
+            .iter()
+            .zip(nonces.into_iter())
+            .zip(signers.iter())
+            .map(|((key_share, n), signer)| {
+                let share = sign(&message, key_share, n, &commitments).unwrap();
+                assert_eq!(share.share, scalar(signer.8));
+                share
+            })
+            .collect();
+        let signature = aggregate(&message, &commitments, &shares, &package).unwrap();
+        assert_eq!(signature, decode("36282629c383bb820a88b71cae937d41f2f2adfcc3d02e55507e2fb9e2dd3cbebd9d2b0844e49ae0f3fa935161e1419aab7b47d21a37ebeae1f17d4987b3160b"));
+        assert!(verify(&message, &signature, &PublicKey(group_public_key)));
+    }
+
+    frost_tests_impl!(FrostEd25519Sha512, verify);
+}
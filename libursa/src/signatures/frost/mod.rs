@@ -0,0 +1,880 @@
+//! Implements FROST threshold Schnorr signatures
+//! (see <https://www.rfc-editor.org/rfc/rfc9591.html>).
+//!
+//! A `threshold` of the holders of `KeyShare`s sign together in two rounds
+//! and an aggregator combines their signature shares into a single signature
+//! that verifies under the group public key like one from a single signer.
+//!
+//! 1. Each signer calls `commit` and sends its `SigningCommitments` to the aggregator,
+//!    keeping its `SigningNonces` secret.
+//! 2. The aggregator sends the message and all commitments to the signers. Each signer
+//!    calls `sign` with them, consuming its nonces, and returns its `SignatureShare`.
+//! 3. The aggregator verifies the shares and combines them with `aggregate`.
+//!
+//! Nonces must never be used twice, otherwise the key share leaks.
+//!
+//! Key shares come from a trusted dealer with `split_private_key`, or from any Shamir
+//! sharing of a secret over the scalar field of the ciphersuite, using `KeyShare::new`
+//! and `PublicKeyPackage::new`. `KeyShare::from_shamir_share` imports the shares of
+//! `sharing::shamir::split_secret` over `Ciphersuite::ORDER`. The `sharing::dkg` shares
+//! are over the BLS12-381 scalar field so can't be used.
+//!
+//! `ed25519::FrostEd25519Sha512` outputs Ed25519 signatures,
+//! `secp256k1::FrostSecp256k1Bip340` outputs BIP-340 Schnorr signatures and
+//! `secp256k1::FrostSecp256k1Sha256` is the secp256k1 ciphersuite of the RFC.
+
+use rand::{rngs::OsRng, RngCore};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use zeroize::Zeroize;
+
+use keys::{PrivateKey, PublicKey};
+#[cfg(any(feature = "sharing", feature = "sharing_native"))]
+use sharing::shamir;
+use {CryptoError, CryptoResult};
+
+/// The prime order group and hash functions for FROST
+pub trait Ciphersuite: Clone + PartialEq + std::fmt::Debug {
+    /// Scalar field element
+    type Scalar: Clone + PartialEq + std::fmt::Debug;
+    /// Group element
+    type Element: Clone + PartialEq + std::fmt::Debug;
+
+    /// The size in bytes of a serialized scalar
+    const SCALAR_SIZE: usize;
+    /// The size in bytes of a serialized group element
+    const ELEMENT_SIZE: usize;
+    /// The order of the scalar field in big-endian hex
+    const ORDER: &'static str;
+    /// Whether scalars are serialized in little-endian order
+    const SCALAR_LITTLE_ENDIAN: bool;
+
+    fn scalar_from_u8(value: u8) -> Self::Scalar;
+    fn scalar_add(a: &Self::Scalar, b: &Self::Scalar) -> Self::Scalar;
+    fn scalar_mul(a: &Self::Scalar, b: &Self::Scalar) -> Self::Scalar;
+    fn scalar_neg(a: &Self::Scalar) -> Self::Scalar;
+    fn scalar_invert(a: &Self::Scalar) -> Self::Scalar;
+    fn random_scalar() -> Self::Scalar;
+    fn zeroize_scalar(a: &mut Self::Scalar);
+    fn serialize_scalar(a: &Self::Scalar) -> Vec<u8>;
+    /// Fails if `data` is not a canonical encoding
+    fn deserialize_scalar(data: &[u8]) -> CryptoResult<Self::Scalar>;
+
+    fn identity() -> Self::Element;
+    fn is_identity(a: &Self::Element) -> bool;
+    fn base_mul(a: &Self::Scalar) -> Self::Element;
+    fn element_add(a: &Self::Element, b: &Self::Element) -> Self::Element;
+    fn element_mul(a: &Self::Element, b: &Self::Scalar) -> Self::Element;
+    fn element_neg(a: &Self::Element) -> Self::Element;
+    fn serialize_element(a: &Self::Element) -> Vec<u8>;
+    /// Fails for invalid encodings and the identity
+    fn deserialize_element(data: &[u8]) -> CryptoResult<Self::Element>;
+
+    /// The secret scalar of a private key of the signature scheme
+    fn secret_scalar(sk: &PrivateKey) -> CryptoResult<Self::Scalar>;
+    /// The public key of the signature scheme for `a`
+    fn public_key(a: &Self::Element) -> PublicKey;
+    /// Whether the signature scheme requires `a` to be negated, e.g. BIP-340 requires even y
+    fn requires_negation(_a: &Self::Element) -> bool {
+        false
+    }
+
+    /// Hash to a scalar for binding factors
+    fn h1(m: &[u8]) -> Self::Scalar;
+    /// The challenge of the signature scheme
+    fn challenge(r: &Self::Element, public_key: &Self::Element, message: &[u8]) -> Self::Scalar;
+    /// Hash to a scalar for nonces
+    fn h3(m: &[u8]) -> Self::Scalar;
+    /// Hash of the message
+    fn h4(m: &[u8]) -> Vec<u8>;
+    /// Hash of the commitments
+    fn h5(m: &[u8]) -> Vec<u8>;
+
+    /// The signature of the signature scheme
+    fn serialize_signature(r: &Self::Element, z: &Self::Scalar) -> Vec<u8>;
+}
+
+/// A share of a private key held by one signer
+#[derive(Clone, PartialEq)]
+pub struct KeyShare<C: Ciphersuite> {
+    identifier: u8,
+    secret: C::Scalar,
+    group_public_key: C::Element,
+}
+
+impl<C: Ciphersuite> KeyShare<C> {
+    /// Create a key share from the `identifier` and `secret` share of a Shamir sharing over
+    /// the scalar field of `C` where the shared secret has `group_public_key`.
+    /// Both are serialized as scalars and elements of `C`.
+    pub fn new(identifier: u8, secret: &[u8], group_public_key: &[u8]) -> CryptoResult<Self> {
+        if identifier == 0 {
+            return Err(CryptoError::GeneralError(
+                "Share must have a non-zero identifier".to_string(),
+            ));
+        }
+        let secret = C::deserialize_scalar(secret)?;
+        let group_public_key = C::deserialize_element(group_public_key)?;
+        // Negate the shared secret so the group public key is usable by the signature scheme
+        if C::requires_negation(&group_public_key) {
+            Ok(Self {
+                identifier,
+                secret: C::scalar_neg(&secret),
+                group_public_key: C::element_neg(&group_public_key),
+            })
+        } else {
+            Ok(Self {
+                identifier,
+                secret,
+                group_public_key,
+            })
+        }
+    }
+
+    /// Create a key share from a `share` of `sharing::shamir::split_secret` over
+    /// `C::ORDER` where the shared secret has `group_public_key`
+    #[cfg(any(feature = "sharing", feature = "sharing_native"))]
+    pub fn from_shamir_share(share: &shamir::Share, group_public_key: &[u8]) -> CryptoResult<Self> {
+        let mut value = share.value.to_bytes()?;
+        if value.len() > C::SCALAR_SIZE {
+            value.zeroize();
+            return Err(CryptoError::ParseError("Invalid share".to_string()));
+        }
+        let mut secret = vec![0u8; C::SCALAR_SIZE - value.len()];
+        secret.extend_from_slice(&value);
+        value.zeroize();
+        if C::SCALAR_LITTLE_ENDIAN {
+            secret.reverse();
+        }
+        let result = Self::new(share.identifier, &secret, group_public_key);
+        secret.zeroize();
+        result
+    }
+
+    /// The x-coordinate of the share
+    pub fn identifier(&self) -> u8 {
+        self.identifier
+    }
+
+    /// The public key of the share used to verify its signature shares
+    pub fn verifying_share(&self) -> Vec<u8> {
+        C::serialize_element(&C::base_mul(&self.secret))
+    }
+
+    /// The public key of the signature scheme that verifies the aggregated signatures
+    pub fn group_public_key(&self) -> PublicKey {
+        C::public_key(&self.group_public_key)
+    }
+
+    /// Return byte representation of this share: identifier || secret || group public key
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = vec![self.identifier];
+        output.extend_from_slice(&C::serialize_scalar(&self.secret));
+        output.extend_from_slice(&C::serialize_element(&self.group_public_key));
+        output
+    }
+
+    /// Convert a share from bytes
+    pub fn from_bytes<B: AsRef<[u8]>>(data: B) -> CryptoResult<Self> {
+        let data = data.as_ref();
+        if data.len() != 1 + C::SCALAR_SIZE + C::ELEMENT_SIZE {
+            return Err(CryptoError::ParseError(
+                "Invalid key share length".to_string(),
+            ));
+        }
+        Self::new(
+            data[0],
+            &data[1..=C::SCALAR_SIZE],
+            &data[(1 + C::SCALAR_SIZE)..],
+        )
+    }
+}
+
+impl<C: Ciphersuite> fmt::Debug for KeyShare<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeyShare")
+            .field("identifier", &self.identifier)
+            .field("secret", &"...")
+            .field("group_public_key", &self.group_public_key)
+            .finish()
+    }
+}
+
+impl<C: Ciphersuite> Zeroize for KeyShare<C> {
+    fn zeroize(&mut self) {
+        C::zeroize_scalar(&mut self.secret);
+    }
+}
+
+impl<C: Ciphersuite> Drop for KeyShare<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// The public keys used to verify signature shares
+#[derive(Debug, Clone, PartialEq)]
+pub struct PublicKeyPackage<C: Ciphersuite> {
+    threshold: u8,
+    group_public_key: C::Element,
+    verifying_shares: BTreeMap<u8, C::Element>,
+}
+
+impl<C: Ciphersuite> PublicKeyPackage<C> {
+    /// Collect the `verifying_shares` of each signer and the `group_public_key` of the
+    /// same sharing, either the public shares and public key output by a distributed key
+    /// generation or `KeyShare::verifying_share` and the group public key of the key shares.
+    /// All are serialized as elements of `C`. The `threshold` of the sharing is the number
+    /// of signers required to sign.
+    pub fn new(
+        verifying_shares: &BTreeMap<u8, Vec<u8>>,
+        group_public_key: &[u8],
+        threshold: u8,
+    ) -> CryptoResult<Self> {
+        if threshold < 2 || threshold as usize > verifying_shares.len() {
+            return Err(CryptoError::GeneralError(
+                "threshold must be at least 2 and at most the number of shares".to_string(),
+            ));
+        }
+        let mut group_public_key = C::deserialize_element(group_public_key)?;
+        // Negate the shares with the group public key like `KeyShare::new`
+        let negate = C::requires_negation(&group_public_key);
+        if negate {
+            group_public_key = C::element_neg(&group_public_key);
+        }
+        let mut shares = BTreeMap::new();
+        for (identifier, share) in verifying_shares {
+            let mut share = C::deserialize_element(share)?;
+            if negate {
+                share = C::element_neg(&share);
+            }
+            shares.insert(*identifier, share);
+        }
+        Ok(Self {
+            threshold,
+            group_public_key,
+            verifying_shares: shares,
+        })
+    }
+
+    /// The number of signers required to sign
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// The public key of the signature scheme that verifies the aggregated signatures
+    pub fn group_public_key(&self) -> PublicKey {
+        C::public_key(&self.group_public_key)
+    }
+}
+
+/// The secret nonces of a signer for one signature
+pub struct SigningNonces<C: Ciphersuite> {
+    hiding: C::Scalar,
+    binding: C::Scalar,
+    commitments: SigningCommitments<C>,
+}
+
+impl<C: Ciphersuite> fmt::Debug for SigningNonces<C> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SigningNonces")
+            .field("hiding", &"...")
+            .field("binding", &"...")
+            .field("commitments", &self.commitments)
+            .finish()
+    }
+}
+
+impl<C: Ciphersuite> Zeroize for SigningNonces<C> {
+    fn zeroize(&mut self) {
+        C::zeroize_scalar(&mut self.hiding);
+        C::zeroize_scalar(&mut self.binding);
+    }
+}
+
+impl<C: Ciphersuite> Drop for SigningNonces<C> {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+/// The public commitments to the `SigningNonces` of a signer
+#[derive(Debug, Clone, PartialEq)]
+pub struct SigningCommitments<C: Ciphersuite> {
+    identifier: u8,
+    hiding: C::Element,
+    binding: C::Element,
+}
+
+impl<C: Ciphersuite> SigningCommitments<C> {
+    /// The identifier of the signer
+    pub fn identifier(&self) -> u8 {
+        self.identifier
+    }
+
+    /// Return byte representation: identifier || hiding || binding
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = vec![self.identifier];
+        output.extend_from_slice(&C::serialize_element(&self.hiding));
+        output.extend_from_slice(&C::serialize_element(&self.binding));
+        output
+    }
+
+    /// Convert commitments from bytes
+    pub fn from_bytes<B: AsRef<[u8]>>(data: B) -> CryptoResult<Self> {
+        let data = data.as_ref();
+        if data.len() != 1 + 2 * C::ELEMENT_SIZE || data[0] == 0 {
+            return Err(CryptoError::ParseError(
+                "Invalid signing commitments".to_string(),
+            ));
+        }
+        Ok(Self {
+            identifier: data[0],
+            hiding: C::deserialize_element(&data[1..=C::ELEMENT_SIZE])?,
+            binding: C::deserialize_element(&data[(1 + C::ELEMENT_SIZE)..])?,
+        })
+    }
+}
+
+/// The share of a signature from one signer
+#[derive(Debug, Clone, PartialEq)]
+pub struct SignatureShare<C: Ciphersuite> {
+    identifier: u8,
+    share: C::Scalar,
+}
+
+impl<C: Ciphersuite> SignatureShare<C> {
+    /// The identifier of the signer
+    pub fn identifier(&self) -> u8 {
+        self.identifier
+    }
+
+    /// Return byte representation: identifier || share
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut output = vec![self.identifier];
+        output.extend_from_slice(&C::serialize_scalar(&self.share));
+        output
+    }
+
+    /// Convert a signature share from bytes
+    pub fn from_bytes<B: AsRef<[u8]>>(data: B) -> CryptoResult<Self> {
+        let data = data.as_ref();
+        if data.len() != 1 + C::SCALAR_SIZE || data[0] == 0 {
+            return Err(CryptoError::ParseError(
+                "Invalid signature share".to_string(),
+            ));
+        }
+        Ok(Self {
+            identifier: data[0],
+            share: C::deserialize_scalar(&data[1..])?,
+        })
+    }
+}
+
+/// Split the private key `sk` of the signature scheme into `total` shares,
+/// which `threshold` are required to sign. The `threshold` and `total` must be at least 2.
+pub fn split_private_key<C: Ciphersuite>(
+    sk: &PrivateKey,
+    threshold: u8,
+    total: u8,
+) -> CryptoResult<(PublicKeyPackage<C>, Vec<KeyShare<C>>)> {
+    if total < threshold {
+        return Err(CryptoError::GeneralError(
+            "total cannot be less than the threshold".to_string(),
+        ));
+    }
+    if threshold < 2 {
+        return Err(CryptoError::GeneralError(
+            "threshold must be at least 2".to_string(),
+        ));
+    }
+    let mut secret = C::secret_scalar(sk)?;
+    let mut group_public_key = C::base_mul(&secret);
+    if C::requires_negation(&group_public_key) {
+        secret = C::scalar_neg(&secret);
+        group_public_key = C::element_neg(&group_public_key);
+    }
+    let mut coefficients: Vec<C::Scalar> = (1..threshold).map(|_| C::random_scalar()).collect();
+    let mut verifying_shares = BTreeMap::new();
+    let shares = (1..=total)
+        .map(|identifier| {
+            // Horner's Method
+            let x = C::scalar_from_u8(identifier);
+            let value = coefficients
+                .iter()
+                .rev()
+                .fold(C::scalar_from_u8(0), |out, a| {
+                    C::scalar_add(&C::scalar_mul(&out, &x), a)
+                });
+            let value = C::scalar_add(&C::scalar_mul(&value, &x), &secret);
+            verifying_shares.insert(identifier, C::base_mul(&value));
+            KeyShare {
+                identifier,
+                secret: value,
+                group_public_key: group_public_key.clone(),
+            }
+        })
+        .collect();
+    C::zeroize_scalar(&mut secret);
+    coefficients.iter_mut().for_each(C::zeroize_scalar);
+    Ok((
+        PublicKeyPackage {
+            threshold,
+            group_public_key,
+            verifying_shares,
+        },
+        shares,
+    ))
+}
+
+/// Round one: generate the nonces for a signature with `key_share`
+pub fn commit<C: Ciphersuite>(
+    key_share: &KeyShare<C>,
+) -> (SigningNonces<C>, SigningCommitments<C>) {
+    let mut hiding = [0u8; 32];
+    let mut binding = [0u8; 32];
+    OsRng.fill_bytes(&mut hiding);
+    OsRng.fill_bytes(&mut binding);
+    let result = commit_with_randomness(key_share, &hiding, &binding);
+    hiding.zeroize();
+    binding.zeroize();
+    result
+}
+
+fn commit_with_randomness<C: Ciphersuite>(
+    key_share: &KeyShare<C>,
+    hiding_randomness: &[u8],
+    binding_randomness: &[u8],
+) -> (SigningNonces<C>, SigningCommitments<C>) {
+    let hiding = nonce_generate::<C>(hiding_randomness, &key_share.secret);
+    let binding = nonce_generate::<C>(binding_randomness, &key_share.secret);
+    let commitments = SigningCommitments {
+        identifier: key_share.identifier,
+        hiding: C::base_mul(&hiding),
+        binding: C::base_mul(&binding),
+    };
+    (
+        SigningNonces {
+            hiding,
+            binding,
+            commitments: commitments.clone(),
+        },
+        commitments,
+    )
+}
+
+/// Round two: sign `message` with `key_share` and the `nonces` from `commit` given the
+/// `commitments` of all signers
+pub fn sign<C: Ciphersuite>(
+    message: &[u8],
+    key_share: &KeyShare<C>,
+    nonces: SigningNonces<C>,
+    commitments: &[SigningCommitments<C>],
+) -> CryptoResult<SignatureShare<C>> {
+    let commitments = commitment_list(commitments)?;
+    if commitments.get(&key_share.identifier) != Some(&nonces.commitments) {
+        return Err(CryptoError::GeneralError(
+            "The commitments of the signer do not match its nonces".to_string(),
+        ));
+    }
+    let binding_factors =
+        compute_binding_factors::<C>(&key_share.group_public_key, &commitments, message);
+    let (group_commitment, negate) = compute_group_commitment::<C>(&commitments, &binding_factors);
+    let lambda = interpolating_value::<C>(&commitments, key_share.identifier);
+    let challenge = C::challenge(&group_commitment, &key_share.group_public_key, message);
+
+    let mut nonce = C::scalar_add(
+        &nonces.hiding,
+        &C::scalar_mul(&nonces.binding, &binding_factors[&key_share.identifier]),
+    );
+    if negate {
+        nonce = C::scalar_neg(&nonce);
+    }
+    let share = C::scalar_add(
+        &nonce,
+        &C::scalar_mul(&C::scalar_mul(&lambda, &key_share.secret), &challenge),
+    );
+    C::zeroize_scalar(&mut nonce);
+    Ok(SignatureShare {
+        identifier: key_share.identifier,
+        share,
+    })
+}
+
+/// Verify the signature `shares` on `message` from the signers of `commitments`
+/// and combine them into a signature that verifies under the group public key.
+pub fn aggregate<C: Ciphersuite>(
+    message: &[u8],
+    commitments: &[SigningCommitments<C>],
+    shares: &[SignatureShare<C>],
+    public_key_package: &PublicKeyPackage<C>,
+) -> CryptoResult<Vec<u8>> {
+    let commitments = commitment_list(commitments)?;
+    let signers: BTreeSet<u8> = shares.iter().map(|s| s.identifier).collect();
+    if signers.len() != shares.len() || !signers.iter().eq(commitments.keys()) {
+        return Err(CryptoError::GeneralError(
+            "Each signer requires one commitment and one signature share".to_string(),
+        ));
+    }
+    if shares.len() < public_key_package.threshold as usize {
+        return Err(CryptoError::GeneralError(
+            "Less than threshold signature shares cannot be aggregated".to_string(),
+        ));
+    }
+    let group_public_key = &public_key_package.group_public_key;
+    let binding_factors = compute_binding_factors::<C>(group_public_key, &commitments, message);
+    let (group_commitment, negate) = compute_group_commitment::<C>(&commitments, &binding_factors);
+    let challenge = C::challenge(&group_commitment, group_public_key, message);
+
+    let mut z = C::scalar_from_u8(0);
+    for share in shares {
+        let identifier = share.identifier;
+        let verifying_share = public_key_package
+            .verifying_shares
+            .get(&identifier)
+            .ok_or_else(|| CryptoError::GeneralError(format!("Unknown signer {}", identifier)))?;
+        let signer = &commitments[&identifier];
+        let mut nonce_commitment = C::element_add(
+            &signer.hiding,
+            &C::element_mul(&signer.binding, &binding_factors[&identifier]),
+        );
+        if negate {
+            nonce_commitment = C::element_neg(&nonce_commitment);
+        }
+        let lambda = interpolating_value::<C>(&commitments, identifier);
+        let expected = C::element_add(
+            &nonce_commitment,
+            &C::element_mul(verifying_share, &C::scalar_mul(&challenge, &lambda)),
+        );
+        if C::base_mul(&share.share) != expected {
+            return Err(CryptoError::GeneralError(format!(
+                "Invalid signature share from signer {}",
+                identifier
+            )));
+        }
+        z = C::scalar_add(&z, &share.share);
+    }
+    Ok(C::serialize_signature(&group_commitment, &z))
+}
+
+fn nonce_generate<C: Ciphersuite>(randomness: &[u8], secret: &C::Scalar) -> C::Scalar {
+    let mut input = randomness.to_vec();
+    input.extend_from_slice(&C::serialize_scalar(secret));
+    let nonce = C::h3(&input);
+    input.zeroize();
+    nonce
+}
+
+/// Sort the commitments by identifier
+fn commitment_list<C: Ciphersuite>(
+    commitments: &[SigningCommitments<C>],
+) -> CryptoResult<BTreeMap<u8, SigningCommitments<C>>> {
+    let mut list = BTreeMap::new();
+    for c in commitments {
+        if c.identifier == 0 || C::is_identity(&c.hiding) || C::is_identity(&c.binding) {
+            return Err(CryptoError::GeneralError(
+                "Invalid signing commitments".to_string(),
+            ));
+        }
+        if list.insert(c.identifier, c.clone()).is_some() {
+            return Err(CryptoError::GeneralError(
+                "Duplicate signing commitments".to_string(),
+            ));
+        }
+    }
+    if list.len() < 2 {
+        return Err(CryptoError::GeneralError(
+            "At least two signers are required".to_string(),
+        ));
+    }
+    Ok(list)
+}
+
+fn compute_binding_factors<C: Ciphersuite>(
+    group_public_key: &C::Element,
+    commitments: &BTreeMap<u8, SigningCommitments<C>>,
+    message: &[u8],
+) -> BTreeMap<u8, C::Scalar> {
+    let encoded: Vec<u8> = commitments
+        .values()
+        .flat_map(|c| {
+            let mut out = C::serialize_scalar(&C::scalar_from_u8(c.identifier));
+            out.extend_from_slice(&C::serialize_element(&c.hiding));
+            out.extend_from_slice(&C::serialize_element(&c.binding));
+            out
+        })
+        .collect();
+    let mut prefix = C::serialize_element(group_public_key);
+    prefix.extend_from_slice(&C::h4(message));
+    prefix.extend_from_slice(&C::h5(&encoded));
+    commitments
+        .keys()
+        .map(|identifier| {
+            let mut input = prefix.clone();
+            input.extend_from_slice(&C::serialize_scalar(&C::scalar_from_u8(*identifier)));
+            (*identifier, C::h1(&input))
+        })
+        .collect()
+}
+
+/// The group commitment and whether the nonces must be negated to match it
+fn compute_group_commitment<C: Ciphersuite>(
+    commitments: &BTreeMap<u8, SigningCommitments<C>>,
+    binding_factors: &BTreeMap<u8, C::Scalar>,
+) -> (C::Element, bool) {
+    let r = commitments.values().fold(C::identity(), |r, c| {
+        let binding = C::element_mul(&c.binding, &binding_factors[&c.identifier]);
+        C::element_add(&C::element_add(&r, &c.hiding), &binding)
+    });
+    if C::requires_negation(&r) {
+        (C::element_neg(&r), true)
+    } else {
+        (r, false)
+    }
+}
+
+/// Lagrange coefficient at 0 for `identifier` among the signers
+fn interpolating_value<C: Ciphersuite>(
+    commitments: &BTreeMap<u8, SigningCommitments<C>>,
+    identifier: u8,
+) -> C::Scalar {
+    let x_i = C::scalar_from_u8(identifier);
+    let (num, denom) = commitments.keys().filter(|x_j| **x_j != identifier).fold(
+        (C::scalar_from_u8(1), C::scalar_from_u8(1)),
+        |(num, denom), x_j| {
+            let x_j = C::scalar_from_u8(*x_j);
+            let diff = C::scalar_add(&x_j, &C::scalar_neg(&x_i));
+            (C::scalar_mul(&num, &x_j), C::scalar_mul(&denom, &diff))
+        },
+    );
+    C::scalar_mul(&num, &C::scalar_invert(&denom))
+}
+
+#[cfg(test)]
+macro_rules! frost_tests_impl {
+    ($suite:ident, $verify:expr) => {
+        use super::super::*;
+        use keys::PrivateKey;
+        use zeroize::Zeroize;
+
+        fn run(
+            message: &[u8],
+            package: &PublicKeyPackage<$suite>,
+            signers: &[KeyShare<$suite>],
+        ) -> Vec<u8> {
+            let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(commit).unzip();
+            let shares: Vec<_> = signers
+                .iter()
+                .zip(nonces.into_iter())
+                .map(|(s, n)| sign(message, s, n, &commitments).unwrap())
+                .collect();
+            aggregate(message, &commitments, &shares, package).unwrap()
+        }
+
+        #[test]
+        fn split_invalid_args() {
+            let sk = private_key();
+            assert!(split_private_key::<$suite>(&sk, 3, 2).is_err());
+            assert!(split_private_key::<$suite>(&sk, 1, 2).is_err());
+            assert!(split_private_key::<$suite>(&PrivateKey(vec![1u8; 3]), 2, 3).is_err());
+        }
+
+        #[test]
+        fn threshold_signature() {
+            let sk = private_key();
+            let pk = public_key(&sk);
+            let (package, shares) = split_private_key::<$suite>(&sk, 3, 5).unwrap();
+            assert_eq!(package.group_public_key(), pk);
+            let message = b"Hello from FROST";
+            for signers in [&shares[..3], &shares[2..], &shares[1..4], &shares[..]].iter() {
+                let signature = run(message, &package, signers);
+                assert!($verify(&message[..], &signature, &pk));
+                assert!(!$verify(&b"Another message"[..], &signature, &pk));
+            }
+        }
+
+        #[test]
+        fn invalid_signing() {
+            let sk = private_key();
+            let (package, shares) = split_private_key::<$suite>(&sk, 2, 3).unwrap();
+            let message = b"message";
+            let (n1, c1) = commit(&shares[0]);
+            let (n2, c2) = commit(&shares[1]);
+            let (_, c3) = commit(&shares[2]);
+
+            // The signer's commitments must be included and match its nonces
+            assert!(sign(message, &shares[0], n1, &[c2.clone(), c3.clone()]).is_err());
+            let (n1, _) = commit(&shares[0]);
+            assert!(sign(message, &shares[0], n1, &[c1, c2.clone()]).is_err());
+            assert!(sign(message, &shares[1], n2, &[c2]).is_err());
+
+            let (n1, c1) = commit(&shares[0]);
+            let (n2, c2) = commit(&shares[1]);
+            let commitments = [c1.clone(), c2.clone()];
+            let s1 = sign(message, &shares[0], n1, &commitments).unwrap();
+            let s2 = sign(message, &shares[1], n2, &commitments).unwrap();
+
+            // Missing share, another message, other signers and a corrupted share
+            assert!(aggregate(message, &commitments, &[s1.clone()], &package).is_err());
+            let both = [s1.clone(), s2.clone()];
+            assert!(aggregate(b"other", &commitments, &both, &package).is_err());
+            assert!(aggregate(message, &[c1, c3], &both, &package).is_err());
+            let mut bad = s2.clone();
+            bad.share = s1.share.clone();
+            assert!(aggregate(message, &commitments, &[s1, bad], &package).is_err());
+            assert!(aggregate(message, &commitments, &both, &package).is_ok());
+        }
+
+        #[test]
+        fn external_shares() {
+            let sk = private_key();
+            let pk = public_key(&sk);
+            let (package, shares) = split_private_key::<$suite>(&sk, 2, 3).unwrap();
+            let imported: Vec<KeyShare<$suite>> = shares
+                .iter()
+                .map(|s| KeyShare::from_bytes(&s.to_bytes()).unwrap())
+                .collect();
+            assert_eq!(imported, shares);
+            assert!(KeyShare::<$suite>::from_bytes(&shares[0].to_bytes()[1..]).is_err());
+
+            let verifying_shares = shares
+                .iter()
+                .map(|s| (s.identifier(), s.verifying_share()))
+                .collect();
+            let group_public_key = $suite::serialize_element(&shares[0].group_public_key);
+            let imported_package =
+                PublicKeyPackage::<$suite>::new(&verifying_shares, &group_public_key, 2).unwrap();
+            assert_eq!(imported_package, package);
+            assert!(
+                PublicKeyPackage::<$suite>::new(&verifying_shares, &group_public_key, 1).is_err()
+            );
+            assert!(
+                PublicKeyPackage::<$suite>::new(&verifying_shares, &group_public_key, 4).is_err()
+            );
+
+            let message = b"message";
+            let signature = run(message, &imported_package, &imported[1..]);
+            assert!($verify(&message[..], &signature, &pk));
+        }
+
+        #[test]
+        fn external_dealer() {
+            // Share a secret and its negation so one group public key requires negation
+            let secret = $suite::random_scalar();
+            for secret in [secret.clone(), $suite::scalar_neg(&secret)].iter() {
+                let group_public_key = $suite::serialize_element(&$suite::base_mul(secret));
+                let coefficient = $suite::random_scalar();
+                let mut verifying_shares = std::collections::BTreeMap::new();
+                let mut shares = Vec::new();
+                for identifier in 1..=3 {
+                    let x = $suite::scalar_from_u8(identifier);
+                    let value = $suite::scalar_add(secret, &$suite::scalar_mul(&coefficient, &x));
+                    verifying_shares.insert(
+                        identifier,
+                        $suite::serialize_element(&$suite::base_mul(&value)),
+                    );
+                    let value = $suite::serialize_scalar(&value);
+                    shares.push(
+                        KeyShare::<$suite>::new(identifier, &value, &group_public_key).unwrap(),
+                    );
+                }
+                let package =
+                    PublicKeyPackage::<$suite>::new(&verifying_shares, &group_public_key, 2)
+                        .unwrap();
+                let pk = package.group_public_key();
+                assert_eq!(shares[0].group_public_key(), pk);
+
+                let message = b"message";
+                for signers in [&shares[..2], &shares[1..], &shares[..]].iter() {
+                    let signature = run(message, &package, signers);
+                    assert!($verify(&message[..], &signature, &pk));
+                }
+            }
+        }
+
+        #[test]
+        fn less_than_threshold_signers() {
+            let sk = private_key();
+            let (package, shares) = split_private_key::<$suite>(&sk, 3, 5).unwrap();
+            assert_eq!(package.threshold(), 3);
+            let message = b"message";
+            let signers = &shares[1..3];
+            let (nonces, commitments): (Vec<_>, Vec<_>) = signers.iter().map(commit).unzip();
+            let shares: Vec<_> = signers
+                .iter()
+                .zip(nonces.into_iter())
+                .map(|(s, n)| sign(message, s, n, &commitments).unwrap())
+                .collect();
+            assert!(aggregate(message, &commitments, &shares, &package).is_err());
+        }
+
+        #[test]
+        fn secrets_redacted() {
+            let sk = private_key();
+            let (_, shares) = split_private_key::<$suite>(&sk, 2, 3).unwrap();
+            let secret = format!("{:?}", shares[0].secret);
+            assert!(!format!("{:?}", shares[0]).contains(&secret));
+            let (nonces, _) = commit(&shares[0]);
+            let hiding = format!("{:?}", nonces.hiding);
+            assert!(!format!("{:?}", nonces).contains(&hiding));
+
+            let mut share = shares[0].clone();
+            share.zeroize();
+            assert_eq!(share.secret, $suite::scalar_from_u8(0));
+        }
+
+        #[cfg(any(feature = "sharing", feature = "sharing_native"))]
+        #[test]
+        fn shamir_shares() {
+            use bn::BigNumber;
+            use sharing::shamir;
+
+            let secret = $suite::random_scalar();
+            let group_public_key = $suite::serialize_element(&$suite::base_mul(&secret));
+            let mut bytes = $suite::serialize_scalar(&secret);
+            if $suite::SCALAR_LITTLE_ENDIAN {
+                bytes.reverse();
+            }
+            let order = BigNumber::from_hex($suite::ORDER).unwrap();
+            let shares: Vec<KeyShare<$suite>> = shamir::split_secret(&bytes, 2, 3, &order)
+                .unwrap()
+                .iter()
+                .map(|s| KeyShare::from_shamir_share(s, &group_public_key).unwrap())
+                .collect();
+            let verifying_shares = shares
+                .iter()
+                .map(|s| (s.identifier(), s.verifying_share()))
+                .collect();
+            // The group public key of the key shares, which may be negated
+            let group_public_key = $suite::serialize_element(&shares[0].group_public_key);
+            let package =
+                PublicKeyPackage::<$suite>::new(&verifying_shares, &group_public_key, 2).unwrap();
+            let pk = package.group_public_key();
+            let message = b"message";
+            for signers in [&shares[..2], &shares[1..]].iter() {
+                let signature = run(message, &package, signers);
+                assert!($verify(&message[..], &signature, &pk));
+            }
+        }
+
+        #[test]
+        fn message_serialization() {
+            let sk = private_key();
+            let (_, shares) = split_private_key::<$suite>(&sk, 2, 3).unwrap();
+            let (n1, c1) = commit(&shares[0]);
+            let (_, c2) = commit(&shares[1]);
+            let bytes = c1.to_bytes();
+            assert_eq!(bytes.len(), 1 + 2 * $suite::ELEMENT_SIZE);
+            assert_eq!(SigningCommitments::from_bytes(&bytes).unwrap(), c1);
+            assert!(SigningCommitments::<$suite>::from_bytes(&bytes[1..]).is_err());
+
+            let share = sign(b"message", &shares[0], n1, &[c1, c2]).unwrap();
+            let bytes = share.to_bytes();
+            assert_eq!(bytes.len(), 1 + $suite::SCALAR_SIZE);
+            assert_eq!(SignatureShare::from_bytes(&bytes).unwrap(), share);
+            assert!(SignatureShare::<$suite>::from_bytes(&bytes[1..]).is_err());
+        }
+    };
+}
+
+pub mod ed25519;
+pub mod secp256k1;
@@ -0,0 +1,527 @@
+//! FROST for secp256k1 with BIP-340 Schnorr signatures
+//! (see <https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki>)
+//! and FROST(secp256k1, SHA-256) from RFC 9591 section 6.5.
+//!
+//! For BIP-340 the group public key and group commitment are negated where needed so both
+//! have an even y-coordinate and the signature is `R.x || z` under the x-only group public
+//! key. The FROST hash functions are BIP-340 tagged hashes reduced modulo the group order.
+
+use rand::{rngs::OsRng, RngCore};
+use rustlibsecp256k1::curve::{
+    Affine, Field, Jacobian, Scalar, ECMULT_CONTEXT, ECMULT_GEN_CONTEXT,
+};
+use sha2::{Digest, Sha256};
+
+use super::Ciphersuite;
+use keys::{PrivateKey, PublicKey};
+use signatures::schnorr_secp256k1::tagged_hash;
+use {CryptoError, CryptoResult};
+
+const CONTEXT_STRING: &[u8] = b"FROST-secp256k1-BIP340-v1";
+const RFC_CONTEXT_STRING: &[u8] = b"FROST-secp256k1-SHA256-v1";
+
+/// 2^256 mod the group order
+const R_MOD_ORDER: [u8; 32] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0x45, 0x51, 0x23, 0x19, 0x50, 0xb7, 0x5f, 0xc4,
+    0x40, 0x2d, 0xa1, 0x73, 0x2f, 0xc9, 0xbe, 0xbf,
+];
+
+/// FROST for BIP-340 Schnorr signatures
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrostSecp256k1Bip340;
+
+/// FROST(secp256k1, SHA-256) with SEC1 compressed group elements and
+/// signatures `R || z` that verify with `z * G == R + c * PK`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrostSecp256k1Sha256;
+
+fn scalar_from_hash(hash: &[u8; 32]) -> Scalar {
+    let mut s = Scalar::default();
+    // Reduce modulo the order like BIP-340
+    let _ = s.set_b32(hash);
+    s
+}
+
+fn hash_to_scalar(label: &[u8], m: &[u8]) -> Scalar {
+    let tag = [CONTEXT_STRING, label].concat();
+    scalar_from_hash(&tagged_hash(&tag, &[m]))
+}
+
+/// hash_to_field from RFC 9380 with expand_message_xmd(SHA-256) and L = 48
+fn hash_to_field(label: &[u8], m: &[u8]) -> Scalar {
+    let mut dst = [RFC_CONTEXT_STRING, label].concat();
+    dst.push(dst.len() as u8);
+    let b_0 = Sha256::new()
+        .chain(&[0u8; 64][..])
+        .chain(m)
+        .chain(&[0u8, 48, 0][..])
+        .chain(&dst)
+        .result();
+    let b_1 = Sha256::new().chain(b_0).chain([1u8]).chain(&dst).result();
+    let xor: Vec<u8> = b_0.iter().zip(b_1.iter()).map(|(a, b)| a ^ b).collect();
+    let b_2 = Sha256::new().chain(&xor).chain([2u8]).chain(&dst).result();
+
+    // The 48 bytes b_1 || b_2[..16] as hi * 2^256 + lo
+    let mut hi = [0u8; 32];
+    hi[16..].copy_from_slice(&b_1[..16]);
+    let mut lo = [0u8; 32];
+    lo[..16].copy_from_slice(&b_1[16..]);
+    lo[16..].copy_from_slice(&b_2[..16]);
+    scalar_from_hash(&hi) * scalar_from_hash(&R_MOD_ORDER) + scalar_from_hash(&lo)
+}
+
+fn rfc_hash(label: &[u8], m: &[u8]) -> Vec<u8> {
+    Sha256::new()
+        .chain(RFC_CONTEXT_STRING)
+        .chain(label)
+        .chain(m)
+        .result()
+        .to_vec()
+}
+
+fn to_affine(a: &Jacobian) -> Affine {
+    if a.is_infinity() {
+        return FrostSecp256k1Bip340::identity();
+    }
+    let mut out = Affine::default();
+    out.set_gej(a);
+    out.x.normalize();
+    out.y.normalize();
+    out
+}
+
+/// The group operations shared by the secp256k1 ciphersuites
+macro_rules! secp256k1_group_impl {
+    () => {
+        type Scalar = Scalar;
+        type Element = Affine;
+
+        const SCALAR_SIZE: usize = 32;
+        const ELEMENT_SIZE: usize = 33;
+        const ORDER: &'static str =
+            "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+        const SCALAR_LITTLE_ENDIAN: bool = false;
+
+        fn scalar_from_u8(value: u8) -> Scalar {
+            Scalar::from_int(u32::from(value))
+        }
+
+        fn scalar_add(a: &Scalar, b: &Scalar) -> Scalar {
+            a + b
+        }
+
+        fn scalar_mul(a: &Scalar, b: &Scalar) -> Scalar {
+            a * b
+        }
+
+        fn scalar_neg(a: &Scalar) -> Scalar {
+            -a
+        }
+
+        fn scalar_invert(a: &Scalar) -> Scalar {
+            a.inv()
+        }
+
+        fn random_scalar() -> Scalar {
+            let mut bytes = [0u8; 32];
+            loop {
+                OsRng.fill_bytes(&mut bytes);
+                if let Ok(s) = Self::deserialize_scalar(&bytes) {
+                    if !s.is_zero() {
+                        return s;
+                    }
+                }
+            }
+        }
+
+        fn zeroize_scalar(a: &mut Scalar) {
+            a.clear();
+        }
+
+        fn serialize_scalar(a: &Scalar) -> Vec<u8> {
+            a.b32().to_vec()
+        }
+
+        fn deserialize_scalar(data: &[u8]) -> CryptoResult<Scalar> {
+            if data.len() != Self::SCALAR_SIZE {
+                return Err(CryptoError::ParseError("Invalid scalar length".to_string()));
+            }
+            let mut s = Scalar::default();
+            if bool::from(s.set_b32(array_ref!(data, 0, 32))) {
+                return Err(CryptoError::ParseError("Invalid scalar".to_string()));
+            }
+            Ok(s)
+        }
+
+        fn identity() -> Affine {
+            Affine {
+                x: Field::default(),
+                y: Field::default(),
+                infinity: true,
+            }
+        }
+
+        fn is_identity(a: &Affine) -> bool {
+            a.is_infinity()
+        }
+
+        fn base_mul(a: &Scalar) -> Affine {
+            let mut r = Jacobian::default();
+            ECMULT_GEN_CONTEXT.ecmult_gen(&mut r, a);
+            to_affine(&r)
+        }
+
+        fn element_add(a: &Affine, b: &Affine) -> Affine {
+            if a.is_infinity() {
+                return b.clone();
+            }
+            to_affine(&Jacobian::from_ge(a).add_ge_var(b, None))
+        }
+
+        fn element_mul(a: &Affine, b: &Scalar) -> Affine {
+            if a.is_infinity() {
+                return Self::identity();
+            }
+            let mut r = Jacobian::default();
+            ECMULT_CONTEXT.ecmult_const(&mut r, a, b);
+            to_affine(&r)
+        }
+
+        fn element_neg(a: &Affine) -> Affine {
+            if a.is_infinity() {
+                return Self::identity();
+            }
+            let mut out = a.neg();
+            out.y.normalize();
+            out
+        }
+
+        fn serialize_element(a: &Affine) -> Vec<u8> {
+            // SEC1 compressed
+            let mut output = vec![if a.y.is_odd() { 0x03 } else { 0x02 }];
+            output.extend_from_slice(&a.x.b32());
+            output
+        }
+
+        fn deserialize_element(data: &[u8]) -> CryptoResult<Affine> {
+            if data.len() != Self::ELEMENT_SIZE {
+                return Err(CryptoError::ParseError(
+                    "Invalid element length".to_string(),
+                ));
+            }
+            let pk = rustlibsecp256k1::PublicKey::parse_compressed(array_ref!(data, 0, 33))
+                .map_err(|e| CryptoError::ParseError(format!("{:?}", e)))?;
+            let mut out: Affine = pk.into();
+            out.x.normalize();
+            out.y.normalize();
+            Ok(out)
+        }
+
+        fn secret_scalar(sk: &PrivateKey) -> CryptoResult<Scalar> {
+            match Self::deserialize_scalar(&sk[..]) {
+                Ok(s) if !s.is_zero() => Ok(s),
+                _ => Err(CryptoError::ParseError(
+                    "Invalid private key provided".to_string(),
+                )),
+            }
+        }
+    };
+}
+
+impl Ciphersuite for FrostSecp256k1Bip340 {
+    secp256k1_group_impl!();
+
+    fn public_key(a: &Affine) -> PublicKey {
+        // x-only
+        PublicKey(a.x.b32().to_vec())
+    }
+
+    fn requires_negation(a: &Affine) -> bool {
+        !a.is_infinity() && a.y.is_odd()
+    }
+
+    fn h1(m: &[u8]) -> Scalar {
+        hash_to_scalar(b"rho", m)
+    }
+
+    fn challenge(r: &Affine, public_key: &Affine, message: &[u8]) -> Scalar {
+        scalar_from_hash(&tagged_hash(
+            b"BIP0340/challenge",
+            &[&r.x.b32(), &public_key.x.b32(), message],
+        ))
+    }
+
+    fn h3(m: &[u8]) -> Scalar {
+        hash_to_scalar(b"nonce", m)
+    }
+
+    fn h4(m: &[u8]) -> Vec<u8> {
+        tagged_hash(&[CONTEXT_STRING, b"msg"].concat(), &[m]).to_vec()
+    }
+
+    fn h5(m: &[u8]) -> Vec<u8> {
+        tagged_hash(&[CONTEXT_STRING, b"com"].concat(), &[m]).to_vec()
+    }
+
+    fn serialize_signature(r: &Affine, z: &Scalar) -> Vec<u8> {
+        let mut output = r.x.b32().to_vec();
+        output.extend_from_slice(&z.b32());
+        output
+    }
+}
+
+impl Ciphersuite for FrostSecp256k1Sha256 {
+    secp256k1_group_impl!();
+
+    fn public_key(a: &Affine) -> PublicKey {
+        PublicKey(Self::serialize_element(a))
+    }
+
+    fn h1(m: &[u8]) -> Scalar {
+        hash_to_field(b"rho", m)
+    }
+
+    fn challenge(r: &Affine, public_key: &Affine, message: &[u8]) -> Scalar {
+        let mut m = Self::serialize_element(r);
+        m.extend_from_slice(&Self::serialize_element(public_key));
+        m.extend_from_slice(message);
+        hash_to_field(b"chal", &m)
+    }
+
+    fn h3(m: &[u8]) -> Scalar {
+        hash_to_field(b"nonce", m)
+    }
+
+    fn h4(m: &[u8]) -> Vec<u8> {
+        rfc_hash(b"msg", m)
+    }
+
+    fn h5(m: &[u8]) -> Vec<u8> {
+        rfc_hash(b"com", m)
+    }
+
+    fn serialize_signature(r: &Affine, z: &Scalar) -> Vec<u8> {
+        let mut output = Self::serialize_element(r);
+        output.extend_from_slice(&z.b32());
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use signatures::{schnorr_secp256k1::SchnorrSecp256k1, SignatureScheme};
+
+    fn private_key() -> PrivateKey {
+        PrivateKey(FrostSecp256k1Bip340::random_scalar().b32().to_vec())
+    }
+
+    fn public_key(sk: &PrivateKey) -> PublicKey {
+        let secret = FrostSecp256k1Bip340::secret_scalar(sk).unwrap();
+        FrostSecp256k1Bip340::public_key(&FrostSecp256k1Bip340::base_mul(&secret))
+    }
+
+    fn verify(message: &[u8], signature: &[u8], pk: &PublicKey) -> bool {
+        SchnorrSecp256k1::new()
+            .verify(message, signature, pk)
+            .unwrap_or(false)
+    }
+
+    #[test]
+    fn bip340_test_vectors() {
+        // Vectors 0 and 1 from the BIP
+        let pk = hex::decode("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9")
+            .unwrap();
+        let sig = hex::decode("E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0").unwrap();
+        assert!(verify(&[0u8; 32], &sig, &PublicKey(pk.clone())));
+        assert!(!verify(&[1u8; 32], &sig, &PublicKey(pk)));
+
+        let pk = hex::decode("DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659")
+            .unwrap();
+        let msg = hex::decode("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89")
+            .unwrap();
+        let sig = hex::decode("6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A").unwrap();
+        assert!(verify(&msg, &sig, &PublicKey(pk)));
+        assert_eq!(
+            public_key(&PrivateKey(vec![
+                0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+                0, 0, 0, 3
+            ]))
+            .0,
+            hex::decode("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn odd_public_key() {
+        // Negated private keys have the same x-only public key and both must work
+        let sk = private_key();
+        let secret = FrostSecp256k1Bip340::secret_scalar(&sk).unwrap();
+        let negated = PrivateKey(FrostSecp256k1Bip340::scalar_neg(&secret).b32().to_vec());
+        let pk = public_key(&sk);
+        assert_eq!(public_key(&negated), pk);
+        for key in [sk, negated].iter() {
+            let (package, shares) = split_private_key::<FrostSecp256k1Bip340>(key, 2, 3).unwrap();
+            assert_eq!(package.group_public_key(), pk);
+            let message = b"message";
+            let (nonces, commitments): (Vec<_>, Vec<_>) = shares.iter().map(commit).unzip();
+            let shares: Vec<_> = shares
+                .iter()
+                .zip(nonces.into_iter())
+                .map(|(s, n)| sign(message, s, n, &commitments).unwrap())
+                .collect();
+            let signature = aggregate(message, &commitments, &shares, &package).unwrap();
+            assert!(verify(message, &signature, &pk));
+        }
+    }
+
+    frost_tests_impl!(FrostSecp256k1Bip340, verify);
+}
+
+#[cfg(test)]
+mod sha256_tests {
+    use super::super::{aggregate, commit_with_randomness, sign, KeyShare, PublicKeyPackage};
+    use super::*;
+
+    fn private_key() -> PrivateKey {
+        PrivateKey(FrostSecp256k1Sha256::random_scalar().b32().to_vec())
+    }
+
+    fn public_key(sk: &PrivateKey) -> PublicKey {
+        let secret = FrostSecp256k1Sha256::secret_scalar(sk).unwrap();
+        FrostSecp256k1Sha256::public_key(&FrostSecp256k1Sha256::base_mul(&secret))
+    }
+
+    fn verify(message: &[u8], signature: &[u8], pk: &PublicKey) -> bool {
+        if signature.len() != 65 {
+            return false;
+        }
+        let parsed = (
+            FrostSecp256k1Sha256::deserialize_element(&signature[..33]),
+            FrostSecp256k1Sha256::deserialize_scalar(&signature[33..]),
+            FrostSecp256k1Sha256::deserialize_element(&pk[..]),
+        );
+        match parsed {
+            (Ok(r), Ok(z), Ok(pk)) => {
+                let c = FrostSecp256k1Sha256::challenge(&r, &pk, message);
+                let expected = FrostSecp256k1Sha256::element_add(
+                    &r,
+                    &FrostSecp256k1Sha256::element_mul(&pk, &c),
+                );
+                FrostSecp256k1Sha256::base_mul(&z) == expected
+            }
+            _ => false,
+        }
+    }
+
+    #[test]
+    fn rfc9591_test_vectors() {
+        // Appendix E.5
+        let decode = |s: &str| hex::decode(s).unwrap();
+        let scalar = |s: &str| FrostSecp256k1Sha256::deserialize_scalar(&decode(s)).unwrap();
+        let group_secret_key =
+            scalar("0d004150d27c3bf2a42f312683d35fac7394b1e9e318249c1bfe7f0795a83114");
+        let group_public_key =
+            decode("02f37c34b66ced1fb51c34a90bdae006901f10625cc06c4f64663b0eae87d87b4f");
+        let coefficient =
+            scalar("fbf85eadae3058ea14f19148bb72b45e4399c0b16028acaf0395c9b03c823579");
+        assert_eq!(
+            FrostSecp256k1Sha256::serialize_element(&FrostSecp256k1Sha256::base_mul(
+                &group_secret_key
+            )),
+            group_public_key
+        );
+        let participant_shares = [
+            "08f89ffe80ac94dcb920c26f3f46140bfc7f95b493f8310f5fc1ea2b01f4254c",
+            "04f0feac2edcedc6ce1253b7fab8c86b856a797f44d83d82a385554e6e401984",
+            "00e95d59dd0d46b0e303e500b62b7ccb0e555d49f5b849f5e748c071da8c0dbc",
+        ];
+        for (i, share) in participant_shares.iter().enumerate() {
+            let x = Scalar::from_int(i as u32 + 1);
+            assert_eq!(&group_secret_key + &(&coefficient * &x), scalar(share));
+        }
+
+        let message = decode("74657374");
+        let signers = [
+            (
+                1,
+                participant_shares[0],
+                "7ea5ed09af19f6ff21040c07ec2d2adbd35b759da5a401d4c99dd26b82391cb2",
+                "47acab018f116020c10cb9b9abdc7ac10aae1b48ca6e36dc15acb6ec9be5cdc5",
+                "841d3a6450d7580b4da83c8e618414d0f024391f2aeb511d7579224420aa81f0",
+                "8d2624f532af631377f33cf44b5ac5f849067cae2eacb88680a31e77c79b5a80",
+                "03c699af97d26bb4d3f05232ec5e1938c12f1e6ae97643c8f8f11c9820303f1904",
+                "02fa2aaccd51b948c9dc1a325d77226e98a5a3fe65fe9ba213761a60123040a45e",
+                "c4fce1775a1e141fb579944166eab0d65eefe7b98d480a569bbbfcb14f91c197",
+            ),
+            (
+                3,
+                participant_shares[2],
+                "e6cc56ccbd0502b3f6f831d91e2ebd01c4de0479e0191b66895a4ffd9b68d544",
+                "7203d55eb82a5ca0d7d83674541ab55f6e76f1b85391d2c13706a89a064fd5b9",
+                "2b19b13f193f4ce83a399362a90cdc1e0ddcd83e57089a7af0bdca71d47869b2",
+                "7a443bde83dc63ef52dda354005225ba0e553243402a4705ce28ffaafe0f5b98",
+                "03077507ba327fc074d2793955ef3410ee3f03b82b4cdc2370f71d865beb926ef6",
+                "02ad53031ddfbbacfc5fbda3d3b0c2445c8e3e99cbc4ca2db2aa283fa68525b135",
+                "0160fd0d388932f4826d2ebcd6b9eaba734f7c71cf25b4279a4ca2581e47b18d",
+            ),
+        ];
+        let mut key_shares = Vec::new();
+        let mut nonces = Vec::new();
+        let mut commitments = Vec::new();
+        let mut verifying_shares = std::collections::BTreeMap::new();
+        for (
+            id,
+            share,
+            hiding_randomness,
+            binding_randomness,
+            hiding,
+            binding,
+            hiding_commitment,
+            binding_commitment,
+            _,
+        ) in signers.iter()
+        {
+            let key_share =
+                KeyShare::<FrostSecp256k1Sha256>::new(*id, &decode(share), &group_public_key)
+                    .unwrap();
+            let (n, c) = commit_with_randomness(
+                &key_share,
+                &decode(hiding_randomness),
+                &decode(binding_randomness),
+            );
+            assert_eq!(n.hiding, scalar(hiding));
+            assert_eq!(n.binding, scalar(binding));
+            assert_eq!(
+                c.to_bytes(),
+                [
+                    &[*id][..],
+                    &decode(hiding_commitment),
+                    &decode(binding_commitment)
+                ]
+                .concat()
+            );
+            verifying_shares.insert(*id, key_share.verifying_share());
+            key_shares.push(key_share);
+            nonces.push(n);
+            commitments.push(c);
+        }
+        let package = PublicKeyPackage::new(&verifying_shares, &group_public_key, 2).unwrap();
+        let shares: Vec<_> = key_shares
+            .iter()
+            .zip(nonces.into_iter())
+            .zip(signers.iter())
+            .map(|((key_share, n), signer)| {
+                let share = sign(&message, key_share, n, &commitments).unwrap();
+                assert_eq!(share.share, scalar(signer.8));
+                share
+            })
+            .collect();
+        let signature = aggregate(&message, &commitments, &shares, &package).unwrap();
+        assert_eq!(signature, decode("0205b6d04d3774c8929413e3c76024d54149c372d57aae62574ed74319b5ea14d0c65dde8492a7471437e6c2fe3da49b90d23f642b5c6dbe7e36089f096dd97324"));
+        assert!(verify(&message, &signature, &PublicKey(group_public_key)));
+    }
+
+    frost_tests_impl!(FrostSecp256k1Sha256, verify);
+}
@@ -2,6 +2,14 @@
 pub mod bls;
 #[cfg(any(feature = "ed25519", feature = "ed25519_asm"))]
 pub mod ed25519;
+#[cfg(feature = "ed448")]
+pub mod ed448;
+#[cfg(feature = "frost")]
+pub mod frost;
+#[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+pub mod nist;
+#[cfg(feature = "schnorr_secp256k1")]
+pub mod schnorr_secp256k1;
 #[cfg(any(
     feature = "ecdsa_secp256k1",
     feature = "ecdsa_secp256k1_native",
@@ -12,16 +20,41 @@ pub mod secp256k1;
 pub mod prelude {
     #[cfg(any(feature = "ed25519", feature = "ed25519_asm"))]
     pub use super::ed25519::Ed25519Sha512;
+    #[cfg(feature = "ed448")]
+    pub use super::ed448::Ed448;
+    #[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+    pub use super::nist::{p256::EcdsaP256Sha256, p384::EcdsaP384Sha384};
+    #[cfg(feature = "schnorr_secp256k1")]
+    pub use super::schnorr_secp256k1::SchnorrSecp256k1;
     #[cfg(any(
         feature = "ecdsa_secp256k1",
         feature = "ecdsa_secp256k1_native",
         feature = "ecdsa_secp256k1_asm"
     ))]
-    pub use super::{secp256k1::EcdsaSecp256k1Sha256, EcdsaPublicKeyHandler};
-    pub use super::{SignatureScheme, Signer};
+    pub use super::secp256k1::EcdsaSecp256k1Sha256;
+    #[cfg(any(
+        feature = "ecdsa_nist",
+        feature = "ecdsa_nist_native",
+        feature = "ecdsa_secp256k1",
+        feature = "ecdsa_secp256k1_native",
+        feature = "ecdsa_secp256k1_asm"
+    ))]
+    pub use super::EcdsaPublicKeyHandler;
+    pub use super::{
+        DynSignatureScheme, SignatureScheme, SignatureSchemeType, Signer, TypedPrivateKey,
+        TypedPublicKey,
+    };
 }
 
+#[cfg(feature = "key_encoding")]
+use encoding::KeyType;
 use keys::{KeyGenOption, PrivateKey, PublicKey};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "key_encoding")]
+use std::convert::TryFrom;
+use std::fmt;
+use std::str::FromStr;
 use CryptoError;
 
 pub trait SignatureScheme {
@@ -83,6 +116,8 @@ impl<'a, 'b, T: 'a + SignatureScheme> Signer<'a, 'b, T> {
 }
 
 #[cfg(any(
+    feature = "ecdsa_nist",
+    feature = "ecdsa_nist_native",
     feature = "ecdsa_secp256k1",
     feature = "ecdsa_secp256k1_native",
     feature = "ecdsa_secp256k1_asm"
@@ -96,3 +131,440 @@ pub trait EcdsaPublicKeyHandler {
     fn parse(&self, data: &[u8]) -> Result<PublicKey, CryptoError>;
     fn public_key_uncompressed_size() -> usize;
 }
+
+/// A signature scheme chosen at runtime, e.g. from configuration.
+///
+/// Keys are tagged with the scheme that created them and keys of another scheme are rejected.
+pub trait DynSignatureScheme {
+    fn scheme_type(&self) -> SignatureSchemeType;
+    fn keypair(
+        &self,
+        options: Option<KeyGenOption>,
+    ) -> Result<(TypedPublicKey, TypedPrivateKey), CryptoError>;
+    fn sign(&self, message: &[u8], sk: &TypedPrivateKey) -> Result<Vec<u8>, CryptoError>;
+    fn verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        pk: &TypedPublicKey,
+    ) -> Result<bool, CryptoError>;
+    fn signature_size(&self) -> usize;
+    fn private_key_size(&self) -> usize;
+    fn public_key_size(&self) -> usize;
+}
+
+/// The signature schemes that can be selected by name
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SignatureSchemeType {
+    #[cfg(feature = "bls_bls12381")]
+    Bls12381Normal,
+    #[cfg(feature = "bls_bls12381")]
+    Bls12381Small,
+    #[cfg(any(feature = "ed25519", feature = "ed25519_asm"))]
+    Ed25519,
+    #[cfg(feature = "ed448")]
+    Ed448,
+    #[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+    EcdsaP256,
+    #[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+    EcdsaP384,
+    #[cfg(any(
+        feature = "ecdsa_secp256k1",
+        feature = "ecdsa_secp256k1_native",
+        feature = "ecdsa_secp256k1_asm"
+    ))]
+    EcdsaSecp256k1,
+    #[cfg(feature = "schnorr_secp256k1")]
+    SchnorrSecp256k1,
+}
+
+impl SignatureSchemeType {
+    pub fn gen_scheme(self) -> Box<dyn DynSignatureScheme> {
+        match self {
+            #[cfg(feature = "bls_bls12381")]
+            SignatureSchemeType::Bls12381Normal => TypedScheme::<bls::normal::Bls>::boxed(self),
+            #[cfg(feature = "bls_bls12381")]
+            SignatureSchemeType::Bls12381Small => TypedScheme::<bls::small::Bls>::boxed(self),
+            #[cfg(any(feature = "ed25519", feature = "ed25519_asm"))]
+            SignatureSchemeType::Ed25519 => TypedScheme::<ed25519::Ed25519Sha512>::boxed(self),
+            #[cfg(feature = "ed448")]
+            SignatureSchemeType::Ed448 => TypedScheme::<ed448::Ed448>::boxed(self),
+            #[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+            SignatureSchemeType::EcdsaP256 => {
+                TypedScheme::<nist::p256::EcdsaP256Sha256>::boxed(self)
+            }
+            #[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+            SignatureSchemeType::EcdsaP384 => {
+                TypedScheme::<nist::p384::EcdsaP384Sha384>::boxed(self)
+            }
+            #[cfg(any(
+                feature = "ecdsa_secp256k1",
+                feature = "ecdsa_secp256k1_native",
+                feature = "ecdsa_secp256k1_asm"
+            ))]
+            SignatureSchemeType::EcdsaSecp256k1 => {
+                TypedScheme::<secp256k1::EcdsaSecp256k1Sha256>::boxed(self)
+            }
+            #[cfg(feature = "schnorr_secp256k1")]
+            SignatureSchemeType::SchnorrSecp256k1 => {
+                TypedScheme::<schnorr_secp256k1::SchnorrSecp256k1>::boxed(self)
+            }
+        }
+    }
+}
+
+impl FromStr for SignatureSchemeType {
+    type Err = CryptoError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            #[cfg(feature = "bls_bls12381")]
+            "bls12381-normal" => Ok(SignatureSchemeType::Bls12381Normal),
+            #[cfg(feature = "bls_bls12381")]
+            "bls12381-small" => Ok(SignatureSchemeType::Bls12381Small),
+            #[cfg(any(feature = "ed25519", feature = "ed25519_asm"))]
+            "ed25519" => Ok(SignatureSchemeType::Ed25519),
+            #[cfg(feature = "ed448")]
+            "ed448" => Ok(SignatureSchemeType::Ed448),
+            #[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+            "ecdsa-p256" => Ok(SignatureSchemeType::EcdsaP256),
+            #[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+            "ecdsa-p384" => Ok(SignatureSchemeType::EcdsaP384),
+            #[cfg(any(
+                feature = "ecdsa_secp256k1",
+                feature = "ecdsa_secp256k1_native",
+                feature = "ecdsa_secp256k1_asm"
+            ))]
+            "ecdsa-secp256k1" => Ok(SignatureSchemeType::EcdsaSecp256k1),
+            #[cfg(feature = "schnorr_secp256k1")]
+            "schnorr-secp256k1" => Ok(SignatureSchemeType::SchnorrSecp256k1),
+            _ => Err(CryptoError::NoSuchAlgorithm(format!(
+                "Invalid signature scheme: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl fmt::Display for SignatureSchemeType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match *self {
+            #[cfg(feature = "bls_bls12381")]
+            SignatureSchemeType::Bls12381Normal => "bls12381-normal",
+            #[cfg(feature = "bls_bls12381")]
+            SignatureSchemeType::Bls12381Small => "bls12381-small",
+            #[cfg(any(feature = "ed25519", feature = "ed25519_asm"))]
+            SignatureSchemeType::Ed25519 => "ed25519",
+            #[cfg(feature = "ed448")]
+            SignatureSchemeType::Ed448 => "ed448",
+            #[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+            SignatureSchemeType::EcdsaP256 => "ecdsa-p256",
+            #[cfg(any(feature = "ecdsa_nist", feature = "ecdsa_nist_native"))]
+            SignatureSchemeType::EcdsaP384 => "ecdsa-p384",
+            #[cfg(any(
+                feature = "ecdsa_secp256k1",
+                feature = "ecdsa_secp256k1_native",
+                feature = "ecdsa_secp256k1_asm"
+            ))]
+            SignatureSchemeType::EcdsaSecp256k1 => "ecdsa-secp256k1",
+            #[cfg(feature = "schnorr_secp256k1")]
+            SignatureSchemeType::SchnorrSecp256k1 => "schnorr-secp256k1",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The key type of the keys made by a signature scheme, for use with `encoding`.
+/// Ed448, NIST ECDSA and BIP-340 Schnorr keys have no `KeyType`.
+#[cfg(feature = "key_encoding")]
+impl TryFrom<SignatureSchemeType> for KeyType {
+    type Error = CryptoError;
+
+    fn try_from(scheme_type: SignatureSchemeType) -> Result<Self, Self::Error> {
+        match scheme_type {
+            #[cfg(feature = "bls_bls12381")]
+            SignatureSchemeType::Bls12381Normal => Ok(KeyType::Bls12381G1),
+            #[cfg(feature = "bls_bls12381")]
+            SignatureSchemeType::Bls12381Small => Ok(KeyType::Bls12381G2),
+            #[cfg(any(feature = "ed25519", feature = "ed25519_asm"))]
+            SignatureSchemeType::Ed25519 => Ok(KeyType::Ed25519),
+            #[cfg(any(
+                feature = "ecdsa_secp256k1",
+                feature = "ecdsa_secp256k1_native",
+                feature = "ecdsa_secp256k1_asm"
+            ))]
+            SignatureSchemeType::EcdsaSecp256k1 => Ok(KeyType::Secp256k1),
+            #[allow(unreachable_patterns)]
+            _ => Err(CryptoError::NoSuchAlgorithm(format!(
+                "No key type for {} keys",
+                scheme_type
+            ))),
+        }
+    }
+}
+
+/// The signature scheme that uses keys of a `KeyType`. X25519 keys are only for key exchange.
+#[cfg(feature = "key_encoding")]
+impl TryFrom<KeyType> for SignatureSchemeType {
+    type Error = CryptoError;
+
+    fn try_from(key_type: KeyType) -> Result<Self, Self::Error> {
+        match key_type {
+            #[cfg(feature = "bls_bls12381")]
+            KeyType::Bls12381G1 => Ok(SignatureSchemeType::Bls12381Normal),
+            #[cfg(feature = "bls_bls12381")]
+            KeyType::Bls12381G2 => Ok(SignatureSchemeType::Bls12381Small),
+            #[cfg(any(feature = "ed25519", feature = "ed25519_asm"))]
+            KeyType::Ed25519 => Ok(SignatureSchemeType::Ed25519),
+            #[cfg(any(
+                feature = "ecdsa_secp256k1",
+                feature = "ecdsa_secp256k1_native",
+                feature = "ecdsa_secp256k1_asm"
+            ))]
+            KeyType::Secp256k1 => Ok(SignatureSchemeType::EcdsaSecp256k1),
+            _ => Err(CryptoError::NoSuchAlgorithm(format!(
+                "No signature scheme for {:?} keys",
+                key_type
+            ))),
+        }
+    }
+}
+
+/// A public key together with the signature scheme it belongs to
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TypedPublicKey {
+    scheme_type: SignatureSchemeType,
+    key: PublicKey,
+}
+
+impl TypedPublicKey {
+    pub fn new(scheme_type: SignatureSchemeType, key: PublicKey) -> Self {
+        TypedPublicKey { scheme_type, key }
+    }
+
+    pub fn scheme_type(&self) -> SignatureSchemeType {
+        self.scheme_type
+    }
+
+    pub fn key(&self) -> &PublicKey {
+        &self.key
+    }
+
+    pub fn into_key(self) -> PublicKey {
+        self.key
+    }
+}
+
+/// A private key together with the signature scheme it belongs to
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TypedPrivateKey {
+    scheme_type: SignatureSchemeType,
+    key: PrivateKey,
+}
+
+impl TypedPrivateKey {
+    pub fn new(scheme_type: SignatureSchemeType, key: PrivateKey) -> Self {
+        TypedPrivateKey { scheme_type, key }
+    }
+
+    pub fn scheme_type(&self) -> SignatureSchemeType {
+        self.scheme_type
+    }
+
+    pub fn key(&self) -> &PrivateKey {
+        &self.key
+    }
+
+    pub fn into_key(self) -> PrivateKey {
+        self.key
+    }
+}
+
+/// Adapts a `SignatureScheme` to `DynSignatureScheme` by tagging its keys
+struct TypedScheme<S: SignatureScheme> {
+    scheme_type: SignatureSchemeType,
+    scheme: S,
+}
+
+impl<S: SignatureScheme + 'static> TypedScheme<S> {
+    fn boxed(scheme_type: SignatureSchemeType) -> Box<dyn DynSignatureScheme> {
+        Box::new(TypedScheme {
+            scheme_type,
+            scheme: S::new(),
+        })
+    }
+
+    fn check_scheme_type(&self, scheme_type: SignatureSchemeType) -> Result<(), CryptoError> {
+        if scheme_type == self.scheme_type {
+            Ok(())
+        } else {
+            Err(CryptoError::ParseError(format!(
+                "Expected a {} key but got a {} key",
+                self.scheme_type, scheme_type
+            )))
+        }
+    }
+}
+
+impl<S: SignatureScheme + 'static> DynSignatureScheme for TypedScheme<S> {
+    fn scheme_type(&self) -> SignatureSchemeType {
+        self.scheme_type
+    }
+
+    fn keypair(
+        &self,
+        options: Option<KeyGenOption>,
+    ) -> Result<(TypedPublicKey, TypedPrivateKey), CryptoError> {
+        let (pk, sk) = self.scheme.keypair(options)?;
+        Ok((
+            TypedPublicKey::new(self.scheme_type, pk),
+            TypedPrivateKey::new(self.scheme_type, sk),
+        ))
+    }
+
+    fn sign(&self, message: &[u8], sk: &TypedPrivateKey) -> Result<Vec<u8>, CryptoError> {
+        self.check_scheme_type(sk.scheme_type())?;
+        self.scheme.sign(message, sk.key())
+    }
+
+    fn verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        pk: &TypedPublicKey,
+    ) -> Result<bool, CryptoError> {
+        self.check_scheme_type(pk.scheme_type())?;
+        self.scheme.verify(message, signature, pk.key())
+    }
+
+    fn signature_size(&self) -> usize {
+        S::signature_size()
+    }
+
+    fn private_key_size(&self) -> usize {
+        S::private_key_size()
+    }
+
+    fn public_key_size(&self) -> usize {
+        S::public_key_size()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MESSAGE: &[u8] = b"This is a dummy message for use with tests";
+
+    fn all_scheme_types() -> Vec<SignatureSchemeType> {
+        [
+            "bls12381-normal",
+            "bls12381-small",
+            "ed25519",
+            "ed448",
+            "ecdsa-p256",
+            "ecdsa-p384",
+            "ecdsa-secp256k1",
+            "schnorr-secp256k1",
+        ]
+        .iter()
+        .filter_map(|name| name.parse().ok())
+        .collect()
+    }
+
+    #[test]
+    fn scheme_type_names() {
+        let scheme_types = all_scheme_types();
+        assert!(!scheme_types.is_empty());
+        for scheme_type in scheme_types {
+            let name = scheme_type.to_string();
+            assert_eq!(name.parse::<SignatureSchemeType>().unwrap(), scheme_type);
+            assert_eq!(
+                name.to_uppercase().parse::<SignatureSchemeType>().unwrap(),
+                scheme_type
+            );
+        }
+        assert!("rsa".parse::<SignatureSchemeType>().is_err());
+        assert!("".parse::<SignatureSchemeType>().is_err());
+    }
+
+    #[test]
+    fn dyn_scheme_sign_verify() {
+        for scheme_type in all_scheme_types() {
+            let scheme = scheme_type.gen_scheme();
+            assert_eq!(scheme.scheme_type(), scheme_type);
+            let (pk, sk) = scheme.keypair(None).unwrap();
+            assert_eq!(pk.scheme_type(), scheme_type);
+            assert_eq!(sk.scheme_type(), scheme_type);
+            assert_eq!(pk.key().0.len(), scheme.public_key_size());
+            assert_eq!(sk.key().0.len(), scheme.private_key_size());
+
+            let signature = scheme.sign(MESSAGE, &sk).unwrap();
+            assert_eq!(signature.len(), scheme.signature_size());
+            assert!(scheme.verify(MESSAGE, &signature, &pk).unwrap());
+
+            // Key generation from a secret key keeps the tag
+            let (pk2, sk2) = scheme
+                .keypair(Some(KeyGenOption::FromSecretKey(sk.clone().into_key())))
+                .unwrap();
+            assert_eq!(pk2, pk);
+            assert_eq!(sk2, sk);
+        }
+    }
+
+    #[test]
+    fn dyn_scheme_rejects_mismatched_keys() {
+        let scheme_types = all_scheme_types();
+        for scheme_type in &scheme_types {
+            let scheme = scheme_type.gen_scheme();
+            let (pk, sk) = scheme.keypair(None).unwrap();
+            let signature = scheme.sign(MESSAGE, &sk).unwrap();
+            for other_type in scheme_types.iter().filter(|t| *t != scheme_type) {
+                let other = other_type.gen_scheme();
+                assert!(other.sign(MESSAGE, &sk).is_err());
+                assert!(other.verify(MESSAGE, &signature, &pk).is_err());
+                // Relabelling the raw key is required to use it elsewhere
+                let relabelled = TypedPublicKey::new(*other_type, pk.key().clone());
+                assert!(scheme.verify(MESSAGE, &signature, &relabelled).is_err());
+            }
+        }
+    }
+
+    #[cfg(feature = "key_encoding")]
+    #[test]
+    fn key_type_conversion() {
+        use encoding::public_key_from_private_key;
+
+        for scheme_type in all_scheme_types() {
+            let key_type = match KeyType::try_from(scheme_type) {
+                Ok(key_type) => key_type,
+                Err(_) => continue,
+            };
+            assert_eq!(
+                SignatureSchemeType::try_from(key_type).unwrap(),
+                scheme_type
+            );
+            let (pk, sk) = scheme_type.gen_scheme().keypair(None).unwrap();
+            assert_eq!(
+                public_key_from_private_key(key_type, sk.key()).unwrap(),
+                *pk.key()
+            );
+        }
+        assert!(SignatureSchemeType::try_from(KeyType::X25519).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn typed_key_serialization() {
+        for scheme_type in all_scheme_types() {
+            let (pk, sk) = scheme_type.gen_scheme().keypair(None).unwrap();
+            let json = serde_json::to_string(&pk).unwrap();
+            assert_eq!(serde_json::from_str::<TypedPublicKey>(&json).unwrap(), pk);
+            let json = serde_json::to_string(&sk).unwrap();
+            assert_eq!(serde_json::from_str::<TypedPrivateKey>(&json).unwrap(), sk);
+        }
+    }
+}
@@ -0,0 +1,491 @@
+//! ECDSA on the NIST curves from FIPS 186-4, P-256 with SHA-256 in `p256` and P-384 with
+//! SHA-384 in `p384`, as used by ES256 and ES384 in JWS and by FIDO and TLS certificates.
+//!
+//! Public keys are compressed SEC1 points and can be parsed from or converted to the
+//! uncompressed form with `EcdsaPublicKeyHandler`. Signatures are the fixed size `r || s`.
+//! The `ecdsa_nist` feature is implemented in Rust with AMCL and `ecdsa_nist_native` with OpenSSL.
+
+macro_rules! ecdsa_nist_impl {
+    ($name:ident, $algorithm:expr, $size:expr, $amcl_curve:ident, $nid:ident, $digest:ident) => {
+        use super::super::{EcdsaPublicKeyHandler, KeyGenOption, SignatureScheme};
+        use keys::{PrivateKey, PublicKey};
+        use rand::rngs::OsRng;
+        use rand::{RngCore, SeedableRng};
+        use rand_chacha::ChaChaRng;
+        use sha2::Digest;
+        use zeroize::Zeroize;
+
+        use CryptoError;
+
+        pub const PRIVATE_KEY_SIZE: usize = $size;
+        pub const PUBLIC_KEY_SIZE: usize = $size + 1;
+        pub const PUBLIC_UNCOMPRESSED_KEY_SIZE: usize = 2 * $size + 1;
+        pub const SIGNATURE_SIZE: usize = 2 * $size;
+        pub const ALGORITHM_NAME: &str = $algorithm;
+
+        pub struct $name(ecdsa_impl::EcdsaImpl);
+
+        impl SignatureScheme for $name {
+            fn new() -> Self {
+                $name(ecdsa_impl::EcdsaImpl::new())
+            }
+            fn keypair(
+                &self,
+                option: Option<KeyGenOption>,
+            ) -> Result<(PublicKey, PrivateKey), CryptoError> {
+                let mut s = [0u8; PRIVATE_KEY_SIZE];
+                match option {
+                    Some(mut o) => match o {
+                        KeyGenOption::UseSeed(ref mut seed) => {
+                            if seed.len() < 32 {
+                                return Err(CryptoError::KeyGenError(
+                                    "Seed must be at least 32 bytes".to_string(),
+                                ));
+                            }
+                            let mut rng = ChaChaRng::from_seed(*array_ref!(seed.as_slice(), 0, 32));
+                            seed.zeroize();
+                            rng.fill_bytes(&mut s);
+                        }
+                        KeyGenOption::FromSecretKey(ref sk) => {
+                            let pk = self.0.public_key(sk)?;
+                            return Ok((pk, PrivateKey(sk[..].to_vec())));
+                        }
+                    },
+                    None => OsRng.fill_bytes(&mut s),
+                };
+                let mut sk = PrivateKey(sha2::$digest::digest(&s).to_vec());
+                s.zeroize();
+                // Hash again in the unlikely case the digest is not less than the group order
+                loop {
+                    if let Ok(pk) = self.0.public_key(&sk) {
+                        return Ok((pk, sk));
+                    }
+                    sk = PrivateKey(sha2::$digest::digest(&sk[..]).to_vec());
+                }
+            }
+            fn sign(&self, message: &[u8], sk: &PrivateKey) -> Result<Vec<u8>, CryptoError> {
+                self.0.sign(message, sk)
+            }
+            fn verify(
+                &self,
+                message: &[u8],
+                signature: &[u8],
+                pk: &PublicKey,
+            ) -> Result<bool, CryptoError> {
+                if signature.len() != SIGNATURE_SIZE {
+                    return Err(CryptoError::ParseError(
+                        "Invalid signature length".to_string(),
+                    ));
+                }
+                self.0.verify(message, signature, pk)
+            }
+            fn signature_size() -> usize {
+                SIGNATURE_SIZE
+            }
+            fn private_key_size() -> usize {
+                PRIVATE_KEY_SIZE
+            }
+            fn public_key_size() -> usize {
+                PUBLIC_KEY_SIZE
+            }
+        }
+
+        impl EcdsaPublicKeyHandler for $name {
+            /// Returns the compressed bytes
+            fn public_key_compressed(&self, pk: &PublicKey) -> Vec<u8> {
+                self.0.serialize(&pk[..], true).unwrap()
+            }
+            /// Returns the uncompressed bytes
+            fn public_key_uncompressed(&self, pk: &PublicKey) -> Vec<u8> {
+                self.0.serialize(&pk[..], false).unwrap()
+            }
+            /// Read raw bytes into key struct. Can be either compressed or uncompressed
+            fn parse(&self, data: &[u8]) -> Result<PublicKey, CryptoError> {
+                Ok(PublicKey(self.0.serialize(data, true)?))
+            }
+            fn public_key_uncompressed_size() -> usize {
+                PUBLIC_UNCOMPRESSED_KEY_SIZE
+            }
+        }
+
+        #[cfg(feature = "ecdsa_nist_native")]
+        mod ecdsa_impl {
+            use super::*;
+            use openssl::bn::{BigNum, BigNumContext};
+            use openssl::ec::{EcGroup, EcKey, EcPoint, PointConversionForm};
+            use openssl::ecdsa::EcdsaSig;
+            use openssl::nid::Nid;
+
+            pub struct EcdsaImpl(EcGroup);
+
+            impl EcdsaImpl {
+                pub fn new() -> Self {
+                    EcdsaImpl(EcGroup::from_curve_name(Nid::$nid).unwrap())
+                }
+                pub fn public_key(&self, sk: &PrivateKey) -> Result<PublicKey, CryptoError> {
+                    let s = self.secret(sk)?;
+                    let point = self.public_point(&s)?;
+                    let mut ctx = BigNumContext::new().map_err(general_error)?;
+                    let pk = point
+                        .to_bytes(&self.0, PointConversionForm::COMPRESSED, &mut ctx)
+                        .map_err(general_error)?;
+                    Ok(PublicKey(pk))
+                }
+                pub fn serialize(&self, data: &[u8], compressed: bool) -> Result<Vec<u8>, CryptoError> {
+                    let point = self.point(data)?;
+                    let form = if compressed {
+                        PointConversionForm::COMPRESSED
+                    } else {
+                        PointConversionForm::UNCOMPRESSED
+                    };
+                    let mut ctx = BigNumContext::new().map_err(general_error)?;
+                    point.to_bytes(&self.0, form, &mut ctx).map_err(general_error)
+                }
+                pub fn sign(&self, message: &[u8], sk: &PrivateKey) -> Result<Vec<u8>, CryptoError> {
+                    let s = self.secret(sk)?;
+                    let point = self.public_point(&s)?;
+                    let key = EcKey::from_private_components(&self.0, &s, &point)
+                        .map_err(|e| CryptoError::SigningError(e.to_string()))?;
+                    let sig = EcdsaSig::sign(&sha2::$digest::digest(message), &key)
+                        .map_err(|e| CryptoError::SigningError(e.to_string()))?;
+                    let mut signature = sig.r().to_vec_padded($size).map_err(general_error)?;
+                    signature.extend(sig.s().to_vec_padded($size).map_err(general_error)?);
+                    Ok(signature)
+                }
+                pub fn verify(
+                    &self,
+                    message: &[u8],
+                    signature: &[u8],
+                    pk: &PublicKey,
+                ) -> Result<bool, CryptoError> {
+                    let point = self.point(&pk[..])?;
+                    let key = EcKey::from_public_key(&self.0, &point)
+                        .map_err(|e| CryptoError::ParseError(e.to_string()))?;
+                    let r = BigNum::from_slice(&signature[..$size]).map_err(general_error)?;
+                    let s = BigNum::from_slice(&signature[$size..]).map_err(general_error)?;
+                    let sig = EcdsaSig::from_private_components(r, s).map_err(general_error)?;
+                    sig.verify(&sha2::$digest::digest(message), &key)
+                        .map_err(general_error)
+                }
+
+                fn secret(&self, sk: &PrivateKey) -> Result<BigNum, CryptoError> {
+                    let mut ctx = BigNumContext::new().map_err(general_error)?;
+                    let mut order = BigNum::new().map_err(general_error)?;
+                    self.0.order(&mut order, &mut ctx).map_err(general_error)?;
+                    let s = BigNum::from_slice(&sk[..]).map_err(general_error)?;
+                    if sk.len() != PRIVATE_KEY_SIZE || s.num_bits() == 0 || s >= order {
+                        return Err(CryptoError::ParseError(
+                            "Invalid private key provided".to_string(),
+                        ));
+                    }
+                    Ok(s)
+                }
+                fn public_point(&self, s: &BigNum) -> Result<EcPoint, CryptoError> {
+                    let ctx = BigNumContext::new().map_err(general_error)?;
+                    let mut point = EcPoint::new(&self.0).map_err(general_error)?;
+                    point.mul_generator(&self.0, s, &ctx).map_err(general_error)?;
+                    Ok(point)
+                }
+                fn point(&self, data: &[u8]) -> Result<EcPoint, CryptoError> {
+                    if data.len() != PUBLIC_KEY_SIZE && data.len() != PUBLIC_UNCOMPRESSED_KEY_SIZE {
+                        return Err(CryptoError::ParseError("Invalid key length".to_string()));
+                    }
+                    let mut ctx = BigNumContext::new().map_err(general_error)?;
+                    EcPoint::from_bytes(&self.0, data, &mut ctx)
+                        .map_err(|e| CryptoError::ParseError(e.to_string()))
+                }
+            }
+
+            fn general_error(e: openssl::error::ErrorStack) -> CryptoError {
+                CryptoError::GeneralError(e.to_string())
+            }
+        }
+
+        #[cfg(feature = "ecdsa_nist")]
+        mod ecdsa_impl {
+            use super::*;
+            use amcl::rand::RAND;
+            use amcl::$amcl_curve::{big::BIG, ecdh, ecp::ECP, rom::CURVE_ORDER};
+
+            pub struct EcdsaImpl;
+
+            impl EcdsaImpl {
+                pub fn new() -> Self {
+                    EcdsaImpl
+                }
+                pub fn public_key(&self, sk: &PrivateKey) -> Result<PublicKey, CryptoError> {
+                    let s = secret(sk)?;
+                    let mut pk = [0u8; PUBLIC_KEY_SIZE];
+                    ECP::generator().mul(&s).tobytes(&mut pk, true);
+                    Ok(PublicKey(pk.to_vec()))
+                }
+                pub fn serialize(&self, data: &[u8], compressed: bool) -> Result<Vec<u8>, CryptoError> {
+                    let point = point(data)?;
+                    let mut output = if compressed {
+                        vec![0u8; PUBLIC_KEY_SIZE]
+                    } else {
+                        vec![0u8; PUBLIC_UNCOMPRESSED_KEY_SIZE]
+                    };
+                    point.tobytes(&mut output, compressed);
+                    Ok(output)
+                }
+                pub fn sign(&self, message: &[u8], sk: &PrivateKey) -> Result<Vec<u8>, CryptoError> {
+                    secret(sk)?;
+                    let mut entropy = [0u8; 128];
+                    OsRng.fill_bytes(&mut entropy);
+                    let mut rng = RAND::new();
+                    rng.clean();
+                    rng.seed(entropy.len(), &entropy);
+                    entropy.zeroize();
+
+                    let mut r = [0u8; $size];
+                    let mut s = [0u8; $size];
+                    if ecdh::ecpsp_dsa($size, &mut rng, &sk[..], message, &mut r, &mut s) != 0 {
+                        return Err(CryptoError::SigningError(
+                            "Unable to sign the message".to_string(),
+                        ));
+                    }
+                    let mut signature = r.to_vec();
+                    signature.extend_from_slice(&s);
+                    Ok(signature)
+                }
+                pub fn verify(
+                    &self,
+                    message: &[u8],
+                    signature: &[u8],
+                    pk: &PublicKey,
+                ) -> Result<bool, CryptoError> {
+                    point(&pk[..])?;
+                    match ecdh::ecpvp_dsa(
+                        $size,
+                        &pk[..],
+                        message,
+                        &signature[..$size],
+                        &signature[$size..],
+                    ) {
+                        0 => Ok(true),
+                        ecdh::INVALID => Ok(false),
+                        _ => Err(CryptoError::ParseError(
+                            "Invalid public key provided".to_string(),
+                        )),
+                    }
+                }
+            }
+
+            fn secret(sk: &PrivateKey) -> Result<BIG, CryptoError> {
+                if sk.len() != PRIVATE_KEY_SIZE {
+                    return Err(CryptoError::ParseError(
+                        "Invalid private key length".to_string(),
+                    ));
+                }
+                let s = BIG::frombytes(&sk[..]);
+                if s.iszilch() || BIG::comp(&s, &BIG::new_ints(&CURVE_ORDER)) >= 0 {
+                    return Err(CryptoError::ParseError(
+                        "Invalid private key provided".to_string(),
+                    ));
+                }
+                Ok(s)
+            }
+
+            fn point(data: &[u8]) -> Result<ECP, CryptoError> {
+                let valid = match data.len() {
+                    PUBLIC_KEY_SIZE => data[0] == 0x02 || data[0] == 0x03,
+                    PUBLIC_UNCOMPRESSED_KEY_SIZE => data[0] == 0x04,
+                    _ => false,
+                };
+                if !valid {
+                    return Err(CryptoError::ParseError("Invalid key length".to_string()));
+                }
+                // Points that are not on the curve are infinity
+                let point = ECP::frombytes(data);
+                if point.is_infinity() {
+                    return Err(CryptoError::ParseError(
+                        "Invalid public key provided".to_string(),
+                    ));
+                }
+                Ok(point)
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+macro_rules! ecdsa_nist_tests_impl {
+    ($name:ident, $nid:ident, $digest:ident) => {
+        use openssl::bn::{BigNum, BigNumContext};
+        use openssl::ec::{EcGroup, EcKey, EcPoint};
+        use openssl::ecdsa::EcdsaSig;
+        use openssl::nid::Nid;
+        use signatures::Signer;
+
+        const MESSAGE_1: &[u8] = b"sample";
+
+        #[test]
+        fn load_keys() {
+            let scheme = $name::new();
+            let secret = PrivateKey(hex::decode(PRIVATE_KEY).unwrap());
+            let (p, s) = scheme
+                .keypair(Some(KeyGenOption::FromSecretKey(secret.clone())))
+                .unwrap();
+            assert_eq!(s, secret);
+            assert_eq!(p.len(), PUBLIC_KEY_SIZE);
+
+            let uncompressed = hex::decode(PUBLIC_KEY).unwrap();
+            assert_eq!(scheme.parse(&uncompressed).unwrap(), p);
+            assert_eq!(scheme.public_key_compressed(&p), p[..].to_vec());
+            assert_eq!(scheme.public_key_uncompressed(&p), uncompressed);
+            let p_u = PublicKey(uncompressed.clone());
+            assert_eq!(scheme.public_key_compressed(&p_u), p[..].to_vec());
+
+            // Not on the curve
+            let mut invalid = uncompressed.clone();
+            invalid[PUBLIC_UNCOMPRESSED_KEY_SIZE - 1] ^= 1;
+            assert!(scheme.parse(&invalid).is_err());
+            assert!(scheme.parse(&uncompressed[1..]).is_err());
+            assert!(scheme.parse(&uncompressed[..PUBLIC_KEY_SIZE]).is_err());
+        }
+
+        #[test]
+        fn invalid_private_keys() {
+            let scheme = $name::new();
+            let group = EcGroup::from_curve_name(Nid::$nid).unwrap();
+            let mut ctx = BigNumContext::new().unwrap();
+            let mut order = BigNum::new().unwrap();
+            group.order(&mut order, &mut ctx).unwrap();
+            for sk in [
+                vec![0u8; PRIVATE_KEY_SIZE],
+                order.to_vec(),
+                vec![1u8; PRIVATE_KEY_SIZE - 1],
+            ]
+            .iter()
+            {
+                let sk = PrivateKey(sk.clone());
+                assert!(scheme
+                    .keypair(Some(KeyGenOption::FromSecretKey(sk.clone())))
+                    .is_err());
+                assert!(scheme.sign(MESSAGE_1, &sk).is_err());
+            }
+            assert!(scheme
+                .keypair(Some(KeyGenOption::UseSeed(vec![1u8; 16])))
+                .is_err());
+        }
+
+        #[test]
+        fn verify() {
+            let scheme = $name::new();
+            let p = scheme.parse(&hex::decode(PUBLIC_KEY).unwrap()).unwrap();
+            let signature = hex::decode(SIGNATURE).unwrap();
+            assert!(scheme.verify(MESSAGE_1, &signature, &p).unwrap());
+            assert!(!scheme.verify(b"test", &signature, &p).unwrap());
+
+            let mut invalid = signature.clone();
+            invalid[PRIVATE_KEY_SIZE] ^= 1;
+            assert!(!scheme.verify(MESSAGE_1, &invalid, &p).unwrap());
+            for b in invalid[..PRIVATE_KEY_SIZE].iter_mut() {
+                *b = 0;
+            }
+            assert!(!scheme.verify(MESSAGE_1, &invalid, &p).unwrap());
+            assert!(scheme.verify(MESSAGE_1, &signature[1..], &p).is_err());
+        }
+
+        #[test]
+        fn sign() {
+            let scheme = $name::new();
+            let (p, s) = scheme.keypair(None).unwrap();
+            assert_eq!(s.len(), PRIVATE_KEY_SIZE);
+            let signature = scheme.sign(MESSAGE_1, &s).unwrap();
+            assert_eq!(signature.len(), SIGNATURE_SIZE);
+            assert!(scheme.verify(MESSAGE_1, &signature, &p).unwrap());
+
+            let signer = Signer::new(&scheme, &s);
+            assert_eq!(signer.get_public_key().unwrap(), p);
+            let signature = signer.sign(MESSAGE_1).unwrap();
+            assert!(scheme.verify(MESSAGE_1, &signature, &p).unwrap());
+
+            let seed = vec![3u8; 32];
+            let (p1, s1) = scheme
+                .keypair(Some(KeyGenOption::UseSeed(seed.clone())))
+                .unwrap();
+            let (p2, s2) = scheme.keypair(Some(KeyGenOption::UseSeed(seed))).unwrap();
+            assert_eq!(p1, p2);
+            assert_eq!(s1, s2);
+            assert_ne!(p1, p);
+        }
+
+        #[test]
+        fn openssl_compatibility() {
+            let scheme = $name::new();
+            let (p, s) = scheme.keypair(None).unwrap();
+            let group = EcGroup::from_curve_name(Nid::$nid).unwrap();
+            let mut ctx = BigNumContext::new().unwrap();
+            let point = EcPoint::from_bytes(&group, &p[..], &mut ctx).unwrap();
+            let key = EcKey::from_private_components(
+                &group,
+                &BigNum::from_slice(&s[..]).unwrap(),
+                &point,
+            )
+            .unwrap();
+            let digest = sha2::$digest::digest(MESSAGE_1);
+
+            // Signatures from this module verify with OpenSSL
+            let signature = scheme.sign(MESSAGE_1, &s).unwrap();
+            let sig = EcdsaSig::from_private_components(
+                BigNum::from_slice(&signature[..PRIVATE_KEY_SIZE]).unwrap(),
+                BigNum::from_slice(&signature[PRIVATE_KEY_SIZE..]).unwrap(),
+            )
+            .unwrap();
+            assert!(sig.verify(&digest, &key).unwrap());
+
+            // And the other way around
+            let sig = EcdsaSig::sign(&digest, &key).unwrap();
+            let mut signature = sig.r().to_vec_padded(PRIVATE_KEY_SIZE as i32).unwrap();
+            signature.extend(sig.s().to_vec_padded(PRIVATE_KEY_SIZE as i32).unwrap());
+            assert!(scheme.verify(MESSAGE_1, &signature, &p).unwrap());
+        }
+    };
+}
+
+pub mod p256 {
+    ecdsa_nist_impl!(
+        EcdsaP256Sha256,
+        "ECDSA_P256_SHA256",
+        32,
+        nist256,
+        X9_62_PRIME256V1,
+        Sha256
+    );
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        // RFC 6979 appendix A.2.5
+        const PRIVATE_KEY: &str =
+            "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+        const PUBLIC_KEY: &str = "0460fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb67903fe1008b8bc99a41ae9e95628bc64f2f1b20c2d7e9f5177a3c294d4462299";
+        const SIGNATURE: &str = "efd48b2aacb6a8fd1140dd9cd45e81d69d2c877b56aaf991c34d0ea84eaf3716f7cb1c942d657c41d436c7a1b6e29f65f3e900dbb9aff4064dc4ab2f843acda8";
+
+        ecdsa_nist_tests_impl!(EcdsaP256Sha256, X9_62_PRIME256V1, Sha256);
+    }
+}
+
+pub mod p384 {
+    ecdsa_nist_impl!(
+        EcdsaP384Sha384,
+        "ECDSA_P384_SHA384",
+        48,
+        nist384,
+        SECP384R1,
+        Sha384
+    );
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        // RFC 6979 appendix A.2.6
+        const PRIVATE_KEY: &str = "6b9d3dad2e1b8c1c05b19875b6659f4de23c3b667bf297ba9aa47740787137d896d5724e4c70a825f872c9ea60d2edf5";
+        const PUBLIC_KEY: &str = "04ec3a4e415b4e19a4568618029f427fa5da9a8bc4ae92e02e06aae5286b300c64def8f0ea9055866064a254515480bc138015d9b72d7d57244ea8ef9ac0c621896708a59367f9dfb9f54ca84b3f1c9db1288b231c3ae0d4fe7344fd2533264720";
+        const SIGNATURE: &str = "94edbb92a5ecb8aad4736e56c691916b3f88140666ce9fa73d64c4ea95ad133c81a648152e44acf96e36dd1e80fabe4699ef4aeb15f178cea1fe40db2603138f130e740a19624526203b6351d0a3a94fa329c145786e679e7b82c71a38628ac8";
+
+        ecdsa_nist_tests_impl!(EcdsaP384Sha384, SECP384R1, Sha384);
+    }
+}
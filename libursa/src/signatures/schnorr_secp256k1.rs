@@ -0,0 +1,825 @@
+//! Schnorr signatures on secp256k1 as specified in BIP-340
+//! (see <https://github.com/bitcoin/bips/blob/master/bip-0340.mediawiki>).
+//!
+//! Public keys are the 32 byte x-coordinate of a point with an even y-coordinate and
+//! signatures are `R.x || s`. Private keys are ordinary 32 byte secp256k1 scalars and
+//! are negated when signing if their public point has an odd y-coordinate.
+//! Keys can be aggregated with MuSig2 KeyAgg from BIP-327
+//! (see <https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki>).
+
+use super::*;
+use rand::{rngs::OsRng, RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use rustlibsecp256k1::curve::{
+    Affine, Field, Jacobian, Scalar, ECMULT_CONTEXT, ECMULT_GEN_CONTEXT,
+};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use CryptoError;
+
+pub const PRIVATE_KEY_SIZE: usize = 32;
+pub const PUBLIC_KEY_SIZE: usize = 32;
+pub const SIGNATURE_SIZE: usize = 64;
+pub const ALGORITHM_NAME: &str = "SCHNORR_SECP256K1_BIP340";
+
+/// The size of a public key with its parity byte as used by MuSig2
+pub const PUBLIC_COMPRESSED_KEY_SIZE: usize = 33;
+
+pub struct SchnorrSecp256k1;
+
+impl SchnorrSecp256k1 {
+    /// Sign `message` with the auxiliary random data `aux` instead of random bytes.
+    /// Signatures are deterministic for a given `aux` which is used by the test vectors.
+    pub fn sign_with_aux_rand(
+        &self,
+        message: &[u8],
+        sk: &PrivateKey,
+        aux: &[u8; 32],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let mut d = parse_private_key(sk)?;
+        let p = base_mul(&d);
+        if p.y.is_odd() {
+            d = -d;
+        }
+        let mut t = d.b32();
+        for (t_i, a_i) in t.iter_mut().zip(tagged_hash(b"BIP0340/aux", &[aux]).iter()) {
+            *t_i ^= a_i;
+        }
+        let mut k = scalar_from_hash(&tagged_hash(b"BIP0340/nonce", &[&t, &p.x.b32(), message]));
+        t.zeroize();
+        if k.is_zero() {
+            return Err(CryptoError::SigningError(
+                "Nonce is zero, try again".to_string(),
+            ));
+        }
+        let r = base_mul(&k);
+        if r.y.is_odd() {
+            k = -k;
+        }
+        let e = challenge(&r.x, &p.x, message);
+        let s = &k + &(&e * &d);
+        d.clear();
+        k.clear();
+
+        let mut signature = r.x.b32().to_vec();
+        signature.extend_from_slice(&s.b32());
+        Ok(signature)
+    }
+
+    /// Verify each signature in `signatures` on the message at the same index in `messages`
+    /// under the public key at the same index in `public_keys`.
+    /// Returns true only if every signature is valid.
+    ///
+    /// Combines all the equations with random coefficients and checks the sum
+    /// with one multi-scalar multiplication.
+    pub fn verify_batch(
+        &self,
+        messages: &[&[u8]],
+        signatures: &[&[u8]],
+        public_keys: &[PublicKey],
+    ) -> Result<bool, CryptoError> {
+        if messages.len() != signatures.len() || messages.len() != public_keys.len() {
+            return Err(CryptoError::GeneralError(
+                "Messages, signatures and public keys must have the same length".to_string(),
+            ));
+        }
+        let mut s_sum = Scalar::default();
+        let mut points = Vec::with_capacity(2 * messages.len());
+        let mut scalars = Vec::with_capacity(2 * messages.len());
+        for (i, ((message, signature), pk)) in messages
+            .iter()
+            .zip(signatures.iter())
+            .zip(public_keys.iter())
+            .enumerate()
+        {
+            let p = parse_public_key(pk)?;
+            let (r, s) = match parse_signature(signature)? {
+                Some(v) => v,
+                None => return Ok(false),
+            };
+            let mut lifted = Affine::default();
+            if !lifted.set_xo_var(&r, false) {
+                return Ok(false);
+            }
+            let e = challenge(&r, &p.x, message);
+            // The first coefficient can be one
+            let a = if i == 0 {
+                Scalar::from_int(1)
+            } else {
+                random_scalar()
+            };
+            // s * G = R + e * P summed with each equation multiplied by a
+            points.push(p);
+            scalars.push(&a * &e);
+            points.push(lifted);
+            s_sum += &a * &s;
+            scalars.push(a);
+        }
+        let mut point = Jacobian::default();
+        ECMULT_GEN_CONTEXT.ecmult_gen(&mut point, &s_sum);
+        let sum = point.add_var(&multi_mul(&points, &scalars).neg(), None);
+        Ok(sum.is_infinity())
+    }
+
+    /// Aggregate `public_keys` into a single x-only public key with MuSig2 KeyAgg.
+    /// The keys can be 33 byte compressed keys as in BIP-327 or 32 byte x-only keys
+    /// which are treated as having an even y-coordinate. The order of the keys matters.
+    pub fn aggregate_public_keys(
+        &self,
+        public_keys: &[PublicKey],
+    ) -> Result<PublicKey, CryptoError> {
+        let (_, q) = key_aggregation(public_keys)?;
+        Ok(PublicKey(q.x.b32().to_vec()))
+    }
+}
+
+impl SignatureScheme for SchnorrSecp256k1 {
+    fn new() -> Self {
+        Self
+    }
+    fn keypair(
+        &self,
+        option: Option<KeyGenOption>,
+    ) -> Result<(PublicKey, PrivateKey), CryptoError> {
+        let mut s = [0u8; PRIVATE_KEY_SIZE];
+        match option {
+            Some(mut o) => match o {
+                KeyGenOption::UseSeed(ref mut seed) => {
+                    if seed.len() < PRIVATE_KEY_SIZE {
+                        return Err(CryptoError::KeyGenError(
+                            "Seed must be at least 32 bytes".to_string(),
+                        ));
+                    }
+                    let mut rng =
+                        ChaChaRng::from_seed(*array_ref!(seed.as_slice(), 0, PRIVATE_KEY_SIZE));
+                    seed.zeroize();
+                    rng.fill_bytes(&mut s);
+                    let k = Sha256::digest(&s);
+                    s.copy_from_slice(k.as_slice());
+                }
+                KeyGenOption::FromSecretKey(ref sk) => {
+                    parse_private_key(sk)?;
+                    s.copy_from_slice(&sk[..]);
+                }
+            },
+            None => {
+                OsRng.fill_bytes(&mut s);
+                let k = Sha256::digest(&s);
+                s.copy_from_slice(k.as_slice());
+            }
+        };
+        let sk = PrivateKey(s.to_vec());
+        s.zeroize();
+        let p = base_mul(&parse_private_key(&sk)?);
+        Ok((PublicKey(p.x.b32().to_vec()), sk))
+    }
+    fn sign(&self, message: &[u8], sk: &PrivateKey) -> Result<Vec<u8>, CryptoError> {
+        let mut aux = [0u8; 32];
+        OsRng.fill_bytes(&mut aux);
+        self.sign_with_aux_rand(message, sk, &aux)
+    }
+    fn verify(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+        pk: &PublicKey,
+    ) -> Result<bool, CryptoError> {
+        let p = parse_public_key(pk)?;
+        let (r, s) = match parse_signature(signature)? {
+            Some(v) => v,
+            None => return Ok(false),
+        };
+        let e = challenge(&r, &p.x, message);
+        // s * G - e * P
+        let mut point = Jacobian::default();
+        ECMULT_CONTEXT.ecmult(&mut point, &Jacobian::from_ge(&p), &-e, &s);
+        let point = to_affine(&point);
+        Ok(!point.is_infinity() && !point.y.is_odd() && point.x == r)
+    }
+    fn signature_size() -> usize {
+        SIGNATURE_SIZE
+    }
+    fn private_key_size() -> usize {
+        PRIVATE_KEY_SIZE
+    }
+    fn public_key_size() -> usize {
+        PUBLIC_KEY_SIZE
+    }
+}
+
+/// SHA256(SHA256(tag) || SHA256(tag) || parts)
+pub(crate) fn tagged_hash(tag: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let tag = Sha256::digest(tag);
+    let mut hasher = Sha256::new();
+    hasher.input(tag);
+    hasher.input(tag);
+    for part in parts {
+        hasher.input(part);
+    }
+    let hash = hasher.result();
+    *array_ref!(hash.as_slice(), 0, 32)
+}
+
+fn scalar_from_hash(hash: &[u8; 32]) -> Scalar {
+    let mut s = Scalar::default();
+    // Reduces modulo the order
+    let _ = s.set_b32(hash);
+    s
+}
+
+fn challenge(r: &Field, p: &Field, message: &[u8]) -> Scalar {
+    scalar_from_hash(&tagged_hash(
+        b"BIP0340/challenge",
+        &[&r.b32(), &p.b32(), message],
+    ))
+}
+
+fn random_scalar() -> Scalar {
+    let mut bytes = [0u8; 32];
+    loop {
+        OsRng.fill_bytes(&mut bytes);
+        let mut s = Scalar::default();
+        if !bool::from(s.set_b32(&bytes)) && !s.is_zero() {
+            return s;
+        }
+    }
+}
+
+/// The window of the wNAF in `multi_mul`
+const WINDOW: usize = 5;
+
+/// Width `WINDOW` NAF of `a` from the least significant digit.
+/// Nonzero digits are odd and less than 2^(WINDOW - 1) in absolute value.
+fn wnaf(a: &Scalar) -> [i32; 257] {
+    let bytes = a.b32();
+    let bit = |i: usize| {
+        if i < 256 {
+            i32::from((bytes[31 - i / 8] >> (i % 8)) & 1)
+        } else {
+            0
+        }
+    };
+    let mut digits = [0i32; 257];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < digits.len() {
+        if bit(i) == carry {
+            i += 1;
+            continue;
+        }
+        let mut word = (0..WINDOW).fold(0, |w, j| w | (bit(i + j) << j)) + carry;
+        carry = (word >> (WINDOW - 1)) & 1;
+        word -= carry << WINDOW;
+        digits[i] = word;
+        i += WINDOW;
+    }
+    digits
+}
+
+/// Sum of `points[i] * scalars[i]` with Strauss' method so all the points share
+/// the same doublings
+fn multi_mul(points: &[Affine], scalars: &[Scalar]) -> Jacobian {
+    // The odd multiples P, 3P, 5P, ... of each point
+    let tables: Vec<Vec<Jacobian>> = points
+        .iter()
+        .map(|p| {
+            let p = Jacobian::from_ge(p);
+            let double = p.double_var(None);
+            let mut table = vec![p];
+            for i in 1..(1 << (WINDOW - 2)) {
+                let next = table[i - 1].add_var(&double, None);
+                table.push(next);
+            }
+            table
+        })
+        .collect();
+    let digits: Vec<[i32; 257]> = scalars.iter().map(wnaf).collect();
+    let mut r = Jacobian::default();
+    r.set_infinity();
+    for i in (0..257).rev() {
+        r = r.double_var(None);
+        for (table, d) in tables.iter().zip(digits.iter()) {
+            if d[i] > 0 {
+                r = r.add_var(&table[(d[i] / 2) as usize], None);
+            } else if d[i] < 0 {
+                r = r.add_var(&table[(-d[i] / 2) as usize].neg(), None);
+            }
+        }
+    }
+    r
+}
+
+fn to_affine(a: &Jacobian) -> Affine {
+    let mut out = Affine::default();
+    if a.is_infinity() {
+        out.infinity = true;
+        return out;
+    }
+    out.set_gej(a);
+    out.x.normalize();
+    out.y.normalize();
+    out
+}
+
+fn base_mul(a: &Scalar) -> Affine {
+    let mut r = Jacobian::default();
+    ECMULT_GEN_CONTEXT.ecmult_gen(&mut r, a);
+    to_affine(&r)
+}
+
+fn parse_private_key(sk: &PrivateKey) -> Result<Scalar, CryptoError> {
+    if sk.len() != PRIVATE_KEY_SIZE {
+        return Err(CryptoError::ParseError(
+            "Invalid private key length".to_string(),
+        ));
+    }
+    let mut d = Scalar::default();
+    if bool::from(d.set_b32(array_ref!(sk[..], 0, PRIVATE_KEY_SIZE))) || d.is_zero() {
+        return Err(CryptoError::ParseError(
+            "Invalid private key provided".to_string(),
+        ));
+    }
+    Ok(d)
+}
+
+/// Lift an x-only public key to the point with an even y-coordinate
+fn parse_public_key(pk: &PublicKey) -> Result<Affine, CryptoError> {
+    if pk.len() != PUBLIC_KEY_SIZE {
+        return Err(CryptoError::ParseError(
+            "Invalid public key length".to_string(),
+        ));
+    }
+    let mut x = Field::default();
+    let mut p = Affine::default();
+    if !x.set_b32(array_ref!(pk[..], 0, PUBLIC_KEY_SIZE)) || !p.set_xo_var(&x, false) {
+        return Err(CryptoError::ParseError(
+            "Invalid public key provided".to_string(),
+        ));
+    }
+    p.x.normalize();
+    p.y.normalize();
+    Ok(p)
+}
+
+/// Returns `None` if `r` or `s` are out of range
+fn parse_signature(signature: &[u8]) -> Result<Option<(Field, Scalar)>, CryptoError> {
+    if signature.len() != SIGNATURE_SIZE {
+        return Err(CryptoError::ParseError(
+            "Invalid signature length".to_string(),
+        ));
+    }
+    let mut r = Field::default();
+    let mut s = Scalar::default();
+    if !r.set_b32(array_ref!(signature, 0, 32))
+        || bool::from(s.set_b32(array_ref!(signature, 32, 32)))
+    {
+        return Ok(None);
+    }
+    Ok(Some((r, s)))
+}
+
+/// Returns the coefficient for each key and the aggregate key
+fn key_aggregation(public_keys: &[PublicKey]) -> Result<(Vec<Scalar>, Affine), CryptoError> {
+    if public_keys.is_empty() {
+        return Err(CryptoError::GeneralError(
+            "At least one public key is required".to_string(),
+        ));
+    }
+    let mut keys = Vec::with_capacity(public_keys.len());
+    let mut points = Vec::with_capacity(public_keys.len());
+    for pk in public_keys {
+        let key = match pk.len() {
+            PUBLIC_KEY_SIZE => {
+                let mut key = vec![0x02];
+                key.extend_from_slice(&pk[..]);
+                key
+            }
+            PUBLIC_COMPRESSED_KEY_SIZE => pk[..].to_vec(),
+            _ => {
+                return Err(CryptoError::ParseError(
+                    "Invalid public key length".to_string(),
+                ))
+            }
+        };
+        let point = rustlibsecp256k1::PublicKey::parse_compressed(array_ref!(
+            key,
+            0,
+            PUBLIC_COMPRESSED_KEY_SIZE
+        ))
+        .map_err(|e| CryptoError::ParseError(format!("{:?}", e)))?;
+        keys.push(key);
+        points.push(point.into());
+    }
+
+    let list = tagged_hash(
+        b"KeyAgg list",
+        &keys.iter().map(|k| k.as_slice()).collect::<Vec<_>>(),
+    );
+    // The first key different from the first gets a coefficient of one
+    let second = keys.iter().find(|k| *k != &keys[0]);
+    let coefficients: Vec<Scalar> = keys
+        .iter()
+        .map(|k| {
+            if Some(k) == second {
+                Scalar::from_int(1)
+            } else {
+                scalar_from_hash(&tagged_hash(b"KeyAgg coefficient", &[&list, k]))
+            }
+        })
+        .collect();
+
+    let mut q = Jacobian::default();
+    q.set_infinity();
+    for (p, a) in points.iter().zip(coefficients.iter()) {
+        let mut t = Jacobian::default();
+        ECMULT_CONTEXT.ecmult(&mut t, &Jacobian::from_ge(p), a, &Scalar::default());
+        q = q.add_var(&t, None);
+    }
+    let q = to_affine(&q);
+    if q.is_infinity() {
+        return Err(CryptoError::GeneralError(
+            "The aggregate public key is infinity".to_string(),
+        ));
+    }
+    Ok((coefficients, q))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const MESSAGE_1: &[u8] = b"This is a dummy message for use with tests";
+
+    // Signing test vectors 0 to 3 and 15 to 18 from the BIP: private key, public key,
+    // aux rand, message and signature
+    const SIGN_VECTORS: [(&str, &str, &str, &str, &str); 8] = [
+        (
+            "0000000000000000000000000000000000000000000000000000000000000003",
+            "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+        ),
+        (
+            "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "0000000000000000000000000000000000000000000000000000000000000001",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+        ),
+        (
+            "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+            "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+            "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+            "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+            "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1BAB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7",
+        ),
+        (
+            "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+            "25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+            "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3",
+        ),
+        (
+            "0340034003400340034003400340034003400340034003400340034003400340",
+            "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "",
+            "71535DB165ECD9FBBC046E5FFAEA61186BB6AD436732FCCC25291A55895464CF6069CE26BF03466228F19A3A62DB8A649F2D560FAC652827D1AF0574E427AB63",
+        ),
+        (
+            "0340034003400340034003400340034003400340034003400340034003400340",
+            "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "11",
+            "08A20A0AFEF64124649232E0693C583AB1B9934AE63B4C3511F3AE1134C6A303EA3173BFEA6683BD101FA5AA5DBC1996FE7CACFC5A577D33EC14564CEC2BACBF",
+        ),
+        (
+            "0340034003400340034003400340034003400340034003400340034003400340",
+            "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "0102030405060708090A0B0C0D0E0F1011",
+            "5130F39A4059B43BC7CAC09A19ECE52B5D8699D1A71E3C52DA9AFDB6B50AC370C4A482B77BF960F8681540E25B6771ECE1E5A37FD80E5A51897C5566A97EA5A5",
+        ),
+        (
+            "0340034003400340034003400340034003400340034003400340034003400340",
+            "778CAA53B4393AC467774D09497A87224BF9FAB6F6E68B23086497324D6FD117",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "99999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999999",
+            "403B12B0D8555A344175EA7EC746566303321E5DBFA8BE6F091635163ECA79A8585ED3E3170807E7C03B720FC54C7B23897FCBA0E9D0B4A06894CFD249F22367",
+        ),
+    ];
+
+    // Verification only test vectors 4 to 14 from the BIP: public key, message,
+    // signature and whether it verifies
+    const VERIFY_VECTORS: [(&str, &str, &str, bool); 11] = [
+        (
+            "D69C3509BB99E412E68B0FE8544E72837DFA30746D8BE2AA65975F29D22DC7B9",
+            "4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703",
+            "00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C6376AFB1548AF603B3EB45C9F8207DEE1060CB71C04E80F593060B07D28308D7F4",
+            true,
+        ),
+        // Public key not on the curve
+        (
+            "EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            false,
+        ),
+        // R has an odd y-coordinate
+        (
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A14602975563CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2",
+            false,
+        ),
+        // Negated message
+        (
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "1FA62E331EDBC21C394792D2AB1100A7B432B013DF3F6FF4F99FCB33E0E1515F28890B3EDB6E7189B630448B515CE4F8622A954CFE545735AAEA5134FCCDB2BD",
+            false,
+        ),
+        // Negated s
+        (
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769961764B3AA9B2FFCB6EF947B6887A226E8D7C93E00C5ED0C1834FF0D0C2E6DA6",
+            false,
+        ),
+        // sG - eP is the point at infinity and R.x is 0
+        (
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "0000000000000000000000000000000000000000000000000000000000000000123DDA8328AF9C23A94C1FEECFD123BA4FB73476F0D594DCB65C6425BD186051",
+            false,
+        ),
+        // sG - eP is the point at infinity and R.x is 1
+        (
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "00000000000000000000000000000000000000000000000000000000000000017615FBAF5AE28864013C099742DEADB4DBA87F11AC6754F93780D5A1837CF197",
+            false,
+        ),
+        // R.x is not on the curve
+        (
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "4A298DACAE57395A15D0795DDBFD1DCB564DA82B0F269BC70A74F8220429BA1D69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            false,
+        ),
+        // R.x is the field size
+        (
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F69E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            false,
+        ),
+        // s is the group order
+        (
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+            false,
+        ),
+        // Public key exceeds the field size
+        (
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC30",
+            "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+            "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E17776969E89B4C5564D00349106B8497785DD7D1D713A8AE82B32FA79D5F7FC407D39B",
+            false,
+        ),
+    ];
+
+    #[test]
+    fn schnorr_secp256k1_test_vectors() {
+        let scheme = SchnorrSecp256k1::new();
+        for (sk, pk, aux, msg, sig) in SIGN_VECTORS.iter() {
+            let sk = PrivateKey(hex::decode(sk).unwrap());
+            let pk = PublicKey(hex::decode(pk).unwrap());
+            let msg = hex::decode(msg).unwrap();
+            let sig = hex::decode(sig).unwrap();
+            let (p, _) = scheme
+                .keypair(Some(KeyGenOption::FromSecretKey(sk.clone())))
+                .unwrap();
+            assert_eq!(p, pk);
+            let aux = hex::decode(aux).unwrap();
+            let signature = scheme
+                .sign_with_aux_rand(&msg, &sk, array_ref!(aux, 0, 32))
+                .unwrap();
+            assert_eq!(signature, sig);
+            assert!(scheme.verify(&msg, &sig, &pk).unwrap());
+        }
+
+        for (pk, msg, sig, expected) in VERIFY_VECTORS.iter() {
+            let pk = PublicKey(hex::decode(pk).unwrap());
+            let msg = hex::decode(msg).unwrap();
+            let sig = hex::decode(sig).unwrap();
+            // Invalid public keys are errors
+            assert_eq!(scheme.verify(&msg, &sig, &pk).unwrap_or(false), *expected);
+        }
+    }
+
+    #[test]
+    fn schnorr_secp256k1_sign() {
+        let scheme = SchnorrSecp256k1::new();
+        let (p, s) = scheme.keypair(None).unwrap();
+        assert_eq!(p.len(), PUBLIC_KEY_SIZE);
+        assert_eq!(s.len(), PRIVATE_KEY_SIZE);
+
+        let sig = scheme.sign(MESSAGE_1, &s).unwrap();
+        assert_eq!(sig.len(), SIGNATURE_SIZE);
+        assert!(scheme.verify(MESSAGE_1, &sig, &p).unwrap());
+        assert!(!scheme.verify(b"Another message", &sig, &p).unwrap());
+        // Signing is randomized
+        assert_ne!(scheme.sign(MESSAGE_1, &s).unwrap(), sig);
+
+        let mut bad = sig.clone();
+        bad[40] ^= 1;
+        assert!(!scheme.verify(MESSAGE_1, &bad, &p).unwrap());
+        assert!(scheme.verify(MESSAGE_1, &sig[..63], &p).is_err());
+        // s is the group order
+        let mut bad = sig.clone();
+        bad[32..].copy_from_slice(
+            &hex::decode("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141")
+                .unwrap(),
+        );
+        assert!(!scheme.verify(MESSAGE_1, &bad, &p).unwrap());
+
+        let signer = Signer::new(&scheme, &s);
+        assert_eq!(signer.get_public_key().unwrap(), p);
+        let signed = signer.sign(MESSAGE_1).unwrap();
+        assert!(scheme.verify(MESSAGE_1, &signed, &p).unwrap());
+    }
+
+    #[test]
+    fn schnorr_secp256k1_load_keys() {
+        let scheme = SchnorrSecp256k1::new();
+        let seed = vec![7u8; 32];
+        let (p1, s1) = scheme
+            .keypair(Some(KeyGenOption::UseSeed(seed.clone())))
+            .unwrap();
+        let (p2, s2) = scheme.keypair(Some(KeyGenOption::UseSeed(seed))).unwrap();
+        assert_eq!(p1, p2);
+        assert_eq!(s1, s2);
+        let (p3, s3) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(s1.clone())))
+            .unwrap();
+        assert_eq!(p1, p3);
+        assert_eq!(s1, s3);
+        assert!(scheme
+            .keypair(Some(KeyGenOption::UseSeed(vec![7u8; 16])))
+            .is_err());
+        assert!(scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(PrivateKey(vec![0u8; 32]))))
+            .is_err());
+        assert!(scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(PrivateKey(vec![1u8; 31]))))
+            .is_err());
+    }
+
+    #[test]
+    fn schnorr_secp256k1_odd_public_key() {
+        // A key and its negation have the same public key and both sign for it
+        let scheme = SchnorrSecp256k1::new();
+        let (p, s) = scheme.keypair(None).unwrap();
+        let negated = PrivateKey((-parse_private_key(&s).unwrap()).b32().to_vec());
+        let (p_neg, _) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(negated.clone())))
+            .unwrap();
+        assert_eq!(p, p_neg);
+        for key in [s, negated].iter() {
+            let sig = scheme.sign(MESSAGE_1, key).unwrap();
+            assert!(scheme.verify(MESSAGE_1, &sig, &p).unwrap());
+        }
+    }
+
+    #[test]
+    fn schnorr_secp256k1_verify_batch() {
+        let scheme = SchnorrSecp256k1::new();
+        let messages: Vec<Vec<u8>> = (0..5u8).map(|i| vec![i; i as usize * 10]).collect();
+        let mut public_keys = Vec::new();
+        let mut signatures = Vec::new();
+        for message in &messages {
+            let (p, s) = scheme.keypair(None).unwrap();
+            signatures.push(scheme.sign(message, &s).unwrap());
+            public_keys.push(p);
+        }
+        let msgs: Vec<&[u8]> = messages.iter().map(|m| m.as_slice()).collect();
+        let sigs: Vec<&[u8]> = signatures.iter().map(|s| s.as_slice()).collect();
+        assert!(scheme.verify_batch(&msgs, &sigs, &public_keys).unwrap());
+        assert!(scheme.verify_batch(&[], &[], &[]).unwrap());
+        assert!(scheme
+            .verify_batch(&msgs[1..], &sigs, &public_keys)
+            .is_err());
+
+        // Swapped signatures
+        let mut swapped = sigs.clone();
+        swapped.swap(1, 2);
+        assert!(!scheme.verify_batch(&msgs, &swapped, &public_keys).unwrap());
+
+        let mut bad = signatures[3].clone();
+        bad[63] ^= 1;
+        let mut invalid = sigs.clone();
+        invalid[3] = &bad;
+        assert!(!scheme.verify_batch(&msgs, &invalid, &public_keys).unwrap());
+    }
+
+    #[test]
+    fn schnorr_secp256k1_multi_mul() {
+        let mut scalars: Vec<Scalar> = (0..6).map(|_| random_scalar()).collect();
+        scalars.push(Scalar::default());
+        scalars.push(Scalar::from_int(1));
+        scalars.push(-Scalar::from_int(1));
+        let points: Vec<Affine> = scalars.iter().map(|_| base_mul(&random_scalar())).collect();
+        let mut expected = Jacobian::default();
+        expected.set_infinity();
+        for (p, a) in points.iter().zip(scalars.iter()) {
+            let mut point = Jacobian::default();
+            ECMULT_CONTEXT.ecmult_const(&mut point, p, a);
+            expected = expected.add_var(&point, None);
+        }
+        let sum = multi_mul(&points, &scalars).add_var(&expected.neg(), None);
+        assert!(sum.is_infinity());
+        assert!(multi_mul(&[], &[]).is_infinity());
+    }
+
+    #[test]
+    fn schnorr_secp256k1_key_aggregation() {
+        // Test vectors from BIP-327
+        let keys: Vec<PublicKey> = [
+            "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+            "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+            "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+        ]
+        .iter()
+        .map(|k| PublicKey(hex::decode(k).unwrap()))
+        .collect();
+        let vectors = [
+            (
+                vec![0, 1, 2],
+                "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+            ),
+            (
+                vec![2, 1, 0],
+                "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B",
+            ),
+            (
+                vec![0, 0, 0],
+                "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935",
+            ),
+            (
+                vec![0, 0, 1, 1],
+                "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E",
+            ),
+        ];
+        let scheme = SchnorrSecp256k1::new();
+        for (indices, expected) in vectors.iter() {
+            let input: Vec<PublicKey> = indices.iter().map(|i| keys[*i].clone()).collect();
+            assert_eq!(
+                scheme.aggregate_public_keys(&input).unwrap(),
+                PublicKey(hex::decode(expected).unwrap())
+            );
+        }
+        // x-only keys are even
+        assert_eq!(
+            scheme
+                .aggregate_public_keys(&[PublicKey(keys[0][1..].to_vec())])
+                .unwrap(),
+            scheme.aggregate_public_keys(&keys[..1]).unwrap()
+        );
+        assert!(scheme.aggregate_public_keys(&[]).is_err());
+        assert!(scheme
+            .aggregate_public_keys(&[PublicKey(vec![2u8; 31])])
+            .is_err());
+    }
+
+    #[test]
+    fn schnorr_secp256k1_aggregate_signature() {
+        // The sum of the private keys weighted by their coefficients signs for the aggregate
+        let scheme = SchnorrSecp256k1::new();
+        let mut keys = Vec::new();
+        let mut secrets = Vec::new();
+        for _ in 0..3 {
+            let (_, s) = scheme.keypair(None).unwrap();
+            let d = parse_private_key(&s).unwrap();
+            let p = base_mul(&d);
+            let mut compressed = vec![if p.y.is_odd() { 0x03 } else { 0x02 }];
+            compressed.extend_from_slice(&p.x.b32());
+            keys.push(PublicKey(compressed));
+            secrets.push(d);
+        }
+        let (coefficients, _) = key_aggregation(&keys).unwrap();
+        let mut d = Scalar::default();
+        for (a, s) in coefficients.iter().zip(secrets.iter()) {
+            d = d + a.clone() * s.clone();
+        }
+        let pk = scheme.aggregate_public_keys(&keys).unwrap();
+        let sig = scheme
+            .sign(MESSAGE_1, &PrivateKey(d.b32().to_vec()))
+            .unwrap();
+        assert!(scheme.verify(MESSAGE_1, &sig, &pk).unwrap());
+    }
+}
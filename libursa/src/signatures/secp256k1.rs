@@ -1,5 +1,6 @@
 use super::*;
 use sha2::digest::generic_array::typenum::U32;
+use sha2::Digest;
 use CryptoError;
 
 use rand::rngs::OsRng;
@@ -12,6 +13,7 @@ pub const PUBLIC_KEY_SIZE: usize = 33;
 pub const PUBLIC_UNCOMPRESSED_KEY_SIZE: usize = 65;
 pub const SIGNATURE_POINT_SIZE: usize = 32;
 pub const SIGNATURE_SIZE: usize = 64;
+pub const RECOVERABLE_SIGNATURE_SIZE: usize = 65;
 pub const ALGORITHM_NAME: &str = "ECDSA_SECP256K1_SHA256";
 
 pub struct EcdsaSecp256k1Sha256(ecdsa_secp256k1::EcdsaSecp256k1Impl);
@@ -20,6 +22,67 @@ impl EcdsaSecp256k1Sha256 {
     pub fn normalize_s(&self, signature: &mut [u8]) -> Result<(), CryptoError> {
         self.0.normalize_s(signature)
     }
+
+    /// Sign a message and append the recovery id, giving `r || s || v` where
+    /// `v` is in the range 0..=3. The first `SIGNATURE_SIZE` bytes are a normal
+    /// compact signature that `verify` accepts.
+    pub fn sign_recoverable(
+        &self,
+        message: &[u8],
+        sk: &PrivateKey,
+    ) -> Result<Vec<u8>, CryptoError> {
+        self.sign_recoverable_with_digest::<sha2::Sha256>(message, sk)
+    }
+
+    /// Same as `sign_recoverable` but hashes the message with `D` instead of SHA-256,
+    /// e.g. `sha3::Keccak256` for Ethereum.
+    pub fn sign_recoverable_with_digest<D>(
+        &self,
+        message: &[u8],
+        sk: &PrivateKey,
+    ) -> Result<Vec<u8>, CryptoError>
+    where
+        D: Digest<OutputSize = U32>,
+    {
+        self.0.sign_recoverable::<D>(message, sk)
+    }
+
+    /// Recover the compressed public key of the signer from a recoverable signature.
+    /// Ethereum style recovery ids 27..=30 are accepted as well as 0..=3.
+    pub fn recover_public_key(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<PublicKey, CryptoError> {
+        self.recover_public_key_with_digest::<sha2::Sha256>(message, signature)
+    }
+
+    /// Same as `recover_public_key` for signatures from `sign_recoverable_with_digest`
+    /// where the message is hashed with `D`.
+    pub fn recover_public_key_with_digest<D>(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<PublicKey, CryptoError>
+    where
+        D: Digest<OutputSize = U32>,
+    {
+        self.0.recover_public_key::<D>(message, signature)
+    }
+}
+
+fn parse_recovery_id(signature: &[u8]) -> Result<u8, CryptoError> {
+    if signature.len() != RECOVERABLE_SIGNATURE_SIZE {
+        return Err(CryptoError::ParseError(format!(
+            "Recoverable signature must be {} bytes in length",
+            RECOVERABLE_SIGNATURE_SIZE
+        )));
+    }
+    match signature[SIGNATURE_SIZE] {
+        v @ 0..=3 => Ok(v),
+        v @ 27..=30 => Ok(v - 27),
+        _ => Err(CryptoError::ParseError("Invalid Recovery Id".to_string())),
+    }
 }
 
 impl SignatureScheme for EcdsaSecp256k1Sha256 {
@@ -184,6 +247,41 @@ mod ecdsa_secp256k1 {
             let sig = self.0.sign(&msg, &s);
             Ok(sig.serialize_compact().to_vec())
         }
+        pub fn sign_recoverable<D>(
+            &self,
+            message: &[u8],
+            sk: &PrivateKey,
+        ) -> Result<Vec<u8>, CryptoError>
+        where
+            D: Digest<OutputSize = U32>,
+        {
+            let h = D::digest(message);
+            let msg = libsecp256k1::Message::from_slice(h.as_slice())?;
+            let s = libsecp256k1::key::SecretKey::from_slice(&sk[..])?;
+            let (recid, sig) = self.0.sign_recoverable(&msg, &s).serialize_compact();
+            let mut output = sig.to_vec();
+            output.push(recid.to_i32() as u8);
+            Ok(output)
+        }
+        pub fn recover_public_key<D>(
+            &self,
+            message: &[u8],
+            signature: &[u8],
+        ) -> Result<PublicKey, CryptoError>
+        where
+            D: Digest<OutputSize = U32>,
+        {
+            let recid =
+                libsecp256k1::recovery::RecoveryId::from_i32(parse_recovery_id(signature)? as i32)?;
+            let h = D::digest(message);
+            let msg = libsecp256k1::Message::from_slice(h.as_slice())?;
+            let sig = libsecp256k1::recovery::RecoverableSignature::from_compact(
+                &signature[..SIGNATURE_SIZE],
+                recid,
+            )?;
+            let pk = self.0.recover(&msg, &sig)?;
+            Ok(PublicKey(pk.serialize().to_vec()))
+        }
         pub fn verify<D>(
             &self,
             message: &[u8],
@@ -301,6 +399,42 @@ mod ecdsa_secp256k1 {
             let (sig, _) = rustlibsecp256k1::sign(&msg, &secret);
             Ok(sig.serialize().to_vec())
         }
+        pub fn sign_recoverable<D>(
+            &self,
+            message: &[u8],
+            sk: &PrivateKey,
+        ) -> Result<Vec<u8>, CryptoError>
+        where
+            D: Digest<OutputSize = U32>,
+        {
+            let h = D::digest(message);
+            let msg =
+                rustlibsecp256k1::Message::parse(array_ref!(h.as_slice(), 0, SIGNATURE_POINT_SIZE));
+            let secret = rustlibsecp256k1::SecretKey::parse_slice(&sk[..])
+                .map_err(|e| CryptoError::SigningError(format!("{:?}", e)))?;
+            let (sig, recid) = rustlibsecp256k1::sign(&msg, &secret);
+            let mut output = sig.serialize().to_vec();
+            output.push(recid.serialize());
+            Ok(output)
+        }
+        pub fn recover_public_key<D>(
+            &self,
+            message: &[u8],
+            signature: &[u8],
+        ) -> Result<PublicKey, CryptoError>
+        where
+            D: Digest<OutputSize = U32>,
+        {
+            let recid = rustlibsecp256k1::RecoveryId::parse(parse_recovery_id(signature)?)
+                .map_err(|e| CryptoError::ParseError(format!("{:?}", e)))?;
+            let h = D::digest(message);
+            let msg =
+                rustlibsecp256k1::Message::parse(array_ref!(h.as_slice(), 0, SIGNATURE_POINT_SIZE));
+            let sig = rustlibsecp256k1::Signature::parse(array_ref!(signature, 0, SIGNATURE_SIZE));
+            let pk = rustlibsecp256k1::recover(&msg, &sig, &recid)
+                .map_err(|e| CryptoError::SigningError(format!("{:?}", e)))?;
+            Ok(PublicKey(pk.serialize_compressed().to_vec()))
+        }
         pub fn verify<D>(
             &self,
             message: &[u8],
@@ -344,6 +478,7 @@ mod test {
     use openssl::ecdsa::EcdsaSig;
     use openssl::nid::Nid;
     use sha2::Digest;
+    use sha3::Keccak256;
 
     const MESSAGE_1: &[u8] = b"This is a dummy message for use with tests";
     const SIGNATURE_1: &str = "ae46d3fec8e2eb95ebeaf95f7f096ec4bf517f5ef898e4379651f8af8e209ed75f3c47156445d6687a5f817fb3e188e2a76df653b330df859ec47579c8c409be";
@@ -530,6 +665,81 @@ mod test {
         }
     }
 
+    #[test]
+    fn secp256k1_recoverable() {
+        let scheme = EcdsaSecp256k1Sha256::new();
+        let secret = PrivateKey(hex::decode(PRIVATE_KEY).unwrap());
+        let (p, s) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(secret)))
+            .unwrap();
+
+        let sig = scheme.sign_recoverable(MESSAGE_1, &s).unwrap();
+        assert_eq!(sig.len(), RECOVERABLE_SIGNATURE_SIZE);
+        assert!(sig[SIGNATURE_SIZE] < 4);
+        assert!(scheme
+            .verify(MESSAGE_1, &sig[..SIGNATURE_SIZE], &p)
+            .unwrap());
+        assert_eq!(scheme.recover_public_key(MESSAGE_1, &sig).unwrap(), p);
+
+        let mut eth_sig = sig.clone();
+        eth_sig[SIGNATURE_SIZE] += 27;
+        assert_eq!(scheme.recover_public_key(MESSAGE_1, &eth_sig).unwrap(), p);
+
+        //Check that recoverable signatures from libsecp256k1 can be recovered here
+        let context = libsecp256k1::Secp256k1::new();
+        let sk = libsecp256k1::key::SecretKey::from_slice(&s[..]).unwrap();
+        let h = sha2::Sha256::digest(&MESSAGE_1);
+        let msg = libsecp256k1::Message::from_slice(h.as_slice()).unwrap();
+        let (recid, compact) = context.sign_recoverable(&msg, &sk).serialize_compact();
+        assert_eq!(compact[..], sig[..SIGNATURE_SIZE]);
+        assert_eq!(recid.to_i32() as u8, sig[SIGNATURE_SIZE]);
+        let mut sig_1 = compact.to_vec();
+        sig_1.push(recid.to_i32() as u8);
+        assert_eq!(scheme.recover_public_key(MESSAGE_1, &sig_1).unwrap(), p);
+
+        //A different message recovers a different key or fails
+        match scheme.recover_public_key(b"Another message", &sig) {
+            Ok(other) => assert_ne!(other, p),
+            Err(_) => {}
+        }
+
+        let mut bad = sig.clone();
+        bad[SIGNATURE_SIZE] = 4;
+        assert!(scheme.recover_public_key(MESSAGE_1, &bad).is_err());
+        assert!(scheme
+            .recover_public_key(MESSAGE_1, &sig[..SIGNATURE_SIZE])
+            .is_err());
+    }
+
+    #[test]
+    fn secp256k1_recoverable_keccak256() {
+        // The transaction from the EIP-155 example, v = 37 is recovery id 0 on chain 1
+        let scheme = EcdsaSecp256k1Sha256::new();
+        let sk = PrivateKey(vec![0x46; PRIVATE_KEY_SIZE]);
+        let message = hex::decode("ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080").unwrap();
+        let expected = "28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa63627667cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d8300";
+        let sig = scheme
+            .sign_recoverable_with_digest::<Keccak256>(&message, &sk)
+            .unwrap();
+        assert_eq!(hex::encode(&sig), expected);
+
+        let mut eth_sig = sig.clone();
+        eth_sig[SIGNATURE_SIZE] += 27;
+        let pk = scheme
+            .recover_public_key_with_digest::<Keccak256>(&message, &eth_sig)
+            .unwrap();
+        let (p, _) = scheme
+            .keypair(Some(KeyGenOption::FromSecretKey(sk)))
+            .unwrap();
+        assert_eq!(pk, p);
+        // The Ethereum address is the end of the hash of the uncompressed public key
+        let address = Keccak256::digest(&scheme.public_key_uncompressed(&pk)[1..]);
+        assert_eq!(
+            hex::encode(&address[12..]),
+            "9d8a62f656a8d1615c1294fd71e9cfb3e4855a4f"
+        );
+    }
+
     #[test]
     fn secp256k1_publickey_compression() {
         let scheme = EcdsaSecp256k1Sha256::new();
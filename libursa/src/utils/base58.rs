@@ -0,0 +1,127 @@
+//! Base58 with the Bitcoin alphabet as used by Base58Check and the `z` multibase prefix
+
+#[cfg(feature = "sha2")]
+use sha2::{Digest, Sha256};
+use zeroize::Zeroize;
+
+use {CryptoError, CryptoResult};
+
+const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+pub fn encode(data: &[u8]) -> String {
+    // Repeated division of the big-endian number by 58, least significant digit first
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for byte in data {
+        let mut carry = *byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+    let zeros = data.iter().take_while(|b| **b == 0).count();
+    let mut output = String::with_capacity(zeros + digits.len());
+    output.push_str(&"1".repeat(zeros));
+    output.extend(digits.iter().rev().map(|d| ALPHABET[*d as usize] as char));
+    digits.zeroize();
+    output
+}
+
+pub fn decode(encoded: &str) -> CryptoResult<Vec<u8>> {
+    let mut bytes: Vec<u8> = Vec::with_capacity(encoded.len());
+    for c in encoded.bytes() {
+        let mut carry = ALPHABET
+            .iter()
+            .position(|a| *a == c)
+            .ok_or_else(|| CryptoError::ParseError("Invalid Base58 character".to_string()))?
+            as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    let zeros = encoded.bytes().take_while(|c| *c == b'1').count();
+    bytes.resize(bytes.len() + zeros, 0);
+    bytes.reverse();
+    Ok(bytes)
+}
+
+/// Base58Check, `payload` followed by the first 4 bytes of its double SHA-256
+#[cfg(feature = "sha2")]
+pub fn encode_check(payload: &[u8]) -> String {
+    let checksum = Sha256::digest(Sha256::digest(payload).as_slice());
+    let mut data = payload.to_vec();
+    data.extend_from_slice(&checksum[..4]);
+    let encoded = encode(&data);
+    data.zeroize();
+    encoded
+}
+
+/// Decode Base58Check and return the payload without the checksum
+#[cfg(feature = "sha2")]
+pub fn decode_check(encoded: &str) -> CryptoResult<Vec<u8>> {
+    let mut bytes = decode(encoded)?;
+    if bytes.len() < 4 {
+        return Err(CryptoError::ParseError(
+            "Base58Check data is too short".to_string(),
+        ));
+    }
+    let payload_len = bytes.len() - 4;
+    let checksum = Sha256::digest(Sha256::digest(&bytes[..payload_len]).as_slice());
+    if checksum[..4] != bytes[payload_len..] {
+        bytes.zeroize();
+        return Err(CryptoError::ParseError(
+            "Invalid Base58Check checksum".to_string(),
+        ));
+    }
+    bytes.truncate(payload_len);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base58_vectors() {
+        let vectors = [
+            ("", ""),
+            ("61", "2g"),
+            ("626262", "a3gV"),
+            ("636363", "aPEr"),
+            ("00000000000000000000", "1111111111"),
+            (
+                "00eb15231dfceb60925886b67d065299925915aeb172c06647",
+                "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+            ),
+            ("516b6fcd0f", "ABnLTmg"),
+            ("572e4794", "3EFU7m"),
+        ];
+        for (data, encoded) in vectors.iter() {
+            let data = hex::decode(data).unwrap();
+            assert_eq!(encode(&data), *encoded);
+            assert_eq!(decode(encoded).unwrap(), data);
+        }
+        assert!(decode("0OIl").is_err());
+    }
+
+    #[cfg(feature = "sha2")]
+    #[test]
+    fn base58check() {
+        let payload = hex::decode("0062e907b15cbf27d5425399ebf6f0fb50ebb88f18").unwrap();
+        let encoded = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+        assert_eq!(encode_check(&payload), encoded);
+        assert_eq!(decode_check(encoded).unwrap(), payload);
+        assert!(decode_check("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb").is_err());
+        assert!(decode_check("2g").is_err());
+    }
+}
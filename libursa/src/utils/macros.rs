@@ -18,17 +18,30 @@ macro_rules! array_copy {
 }
 
 #[cfg(any(
+    feature = "bip32",
+    feature = "bip39",
     feature = "bls_bls12381",
     feature = "ed25519",
     feature = "ed25519_asm",
+    feature = "ed448",
+    feature = "ecdh_nist",
+    feature = "ecdh_nist_native",
     feature = "ecdh_secp256k1",
     feature = "ecdh_secp256k1_native",
     feature = "ecdh_secp256k1_asm",
+    feature = "ecdsa_nist",
+    feature = "ecdsa_nist_native",
     feature = "ecdsa_secp256k1",
     feature = "ecdsa_secp256k1_native",
     feature = "ecdsa_secp256k1_asm",
+    feature = "eip2333",
+    feature = "frost",
+    feature = "key_encoding",
+    feature = "schnorr_secp256k1",
+    feature = "slip10",
     feature = "x25519",
     feature = "x25519_asm",
+    feature = "x448",
     feature = "wasm"
 ))]
 macro_rules! impl_bytearray {
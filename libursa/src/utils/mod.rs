@@ -1,3 +1,5 @@
+#[cfg(any(feature = "bip32", feature = "key_encoding"))]
+pub mod base58;
 #[cfg(feature = "ffi")]
 #[macro_use]
 pub mod ctypes;
@@ -5,12 +5,16 @@ pub mod bls;
 
 #[cfg(feature = "ed25519")]
 pub mod ed25519;
+#[cfg(feature = "ed448")]
+pub mod ed448;
 #[cfg(feature = "encryption")]
 pub mod encryption;
 #[cfg(feature = "ecdsa_secp256k1")]
 pub mod secp256k1;
 #[cfg(feature = "x25519")]
 pub mod x25519;
+#[cfg(feature = "x448")]
+pub mod x448;
 
 use keys::{PrivateKey, PublicKey, SessionKey};
 
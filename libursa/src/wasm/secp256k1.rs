@@ -1,5 +1,6 @@
 use kex::{secp256k1::EcdhSecp256k1Sha256 as EcdhSecp256k1Sha256Impl, KeyExchangeScheme};
 use keys::{KeyGenOption, PrivateKey, PublicKey};
+use sha3::Keccak256;
 use signatures::{
     secp256k1::EcdsaSecp256k1Sha256 as EcdsaSecp256k1Sha256Impl, EcdsaPublicKeyHandler,
     SignatureScheme,
@@ -58,6 +59,44 @@ impl EcdsaSecp256k1Sha256 {
         Ok(maperr!(self.0.verify(message, signature, &pk)))
     }
 
+    pub fn signRecoverable(&self, message: &[u8], sk: &WasmPrivateKey) -> Result<Vec<u8>, JsValue> {
+        let sk = PrivateKey::from(sk);
+        let sig = maperr!(self.0.sign_recoverable(message, &sk));
+        Ok(sig)
+    }
+
+    pub fn recoverPublicKey(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<WasmPublicKey, JsValue> {
+        let pk = maperr!(self.0.recover_public_key(message, signature));
+        Ok(WasmPublicKey::from(&pk))
+    }
+
+    pub fn signRecoverableKeccak256(
+        &self,
+        message: &[u8],
+        sk: &WasmPrivateKey,
+    ) -> Result<Vec<u8>, JsValue> {
+        let sk = PrivateKey::from(sk);
+        let sig = maperr!(self
+            .0
+            .sign_recoverable_with_digest::<Keccak256>(message, &sk));
+        Ok(sig)
+    }
+
+    pub fn recoverPublicKeyKeccak256(
+        &self,
+        message: &[u8],
+        signature: &[u8],
+    ) -> Result<WasmPublicKey, JsValue> {
+        let pk = maperr!(self
+            .0
+            .recover_public_key_with_digest::<Keccak256>(message, signature));
+        Ok(WasmPublicKey::from(&pk))
+    }
+
     pub fn normalizeS(&self, signature: &mut [u8]) -> Result<(), JsValue> {
         maperr!(self.0.normalize_s(signature));
         Ok(())
@@ -0,0 +1,55 @@
+use kex::{x448::X448Sha512 as X448Sha512Impl, KeyExchangeScheme};
+use keys::{KeyGenOption, PrivateKey, PublicKey};
+
+use wasm_bindgen::prelude::*;
+
+use super::{KeyPair, WasmPrivateKey, WasmPublicKey, WasmSessionKey};
+
+#[wasm_bindgen]
+pub struct X448Sha512;
+
+#[wasm_bindgen]
+#[allow(non_snake_case)]
+impl X448Sha512 {
+    pub fn new() -> Self {
+        Self
+    }
+
+    pub fn keypair(&self) -> Result<KeyPair, JsValue> {
+        let scheme = X448Sha512Impl {};
+        let (pk, sk) = maperr!(scheme.keypair(None));
+        Ok(KeyPair {
+            pk: pk.into(),
+            sk: sk.into(),
+        })
+    }
+
+    pub fn keyPairFromSeed(&self, seed: &[u8]) -> Result<KeyPair, JsValue> {
+        let scheme = X448Sha512Impl {};
+        let (pk, sk) = maperr!(scheme.keypair(Some(KeyGenOption::UseSeed(seed.to_vec()))));
+        Ok(KeyPair {
+            pk: pk.into(),
+            sk: sk.into(),
+        })
+    }
+
+    pub fn getPublicKey(&self, sk: &WasmPrivateKey) -> Result<WasmPublicKey, JsValue> {
+        let sk = sk.into();
+        let scheme = X448Sha512Impl {};
+        let (pk, _) = maperr!(scheme.keypair(Some(KeyGenOption::FromSecretKey(sk))));
+        Ok(pk.into())
+    }
+
+    pub fn computeSharedSecret(
+        &self,
+        sk: &WasmPrivateKey,
+        pk: &WasmPublicKey,
+    ) -> Result<WasmSessionKey, JsValue> {
+        let sk = PrivateKey::from(sk);
+        let pk = PublicKey::from(pk);
+        let scheme = X448Sha512Impl {};
+        let secret = maperr!(scheme.compute_shared_secret(&sk, &pk));
+        let secret = WasmSessionKey::from(secret);
+        Ok(secret)
+    }
+}
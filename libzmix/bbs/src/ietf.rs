@@ -0,0 +1,1235 @@
+//! The BBS signature scheme as specified by the IRTF CFRG draft
+//! <https://datatracker.ietf.org/doc/draft-irtf-cfrg-bbs-signatures/>.
+//!
+//! This differs from the BBS+ signatures in the rest of this crate. The signature has no `s`
+//! value and the generators come from the ciphersuite instead of the public key, so a public
+//! key is only the commitment `W` to the secret key. Messages are octet strings that the
+//! ciphersuite maps to scalars. Signatures bind a header from the signer and proofs bind a
+//! presentation header from the prover. Keys, signatures and proofs use the encodings from
+//! the draft so they can be exchanged with other implementations.
+
+use crate::errors::prelude::*;
+use crate::keys::{DeterministicPublicKey, SecretKey};
+use crate::{
+    multi_scalar_mul_const_time_g1, multi_scalar_mul_var_time_g1, GeneratorG1, SignatureMessage,
+    FR_COMPRESSED_SIZE, FR_UNCOMPRESSED_SIZE, G1_COMPRESSED_SIZE,
+};
+use blake2::digest::generic_array::GenericArray;
+use ff_zeroize::{Field, PrimeField, PrimeFieldRepr, SqrtField};
+use pairing_plus::{
+    bls12_381::{Bls12, Fq, Fq12, FqRepr, Fr, G1, G2},
+    hash_to_curve::HashToCurve,
+    hash_to_field::{BaseFromRO, ExpandMsg, ExpandMsgXmd, ExpandMsgXof},
+    serdes::SerDes,
+    signum::Signum0,
+    CurveAffine, CurveProjective, Engine,
+};
+use rand::prelude::*;
+use sha2::Sha256;
+use sha3::Shake256;
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::Cursor;
+
+/// The number of bytes in a signature
+pub const SIGNATURE_SIZE: usize = G1_COMPRESSED_SIZE + FR_COMPRESSED_SIZE;
+/// The number of bytes in a proof that hides no messages.
+/// Each hidden message adds `FR_COMPRESSED_SIZE` bytes.
+pub const PROOF_MIN_SIZE: usize = 3 * G1_COMPRESSED_SIZE + 4 * FR_COMPRESSED_SIZE;
+
+/// Bytes of `expand_message` output for each scalar or generator seed
+const EXPAND_LEN: usize = FR_UNCOMPRESSED_SIZE;
+
+/// Bytes of `expand_message` output for each element of Fq in hash_to_curve
+const HASH_TO_FIELD_LEN: usize = 64;
+
+/// A' of the curve 11-isogenous to G1 used by the simplified SWU map
+const ISO_A: FqRepr = FqRepr([
+    0x5cf4_2808_2d58_4c1d,
+    0x9893_6f8d_a0e0_f97f,
+    0xd8e8_981a_efd8_81ac,
+    0xb0ea_9853_83ee_66a8,
+    0x3d69_3a02_c96d_4982,
+    0x0014_4698_a3b8_e943,
+]);
+
+/// B' of the curve 11-isogenous to G1 used by the simplified SWU map
+const ISO_B: FqRepr = FqRepr([
+    0xd1cc_48e9_8e17_2be0,
+    0x5a23_215a_316c_eaa5,
+    0xa0b9_c14f_cef3_5ef5,
+    0x2016_c1f0_f24f_4070,
+    0x018b_12e8_753e_ee3b,
+    0x12e2_908d_1168_8030,
+]);
+
+/// Z of the simplified SWU map for G1
+const SSWU_Z: u64 = 11;
+
+/// The ciphersuites defined in the draft
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Ciphersuite {
+    /// BLS12-381-SHA-256 using `expand_message_xmd` with SHA-256
+    Bls12381Sha256,
+    /// BLS12-381-SHAKE-256 using `expand_message_xof` with SHAKE-256
+    Bls12381Shake256,
+}
+
+impl Ciphersuite {
+    /// The ciphersuite identifier that prefixes every domain separation tag
+    pub fn id(self) -> &'static [u8] {
+        match self {
+            Ciphersuite::Bls12381Sha256 => b"BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_",
+            Ciphersuite::Bls12381Shake256 => b"BBS_BLS12381G1_XOF:SHAKE-256_SSWU_RO_",
+        }
+    }
+
+    /// Create a key pair from at least 32 bytes of secret `key_material`.
+    /// `key_info` is optional context like a key identifier and `key_dst` defaults to
+    /// the interface id followed by `KEYGEN_DST_`.
+    pub fn keygen(
+        self,
+        key_material: &[u8],
+        key_info: &[u8],
+        key_dst: Option<&[u8]>,
+    ) -> Result<(DeterministicPublicKey, SecretKey), BBSError> {
+        if key_material.len() < 32 || key_info.len() > u16::MAX as usize {
+            return Err(BBSErrorKind::KeyGenError.into());
+        }
+        let default_dst = self.api_dst(b"KEYGEN_DST_");
+        let mut derive_input = key_material.to_vec();
+        derive_input.extend_from_slice(&(key_info.len() as u16).to_be_bytes());
+        derive_input.extend_from_slice(key_info);
+        let sk = self.hash_to_scalar(&derive_input, key_dst.unwrap_or(&default_dst));
+        if sk.is_zero() {
+            return Err(BBSErrorKind::KeyGenError.into());
+        }
+        let mut w = G2::one();
+        w.mul_assign(sk);
+        Ok((DeterministicPublicKey(w), SecretKey(sk)))
+    }
+
+    /// Sign `messages` and `header`. `pk` must be the public key for `sk`.
+    pub fn sign<M: AsRef<[u8]>>(
+        self,
+        sk: &SecretKey,
+        pk: &DeterministicPublicKey,
+        header: &[u8],
+        messages: &[M],
+    ) -> Result<Signature, BBSError> {
+        let generators = self.create_generators(messages.len() + 1);
+        let scalars = self.messages_to_scalars(messages);
+        let domain = self.calculate_domain(pk, &generators, header);
+
+        let mut e_input = Vec::with_capacity(FR_COMPRESSED_SIZE * (scalars.len() + 2));
+        push_scalar(&mut e_input, &sk.0);
+        for m in &scalars {
+            push_scalar(&mut e_input, &m.0);
+        }
+        push_scalar(&mut e_input, &domain);
+        let e = self.hash_to_scalar(&e_input, &self.api_dst(b"H2S_"));
+
+        let mut exp = sk.0;
+        exp.add_assign(&e);
+        let exp = exp
+            .inverse()
+            .ok_or_else(|| BBSError::from_kind(BBSErrorKind::KeyGenError))?;
+        let mut a = self.compute_b(&generators, &domain, &scalars, true);
+        a.mul_assign(exp);
+        Ok(Signature { a, e })
+    }
+
+    /// Check `signature` is valid for `messages` and `header`
+    pub fn verify<M: AsRef<[u8]>>(
+        self,
+        pk: &DeterministicPublicKey,
+        signature: &Signature,
+        header: &[u8],
+        messages: &[M],
+    ) -> Result<bool, BBSError> {
+        check_public_key(pk)?;
+        let generators = self.create_generators(messages.len() + 1);
+        let scalars = self.messages_to_scalars(messages);
+        let domain = self.calculate_domain(pk, &generators, header);
+        let mut b = self.compute_b(&generators, &domain, &scalars, false);
+        b.negate();
+
+        // e(A, W + BP2 * e) * e(-B, BP2) == 1
+        let mut w_e = G2::one();
+        w_e.mul_assign(signature.e);
+        w_e.add_assign(&pk.0);
+        Ok(pairing_is_one(&signature.a, &w_e, &b))
+    }
+
+    /// Create a proof of knowledge of `signature` that reveals the messages in
+    /// `disclosed_indices` and binds the presentation header `ph`
+    pub fn proof_gen<M: AsRef<[u8]>>(
+        self,
+        pk: &DeterministicPublicKey,
+        signature: &Signature,
+        header: &[u8],
+        ph: &[u8],
+        messages: &[M],
+        disclosed_indices: &[usize],
+    ) -> Result<Proof, BBSError> {
+        self.proof_gen_with_scalars(
+            pk,
+            signature,
+            header,
+            ph,
+            messages,
+            disclosed_indices,
+            |count| {
+                let mut rng = thread_rng();
+                (0..count).map(|_| Fr::random(&mut rng)).collect()
+            },
+        )
+    }
+
+    /// `proof_gen` with the random scalars `r1, r2, e~, r1~, r3~, m~_1, ..., m~_U` in the
+    /// order the draft draws them, so its fixtures can be reproduced with mocked scalars
+    #[allow(clippy::too_many_arguments)]
+    fn proof_gen_with_scalars<M: AsRef<[u8]>, F: FnOnce(usize) -> Vec<Fr>>(
+        self,
+        pk: &DeterministicPublicKey,
+        signature: &Signature,
+        header: &[u8],
+        ph: &[u8],
+        messages: &[M],
+        disclosed_indices: &[usize],
+        random_scalars: F,
+    ) -> Result<Proof, BBSError> {
+        let disclosed: BTreeSet<usize> = disclosed_indices.iter().copied().collect();
+        if disclosed.iter().any(|i| *i >= messages.len()) {
+            return Err(BBSErrorKind::GeneralError {
+                msg: "Disclosed index is larger than the number of messages".to_string(),
+            }
+            .into());
+        }
+        let generators = self.create_generators(messages.len() + 1);
+        let scalars = self.messages_to_scalars(messages);
+        let domain = self.calculate_domain(pk, &generators, header);
+        let hidden: Vec<usize> = (0..messages.len())
+            .filter(|i| !disclosed.contains(i))
+            .collect();
+
+        let random_scalars = random_scalars(5 + hidden.len());
+        let (r1, r2, e_tilde, r1_tilde, r3_tilde) = (
+            random_scalars[0],
+            random_scalars[1],
+            random_scalars[2],
+            random_scalars[3],
+            random_scalars[4],
+        );
+        let m_tilde = &random_scalars[5..];
+
+        // D = B * r2, Abar = A * (r1 * r2), Bbar = D * r1 - Abar * e
+        let b = self.compute_b(&generators, &domain, &scalars, true);
+        let d = multi_scalar_mul_const_time_g1([b], [r2]);
+        let mut r1_r2 = r1;
+        r1_r2.mul_assign(&r2);
+        let a_bar = multi_scalar_mul_const_time_g1([signature.a], [r1_r2]);
+        let mut minus_e = signature.e;
+        minus_e.negate();
+        let b_bar = multi_scalar_mul_const_time_g1([d, a_bar], [r1, minus_e]);
+
+        // T1 = Abar * e~ + D * r1~, T2 = D * r3~ + sum(H_j * m~_j)
+        let t1 = multi_scalar_mul_const_time_g1([a_bar, d], [e_tilde, r1_tilde]);
+        let mut bases = vec![d];
+        let mut exps = vec![r3_tilde];
+        for (j, m) in hidden.iter().zip(m_tilde.iter()) {
+            bases.push(generators[j + 1].0);
+            exps.push(*m);
+        }
+        let t2 = multi_scalar_mul_const_time_g1(&bases, &exps);
+
+        let disclosed_messages: BTreeMap<usize, Fr> =
+            disclosed.iter().map(|i| (*i, scalars[*i].0)).collect();
+        let challenge = self.challenge(
+            &disclosed_messages,
+            [&a_bar, &b_bar, &d, &t1, &t2],
+            &domain,
+            ph,
+        );
+
+        let r3 = r2
+            .inverse()
+            .ok_or_else(|| BBSError::from_kind(BBSErrorKind::SignatureValueIncorrectSize))?;
+        let mut e_hat = signature.e;
+        e_hat.mul_assign(&challenge);
+        e_hat.add_assign(&e_tilde);
+        let r1_hat = schnorr_response(&r1_tilde, &r1, &challenge);
+        let r3_hat = schnorr_response(&r3_tilde, &r3, &challenge);
+        let m_hat = hidden
+            .iter()
+            .zip(m_tilde.iter())
+            .map(|(j, m)| {
+                let mut r = scalars[*j].0;
+                r.mul_assign(&challenge);
+                r.add_assign(m);
+                r
+            })
+            .collect();
+
+        Ok(Proof {
+            a_bar,
+            b_bar,
+            d,
+            e_hat,
+            r1_hat,
+            r3_hat,
+            m_hat,
+            challenge,
+        })
+    }
+
+    /// Check `proof` reveals `disclosed_messages`, indexed by their position in the signed
+    /// messages, and was created with the same `header` and `ph`
+    pub fn proof_verify<M: AsRef<[u8]>>(
+        self,
+        pk: &DeterministicPublicKey,
+        proof: &Proof,
+        header: &[u8],
+        ph: &[u8],
+        disclosed_messages: &BTreeMap<usize, M>,
+    ) -> Result<bool, BBSError> {
+        check_public_key(pk)?;
+        let message_count = disclosed_messages.len() + proof.m_hat.len();
+        if disclosed_messages.keys().any(|i| *i >= message_count) {
+            return Ok(false);
+        }
+        let generators = self.create_generators(message_count + 1);
+        let domain = self.calculate_domain(pk, &generators, header);
+        let disclosed_scalars: BTreeMap<usize, Fr> = disclosed_messages
+            .iter()
+            .map(|(i, m)| (*i, self.map_message_to_scalar(m.as_ref())))
+            .collect();
+
+        // T1 = Bbar * c + Abar * e^ + D * r1^
+        let t1 = multi_scalar_mul_var_time_g1(
+            [proof.b_bar, proof.a_bar, proof.d],
+            [proof.challenge, proof.e_hat, proof.r1_hat],
+        );
+        // T2 = Bv * c + D * r3^ + sum(H_j * m^_j) where Bv = P1 + Q_1 * domain + sum(H_i * msg_i)
+        let mut bases = vec![self.p1().0, generators[0].0];
+        let mut exps = vec![Fr::one(), domain];
+        for (i, m) in &disclosed_scalars {
+            bases.push(generators[i + 1].0);
+            exps.push(*m);
+        }
+        let mut bv = multi_scalar_mul_var_time_g1(&bases, &exps);
+        bv.mul_assign(proof.challenge);
+        let mut bases = vec![proof.d];
+        let mut exps = vec![proof.r3_hat];
+        let hidden = (0..message_count).filter(|i| !disclosed_scalars.contains_key(i));
+        for (j, m) in hidden.zip(proof.m_hat.iter()) {
+            bases.push(generators[j + 1].0);
+            exps.push(*m);
+        }
+        let mut t2 = multi_scalar_mul_var_time_g1(&bases, &exps);
+        t2.add_assign(&bv);
+
+        let challenge = self.challenge(
+            &disclosed_scalars,
+            [&proof.a_bar, &proof.b_bar, &proof.d, &t1, &t2],
+            &domain,
+            ph,
+        );
+        if challenge != proof.challenge {
+            return Ok(false);
+        }
+
+        // e(Abar, W) * e(-Bbar, BP2) == 1
+        let mut b_bar = proof.b_bar;
+        b_bar.negate();
+        Ok(pairing_is_one(&proof.a_bar, &pk.0, &b_bar))
+    }
+
+    /// Map each message to a scalar with `hash_to_scalar`
+    pub fn messages_to_scalars<M: AsRef<[u8]>>(self, messages: &[M]) -> Vec<SignatureMessage> {
+        messages
+            .iter()
+            .map(|m| SignatureMessage(self.map_message_to_scalar(m.as_ref())))
+            .collect()
+    }
+
+    /// The generators `Q_1, H_1, ..., H_{count - 1}` used for signing `count - 1` messages
+    pub fn create_generators(self, count: usize) -> Vec<GeneratorG1> {
+        self.hash_to_generators(count, &self.api_dst(b"MESSAGE_GENERATOR_SEED"))
+    }
+
+    /// The fixed point `P1` that is the base of the signed commitment
+    pub fn p1(self) -> GeneratorG1 {
+        self.hash_to_generators(1, &self.api_dst(b"BP_MESSAGE_GENERATOR_SEED"))[0]
+    }
+
+    fn hash_to_generators(self, count: usize, generator_seed: &[u8]) -> Vec<GeneratorG1> {
+        let seed_dst = self.api_dst(b"SIG_GENERATOR_SEED_");
+        let generator_dst = self.api_dst(b"SIG_GENERATOR_DST_");
+        let mut v = self.expand_message(generator_seed, &seed_dst, EXPAND_LEN);
+        (1..=count as u64)
+            .map(|i| {
+                v.extend_from_slice(&i.to_be_bytes());
+                v = self.expand_message(&v, &seed_dst, EXPAND_LEN);
+                GeneratorG1(self.hash_to_curve_g1(&v, &generator_dst))
+            })
+            .collect()
+    }
+
+    fn calculate_domain(
+        self,
+        pk: &DeterministicPublicKey,
+        generators: &[GeneratorG1],
+        header: &[u8],
+    ) -> Fr {
+        let api_id = self.api_dst(b"");
+        let mut input = Vec::new();
+        pk.0.serialize(&mut input, true).unwrap();
+        input.extend_from_slice(&(generators.len() as u64 - 1).to_be_bytes());
+        for g in generators {
+            g.0.serialize(&mut input, true).unwrap();
+        }
+        input.extend_from_slice(&api_id);
+        input.extend_from_slice(&(header.len() as u64).to_be_bytes());
+        input.extend_from_slice(header);
+        self.hash_to_scalar(&input, &self.api_dst(b"H2S_"))
+    }
+
+    /// B = P1 + Q_1 * domain + H_1 * msg_1 + ... + H_L * msg_L
+    fn compute_b(
+        self,
+        generators: &[GeneratorG1],
+        domain: &Fr,
+        messages: &[SignatureMessage],
+        const_time: bool,
+    ) -> G1 {
+        let mut bases = Vec::with_capacity(generators.len() + 1);
+        let mut exps = Vec::with_capacity(generators.len() + 1);
+        bases.push(self.p1().0);
+        exps.push(Fr::one());
+        bases.push(generators[0].0);
+        exps.push(*domain);
+        for (h, m) in generators[1..].iter().zip(messages.iter()) {
+            bases.push(h.0);
+            exps.push(m.0);
+        }
+        if const_time {
+            multi_scalar_mul_const_time_g1(&bases, &exps)
+        } else {
+            multi_scalar_mul_var_time_g1(&bases, &exps)
+        }
+    }
+
+    fn challenge(
+        self,
+        disclosed: &BTreeMap<usize, Fr>,
+        points: [&G1; 5],
+        domain: &Fr,
+        ph: &[u8],
+    ) -> Fr {
+        let mut input = Vec::new();
+        input.extend_from_slice(&(disclosed.len() as u64).to_be_bytes());
+        for (i, m) in disclosed {
+            input.extend_from_slice(&(*i as u64).to_be_bytes());
+            push_scalar(&mut input, m);
+        }
+        for p in points.iter() {
+            p.serialize(&mut input, true).unwrap();
+        }
+        push_scalar(&mut input, domain);
+        input.extend_from_slice(&(ph.len() as u64).to_be_bytes());
+        input.extend_from_slice(ph);
+        self.hash_to_scalar(&input, &self.api_dst(b"H2S_"))
+    }
+
+    fn map_message_to_scalar(self, message: &[u8]) -> Fr {
+        self.hash_to_scalar(message, &self.api_dst(b"MAP_MSG_TO_SCALAR_AS_HASH_"))
+    }
+
+    pub(crate) fn hash_to_scalar(self, msg: &[u8], dst: &[u8]) -> Fr {
+        let okm = self.expand_message(msg, dst, EXPAND_LEN);
+        Fr::from_okm(GenericArray::from_slice(&okm))
+    }
+
+    fn expand_message(self, msg: &[u8], dst: &[u8], len: usize) -> Vec<u8> {
+        match self {
+            Ciphersuite::Bls12381Sha256 => ExpandMsgXmd::<Sha256>::expand_message(msg, dst, len),
+            Ciphersuite::Bls12381Shake256 => {
+                ExpandMsgXof::<Shake256>::expand_message(msg, dst, len)
+            }
+        }
+    }
+
+    /// hash_to_curve from RFC 9380. pairing-plus implements an earlier draft whose `sgn0` is
+    /// not the parity of the element, so each of the two mapped points is negated when the
+    /// draft chose the other square root. The isogeny and cofactor clearing are homomorphisms
+    /// so correcting the mapped points after them gives the same sum.
+    fn hash_to_curve_g1(self, msg: &[u8], dst: &[u8]) -> G1 {
+        let uniform_bytes = self.expand_message(msg, dst, 2 * HASH_TO_FIELD_LEN);
+        let mut point = G1::zero();
+        for okm in uniform_bytes.chunks(HASH_TO_FIELD_LEN) {
+            let u = Fq::from_okm(GenericArray::from_slice(okm));
+            let mut q = <G1 as HashToCurve<UniformBytes>>::encode_to_curve(okm, []);
+            if sswu_y_parity_differs(&u) {
+                q.negate();
+            }
+            point.add_assign(&q);
+        }
+        point
+    }
+
+    /// The ciphersuite id followed by `suffix`
+    fn dst(self, suffix: &[u8]) -> Vec<u8> {
+        let mut dst = self.id().to_vec();
+        dst.extend_from_slice(suffix);
+        dst
+    }
+
+    /// The interface id, the ciphersuite id followed by `H2G_HM2S_`, followed by `suffix`
+    fn api_dst(self, suffix: &[u8]) -> Vec<u8> {
+        let mut dst = self.dst(b"H2G_HM2S_");
+        dst.extend_from_slice(suffix);
+        dst
+    }
+}
+
+/// A BBS signature
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Signature {
+    a: G1,
+    e: Fr,
+}
+
+impl Signature {
+    /// Convert to the encoding in the draft, `A || e`
+    pub fn to_bytes(&self) -> [u8; SIGNATURE_SIZE] {
+        let mut out = Vec::with_capacity(SIGNATURE_SIZE);
+        self.a.serialize(&mut out, true).unwrap();
+        push_scalar(&mut out, &self.e);
+        *array_ref![out, 0, SIGNATURE_SIZE]
+    }
+
+    /// Convert from the encoding in the draft
+    pub fn from_bytes(data: &[u8]) -> Result<Self, BBSError> {
+        if data.len() != SIGNATURE_SIZE {
+            return Err(BBSErrorKind::InvalidNumberOfBytes(SIGNATURE_SIZE, data.len()).into());
+        }
+        let mut c = Cursor::new(data);
+        let a = read_point(&mut c)?;
+        let e = read_scalar(&mut c)?;
+        Ok(Self { a, e })
+    }
+}
+
+/// A BBS proof of knowledge of a signature
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proof {
+    a_bar: G1,
+    b_bar: G1,
+    d: G1,
+    e_hat: Fr,
+    r1_hat: Fr,
+    r3_hat: Fr,
+    m_hat: Vec<Fr>,
+    challenge: Fr,
+}
+
+impl Proof {
+    /// The number of messages hidden by the proof
+    pub fn hidden_message_count(&self) -> usize {
+        self.m_hat.len()
+    }
+
+    /// Convert to the encoding in the draft,
+    /// `Abar || Bbar || D || e^ || r1^ || r3^ || m^_1 || ... || m^_U || c`
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(PROOF_MIN_SIZE + FR_COMPRESSED_SIZE * self.m_hat.len());
+        for p in &[self.a_bar, self.b_bar, self.d] {
+            p.serialize(&mut out, true).unwrap();
+        }
+        for s in [self.e_hat, self.r1_hat, self.r3_hat]
+            .iter()
+            .chain(self.m_hat.iter())
+            .chain(std::iter::once(&self.challenge))
+        {
+            push_scalar(&mut out, s);
+        }
+        out
+    }
+
+    /// Convert from the encoding in the draft
+    pub fn from_bytes(data: &[u8]) -> Result<Self, BBSError> {
+        let hidden = data.len().saturating_sub(PROOF_MIN_SIZE) / FR_COMPRESSED_SIZE;
+        if data.len() != PROOF_MIN_SIZE + hidden * FR_COMPRESSED_SIZE {
+            return Err(BBSErrorKind::InvalidNumberOfBytes(PROOF_MIN_SIZE, data.len()).into());
+        }
+        let mut c = Cursor::new(data);
+        let a_bar = read_point(&mut c)?;
+        let b_bar = read_point(&mut c)?;
+        let d = read_point(&mut c)?;
+        let e_hat = read_scalar(&mut c)?;
+        let r1_hat = read_scalar(&mut c)?;
+        let r3_hat = read_scalar(&mut c)?;
+        let mut m_hat = Vec::with_capacity(hidden);
+        for _ in 0..hidden {
+            m_hat.push(read_scalar(&mut c)?);
+        }
+        let challenge = read_scalar(&mut c)?;
+        Ok(Self {
+            a_bar,
+            b_bar,
+            d,
+            e_hat,
+            r1_hat,
+            r3_hat,
+            m_hat,
+            challenge,
+        })
+    }
+}
+
+/// Passes already expanded bytes through so pairing-plus maps a single field element
+struct UniformBytes;
+
+impl ExpandMsg for UniformBytes {
+    fn expand_message(msg: &[u8], _dst: &[u8], len_in_bytes: usize) -> Vec<u8> {
+        debug_assert_eq!(msg.len(), len_in_bytes);
+        msg.to_vec()
+    }
+}
+
+/// Whether the y coordinate pairing-plus picks when mapping `u` to the isogenous curve has a
+/// different parity to `u`, which is where RFC 9380 and the earlier draft disagree
+fn sswu_y_parity_differs(u: &Fq) -> bool {
+    let a = Fq::from_repr(ISO_A).unwrap();
+    let b = Fq::from_repr(ISO_B).unwrap();
+    let z = Fq::from_repr(FqRepr::from(SSWU_Z)).unwrap();
+    let g = |x: &Fq| {
+        // x^3 + A' * x + B'
+        let mut gx = *x;
+        gx.square();
+        gx.add_assign(&a);
+        gx.mul_assign(x);
+        gx.add_assign(&b);
+        gx
+    };
+
+    // tv = Z^2 * u^4 + Z * u^2
+    let mut z_u2 = *u;
+    z_u2.square();
+    z_u2.mul_assign(&z);
+    let mut tv = z_u2;
+    tv.square();
+    tv.add_assign(&z_u2);
+
+    // x1 = -B' / A' * (1 + 1 / tv), or B' / (Z * A') when tv is zero
+    let mut x1 = b;
+    match tv.inverse() {
+        None => {
+            let mut den = z;
+            den.mul_assign(&a);
+            x1.mul_assign(&den.inverse().unwrap());
+        }
+        Some(mut tv_inv) => {
+            tv_inv.add_assign(&Fq::one());
+            x1.mul_assign(&tv_inv);
+            x1.mul_assign(&a.inverse().unwrap());
+            x1.negate();
+        }
+    }
+    let mut y = match g(&x1).sqrt() {
+        Some(y) => y,
+        None => {
+            let mut x2 = x1;
+            x2.mul_assign(&z_u2);
+            g(&x2).sqrt().unwrap()
+        }
+    };
+
+    if y.sgn0() != u.sgn0() {
+        y.negate();
+    }
+    y.into_repr().is_odd() != u.into_repr().is_odd()
+}
+
+/// r~ - r * c
+fn schnorr_response(blinding: &Fr, secret: &Fr, challenge: &Fr) -> Fr {
+    let mut r = *secret;
+    r.mul_assign(challenge);
+    r.negate();
+    r.add_assign(blinding);
+    r
+}
+
+/// e(p1, q1) * e(p2, BP2) == 1
+fn pairing_is_one(p1: &G1, q1: &G2, p2: &G1) -> bool {
+    let p1 = p1.into_affine().prepare();
+    let q1 = q1.into_affine().prepare();
+    let p2 = p2.into_affine().prepare();
+    let g2 = G2::one().into_affine().prepare();
+    match Bls12::final_exponentiation(&Bls12::miller_loop(&[(&p1, &q1), (&p2, &g2)])) {
+        None => false,
+        Some(product) => product == Fq12::one(),
+    }
+}
+
+fn check_public_key(pk: &DeterministicPublicKey) -> Result<(), BBSError> {
+    if pk.0.is_zero() {
+        Err(BBSErrorKind::MalformedPublicKey.into())
+    } else {
+        Ok(())
+    }
+}
+
+fn push_scalar(out: &mut Vec<u8>, s: &Fr) {
+    s.serialize(out, true).unwrap();
+}
+
+/// Points must be in G1 and not the identity
+fn read_point(c: &mut Cursor<&[u8]>) -> Result<G1, BBSError> {
+    let p = G1::deserialize(c, true)?;
+    if p.is_zero() {
+        return Err(BBSErrorKind::SignatureValueIncorrectSize.into());
+    }
+    Ok(p)
+}
+
+/// Scalars must be in (0, r)
+fn read_scalar(c: &mut Cursor<&[u8]>) -> Result<Fr, BBSError> {
+    let s = Fr::deserialize(c, true)?;
+    if s.is_zero() {
+        return Err(BBSErrorKind::SignatureValueIncorrectSize.into());
+    }
+    Ok(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUITES: [Ciphersuite; 2] = [Ciphersuite::Bls12381Sha256, Ciphersuite::Bls12381Shake256];
+
+    fn messages() -> Vec<Vec<u8>> {
+        (0..5)
+            .map(|i| format!("message {}", i).into_bytes())
+            .collect()
+    }
+
+    fn key_pair(suite: Ciphersuite) -> (DeterministicPublicKey, SecretKey) {
+        suite.keygen(&[7u8; 32], b"test key", None).unwrap()
+    }
+
+    #[test]
+    fn hash_to_curve_test_vectors() {
+        // RFC 9380 appendix J.9.1
+        let dst = b"QUUX-V01-CS02-with-BLS12381G1_XMD:SHA-256_SSWU_RO_";
+        for (msg, expected) in &[
+            (
+                &b""[..],
+                "052926add2207b76ca4fa57a8734416c8dc95e24501772c814278700eed6d1e4e8cf62d9c09db0fac349612b759e79a1\
+                 08ba738453bfed09cb546dbb0783dbb3a5f1f566ed67bb6be0e8c67e2e81a4cc68ee29813bb7994998f3eae0c9c6a265",
+            ),
+            (
+                &b"abc"[..],
+                "03567bc5ef9c690c2ab2ecdf6a96ef1c139cc0b2f284dca0a9a7943388a49a3aee664ba5379a7655d3c68900be2f6903\
+                 0b9c15f3fe6e5cf4211f346271d7b01c8f3b28be689c8429c85b67af215533311f0b8dfaaa154fa6b88176c229f2885d",
+            ),
+        ] {
+            let p = Ciphersuite::Bls12381Sha256.hash_to_curve_g1(msg, dst);
+            let mut bytes = Vec::new();
+            p.serialize(&mut bytes, false).unwrap();
+            assert_eq!(hex::encode(bytes), *expected);
+        }
+    }
+
+    #[test]
+    fn p1_matches_draft() {
+        assert_eq!(
+            hex::encode(Ciphersuite::Bls12381Sha256.p1().to_bytes_compressed_form()),
+            "a8ce256102840821a3e94ea9025e4662b205762f9776b3a766c872b948f1fd225e7c59698588e70d11406d161b4e28c9"
+        );
+        assert_eq!(
+            hex::encode(Ciphersuite::Bls12381Shake256.p1().to_bytes_compressed_form()),
+            "8929dfbc7e6642c4ed9cba0856e493f8b9d7d5fcb0c31ef8fdcd34d50648a56c795e106e9eada6e0bda386b414150755"
+        );
+    }
+
+    #[test]
+    fn keygen() {
+        for suite in SUITES.iter() {
+            let (pk, sk) = key_pair(*suite);
+            assert_eq!((pk, sk), key_pair(*suite));
+            let (pk_2, _) = suite.keygen(&[7u8; 32], b"other key", None).unwrap();
+            assert_ne!(pk, pk_2);
+            let (pk_2, _) = suite
+                .keygen(&[7u8; 32], b"test key", Some(b"OTHER_DST_"))
+                .unwrap();
+            assert_ne!(pk, pk_2);
+            assert!(suite.keygen(&[7u8; 31], b"test key", None).is_err());
+        }
+        assert_ne!(
+            key_pair(Ciphersuite::Bls12381Sha256).0,
+            key_pair(Ciphersuite::Bls12381Shake256).0
+        );
+    }
+
+    #[test]
+    fn sign_verify() {
+        for suite in SUITES.iter() {
+            let (pk, sk) = key_pair(*suite);
+            let msgs = messages();
+            let sig = suite.sign(&sk, &pk, b"header", &msgs).unwrap();
+            assert!(suite.verify(&pk, &sig, b"header", &msgs).unwrap());
+            assert!(!suite.verify(&pk, &sig, b"other", &msgs).unwrap());
+            assert!(!suite.verify(&pk, &sig, b"header", &msgs[1..]).unwrap());
+
+            let mut wrong = msgs.clone();
+            wrong[2] = b"wrong".to_vec();
+            assert!(!suite.verify(&pk, &sig, b"header", &wrong).unwrap());
+
+            let (other_pk, _) = suite.keygen(&[8u8; 32], b"", None).unwrap();
+            assert!(!suite.verify(&other_pk, &sig, b"header", &msgs).unwrap());
+
+            // Signing is deterministic
+            assert_eq!(sig, suite.sign(&sk, &pk, b"header", &msgs).unwrap());
+
+            let no_messages: [&[u8]; 0] = [];
+            let sig = suite.sign(&sk, &pk, b"", &no_messages).unwrap();
+            assert!(suite.verify(&pk, &sig, b"", &no_messages).unwrap());
+        }
+    }
+
+    #[test]
+    fn suites_are_not_interchangeable() {
+        let (pk, sk) = key_pair(Ciphersuite::Bls12381Sha256);
+        let msgs = messages();
+        let sig = Ciphersuite::Bls12381Sha256
+            .sign(&sk, &pk, b"header", &msgs)
+            .unwrap();
+        assert!(!Ciphersuite::Bls12381Shake256
+            .verify(&pk, &sig, b"header", &msgs)
+            .unwrap());
+    }
+
+    #[test]
+    fn proof_gen_verify() {
+        for suite in SUITES.iter() {
+            let (pk, sk) = key_pair(*suite);
+            let msgs = messages();
+            let sig = suite.sign(&sk, &pk, b"header", &msgs).unwrap();
+
+            for disclosed_indices in &[vec![], vec![0, 2, 4], vec![1], vec![0, 1, 2, 3, 4]] {
+                let proof = suite
+                    .proof_gen(&pk, &sig, b"header", b"ph", &msgs, disclosed_indices)
+                    .unwrap();
+                assert_eq!(
+                    proof.hidden_message_count(),
+                    msgs.len() - disclosed_indices.len()
+                );
+                let disclosed: BTreeMap<usize, &[u8]> = disclosed_indices
+                    .iter()
+                    .map(|i| (*i, msgs[*i].as_slice()))
+                    .collect();
+                assert!(suite
+                    .proof_verify(&pk, &proof, b"header", b"ph", &disclosed)
+                    .unwrap());
+                assert!(!suite
+                    .proof_verify(&pk, &proof, b"header", b"other", &disclosed)
+                    .unwrap());
+                assert!(!suite
+                    .proof_verify(&pk, &proof, b"other", b"ph", &disclosed)
+                    .unwrap());
+
+                if let Some(i) = disclosed_indices.first() {
+                    let mut wrong = disclosed.clone();
+                    wrong.insert(*i, b"wrong");
+                    assert!(!suite
+                        .proof_verify(&pk, &proof, b"header", b"ph", &wrong)
+                        .unwrap());
+                }
+            }
+
+            // Proofs are unlinkable so two proofs of the same signature differ
+            let proof_1 = suite
+                .proof_gen(&pk, &sig, b"header", b"ph", &msgs, &[0])
+                .unwrap();
+            let proof_2 = suite
+                .proof_gen(&pk, &sig, b"header", b"ph", &msgs, &[0])
+                .unwrap();
+            assert_ne!(proof_1, proof_2);
+
+            assert!(suite
+                .proof_gen(&pk, &sig, b"header", b"ph", &msgs, &[5])
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn proof_of_invalid_signature_fails() {
+        let suite = Ciphersuite::Bls12381Sha256;
+        let (pk, sk) = key_pair(suite);
+        let msgs = messages();
+        let sig = suite.sign(&sk, &pk, b"header", &msgs).unwrap();
+        let mut wrong = msgs.clone();
+        wrong[1] = b"wrong".to_vec();
+        let proof = suite
+            .proof_gen(&pk, &sig, b"header", b"", &wrong, &[0])
+            .unwrap();
+        let mut disclosed = BTreeMap::new();
+        disclosed.insert(0, msgs[0].as_slice());
+        assert!(!suite
+            .proof_verify(&pk, &proof, b"header", b"", &disclosed)
+            .unwrap());
+    }
+
+    #[test]
+    fn serialization() {
+        let suite = Ciphersuite::Bls12381Shake256;
+        let (pk, sk) = key_pair(suite);
+        let msgs = messages();
+        let sig = suite.sign(&sk, &pk, b"header", &msgs).unwrap();
+        let bytes = sig.to_bytes();
+        assert_eq!(Signature::from_bytes(&bytes).unwrap(), sig);
+        assert!(Signature::from_bytes(&bytes[1..]).is_err());
+
+        // The identity is not a valid A
+        let mut identity = [0u8; SIGNATURE_SIZE];
+        identity[0] = 0xc0;
+        identity[SIGNATURE_SIZE - 1] = 1;
+        assert!(Signature::from_bytes(&identity).is_err());
+
+        // e = 0 is rejected
+        let mut zero_e = bytes;
+        for b in zero_e[G1_COMPRESSED_SIZE..].iter_mut() {
+            *b = 0;
+        }
+        assert!(Signature::from_bytes(&zero_e).is_err());
+
+        let proof = suite
+            .proof_gen(&pk, &sig, b"header", b"ph", &msgs, &[1, 3])
+            .unwrap();
+        let bytes = proof.to_bytes();
+        assert_eq!(bytes.len(), PROOF_MIN_SIZE + 3 * FR_COMPRESSED_SIZE);
+        assert_eq!(Proof::from_bytes(&bytes).unwrap(), proof);
+        assert!(Proof::from_bytes(&bytes[1..]).is_err());
+        assert!(Proof::from_bytes(&bytes[..PROOF_MIN_SIZE - 1]).is_err());
+    }
+
+    // Test vectors from the appendix of the draft. The messages, header, presentation
+    // header and key material are shared by both ciphersuites.
+    const KEY_MATERIAL: &str =
+        "746869732d49532d6a7573742d616e2d546573742d494b4d2d746f2d67656e65726174652d246528724074232d6b6579";
+    const KEY_INFO: &str =
+        "746869732d49532d736f6d652d6b65792d6d657461646174612d746f2d62652d757365642d696e2d746573742d6b65792d67656e";
+    const MESSAGES: [&str; 10] = [
+        "9872ad089e452c7b6e283dfac2a80d58e8d0ff71cc4d5e310a1debdda4a45f02",
+        "c344136d9ab02da4dd5908bbba913ae6f58c2cc844b802a6f811f5fb075f9b80",
+        "7372e9daa5ed31e6cd5c825eac1b855e84476a1d94932aa348e07b73",
+        "77fe97eb97a1ebe2e81e4e3597a3ee740a66e9ef2412472c",
+        "496694774c5604ab1b2544eababcf0f53278ff50",
+        "515ae153e22aae04ad16f759e07237b4",
+        "d183ddc6e2665aa4e2f088af",
+        "ac55fb33a75909ed",
+        "96012096",
+        "",
+    ];
+    const HEADER: &str = "11223344556677889900aabbccddeeff";
+    const PRESENTATION_HEADER: &str =
+        "bed231d880675ed101ead304512e043ade9958dd0241ea70b4b3957fba941501";
+    /// The seed of the mocked random scalars, "3.141592653589793238462643383279"
+    const MOCKED_SCALARS_SEED: &str =
+        "332e313431353932363533353839373933323338343632363433333833323739";
+
+    struct Fixtures {
+        suite: Ciphersuite,
+        secret_key: &'static str,
+        public_key: &'static str,
+        /// Q_1 followed by H_1 to H_10
+        generators: [&'static str; 11],
+        message_scalars: [&'static str; 10],
+        /// Signature over the first message
+        signature_single: &'static str,
+        /// Signature over all ten messages
+        signature_multi: &'static str,
+        /// The first of ten mocked random scalars
+        mocked_scalar: &'static str,
+        /// Proof of `signature_single` disclosing its message
+        proof_single: &'static str,
+        /// Proof of `signature_multi` disclosing all messages
+        proof_all_disclosed: &'static str,
+        /// Proof of `signature_multi` disclosing messages 0, 2, 4 and 6
+        proof_some_disclosed: &'static str,
+    }
+
+    const SHA256_FIXTURES: Fixtures = Fixtures {
+        suite: Ciphersuite::Bls12381Sha256,
+        secret_key: "60e55110f76883a13d030b2f6bd11883422d5abde717569fc0731f51237169fc",
+        public_key: "a820f230f6ae38503b86c70dc50b61c58a77e45c39ab25c0652bbaa8fa136f2851bd4781c9dcde39fc9d1d52c9e60268\
+         061e7d7632171d91aa8d460acee0e96f1e7c4cfb12d3ff9ab5d5dc91c277db75c845d649ef3c4f63aebc364cd55ded0c",
+        generators: [
+            "a9ec65b70a7fbe40c874c9eb041c2cb0a7af36ccec1bea48fa2ba4c2eb67ef7f9ecb17ed27d38d27cdeddff44c8137be",
+            "98cd5313283aaf5db1b3ba8611fe6070d19e605de4078c38df36019fbaad0bd28dd090fd24ed27f7f4d22d5ff5dea7d4",
+            "a31fbe20c5c135bcaa8d9fc4e4ac665cc6db0226f35e737507e803044093f37697a9d452490a970eea6f9ad6c3dcaa3a",
+            "b479263445f4d2108965a9086f9d1fdc8cde77d14a91c856769521ad3344754cc5ce90d9bc4c696dffbc9ef1d6ad1b62",
+            "ac0401766d2128d4791d922557c7b4d1ae9a9b508ce266575244a8d6f32110d7b0b7557b77604869633bb49afbe20035",
+            "b95d2898370ebc542857746a316ce32fa5151c31f9b57915e308ee9d1de7db69127d919e984ea0747f5223821b596335",
+            "8f19359ae6ee508157492c06765b7df09e2e5ad591115742f2de9c08572bb2845cbf03fd7e23b7f031ed9c7564e52f39",
+            "abc914abe2926324b2c848e8a411a2b6df18cbe7758db8644145fefb0bf0a2d558a8c9946bd35e00c69d167aadf304c1",
+            "80755b3eb0dd4249cbefd20f177cee88e0761c066b71794825c9997b551f24051c352567ba6c01e57ac75dff763eaa17",
+            "82701eb98070728e1769525e73abff1783cedc364adb20c05c897a62f2ab2927f86f118dcb7819a7b218d8f3fee4bd7f",
+            "a1f229540474f4d6f1134761b92b788128c7ac8dc9b0c52d59493132679673032ac7db3fb3d79b46b13c1c41ee495bca",
+        ],
+        message_scalars: [
+            "1cb5bb86114b34dc438a911617655a1db595abafac92f47c5001799cf624b430",
+            "154249d503c093ac2df516d4bb88b510d54fd97e8d7121aede420a25d9521952",
+            "0c7c4c85cdab32e6fdb0de267b16fa3212733d4e3a3f0d0f751657578b26fe22",
+            "4a196deafee5c23f630156ae13be3e46e53b7e39094d22877b8cba7f14640888",
+            "34c5ea4f2ba49117015a02c711bb173c11b06b3f1571b88a2952b93d0ed4cf7e",
+            "4045b39b83055cd57a4d0203e1660800fabe434004dbdc8730c21ce3f0048b08",
+            "064621da4377b6b1d05ecc37cf3b9dfc94b9498d7013dc5c4a82bf3bb1750743",
+            "34ac9196ace0a37e147e32319ea9b3d8cc7d21870d3c3ba071246859cca49b02",
+            "57eb93f417c43200e9784fa5ea5a59168d3dbc38df707a13bb597c871b2a5f74",
+            "08e3afeb2b4f2b5f907924ef42856616e6f2d5f1fb373736db1cca32707a7d16",
+        ],
+        signature_single: "84773160b824e194073a57493dac1a20b667af70cd2352d8af241c77658da5253aa8458317cca0eae615690d55b1f271\
+         64657dcafee1d5c1973947aa70e2cfbb4c892340be5969920d0916067b4565a0",
+        signature_multi: "8339b285a4acd89dec7777c09543a43e3cc60684b0a6f8ab335da4825c96e1463e28f8c5f4fd0641d19cec5920d3a8ff\
+         4bedb6c9691454597bbd298288abed3632078557b2ace7d44caed846e1a0a1e8",
+        mocked_scalar: "04f8e2518993c4383957ad14eb13a023c4ad0c67d01ec86eeb902e732ed6df3f",
+        proof_single: "94916292a7a6bade28456c601d3af33fcf39278d6594b467e128a3f83686a104ef2b2fcf72df0215eeaf69262ffe8194\
+         a19fab31a82ddbe06908985abc4c9825788b8a1610942d12b7f5debbea8985296361206dbace7af0cc834c80f33e0aad\
+         aeea5597befbb651827b5eed5a66f1a959bb46cfd5ca1a817a14475960f69b32c54db7587b5ee3ab665fbd37b506830a\
+         49f21d592f5e634f47cee05a025a2f8f94e73a6c15f02301d1178a92873b6e8634bafe4983c3e15a663d64080678dbf2\
+         9417519b78af042be2b3e1c4d08b8d520ffab008cbaaca5671a15b22c239b38e940cfeaa5e72104576a9ec4a6fad78c5\
+         32381aeaa6fb56409cef56ee5c140d455feeb04426193c57086c9b6d397d9418",
+        proof_all_disclosed: "b1f468aec2001c4f54cb56f707c6222a43e5803a25b2253e67b2210ab2ef9eab52db2d4b379935c4823281eaf767fd37\
+         b08ce80dc65de8f9769d27099ae649ad4c9b4bd2cc23edcba52073a298087d2495e6d57aaae051ef741adf1cbce65c64\
+         a73c8c97264177a76c4a03341956d2ae45ed3438ce598d5cda4f1bf9507fecef47855480b7b30b5e4052c92a4360110c\
+         67327365763f5aa9fb85ddcbc2975449b8c03db1216ca66b310f07d0ccf12ab460cdc6003b677fed36d0a23d0818a9d4\
+         d098d44f749e91008cf50e8567ef936704c8277b7710f41ab7e6e16408ab520edc290f9801349aee7b7b4e318e6a76e0\
+         28e1dea911e2e7baec6a6a174da1a22362717fbae1cd961d7bf4adce1d31c2ab",
+        proof_some_disclosed: "a2ed608e8e12ed21abc2bf154e462d744a367c7f1f969bdbf784a2a134c7db2d340394223a5397a3011b1c340ebc4151\
+         99462ba6f31106d8a6da8b513b37a47afe93c9b3474d0d7a354b2edc1b88818b063332df774c141f7a07c48fe50d452f\
+         897739228c88afc797916dca01e8f03bd9c5375c7a7c59996e514bb952a436afd24457658acbaba5ddac2e693ac48135\
+         6918cd38025d86b28650e909defe9604a7259f44386b861608be742af7775a2e71a6070e5836f5f54dc43c60096834a5\
+         b6da295bf8f081f72b7cdf7f3b4347fb3ff19edaa9e74055c8ba46dbcb7594fb2b06633bb5324192eb9be91be0d33e45\
+         3b4d3127459de59a5e2193c900816f049a02cb9127dac894418105fa1641d5a206ec9c42177af9316f43341744147827\
+         6ca0303da8f941bf2e0222a43251cf5c2bf6eac1961890aa740534e519c1767e1223392a3a286b0f4d91f7f25217a786\
+         2b8fcc1810cdcfddde2a01c80fcc90b632585fec12dc4ae8fea1918e9ddeb9414623a457e88f53f545841f9d5dcb1f8e\
+         160d1560770aa79d65e2eca8edeaecb73fb7e995608b820c4a64de6313a370ba05dc25ed7c1d185192084963652f2870\
+         341bdaa4b1a37f8c06348f38a4f80c5a2650a21d59f09e8305dcd3fc3ac30e2a",
+    };
+
+    const SHAKE256_FIXTURES: Fixtures = Fixtures {
+        suite: Ciphersuite::Bls12381Shake256,
+        secret_key: "2eee0f60a8a3a8bec0ee942bfd46cbdae9a0738ee68f5a64e7238311cf09a079",
+        public_key: "92d37d1d6cd38fea3a873953333eab23a4c0377e3e049974eb62bd45949cdeb18fb0490edcd4429adff56e65cbce42cf\
+         188b31bddbd619e419b99c2c41b38179eb001963bc3decaae0d9f702c7a8c004f207f46c734a5eae2e8e82833f3e7ea5",
+        generators: [
+            "a9d40131066399fd41af51d883f4473b0dcd7d028d3d34ef17f3241d204e28507d7ecae032afa1d5490849b7678ec1f8",
+            "903c7ca0b7e78a2017d0baf74103bd00ca8ff9bf429f834f071c75ffe6bfdec6d6dca15417e4ac08ca4ae1e78b7adc0e",
+            "84321f5855bfb6b001f0dfcb47ac9b5cc68f1a4edd20f0ec850e0563b27d2accee6edff1a26b357762fb24e8ddbb6fcb",
+            "b3060dff0d12a32819e08da00e61810676cc9185fdd750e5ef82b1a9798c7d76d63de3b6225d6c9a479d6c21a7c8bf93",
+            "8f1093d1e553cdead3c70ce55b6d664e5d1912cc9edfdd37bf1dad11ca396a0a8bb062092d391ebf8790ea5722413f68",
+            "990824e00b48a68c3d9a308e8c52a57b1bc84d1cf5d3c0f8c6fb6b1230e4e5b8eb752fb374da0b1ef687040024868140",
+            "b86d1c6ab8ce22bc53f625d1ce9796657f18060fcb1893ce8931156ef992fe56856199f8fa6c998e5d855a354a26b0dd",
+            "b4cdd98c5c1e64cb324e0c57954f719d5c5f9e8d991fd8e159b31c8d079c76a67321a30311975c706578d3a0ddc313b7",
+            "8311492d43ec9182a5fc44a75419b09547e311251fe38b6864dc1e706e29446cb3ea4d501634eb13327245fd8a574f77",
+            "ac00b493f92d17837a28d1f5b07991ca5ab9f370ae40d4f9b9f2711749ca200110ce6517dc28400d4ea25dddc146cacc",
+            "965a6c62451d4be6cb175dec39727dc665762673ee42bf0ac13a37a74784fbd61e84e0915277a6f59863b2bb4f5f6005",
+        ],
+        message_scalars: [
+            "1e0dea6c9ea8543731d331a0ab5f64954c188542b33c5bbc8ae5b3a830f2d99f",
+            "3918a40fb277b4c796805d1371931e08a314a8bf8200a92463c06054d2c56a9f",
+            "6642b981edf862adf34214d933c5d042bfa8f7ef343165c325131e2ffa32fa94",
+            "33c021236956a2006f547e22ff8790c9d2d40c11770c18cce6037786c6f23512",
+            "52b249313abbe323e7d84230550f448d99edfb6529dec8c4e783dbd6dd2a8471",
+            "2a50bdcbe7299e47e1046100aadffe35b4247bf3f059d525f921537484dd54fc",
+            "0e92550915e275f8cfd6da5e08e334d8ef46797ee28fa29de40a1ebccd9d95d3",
+            "4c28f612e6c6f82f51f95e1e4faaf597547f93f6689827a6dcda3cb94971d356",
+            "1db51bedc825b85efe1dab3e3ab0274fa82bbd39732be3459525faf70f197650",
+            "27878da72f7775e709bb693d81b819dc4e9fa60711f4ea927740e40073489e78",
+        ],
+        signature_single: "b9a622a4b404e6ca4c85c15739d2124a1deb16df750be202e2430e169bc27fb71c44d98e6d40792033e1c452145ada95\
+         030832c5dc778334f2f1b528eced21b0b97a12025a283d78b7136bb9825d04ef",
+        signature_multi: "956a3427b1b8e3642e60e6a7990b67626811adeec7a0a6cb4f770cdd7c20cf08faabb913ac94d18e1e92832e924cb6e2\
+         02912b624261fc6c59b0fea801547f67fb7d3253e1e2acbcf90ef59a6911931e",
+        mocked_scalar: "1004262112c3eaa95941b2b0d1311c09c845db0099a50e67eda628ad26b43083",
+        proof_single: "89e4ab0c160880e0c2f12a754b9c051ed7f5fccfee3d5cbbb62e1239709196c737fff4303054660f8fcd08267a5de668\
+         a2e395ebe8866bdcb0dff9786d7014fa5e3c8cf7b41f8d7510e27d307f18032f6b788e200b9d6509f40ce1d2f962ceed\
+         b023d58ee44d660434e6ba60ed0da1a5d2cde031b483684cd7c5b13295a82f57e209b584e8fe894bcc964117bf3521b4\
+         3d8e2eb59ce31f34d68b39f05bb2c625e4de5e61e95ff38bfd62ab07105d016414b45b01625c69965ad3c8a933e7b25d\
+         93daeb777302b966079827a99178240e6c3f13b7db2fb1f14790940e239d775ab32f539bdf9f9b582b250b0588299683\
+         2652f7f5d3b6e04744c73ada1702d6791940ccbd75e719537f7ace6ee817298d",
+        proof_all_disclosed: "91b0f598268c57b67bc9e55327c3c2b9b1654be89a0cf963ab392fa9e1637c565241d71fd6d7bbd7dfe243de85a9bac8\
+         b7461575c1e13b5055fed0b51fd0ec1433096607755b2f2f9ba6dc614dfa456916ca0d7fc6482b39c679cfb747a50ea1\
+         b3dd7ed57aaadc348361e2501a17317352e555a333e014e8e7d71eef808ae4f8fbdf45cd19fde45038bb310d5135f520\
+         5fc550b077e381fb3a3543dca31a0d8bba97bc0b660a5aa239eb74921e184aa3035fa01eaba32f52029319ec3df4fa4a\
+         4f716edb31a6ce19a19dbb971380099345070bd0fdeecf7c4774a33e0a116e069d5e215992fb637984802066dee69191\
+         46ae50b70ea52332dfe57f6e05c66e99f1764d8b890d121d65bfcc2984886ee0",
+        proof_some_disclosed: "b1f8bf99a11c39f04e2a032183c1ead12956ad322dd06799c50f20fb8cf6b0ac279210ef5a2920a7be3ec2aa0911ace7\
+         b96811a98f3c1cceba4a2147ae763b3ba036f47bc21c39179f2b395e0ab1ac49017ea5b27848547bedd27be481c1dfc0\
+         b73372346feb94ab16189d4c525652b8d3361bab43463700720ecfb0ee75e595ea1b13330615011050a0dfcffdb21af3\
+         56dd39bf8bcbfd41bf95d913f4c9b2979e1ed2ca10ac7e881bb6a271722549681e398d29e9ba4eac8848b168eddd5e4a\
+         cec7df4103e2ed165e6e32edc80f0a3b28c36fb39ca19b4b8acee570deadba2da9ec20d1f236b571e0d4c2ea3b826fe9\
+         24175ed4dfffbf18a9cfa98546c241efb9164c444d970e8c89849bc8601e96cf228fdefe38ab3b7e289cac859e68d9cb\
+         b0e648faf692b27df5ff6539c30da17e5444a65143de02ca64cee7b0823be65865cdc310be038ec6b594b99280072ae0\
+         67bad1117b0ff3201a5506a8533b925c7ffae9cdb64558857db0ac5f5e0f18e750ae77ec9cf35263474fef3f78138c7a\
+         1ef5cfbc878975458239824fad3ce05326ba3969b1f5451bd82bd1f8075f3d32ece2d61d89a064ab4804c3c892d651d1\
+         1bc325464a71cd7aacc2d956a811aaff13ea4c35cef7842b656e8ba4758e7558",
+    };
+
+    fn draft_messages() -> Vec<Vec<u8>> {
+        MESSAGES.iter().map(|m| hex::decode(m).unwrap()).collect()
+    }
+
+    /// The draft's replacement for random scalars in proof generation
+    fn mocked_scalars(suite: Ciphersuite, count: usize) -> Vec<Fr> {
+        suite
+            .expand_message(
+                &hex::decode(MOCKED_SCALARS_SEED).unwrap(),
+                &suite.api_dst(b"MOCK_RANDOM_SCALARS_DST_"),
+                count * EXPAND_LEN,
+            )
+            .chunks(EXPAND_LEN)
+            .map(|okm| Fr::from_okm(GenericArray::from_slice(okm)))
+            .collect()
+    }
+
+    fn scalar_hex(s: &Fr) -> String {
+        let mut bytes = Vec::new();
+        push_scalar(&mut bytes, s);
+        hex::encode(bytes)
+    }
+
+    fn draft_key_pair(suite: Ciphersuite) -> (DeterministicPublicKey, SecretKey) {
+        suite
+            .keygen(
+                &hex::decode(KEY_MATERIAL).unwrap(),
+                &hex::decode(KEY_INFO).unwrap(),
+                None,
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn draft_keygen_fixtures() {
+        for f in &[SHA256_FIXTURES, SHAKE256_FIXTURES] {
+            let (pk, sk) = draft_key_pair(f.suite);
+            assert_eq!(hex::encode(sk.to_bytes_compressed_form()), f.secret_key);
+            assert_eq!(hex::encode(pk.to_bytes_compressed_form()), f.public_key);
+        }
+    }
+
+    #[test]
+    fn draft_generator_and_scalar_fixtures() {
+        for f in &[SHA256_FIXTURES, SHAKE256_FIXTURES] {
+            let generators: Vec<String> = f
+                .suite
+                .create_generators(f.generators.len())
+                .iter()
+                .map(|g| hex::encode(g.to_bytes_compressed_form()))
+                .collect();
+            assert_eq!(generators, f.generators);
+
+            let scalars: Vec<String> = f
+                .suite
+                .messages_to_scalars(&draft_messages())
+                .iter()
+                .map(|m| hex::encode(m.to_bytes_compressed_form()))
+                .collect();
+            assert_eq!(scalars, f.message_scalars);
+
+            assert_eq!(scalar_hex(&mocked_scalars(f.suite, 10)[0]), f.mocked_scalar);
+        }
+    }
+
+    #[test]
+    fn draft_signature_fixtures() {
+        let header = hex::decode(HEADER).unwrap();
+        let msgs = draft_messages();
+        for f in &[SHA256_FIXTURES, SHAKE256_FIXTURES] {
+            let (pk, sk) = draft_key_pair(f.suite);
+            for (signature, msgs) in &[
+                (f.signature_single, &msgs[..1]),
+                (f.signature_multi, &msgs[..]),
+            ] {
+                let sig = f.suite.sign(&sk, &pk, &header, msgs).unwrap();
+                assert_eq!(hex::encode(&sig.to_bytes()[..]), *signature);
+
+                let sig = Signature::from_bytes(&hex::decode(signature).unwrap()).unwrap();
+                assert!(f.suite.verify(&pk, &sig, &header, msgs).unwrap());
+                assert!(!f.suite.verify(&pk, &sig, b"", msgs).unwrap());
+                let mut modified = msgs.to_vec();
+                modified[0] = b"modified message".to_vec();
+                assert!(!f.suite.verify(&pk, &sig, &header, &modified).unwrap());
+            }
+        }
+        // Each ciphersuite only accepts its own signatures
+        let (pk, _) = draft_key_pair(Ciphersuite::Bls12381Sha256);
+        let sig = Signature::from_bytes(&hex::decode(SHAKE256_FIXTURES.signature_single).unwrap())
+            .unwrap();
+        assert!(!Ciphersuite::Bls12381Sha256
+            .verify(&pk, &sig, &header, &msgs[..1])
+            .unwrap());
+    }
+
+    #[test]
+    fn draft_proof_fixtures() {
+        let header = hex::decode(HEADER).unwrap();
+        let ph = hex::decode(PRESENTATION_HEADER).unwrap();
+        let msgs = draft_messages();
+        for f in &[SHA256_FIXTURES, SHAKE256_FIXTURES] {
+            let (pk, _) = draft_key_pair(f.suite);
+            for (proof, signature, msgs, disclosed_indices) in &[
+                (f.proof_single, f.signature_single, &msgs[..1], vec![0]),
+                (
+                    f.proof_all_disclosed,
+                    f.signature_multi,
+                    &msgs[..],
+                    (0..msgs.len()).collect(),
+                ),
+                (
+                    f.proof_some_disclosed,
+                    f.signature_multi,
+                    &msgs[..],
+                    vec![0, 2, 4, 6],
+                ),
+            ] {
+                let disclosed: BTreeMap<usize, &[u8]> = disclosed_indices
+                    .iter()
+                    .map(|i| (*i, msgs[*i].as_slice()))
+                    .collect();
+                let decoded = Proof::from_bytes(&hex::decode(proof).unwrap()).unwrap();
+                assert_eq!(decoded.hidden_message_count(), msgs.len() - disclosed.len());
+                assert!(f
+                    .suite
+                    .proof_verify(&pk, &decoded, &header, &ph, &disclosed)
+                    .unwrap());
+                assert!(!f
+                    .suite
+                    .proof_verify(&pk, &decoded, &header, b"", &disclosed)
+                    .unwrap());
+                assert!(!f
+                    .suite
+                    .proof_verify(&pk, &decoded, b"", &ph, &disclosed)
+                    .unwrap());
+
+                // The mocked random scalars reproduce the proof
+                let sig = Signature::from_bytes(&hex::decode(signature).unwrap()).unwrap();
+                let generated = f
+                    .suite
+                    .proof_gen_with_scalars(
+                        &pk,
+                        &sig,
+                        &header,
+                        &ph,
+                        msgs,
+                        disclosed_indices,
+                        |count| mocked_scalars(f.suite, count),
+                    )
+                    .unwrap();
+                assert_eq!(hex::encode(generated.to_bytes()), *proof);
+            }
+        }
+    }
+}
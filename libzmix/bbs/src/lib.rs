@@ -86,6 +86,7 @@ pub mod messages;
 pub mod pok_vc;
 /// The errors that BBS+ throws
 pub mod errors;
+pub mod ietf;
 /// Represents steps taken by the issuer to create a BBS+ signature
 /// whether its 2PC or all in one
 pub mod issuer;
@@ -647,11 +648,24 @@ impl SignatureProof {
         }
 
         let proof_len = u32::from_be_bytes(*array_ref![data, 0, 4]) as usize + 4;
+        if data.len() < proof_len + 4 {
+            return Err(BBSError::from(BBSErrorKind::InvalidNumberOfBytes(
+                proof_len + 4,
+                data.len(),
+            )));
+        }
         let proof = PoKOfSignatureProof::from_bytes(&data[4..proof_len], g1_size, compressed)?;
 
         let mut offset = proof_len;
         let revealed_messages_len = u32::from_be_bytes(*array_ref![data, offset, 4]) as usize;
         offset += 4;
+        let expected = offset + revealed_messages_len * (4 + FR_COMPRESSED_SIZE);
+        if data.len() != expected {
+            return Err(BBSError::from(BBSErrorKind::InvalidNumberOfBytes(
+                expected,
+                data.len(),
+            )));
+        }
         let mut end = offset + 4;
 
         let mut revealed_messages = BTreeMap::new();
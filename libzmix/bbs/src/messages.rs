@@ -60,6 +60,7 @@ macro_rules! pm_hidden_raw {
 use crate::{ProofNonce, SignatureMessage};
 
 /// A message classification by the prover
+#[derive(Clone, Debug)]
 pub enum ProofMessage {
     /// Message will be revealed to a verifier
     Revealed(SignatureMessage),
@@ -79,6 +80,7 @@ impl ProofMessage {
 }
 
 /// Two types of hidden messages
+#[derive(Clone, Debug)]
 pub enum HiddenMessage {
     /// Indicates the message is hidden and no other work is involved
     ///     so a blinding factor will be generated specific to this proof
@@ -156,4 +156,73 @@ impl Prover {
             proof,
         })
     }
+
+    /// Create signature proofs of knowledge for several signatures, possibly from
+    /// different issuers, that also prove some hidden messages are equal.
+    /// Use `create_challenge_hash` and `generate_signature_pok` on the result to finish the
+    /// proofs so they all use the same challenge.
+    ///
+    /// # Arguments
+    /// * `requests` - Proof request from verifier for each signature
+    /// * `proof_messages` - The messages for each signature
+    /// * `signatures` - The signatures
+    /// * `equal_messages` - Groups of `(signature index, message index)` for hidden messages
+    ///   that are equal, like a link secret. Each group gets its own shared blinding factor.
+    pub fn commit_signatures_pok(
+        requests: &[ProofRequest],
+        proof_messages: &[Vec<ProofMessage>],
+        signatures: &[Signature],
+        equal_messages: &[Vec<(usize, usize)>],
+    ) -> Result<Vec<PoKOfSignature>, BBSError> {
+        if requests.len() != proof_messages.len() || requests.len() != signatures.len() {
+            return Err(BBSErrorKind::GeneralError {
+                msg: format!(
+                    "Expected the same number of requests, messages and signatures, found {}, {} and {}",
+                    requests.len(),
+                    proof_messages.len(),
+                    signatures.len()
+                ),
+            }
+            .into());
+        }
+
+        let mut proof_messages = proof_messages.to_vec();
+        for group in equal_messages {
+            let blinding = ProofNonce::random();
+            let mut value = None;
+            for &(s, m) in group {
+                let proof_message = proof_messages
+                    .get_mut(s)
+                    .and_then(|p| p.get_mut(m))
+                    .ok_or_else(|| BBSErrorKind::GeneralError {
+                        msg: format!("No message {} in signature {}", m, s),
+                    })?;
+                if let ProofMessage::Revealed(_) = proof_message {
+                    return Err(BBSErrorKind::GeneralError {
+                        msg: format!("Message {} in signature {} is revealed", m, s),
+                    }
+                    .into());
+                }
+                let message = proof_message.get_message();
+                if *value.get_or_insert(message) != message {
+                    return Err(BBSErrorKind::GeneralError {
+                        msg: format!(
+                            "Message {} in signature {} is not equal to the others",
+                            m, s
+                        ),
+                    }
+                    .into());
+                }
+                *proof_message =
+                    ProofMessage::Hidden(HiddenMessage::ExternalBlinding(message, blinding));
+            }
+        }
+
+        requests
+            .iter()
+            .zip(proof_messages.iter())
+            .zip(signatures.iter())
+            .map(|((r, m), s)| Self::commit_signature_pok(r, m.as_slice(), s))
+            .collect()
+    }
 }
@@ -67,6 +67,92 @@ impl Verifier {
         }
     }
 
+    /// Check signature proofs of knowledge created with `Prover::commit_signatures_pok`
+    /// and that the hidden messages in each group of `equal_messages` are equal.
+    /// Returns the revealed messages for each proof.
+    ///
+    /// # Arguments
+    /// * `proof_requests` - The request for each proof
+    /// * `signature_proofs` - The proofs, in the same order
+    /// * `equal_messages` - Groups of `(signature index, message index)` for hidden messages
+    ///   that must be equal
+    /// * `nonce` - The nonce sent to the prover
+    pub fn verify_signatures_pok(
+        proof_requests: &[ProofRequest],
+        signature_proofs: &[SignatureProof],
+        equal_messages: &[Vec<(usize, usize)>],
+        nonce: &ProofNonce,
+    ) -> Result<Vec<Vec<SignatureMessage>>, BBSError> {
+        if proof_requests.len() != signature_proofs.len() {
+            return Err(BBSErrorKind::GeneralError {
+                msg: format!(
+                    "Expected a proof for each request, found {} requests and {} proofs",
+                    proof_requests.len(),
+                    signature_proofs.len()
+                ),
+            }
+            .into());
+        }
+
+        let challenge = Self::create_challenge_hash(signature_proofs, proof_requests, nonce, None)?;
+        let mut revealed = Vec::with_capacity(signature_proofs.len());
+        for (request, signature_proof) in proof_requests.iter().zip(signature_proofs.iter()) {
+            match signature_proof.proof.verify(
+                &request.verification_key,
+                &signature_proof.revealed_messages,
+                &challenge,
+            )? {
+                PoKOfSignatureProofStatus::Success => revealed.push(
+                    signature_proof
+                        .revealed_messages
+                        .values()
+                        .copied()
+                        .collect::<Vec<SignatureMessage>>(),
+                ),
+                e => return Err(BBSErrorKind::InvalidProof { status: e }.into()),
+            }
+        }
+
+        for group in equal_messages {
+            let mut response = None;
+            for &(s, m) in group {
+                let r = Self::hidden_message_response(proof_requests, signature_proofs, s, m)?;
+                if *response.get_or_insert(r) != r {
+                    return Err(BBSErrorKind::InvalidProof {
+                        status: PoKOfSignatureProofStatus::BadHiddenMessage,
+                    }
+                    .into());
+                }
+            }
+        }
+        Ok(revealed)
+    }
+
+    /// The proof response for hidden message `m` in signature proof `s`
+    fn hidden_message_response(
+        proof_requests: &[ProofRequest],
+        signature_proofs: &[SignatureProof],
+        s: usize,
+        m: usize,
+    ) -> Result<SignatureMessage, BBSError> {
+        let hidden = |r: &ProofRequest, p: &SignatureProof| {
+            m < r.verification_key.message_count() && !p.revealed_messages.contains_key(&m)
+        };
+        match (proof_requests.get(s), signature_proofs.get(s)) {
+            (Some(r), Some(p)) if hidden(r, p) => {
+                // Responses are only kept for hidden messages, in message order
+                let hidden_index = (0..m)
+                    .filter(|i| !p.revealed_messages.contains_key(i))
+                    .count();
+                p.proof.get_resp_for_message(hidden_index)
+            }
+            _ => Err(BBSErrorKind::GeneralError {
+                msg: format!("Message {} in signature {} is not a hidden message", m, s),
+            }
+            .into()),
+        }
+    }
+
     /// Create a nonce used for the proof request context
     pub fn generate_proof_nonce() -> ProofNonce {
         ProofNonce::random()
@@ -677,3 +677,133 @@ fn bbs_demo() {
         proof2.proof.get_resp_for_message(3).unwrap()
     );
 }
+
+#[test]
+fn multi_signature_pok() {
+    let link_secret = Prover::new_link_secret();
+    let (pk1, sk1) = Issuer::new_keys(3).unwrap();
+    let (pk2, sk2) = Issuer::new_keys(2).unwrap();
+
+    let messages1 = vec![
+        link_secret,
+        SignatureMessage::hash(b"Alice"),
+        SignatureMessage::hash(b"Iceland"),
+    ];
+    let messages2 = vec![SignatureMessage::hash(b"member"), link_secret];
+    let signature1 = Issuer::sign(messages1.as_slice(), &sk1, &pk1).unwrap();
+    let signature2 = Issuer::sign(messages2.as_slice(), &sk2, &pk2).unwrap();
+
+    let nonce = Verifier::generate_proof_nonce();
+    let requests = vec![
+        Verifier::new_proof_request(&[1], &pk1).unwrap(),
+        Verifier::new_proof_request(&[0], &pk2).unwrap(),
+    ];
+    let proof_messages = vec![
+        vec![
+            pm_hidden_raw!(link_secret),
+            pm_revealed!(b"Alice"),
+            pm_hidden!(b"Iceland"),
+        ],
+        vec![pm_revealed!(b"member"), pm_hidden_raw!(link_secret)],
+    ];
+    // The link secret is message 0 of the first signature and message 1 of the second
+    let equal_messages = vec![vec![(0, 0), (1, 1)]];
+
+    let poks = Prover::commit_signatures_pok(
+        requests.as_slice(),
+        proof_messages.as_slice(),
+        &[signature1, signature2],
+        equal_messages.as_slice(),
+    )
+    .unwrap();
+    let challenge = Prover::create_challenge_hash(poks.as_slice(), None, &nonce).unwrap();
+    let proofs = poks
+        .into_iter()
+        .map(|pok| Prover::generate_signature_pok(pok, &challenge).unwrap())
+        .collect::<Vec<SignatureProof>>();
+
+    let revealed = Verifier::verify_signatures_pok(
+        requests.as_slice(),
+        proofs.as_slice(),
+        equal_messages.as_slice(),
+        &nonce,
+    )
+    .unwrap();
+    assert_eq!(revealed[0], vec![SignatureMessage::hash(b"Alice")]);
+    assert_eq!(revealed[1], vec![SignatureMessage::hash(b"member")]);
+
+    // The hidden message of the first signature is not the link secret
+    assert!(Verifier::verify_signatures_pok(
+        requests.as_slice(),
+        proofs.as_slice(),
+        &[vec![(0, 2), (1, 1)]],
+        &nonce,
+    )
+    .is_err());
+    // Revealed messages can't be part of an equality
+    assert!(Verifier::verify_signatures_pok(
+        requests.as_slice(),
+        proofs.as_slice(),
+        &[vec![(0, 1), (1, 1)]],
+        &nonce,
+    )
+    .is_err());
+    // Different nonce
+    assert!(Verifier::verify_signatures_pok(
+        requests.as_slice(),
+        proofs.as_slice(),
+        equal_messages.as_slice(),
+        &Verifier::generate_proof_nonce(),
+    )
+    .is_err());
+}
+
+#[test]
+fn multi_signature_pok_unequal_messages() {
+    let (pk1, sk1) = Issuer::new_keys(1).unwrap();
+    let (pk2, sk2) = Issuer::new_keys(1).unwrap();
+    let signature1 = Issuer::sign(&[SignatureMessage::hash(b"Alice")], &sk1, &pk1).unwrap();
+    let signature2 = Issuer::sign(&[SignatureMessage::hash(b"Bob")], &sk2, &pk2).unwrap();
+
+    let requests = vec![
+        Verifier::new_proof_request(&[], &pk1).unwrap(),
+        Verifier::new_proof_request(&[], &pk2).unwrap(),
+    ];
+    let proof_messages = vec![vec![pm_hidden!(b"Alice")], vec![pm_hidden!(b"Bob")]];
+    let equal_messages = vec![vec![(0, 0), (1, 0)]];
+
+    // The prover can't claim that different messages are equal
+    assert!(Prover::commit_signatures_pok(
+        requests.as_slice(),
+        proof_messages.as_slice(),
+        &[signature1.clone(), signature2.clone()],
+        equal_messages.as_slice(),
+    )
+    .is_err());
+
+    // Proofs made without the shared blinding don't pass the equality check
+    let nonce = Verifier::generate_proof_nonce();
+    let poks = Prover::commit_signatures_pok(
+        requests.as_slice(),
+        proof_messages.as_slice(),
+        &[signature1, signature2],
+        &[],
+    )
+    .unwrap();
+    let challenge = Prover::create_challenge_hash(poks.as_slice(), None, &nonce).unwrap();
+    let proofs = poks
+        .into_iter()
+        .map(|pok| Prover::generate_signature_pok(pok, &challenge).unwrap())
+        .collect::<Vec<SignatureProof>>();
+    assert!(
+        Verifier::verify_signatures_pok(requests.as_slice(), proofs.as_slice(), &[], &nonce)
+            .is_ok()
+    );
+    assert!(Verifier::verify_signatures_pok(
+        requests.as_slice(),
+        proofs.as_slice(),
+        equal_messages.as_slice(),
+        &nonce
+    )
+    .is_err());
+}
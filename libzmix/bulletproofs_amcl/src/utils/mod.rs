@@ -3,6 +3,7 @@ extern crate rand;
 pub mod hash_db;
 pub mod vector_poly;
 
+use amcl_wrapper::constants::MODBYTES;
 use amcl_wrapper::field_elem::FieldElement;
 use amcl_wrapper::group_elem::GroupElement;
 use amcl_wrapper::group_elem_g1::G1;
@@ -16,6 +17,14 @@ pub fn get_generators(prefix: &str, n: usize) -> Vec<G1> {
     gens
 }
 
+/// Convert through the big-endian bytes. `FieldElement::from(u64)` puts the value in a
+/// single limb of the big number, which is only correct below 2^58.
+pub fn field_elem_from_u64(x: u64) -> FieldElement {
+    let mut bytes = [0u8; MODBYTES];
+    bytes[MODBYTES - 8..].copy_from_slice(&x.to_be_bytes());
+    FieldElement::from_bytes(&bytes).expect("MODBYTES bytes are a field element")
+}
+
 pub fn gen_challenges(input: &[&G1], state: &mut Vec<u8>, n: usize) -> Vec<FieldElement> {
     let mut r = Vec::<FieldElement>::with_capacity(n);
     for i in 0..input.len() {
@@ -0,0 +1,115 @@
+#ifndef __zmix__included__
+#define __zmix__included__
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+typedef enum {
+    BBS_SUCCESS = 0,
+    BBS_KEYPAIR_ERROR = 1,
+    BBS_SIGNING_ERROR = 2,
+    BBS_VERIFY_ERROR = 3,
+    BBS_BLIND_SIGNING_ERROR = 4,
+    BBS_PROOF_ERROR = 5,
+    BBS_INVALID_PARAM = 6,
+} zmix_bbs_error_t;
+
+struct ByteBuffer {
+    int64_t len;
+    uint8_t *data;
+};
+
+struct ByteArray {
+    size_t length;
+    const uint8_t *data;
+};
+
+struct ExternError {
+    zmix_bbs_error_t code;
+    char* message; /* note: nullable */
+};
+
+extern void zmix_bytebuffer_free(struct ByteBuffer buffer);
+extern void zmix_string_free(char *s);
+
+extern int32_t zmix_bbs_get_message_size(void);
+extern int32_t zmix_bbs_get_secret_key_size(void);
+extern int32_t zmix_bbs_get_signature_size(void);
+extern int32_t zmix_bbs_get_nonce_size(void);
+
+extern int32_t zmix_bbs_keypair_new(uint32_t message_count,
+                                    struct ByteBuffer* public_key,
+                                    struct ByteBuffer* secret_key,
+                                    struct ExternError* err);
+
+extern int32_t zmix_bbs_hash_message(const struct ByteArray* const data,
+                                     struct ByteBuffer* message,
+                                     struct ExternError* err);
+
+extern int32_t zmix_bbs_link_secret_new(struct ByteBuffer* link_secret,
+                                        struct ExternError* err);
+
+extern int32_t zmix_bbs_nonce_new(struct ByteBuffer* nonce,
+                                  struct ExternError* err);
+
+extern int32_t zmix_bbs_sign(const struct ByteArray* const messages,
+                             const struct ByteArray* const secret_key,
+                             const struct ByteArray* const public_key,
+                             struct ByteBuffer* signature,
+                             struct ExternError* err);
+
+extern int32_t zmix_bbs_verify(const struct ByteArray* const messages,
+                               const struct ByteArray* const signature,
+                               const struct ByteArray* const public_key,
+                               struct ExternError* err);
+
+extern int32_t zmix_bbs_blind_signature_context_new(const struct ByteArray* const public_key,
+                                                    const struct ByteArray* const indices,
+                                                    const struct ByteArray* const messages,
+                                                    const struct ByteArray* const nonce,
+                                                    struct ByteBuffer* context,
+                                                    struct ByteBuffer* blinding,
+                                                    struct ExternError* err);
+
+extern int32_t zmix_bbs_blind_sign(const struct ByteArray* const context,
+                                   const struct ByteArray* const indices,
+                                   const struct ByteArray* const messages,
+                                   const struct ByteArray* const secret_key,
+                                   const struct ByteArray* const public_key,
+                                   const struct ByteArray* const nonce,
+                                   struct ByteBuffer* blind_signature,
+                                   struct ExternError* err);
+
+extern int32_t zmix_bbs_unblind_signature(const struct ByteArray* const blind_signature,
+                                          const struct ByteArray* const blinding,
+                                          const struct ByteArray* const messages,
+                                          const struct ByteArray* const public_key,
+                                          struct ByteBuffer* signature,
+                                          struct ExternError* err);
+
+extern int32_t zmix_bbs_proof_request_new(const struct ByteArray* const revealed,
+                                          const struct ByteArray* const public_key,
+                                          struct ByteBuffer* request,
+                                          struct ExternError* err);
+
+extern int32_t zmix_bbs_proof_new(const struct ByteArray* const request,
+                                  const struct ByteArray* const signature,
+                                  const struct ByteArray* const messages,
+                                  const struct ByteArray* const nonce,
+                                  struct ByteBuffer* proof,
+                                  struct ExternError* err);
+
+extern int32_t zmix_bbs_proof_verify(const struct ByteArray* const request,
+                                     const struct ByteArray* const proof,
+                                     const struct ByteArray* const nonce,
+                                     struct ExternError* err);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif
@@ -0,0 +1,741 @@
+// FFI functions to BBS+ signatures and selective disclosure proofs
+//
+// All values cross the boundary as bytes in the compressed form used by the bbs crate.
+// Lists of messages are the 32 byte messages concatenated, use `zmix_bbs_hash_message`
+// to turn arbitrary data into a message. Lists of message indices are 4 byte big-endian
+// integers concatenated.
+//
+// Example of how to use these functions from C
+// #include <stdio.h>
+// #include <string.h>
+// #include "zmix.h"
+//
+// int main(void) {
+//     struct ByteBuffer public_key, secret_key, message, signature;
+//     struct ExternError err;
+//     struct ByteArray data, messages, pk, sk, sig;
+//     const char *name = "Alice";
+//
+//     if (!zmix_bbs_keypair_new(1, &public_key, &secret_key, &err)) {
+//         printf("Failed to generate keys: %s\n", err.message);
+//         zmix_string_free(err.message);
+//         return 1;
+//     }
+//
+//     data.length = strlen(name);
+//     data.data = (const uint8_t *)name;
+//     zmix_bbs_hash_message(&data, &message, &err);
+//
+//     messages.length = message.len;
+//     messages.data = message.data;
+//     pk.length = public_key.len;
+//     pk.data = public_key.data;
+//     sk.length = secret_key.len;
+//     sk.data = secret_key.data;
+//     if (!zmix_bbs_sign(&messages, &sk, &pk, &signature, &err)) {
+//         printf("Failed to sign: %s\n", err.message);
+//         zmix_string_free(err.message);
+//         return 1;
+//     }
+//
+//     sig.length = signature.len;
+//     sig.data = signature.data;
+//     if (!zmix_bbs_verify(&messages, &sig, &pk, &err)) {
+//         printf("Verification failed\n");
+//     }
+//
+//     zmix_bytebuffer_free(signature);
+//     zmix_bytebuffer_free(message);
+//     zmix_bytebuffer_free(secret_key);
+//     zmix_bytebuffer_free(public_key);
+//     return 0;
+// }
+
+use bbs::prelude::*;
+use ffi_support::{ByteBuffer, ErrorCode, ExternError};
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
+use ursa::ffi::ByteArray;
+
+pub mod bbs_error_codes {
+    pub const KEYPAIR_ERROR: i32 = 1;
+    pub const SIGNING_ERROR: i32 = 2;
+    pub const VERIFY_ERROR: i32 = 3;
+    pub const BLIND_SIGNING_ERROR: i32 = 4;
+    pub const PROOF_ERROR: i32 = 5;
+    pub const INVALID_PARAM: i32 = 6;
+}
+
+/// Return the number of bytes in a message - 32 bytes
+#[no_mangle]
+pub extern "C" fn zmix_bbs_get_message_size() -> i32 {
+    FR_COMPRESSED_SIZE as i32
+}
+
+/// Return the number of bytes in a secret key - 32 bytes
+#[no_mangle]
+pub extern "C" fn zmix_bbs_get_secret_key_size() -> i32 {
+    FR_COMPRESSED_SIZE as i32
+}
+
+/// Return the number of bytes in a signature or blind signature - 112 bytes
+#[no_mangle]
+pub extern "C" fn zmix_bbs_get_signature_size() -> i32 {
+    SIGNATURE_COMPRESSED_SIZE as i32
+}
+
+/// Return the number of bytes in a nonce or signature blinding - 32 bytes
+#[no_mangle]
+pub extern "C" fn zmix_bbs_get_nonce_size() -> i32 {
+    FR_COMPRESSED_SIZE as i32
+}
+
+/// Create a new keypair that can sign `message_count` messages.
+/// Caller will need to call `zmix_bytebuffer_free` on `public_key` and `secret_key`
+/// to free the memory.
+/// If an error occurs, caller will need to call `zmix_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_keypair_new(
+    message_count: u32,
+    public_key: &mut ByteBuffer,
+    secret_key: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    match Issuer::new_keys(message_count as usize) {
+        Ok((pk, sk)) => {
+            *err = ExternError::success();
+            *public_key = ByteBuffer::from_vec(pk.to_bytes_compressed_form());
+            *secret_key = ByteBuffer::from_vec(sk.to_bytes_compressed_form().to_vec());
+            1
+        }
+        Err(e) => {
+            *err = ExternError::new_error(
+                ErrorCode::new(bbs_error_codes::KEYPAIR_ERROR),
+                e.to_string(),
+            );
+            0
+        }
+    }
+}
+
+/// Hash arbitrary data to a message that can be signed.
+/// Caller will need to call `zmix_bytebuffer_free` on `message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_hash_message(
+    data: &ByteArray,
+    message: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    *err = ExternError::success();
+    *message = ByteBuffer::from_vec(
+        SignatureMessage::hash(data.to_vec())
+            .to_bytes_compressed_form()
+            .to_vec(),
+    );
+    1
+}
+
+/// Create a random link secret. It should only ever be signed as a hidden message.
+/// Caller will need to call `zmix_bytebuffer_free` on `link_secret` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_link_secret_new(
+    link_secret: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    *err = ExternError::success();
+    *link_secret = ByteBuffer::from_vec(
+        Prover::new_link_secret()
+            .to_bytes_compressed_form()
+            .to_vec(),
+    );
+    1
+}
+
+/// Create a random nonce for blind signing or for a proof.
+/// Caller will need to call `zmix_bytebuffer_free` on `nonce` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_nonce_new(nonce: &mut ByteBuffer, err: &mut ExternError) -> i32 {
+    *err = ExternError::success();
+    *nonce = ByteBuffer::from_vec(ProofNonce::random().to_bytes_compressed_form().to_vec());
+    1
+}
+
+/// Sign all `messages`.
+/// Caller will need to call `zmix_bytebuffer_free` on `signature` to free the memory.
+/// If an error occurs, caller will need to call `zmix_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_sign(
+    messages: &ByteArray,
+    secret_key: &ByteArray,
+    public_key: &ByteArray,
+    signature: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let result = (|| {
+        let messages = to_messages(messages)?;
+        let sk = to_secret_key(secret_key)?;
+        let pk = to_public_key(public_key)?;
+        Issuer::sign(messages.as_slice(), &sk, &pk)
+            .map(|s| s.to_bytes_compressed_form().to_vec())
+            .map_err(|e| (bbs_error_codes::SIGNING_ERROR, e.to_string()))
+    })();
+    set_output(result, signature, err)
+}
+
+/// Verify a signature over all `messages`.
+/// Returns 1 if the signature is valid and 0 otherwise.
+/// `err` is only set when the inputs can't be read.
+/// If an error occurs, caller will need to call `zmix_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_verify(
+    messages: &ByteArray,
+    signature: &ByteArray,
+    public_key: &ByteArray,
+    err: &mut ExternError,
+) -> i32 {
+    let result = (|| {
+        let messages = to_messages(messages)?;
+        let signature = Signature::try_from(signature.to_vec().as_slice())
+            .map_err(|e| invalid_param("signature", e))?;
+        let pk = to_public_key(public_key)?;
+        signature
+            .verify(messages.as_slice(), &pk)
+            .map_err(|e| (bbs_error_codes::VERIFY_ERROR, e.to_string()))
+    })();
+    set_status(result, err)
+}
+
+/// Commit to the messages at `indices` that will be hidden from the issuer.
+/// `nonce` comes from the issuer.
+/// Send `context` to the issuer and keep `blinding` to unblind the signature.
+/// Caller will need to call `zmix_bytebuffer_free` on `context` and `blinding`
+/// to free the memory.
+/// If an error occurs, caller will need to call `zmix_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_blind_signature_context_new(
+    public_key: &ByteArray,
+    indices: &ByteArray,
+    messages: &ByteArray,
+    nonce: &ByteArray,
+    context: &mut ByteBuffer,
+    blinding: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let result = (|| {
+        let pk = to_public_key(public_key)?;
+        let messages = to_indexed_messages(indices, messages)?;
+        let nonce = to_nonce(nonce)?;
+        Prover::new_blind_signature_context(&pk, &messages, &nonce)
+            .map_err(|e| (bbs_error_codes::BLIND_SIGNING_ERROR, e.to_string()))
+    })();
+    match result {
+        Ok((ctx, b)) => {
+            *err = ExternError::success();
+            *context = ByteBuffer::from_vec(ctx.to_bytes_compressed_form());
+            *blinding = ByteBuffer::from_vec(b.to_bytes_compressed_form().to_vec());
+            1
+        }
+        Err((code, msg)) => {
+            *err = ExternError::new_error(ErrorCode::new(code), msg);
+            0
+        }
+    }
+}
+
+/// Check the holder's `context` and sign it with the known messages at `indices`.
+/// `nonce` is the one sent to the holder.
+/// Caller will need to call `zmix_bytebuffer_free` on `blind_signature` to free the memory.
+/// If an error occurs, caller will need to call `zmix_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_blind_sign(
+    context: &ByteArray,
+    indices: &ByteArray,
+    messages: &ByteArray,
+    secret_key: &ByteArray,
+    public_key: &ByteArray,
+    nonce: &ByteArray,
+    blind_signature: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let result = (|| {
+        let context = BlindSignatureContext::from_bytes_compressed_form(context.to_vec())
+            .map_err(|e| invalid_param("context", e))?;
+        let messages = to_indexed_messages(indices, messages)?;
+        let sk = to_secret_key(secret_key)?;
+        let pk = to_public_key(public_key)?;
+        let nonce = to_nonce(nonce)?;
+        Issuer::blind_sign(&context, &messages, &sk, &pk, &nonce)
+            .map(|s| s.to_bytes_compressed_form().to_vec())
+            .map_err(|e| (bbs_error_codes::BLIND_SIGNING_ERROR, e.to_string()))
+    })();
+    set_output(result, blind_signature, err)
+}
+
+/// Unblind a signature from the issuer and verify it over all `messages`.
+/// Caller will need to call `zmix_bytebuffer_free` on `signature` to free the memory.
+/// If an error occurs, caller will need to call `zmix_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_unblind_signature(
+    blind_signature: &ByteArray,
+    blinding: &ByteArray,
+    messages: &ByteArray,
+    public_key: &ByteArray,
+    signature: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let result = (|| {
+        let blind_signature = BlindSignature::try_from(blind_signature.to_vec().as_slice())
+            .map_err(|e| invalid_param("blind_signature", e))?;
+        let blinding = SignatureBlinding::try_from(blinding.to_vec().as_slice())
+            .map_err(|e| invalid_param("blinding", e))?;
+        let messages = to_messages(messages)?;
+        let pk = to_public_key(public_key)?;
+        Prover::complete_signature(&pk, messages.as_slice(), &blind_signature, &blinding)
+            .map(|s| s.to_bytes_compressed_form().to_vec())
+            .map_err(|e| (bbs_error_codes::BLIND_SIGNING_ERROR, e.to_string()))
+    })();
+    set_output(result, signature, err)
+}
+
+/// Create a request for a proof that reveals the messages at `revealed`.
+/// Caller will need to call `zmix_bytebuffer_free` on `request` to free the memory.
+/// If an error occurs, caller will need to call `zmix_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_proof_request_new(
+    revealed: &ByteArray,
+    public_key: &ByteArray,
+    request: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let result = (|| {
+        let revealed = to_indices(revealed)?;
+        let pk = to_public_key(public_key)?;
+        Verifier::new_proof_request(revealed.as_slice(), &pk)
+            .map(|r| r.to_bytes_compressed_form())
+            .map_err(|e| (bbs_error_codes::PROOF_ERROR, e.to_string()))
+    })();
+    set_output(result, request, err)
+}
+
+/// Create a proof for `request` from a signature over all `messages`.
+/// The messages not revealed by the request are hidden.
+/// Caller will need to call `zmix_bytebuffer_free` on `proof` to free the memory.
+/// If an error occurs, caller will need to call `zmix_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_proof_new(
+    request: &ByteArray,
+    signature: &ByteArray,
+    messages: &ByteArray,
+    nonce: &ByteArray,
+    proof: &mut ByteBuffer,
+    err: &mut ExternError,
+) -> i32 {
+    let result = (|| {
+        let request = to_proof_request(request)?;
+        let signature = Signature::try_from(signature.to_vec().as_slice())
+            .map_err(|e| invalid_param("signature", e))?;
+        let messages = to_messages(messages)?
+            .into_iter()
+            .enumerate()
+            .map(|(i, m)| {
+                if request.revealed_messages.contains(&i) {
+                    ProofMessage::Revealed(m)
+                } else {
+                    ProofMessage::Hidden(HiddenMessage::ProofSpecificBlinding(m))
+                }
+            })
+            .collect::<Vec<ProofMessage>>();
+        let nonce = to_nonce(nonce)?;
+
+        let pok = Prover::commit_signature_pok(&request, messages.as_slice(), &signature)
+            .map_err(|e| (bbs_error_codes::PROOF_ERROR, e.to_string()))?;
+        let challenge = Prover::create_challenge_hash(std::slice::from_ref(&pok), None, &nonce)
+            .map_err(|e| (bbs_error_codes::PROOF_ERROR, e.to_string()))?;
+        Prover::generate_signature_pok(pok, &challenge)
+            .map(|p| p.to_bytes_compressed_form())
+            .map_err(|e| (bbs_error_codes::PROOF_ERROR, e.to_string()))
+    })();
+    set_output(result, proof, err)
+}
+
+/// Verify a proof created for `request` and `nonce`.
+/// Returns 1 if the proof is valid and 0 otherwise.
+/// `err` is only set when the inputs can't be read or the proof can't be checked.
+/// If an error occurs, caller will need to call `zmix_string_free`
+/// on `err.message` to free the memory.
+#[no_mangle]
+pub extern "C" fn zmix_bbs_proof_verify(
+    request: &ByteArray,
+    proof: &ByteArray,
+    nonce: &ByteArray,
+    err: &mut ExternError,
+) -> i32 {
+    let result = (|| {
+        let request = to_proof_request(request)?;
+        let proof = SignatureProof::from_bytes_compressed_form(proof.to_vec())
+            .map_err(|e| invalid_param("proof", e))?;
+        let nonce = to_nonce(nonce)?;
+        match Verifier::verify_signature_pok(&request, &proof, &nonce) {
+            Ok(_) => Ok(true),
+            Err(e) => match e.kind() {
+                BBSErrorKind::InvalidProof { .. } => Ok(false),
+                _ => Err((bbs_error_codes::PROOF_ERROR, e.to_string())),
+            },
+        }
+    })();
+    set_status(result, err)
+}
+
+type FfiResult<T> = Result<T, (i32, String)>;
+
+fn set_output(result: FfiResult<Vec<u8>>, output: &mut ByteBuffer, err: &mut ExternError) -> i32 {
+    match result {
+        Ok(bytes) => {
+            *err = ExternError::success();
+            *output = ByteBuffer::from_vec(bytes);
+            1
+        }
+        Err((code, msg)) => {
+            *err = ExternError::new_error(ErrorCode::new(code), msg);
+            0
+        }
+    }
+}
+
+fn set_status(result: FfiResult<bool>, err: &mut ExternError) -> i32 {
+    match result {
+        Ok(valid) => {
+            *err = ExternError::success();
+            valid as i32
+        }
+        Err((code, msg)) => {
+            *err = ExternError::new_error(ErrorCode::new(code), msg);
+            0
+        }
+    }
+}
+
+fn invalid_param<E: std::fmt::Display>(name: &str, e: E) -> (i32, String) {
+    (
+        bbs_error_codes::INVALID_PARAM,
+        format!("Invalid {}: {}", name, e),
+    )
+}
+
+fn to_public_key(public_key: &ByteArray) -> FfiResult<PublicKey> {
+    PublicKey::from_bytes_compressed_form(public_key.to_vec())
+        .map_err(|e| invalid_param("public_key", e))
+}
+
+fn to_secret_key(secret_key: &ByteArray) -> FfiResult<SecretKey> {
+    SecretKey::try_from(secret_key.to_vec().as_slice()).map_err(|e| invalid_param("secret_key", e))
+}
+
+fn to_nonce(nonce: &ByteArray) -> FfiResult<ProofNonce> {
+    ProofNonce::try_from(nonce.to_vec().as_slice()).map_err(|e| invalid_param("nonce", e))
+}
+
+fn to_proof_request(request: &ByteArray) -> FfiResult<ProofRequest> {
+    ProofRequest::from_bytes_compressed_form(request.to_vec())
+        .map_err(|e| invalid_param("request", e))
+}
+
+fn to_messages(messages: &ByteArray) -> FfiResult<Vec<SignatureMessage>> {
+    let bytes = messages.to_vec();
+    let chunks = bytes.chunks_exact(FR_COMPRESSED_SIZE);
+    if !chunks.remainder().is_empty() {
+        return Err(invalid_param(
+            "messages",
+            format!("length must be a multiple of {}", FR_COMPRESSED_SIZE),
+        ));
+    }
+    chunks
+        .map(|m| SignatureMessage::try_from(m).map_err(|e| invalid_param("messages", e)))
+        .collect()
+}
+
+fn to_indices(indices: &ByteArray) -> FfiResult<Vec<usize>> {
+    let bytes = indices.to_vec();
+    let chunks = bytes.chunks_exact(4);
+    if !chunks.remainder().is_empty() {
+        return Err(invalid_param("indices", "length must be a multiple of 4"));
+    }
+    Ok(chunks
+        .map(|i| u32::from_be_bytes(*array_ref![i, 0, 4]) as usize)
+        .collect())
+}
+
+fn to_indexed_messages(
+    indices: &ByteArray,
+    messages: &ByteArray,
+) -> FfiResult<BTreeMap<usize, SignatureMessage>> {
+    let indices = to_indices(indices)?;
+    let messages = to_messages(messages)?;
+    if indices.len() != messages.len() {
+        return Err(invalid_param(
+            "indices",
+            "must have one index for each message",
+        ));
+    }
+    Ok(indices.into_iter().zip(messages).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keypair(message_count: u32) -> (Vec<u8>, Vec<u8>) {
+        let mut public_key = ByteBuffer::default();
+        let mut secret_key = ByteBuffer::default();
+        let mut error = ExternError::success();
+        let res = zmix_bbs_keypair_new(message_count, &mut public_key, &mut secret_key, &mut error);
+        assert_eq!(res, 1);
+        assert!(error.get_code().is_success());
+        (public_key.into_vec(), secret_key.into_vec())
+    }
+
+    fn message(data: &[u8]) -> Vec<u8> {
+        let mut message = ByteBuffer::default();
+        let mut error = ExternError::success();
+        assert_eq!(
+            zmix_bbs_hash_message(&ByteArray::from(data), &mut message, &mut error),
+            1
+        );
+        message.into_vec()
+    }
+
+    fn nonce() -> Vec<u8> {
+        let mut nonce = ByteBuffer::default();
+        let mut error = ExternError::success();
+        assert_eq!(zmix_bbs_nonce_new(&mut nonce, &mut error), 1);
+        nonce.into_vec()
+    }
+
+    fn indices(indices: &[u32]) -> Vec<u8> {
+        indices
+            .iter()
+            .flat_map(|i| i.to_be_bytes().to_vec())
+            .collect()
+    }
+
+    #[test]
+    fn ffi_sign_and_verify() {
+        let (pk, sk) = keypair(2);
+        assert_eq!(sk.len(), zmix_bbs_get_secret_key_size() as usize);
+        let mut messages = message(b"Alice");
+        messages.extend_from_slice(&message(b"21"));
+
+        let mut signature = ByteBuffer::default();
+        let mut error = ExternError::success();
+        let res = zmix_bbs_sign(
+            &ByteArray::from(&messages),
+            &ByteArray::from(&sk),
+            &ByteArray::from(&pk),
+            &mut signature,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        let signature = signature.into_vec();
+        assert_eq!(signature.len(), zmix_bbs_get_signature_size() as usize);
+
+        let res = zmix_bbs_verify(
+            &ByteArray::from(&messages),
+            &ByteArray::from(&signature),
+            &ByteArray::from(&pk),
+            &mut error,
+        );
+        assert_eq!(res, 1);
+
+        // Different message
+        let mut other = message(b"Bob");
+        other.extend_from_slice(&message(b"21"));
+        let res = zmix_bbs_verify(
+            &ByteArray::from(&other),
+            &ByteArray::from(&signature),
+            &ByteArray::from(&pk),
+            &mut error,
+        );
+        assert_eq!(res, 0);
+        assert!(error.get_code().is_success());
+
+        // Truncated message
+        let res = zmix_bbs_verify(
+            &ByteArray::from(&messages[1..]),
+            &ByteArray::from(&signature),
+            &ByteArray::from(&pk),
+            &mut error,
+        );
+        assert_eq!(res, 0);
+        assert_eq!(
+            error.get_code(),
+            ErrorCode::new(bbs_error_codes::INVALID_PARAM)
+        );
+        unsafe { error.manually_release() };
+    }
+
+    #[test]
+    fn ffi_blind_sign() {
+        let (pk, sk) = keypair(2);
+        let link_secret = {
+            let mut link_secret = ByteBuffer::default();
+            let mut error = ExternError::success();
+            assert_eq!(zmix_bbs_link_secret_new(&mut link_secret, &mut error), 1);
+            link_secret.into_vec()
+        };
+        let name = message(b"Alice");
+        let signing_nonce = nonce();
+
+        let mut context = ByteBuffer::default();
+        let mut blinding = ByteBuffer::default();
+        let mut error = ExternError::success();
+        let res = zmix_bbs_blind_signature_context_new(
+            &ByteArray::from(&pk),
+            &ByteArray::from(&indices(&[0])),
+            &ByteArray::from(&link_secret),
+            &ByteArray::from(&signing_nonce),
+            &mut context,
+            &mut blinding,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        let context = context.into_vec();
+        let blinding = blinding.into_vec();
+
+        let mut blind_signature = ByteBuffer::default();
+        let res = zmix_bbs_blind_sign(
+            &ByteArray::from(&context),
+            &ByteArray::from(&indices(&[1])),
+            &ByteArray::from(&name),
+            &ByteArray::from(&sk),
+            &ByteArray::from(&pk),
+            &ByteArray::from(&signing_nonce),
+            &mut blind_signature,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        let blind_signature = blind_signature.into_vec();
+
+        let mut messages = link_secret.clone();
+        messages.extend_from_slice(&name);
+        let mut signature = ByteBuffer::default();
+        let res = zmix_bbs_unblind_signature(
+            &ByteArray::from(&blind_signature),
+            &ByteArray::from(&blinding),
+            &ByteArray::from(&messages),
+            &ByteArray::from(&pk),
+            &mut signature,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        let signature = signature.into_vec();
+        assert_eq!(
+            zmix_bbs_verify(
+                &ByteArray::from(&messages),
+                &ByteArray::from(&signature),
+                &ByteArray::from(&pk),
+                &mut error,
+            ),
+            1
+        );
+
+        // The issuer checks the context against its nonce
+        let mut blind_signature = ByteBuffer::default();
+        let res = zmix_bbs_blind_sign(
+            &ByteArray::from(&context),
+            &ByteArray::from(&indices(&[1])),
+            &ByteArray::from(&name),
+            &ByteArray::from(&sk),
+            &ByteArray::from(&pk),
+            &ByteArray::from(&nonce()),
+            &mut blind_signature,
+            &mut error,
+        );
+        assert_eq!(res, 0);
+        assert_eq!(
+            error.get_code(),
+            ErrorCode::new(bbs_error_codes::BLIND_SIGNING_ERROR)
+        );
+        unsafe { error.manually_release() };
+    }
+
+    #[test]
+    fn ffi_proof() {
+        let (pk, sk) = keypair(3);
+        let mut messages = message(b"Alice");
+        messages.extend_from_slice(&message(b"21"));
+        messages.extend_from_slice(&message(b"Iceland"));
+
+        let mut signature = ByteBuffer::default();
+        let mut error = ExternError::success();
+        zmix_bbs_sign(
+            &ByteArray::from(&messages),
+            &ByteArray::from(&sk),
+            &ByteArray::from(&pk),
+            &mut signature,
+            &mut error,
+        );
+        let signature = signature.into_vec();
+
+        let mut request = ByteBuffer::default();
+        let res = zmix_bbs_proof_request_new(
+            &ByteArray::from(&indices(&[0, 2])),
+            &ByteArray::from(&pk),
+            &mut request,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        let request = request.into_vec();
+
+        let proof_nonce = nonce();
+        let mut proof = ByteBuffer::default();
+        let res = zmix_bbs_proof_new(
+            &ByteArray::from(&request),
+            &ByteArray::from(&signature),
+            &ByteArray::from(&messages),
+            &ByteArray::from(&proof_nonce),
+            &mut proof,
+            &mut error,
+        );
+        assert_eq!(res, 1);
+        let proof = proof.into_vec();
+
+        let res = zmix_bbs_proof_verify(
+            &ByteArray::from(&request),
+            &ByteArray::from(&proof),
+            &ByteArray::from(&proof_nonce),
+            &mut error,
+        );
+        assert_eq!(res, 1);
+
+        // Different nonce
+        let res = zmix_bbs_proof_verify(
+            &ByteArray::from(&request),
+            &ByteArray::from(&proof),
+            &ByteArray::from(&nonce()),
+            &mut error,
+        );
+        assert_eq!(res, 0);
+        assert!(error.get_code().is_success());
+
+        // Malformed proof
+        let res = zmix_bbs_proof_verify(
+            &ByteArray::from(&request),
+            &ByteArray::from(&proof[1..]),
+            &ByteArray::from(&proof_nonce),
+            &mut error,
+        );
+        assert_eq!(res, 0);
+        assert_eq!(
+            error.get_code(),
+            ErrorCode::new(bbs_error_codes::INVALID_PARAM)
+        );
+    }
+}
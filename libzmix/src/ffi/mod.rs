@@ -1 +1,4 @@
+pub mod bbs;
 
+define_bytebuffer_destructor!(zmix_bytebuffer_free);
+define_string_destructor!(zmix_string_free);
@@ -2,16 +2,22 @@
 //extern crate lazy_static;
 pub extern crate amcl_wrapper;
 extern crate failure;
+#[cfg(feature = "ffi")]
+#[macro_use]
+extern crate ffi_support;
 #[macro_use]
 extern crate arrayref;
 #[macro_use]
 extern crate serde;
+extern crate serde_cbor;
 extern crate serde_json;
 
+#[cfg(feature = "bbs")]
+#[macro_use]
+extern crate bbs;
 extern crate bulletproofs_amcl as bulletproofs;
 #[cfg(feature = "hash2curve")]
 pub extern crate hash2curve;
-#[cfg(feature = "hex")]
 extern crate hex;
 extern crate merlin;
 extern crate rand;
@@ -26,6 +32,11 @@ pub extern crate ursa;
 pub mod commitments;
 #[macro_use]
 pub mod errors;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod prf;
 pub mod signatures;
 #[cfg(feature = "ver_enc")]
 pub mod verifiable_encryption;
+#[cfg(all(feature = "bbs", feature = "ver_enc"))]
+pub mod zkl;
@@ -0,0 +1,107 @@
+// Dodis-Yampolskiy verifiable random function over G1 of BLS12-381.
+// Based on the paper A Verifiable Random Function With Short Proofs and Keys, https://eprint.iacr.org/2004/310.pdf
+// F_k(x) = g^{1/(k + x)}. When the key k is a holder's link secret and x is derived from a scope,
+// the output is a pseudonym that is stable within the scope and unlinkable across scopes.
+
+use super::PRF;
+use amcl_wrapper::field_elem::FieldElement;
+use amcl_wrapper::group_elem::GroupElement;
+use amcl_wrapper::group_elem_g1::G1;
+use std::collections::HashMap;
+
+pub struct DodisYampolskiyPRF {
+    key: FieldElement,
+    generator: G1,
+}
+
+impl DodisYampolskiyPRF {
+    /// Use the generator of G1 unless `generator` is given
+    pub fn with_key(key: FieldElement, generator: Option<G1>) -> Self {
+        Self {
+            key,
+            generator: generator.unwrap_or_else(G1::generator),
+        }
+    }
+
+    /// Map arbitrary input, like a scope, to the field
+    pub fn input_from_bytes(input: &[u8]) -> FieldElement {
+        FieldElement::from_msg_hash(input)
+    }
+
+    pub fn generator(&self) -> &G1 {
+        &self.generator
+    }
+
+    /// g^{1/(key + x)}. Returns None in the negligible case that key + x = 0.
+    pub fn eval(&self, x: &FieldElement) -> Option<G1> {
+        let exp = &self.key + x;
+        if exp.is_zero() {
+            return None;
+        }
+        Some(&self.generator * &exp.inverse())
+    }
+}
+
+impl PRF for DodisYampolskiyPRF {
+    /// `args` must contain "key", a serialized field element, and can contain "generator",
+    /// a serialized G1 element. Panics if they can't be deserialized.
+    fn new(args: HashMap<String, &[u8]>) -> Self {
+        let key = FieldElement::from_bytes(args["key"]).expect("invalid key");
+        let generator = args
+            .get("generator")
+            .map(|g| G1::from_bytes(g).expect("invalid generator"));
+        Self::with_key(key, generator)
+    }
+
+    fn compute(&self, input: &[u8]) -> Vec<u8> {
+        self.eval(&Self::input_from_bytes(input))
+            .map(|y| y.to_bytes())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn eval_dodis_yampolskiy_prf() {
+        let key = FieldElement::random();
+        let prf = DodisYampolskiyPRF::with_key(key.clone(), None);
+        let x = DodisYampolskiyPRF::input_from_bytes(b"scope 1");
+        let y = prf.eval(&x).unwrap();
+
+        // y^{key + x} = g
+        assert_eq!(&y * &(&key + &x), G1::generator());
+        assert_eq!(prf.eval(&x).unwrap(), y);
+        assert_ne!(
+            prf.eval(&DodisYampolskiyPRF::input_from_bytes(b"scope 2"))
+                .unwrap(),
+            y
+        );
+        assert_ne!(
+            DodisYampolskiyPRF::with_key(FieldElement::random(), None)
+                .eval(&x)
+                .unwrap(),
+            y
+        );
+        assert!(prf.eval(&key.negation()).is_none());
+    }
+
+    #[test]
+    fn compute_dodis_yampolskiy_prf() {
+        let key = FieldElement::random();
+        let generator = G1::from_msg_hash(b"generator");
+        let key_bytes = key.to_bytes();
+        let generator_bytes = generator.to_bytes();
+        let mut args = HashMap::new();
+        args.insert("key".to_string(), key_bytes.as_slice());
+        args.insert("generator".to_string(), generator_bytes.as_slice());
+        let prf = DodisYampolskiyPRF::new(args);
+
+        let expected = DodisYampolskiyPRF::with_key(key, Some(generator))
+            .eval(&DodisYampolskiyPRF::input_from_bytes(b"scope"))
+            .unwrap();
+        assert_eq!(prf.compute(b"scope"), expected.to_bytes());
+    }
+}
@@ -1,5 +1,7 @@
 use std::collections::HashMap;
 
+pub mod dodis_yampolskiy;
+
 /*
 Pseudo Random Function like modular exponentiation.
 Say you want to exponentiate in a prime field `p` with generator `g`, there would be an implementation of this
@@ -18,7 +20,7 @@ let x = [100, 200, 250, 901, .....]
 owf.compute(&x)
 ```
 */
-trait PRF {
+pub trait PRF {
     // Creates a new Pseudo Random Function. `args` is a map, some example args can be curve type,
     // curve order, compression support.
     fn new(args: HashMap<String, &[u8]>) -> Self;
@@ -0,0 +1,96 @@
+// Credential clause: proof of knowledge of a BBS+ signature with selective disclosure.
+// Hidden attributes use the blinding shared by all clauses so the verifier can check
+// that an attribute index used by several clauses holds the same value.
+
+use super::{record_response, ZKLError};
+use bbs::prelude::*;
+use std::collections::{BTreeMap, BTreeSet};
+use zkl::spec::CredentialClause;
+
+pub(crate) fn validate(clause: &CredentialClause, attribute_count: u32) -> Result<(), ZKLError> {
+    if clause.attrs.is_empty()
+        || clause.attrs.len() != clause.pk.message_count()
+        || clause.attrs.iter().any(|a| *a >= attribute_count)
+    {
+        return Err(ZKLError::InvalidProofSpec);
+    }
+    clause.pk.validate().map_err(|_| ZKLError::InvalidProofSpec)
+}
+
+pub(crate) fn commit(
+    clause: &CredentialClause,
+    signature: &Signature,
+    attributes: &BTreeMap<u32, SignatureMessage>,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+    blindings: &BTreeMap<u32, ProofNonce>,
+) -> Result<PoKOfSignature, ZKLError> {
+    let mut proof_messages = Vec::with_capacity(clause.attrs.len());
+    for a in &clause.attrs {
+        let message = *attributes.get(a).ok_or(ZKLError::InvalidWitness)?;
+        match disclosed.get(a) {
+            Some(d) => {
+                if *d != message {
+                    return Err(ZKLError::InvalidWitness);
+                }
+                proof_messages.push(pm_revealed_raw!(message));
+            }
+            None => proof_messages.push(pm_hidden_raw!(message, blindings[a])),
+        }
+    }
+    PoKOfSignature::init(signature, &clause.pk, proof_messages.as_slice())
+        .map_err(|_| ZKLError::InvalidWitness)
+}
+
+pub(crate) fn challenge_bytes(
+    clause: &CredentialClause,
+    proof: &PoKOfSignatureProof,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+) -> Vec<u8> {
+    let revealed: BTreeSet<usize> = revealed_messages(clause, disclosed)
+        .keys()
+        .copied()
+        .collect();
+    proof.get_bytes_for_challenge(revealed, &clause.pk)
+}
+
+pub(crate) fn verify(
+    clause: &CredentialClause,
+    proof: &PoKOfSignatureProof,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+    challenge: &ProofChallenge,
+    responses: &mut BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    let revealed = revealed_messages(clause, disclosed);
+    let status = proof
+        .verify(&clause.pk, &revealed, challenge)
+        .map_err(|_| ZKLError::InvalidProof)?;
+    if !status.is_valid() {
+        return Err(ZKLError::InvalidProof);
+    }
+
+    // Responses are ordered by the position of the hidden message in the signature
+    let hidden = clause
+        .attrs
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| !revealed.contains_key(i));
+    for (hidden_index, (_, a)) in hidden.enumerate() {
+        let response = proof
+            .get_resp_for_message(hidden_index)
+            .map_err(|_| ZKLError::InvalidProof)?;
+        record_response(responses, *a, response)?;
+    }
+    Ok(())
+}
+
+fn revealed_messages(
+    clause: &CredentialClause,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+) -> BTreeMap<usize, SignatureMessage> {
+    clause
+        .attrs
+        .iter()
+        .enumerate()
+        .filter_map(|(i, a)| disclosed.get(a).map(|m| (i, *m)))
+        .collect()
+}
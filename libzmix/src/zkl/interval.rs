@@ -0,0 +1,161 @@
+// Interval clause: a hidden integer attribute lies in [min, max].
+// The R1CS bound check proves the range of the value in the attribute commitment.
+
+use super::pedersen::{
+    commitment_bytes, opening_challenge_bytes, verify_opening, Generators, OpeningCommitted,
+};
+use super::ZKLError;
+use amcl_wrapper::field_elem::FieldElement;
+use amcl_wrapper::group_elem_g1::G1;
+use bbs::prelude::*;
+use bulletproofs::r1cs::gadgets::bound_check::{
+    gen_proof_of_bounded_num, verify_proof_of_bounded_num,
+};
+use bulletproofs::r1cs::R1CSProof;
+use bulletproofs::utils::field_elem_from_u64;
+use commitments::pok_vc::ProofG1;
+use rand::rngs::ThreadRng;
+use std::collections::BTreeMap;
+use zkl::spec::IntervalClause;
+
+const TRANSCRIPT_LABEL: &[u8] = b"zkl interval clause";
+
+/// The bound check proof for the committed attribute with the proof of knowledge of its opening
+#[derive(Serialize, Deserialize)]
+pub struct IntervalProof {
+    /// Commitments to the attribute, `attribute - min` and `max - attribute`
+    commitments: Vec<G1>,
+    pok: ProofG1,
+    proof: R1CSProof,
+}
+
+pub(crate) struct IntervalCommitted {
+    commitments: Vec<G1>,
+    opening: OpeningCommitted,
+    proof: R1CSProof,
+}
+
+pub(crate) fn validate(
+    clause: &IntervalClause,
+    attribute_count: u32,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    // Disclosed attributes can be checked by the verifier directly
+    if clause.attr >= attribute_count
+        || clause.min > clause.max
+        || disclosed.contains_key(&clause.attr)
+    {
+        return Err(ZKLError::InvalidProofSpec);
+    }
+    Ok(())
+}
+
+pub(crate) fn commit(
+    clause: &IntervalClause,
+    attributes: &BTreeMap<u32, SignatureMessage>,
+    blindings: &BTreeMap<u32, ProofNonce>,
+) -> Result<IntervalCommitted, ZKLError> {
+    let message = attributes
+        .get(&clause.attr)
+        .ok_or(ZKLError::InvalidWitness)?;
+    let value = decode_integer(message).ok_or(ZKLError::InvalidWitness)?;
+    if value < clause.min || value > clause.max {
+        return Err(ZKLError::InvalidWitness);
+    }
+
+    let bits = bits_for(clause);
+    let gens = Generators::new(2 * bits);
+    let r = FieldElement::random();
+    let (proof, commitments) = gen_proof_of_bounded_num(
+        value,
+        Some(r.clone()),
+        clause.min,
+        clause.max,
+        bits,
+        None::<&mut ThreadRng>,
+        TRANSCRIPT_LABEL,
+        &gens.g,
+        &gens.h,
+        &gens.big_g,
+        &gens.big_h,
+    )
+    .map_err(|_| ZKLError::InvalidWitness)?;
+
+    Ok(IntervalCommitted {
+        commitments,
+        opening: OpeningCommitted::new(field_elem_from_u64(value), r, &blindings[&clause.attr]),
+        proof,
+    })
+}
+
+impl IntervalCommitted {
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = commitment_bytes(&self.commitments);
+        bytes.append(&mut self.opening.to_bytes());
+        bytes
+    }
+
+    pub(crate) fn gen_proof(self, challenge: &ProofChallenge) -> Result<IntervalProof, ZKLError> {
+        Ok(IntervalProof {
+            commitments: self.commitments,
+            pok: self.opening.gen_proof(challenge)?,
+            proof: self.proof,
+        })
+    }
+}
+
+pub(crate) fn challenge_bytes(proof: &IntervalProof) -> Vec<u8> {
+    let mut bytes = commitment_bytes(&proof.commitments);
+    bytes.append(&mut opening_challenge_bytes(&proof.pok));
+    bytes
+}
+
+pub(crate) fn verify(
+    clause: &IntervalClause,
+    proof: &IntervalProof,
+    challenge: &ProofChallenge,
+    responses: &mut BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    if proof.commitments.len() != 3 {
+        return Err(ZKLError::InvalidProof);
+    }
+    verify_opening(
+        &proof.pok,
+        &proof.commitments[0],
+        challenge,
+        clause.attr,
+        responses,
+    )?;
+
+    let bits = bits_for(clause);
+    let gens = Generators::new(2 * bits);
+    verify_proof_of_bounded_num(
+        clause.min,
+        clause.max,
+        bits,
+        proof.proof.clone(),
+        proof.commitments.clone(),
+        TRANSCRIPT_LABEL,
+        &gens.g,
+        &gens.h,
+        &gens.big_g,
+        &gens.big_h,
+    )
+    .map_err(|_| ZKLError::InvalidProof)
+}
+
+/// Number of bits needed for `attribute - min` and `max - attribute`
+fn bits_for(clause: &IntervalClause) -> usize {
+    let bits = 64 - (clause.max - clause.min).leading_zeros() as usize;
+    bits.max(1)
+}
+
+/// Reverse of `encode_attribute` for integers
+pub(crate) fn decode_integer(message: &SignatureMessage) -> Option<u64> {
+    let bytes = message.to_bytes_compressed_form();
+    let (high, low) = bytes.split_at(FR_COMPRESSED_SIZE - 8);
+    if high.iter().any(|b| *b != 0) {
+        return None;
+    }
+    Some(u64::from_be_bytes(*array_ref![low, 0, 8]))
+}
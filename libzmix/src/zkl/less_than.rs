@@ -0,0 +1,191 @@
+// Less than clause: a hidden integer attribute is less than another hidden integer attribute.
+// Both attributes are committed and the R1CS proof shows that they and their difference
+// `other - attr - 1` are all 64 bit values. The difference's commitment is derived from the
+// attribute commitments so the verifier can check it is the one in the R1CS proof.
+
+use super::interval::decode_integer;
+use super::pedersen::{
+    commitment_bytes, opening_challenge_bytes, verify_opening, Generators, OpeningCommitted,
+};
+use super::ZKLError;
+use amcl_wrapper::field_elem::FieldElement;
+use amcl_wrapper::group_elem_g1::G1;
+use bbs::prelude::*;
+use bulletproofs::r1cs::gadgets::bound_check::{prove_bounded_num, verify_bounded_num};
+use bulletproofs::r1cs::{Prover, R1CSProof, Verifier};
+use bulletproofs::utils::field_elem_from_u64;
+use commitments::pok_vc::ProofG1;
+use merlin::Transcript;
+use rand::rngs::ThreadRng;
+use std::collections::BTreeMap;
+use zkl::spec::LessThanClause;
+
+const TRANSCRIPT_LABEL: &[u8] = b"zkl less than clause";
+
+/// Bits in each bounded value
+const BITS: usize = 64;
+
+const MAX: u64 = u64::MAX;
+
+/// The range proofs for the committed attributes and their difference with the proofs of
+/// knowledge of the openings of the attribute commitments
+#[derive(Serialize, Deserialize)]
+pub struct LessThanProof {
+    /// Three commitments for each of the attribute, the other attribute and the difference
+    commitments: Vec<G1>,
+    pok: ProofG1,
+    pok_other: ProofG1,
+    proof: R1CSProof,
+}
+
+pub(crate) struct LessThanCommitted {
+    commitments: Vec<G1>,
+    opening: OpeningCommitted,
+    opening_other: OpeningCommitted,
+    proof: R1CSProof,
+}
+
+pub(crate) fn validate(
+    clause: &LessThanClause,
+    attribute_count: u32,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    // Use an interval clause to compare with a disclosed attribute
+    if clause.attr >= attribute_count
+        || clause.other >= attribute_count
+        || clause.attr == clause.other
+        || disclosed.contains_key(&clause.attr)
+        || disclosed.contains_key(&clause.other)
+    {
+        return Err(ZKLError::InvalidProofSpec);
+    }
+    Ok(())
+}
+
+pub(crate) fn commit(
+    clause: &LessThanClause,
+    attributes: &BTreeMap<u32, SignatureMessage>,
+    blindings: &BTreeMap<u32, ProofNonce>,
+) -> Result<LessThanCommitted, ZKLError> {
+    let value = attributes
+        .get(&clause.attr)
+        .and_then(decode_integer)
+        .ok_or(ZKLError::InvalidWitness)?;
+    let other = attributes
+        .get(&clause.other)
+        .and_then(decode_integer)
+        .ok_or(ZKLError::InvalidWitness)?;
+    if value >= other {
+        return Err(ZKLError::InvalidWitness);
+    }
+
+    let gens = Generators::new(multipliers());
+    let r = FieldElement::random();
+    let r_other = FieldElement::random();
+    let mut transcript = Transcript::new(TRANSCRIPT_LABEL);
+    let mut prover = Prover::new(&gens.g, &gens.h, &mut transcript);
+    let mut commitments = Vec::with_capacity(9);
+    for (v, blinding) in [
+        (value, r.clone()),
+        (other, r_other.clone()),
+        (other - value - 1, &r_other - &r),
+    ] {
+        commitments.append(
+            &mut prove_bounded_num(
+                v,
+                Some(blinding),
+                0,
+                MAX,
+                BITS,
+                None::<&mut ThreadRng>,
+                &mut prover,
+            )
+            .map_err(|_| ZKLError::InvalidWitness)?,
+        );
+    }
+    let proof = prover
+        .prove(&gens.big_g, &gens.big_h)
+        .map_err(|_| ZKLError::InvalidWitness)?;
+
+    Ok(LessThanCommitted {
+        commitments,
+        opening: OpeningCommitted::new(field_elem_from_u64(value), r, &blindings[&clause.attr]),
+        opening_other: OpeningCommitted::new(
+            field_elem_from_u64(other),
+            r_other,
+            &blindings[&clause.other],
+        ),
+        proof,
+    })
+}
+
+impl LessThanCommitted {
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = commitment_bytes(&self.commitments);
+        bytes.append(&mut self.opening.to_bytes());
+        bytes.append(&mut self.opening_other.to_bytes());
+        bytes
+    }
+
+    pub(crate) fn gen_proof(self, challenge: &ProofChallenge) -> Result<LessThanProof, ZKLError> {
+        Ok(LessThanProof {
+            commitments: self.commitments,
+            pok: self.opening.gen_proof(challenge)?,
+            pok_other: self.opening_other.gen_proof(challenge)?,
+            proof: self.proof,
+        })
+    }
+}
+
+pub(crate) fn challenge_bytes(proof: &LessThanProof) -> Vec<u8> {
+    let mut bytes = commitment_bytes(&proof.commitments);
+    bytes.append(&mut opening_challenge_bytes(&proof.pok));
+    bytes.append(&mut opening_challenge_bytes(&proof.pok_other));
+    bytes
+}
+
+pub(crate) fn verify(
+    clause: &LessThanClause,
+    proof: &LessThanProof,
+    challenge: &ProofChallenge,
+    responses: &mut BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    if proof.commitments.len() != 9 {
+        return Err(ZKLError::InvalidProof);
+    }
+    let gens = Generators::new(multipliers());
+    // other - attr - 1
+    let difference = &(&proof.commitments[3] - &proof.commitments[0]) - &gens.g;
+    if difference != proof.commitments[6] {
+        return Err(ZKLError::InvalidProof);
+    }
+    verify_opening(
+        &proof.pok,
+        &proof.commitments[0],
+        challenge,
+        clause.attr,
+        responses,
+    )?;
+    verify_opening(
+        &proof.pok_other,
+        &proof.commitments[3],
+        challenge,
+        clause.other,
+        responses,
+    )?;
+
+    let mut transcript = Transcript::new(TRANSCRIPT_LABEL);
+    let mut verifier = Verifier::new(&mut transcript);
+    for commitments in proof.commitments.chunks(3) {
+        verify_bounded_num(0, MAX, BITS, commitments.to_vec(), &mut verifier)
+            .map_err(|_| ZKLError::InvalidProof)?;
+    }
+    verifier
+        .verify(&proof.proof, &gens.g, &gens.h, &gens.big_g, &gens.big_h)
+        .map_err(|_| ZKLError::InvalidProof)
+}
+
+/// Three bound checks with two range proofs each
+fn multipliers() -> usize {
+    3 * 2 * BITS
+}
@@ -1,51 +1,471 @@
 pub mod spec;
 
+mod credential;
+mod interval;
+mod less_than;
+mod not_equal;
+mod nym;
+mod pedersen;
+mod serialization;
+mod set_membership;
+mod verifiable_encryption;
+
+pub use self::interval::IntervalProof;
+pub use self::less_than::LessThanProof;
+pub use self::not_equal::NotEqualProof;
+pub use self::nym::NymProof;
+pub use self::serialization::FORMAT_VERSION;
+pub use self::set_membership::SetMembershipProof;
+pub use self::verifiable_encryption::VerifiableEncryptionProof;
+
+use self::interval::IntervalCommitted;
+use self::less_than::LessThanCommitted;
+use self::not_equal::NotEqualCommitted;
+use self::nym::NymCommitted;
+use self::set_membership::SetMembershipCommitted;
+use self::spec::{ProofSpec, ProofSpecClauseType};
+use self::verifiable_encryption::VerifiableEncryptionCommitted;
+use amcl_wrapper::constants::FieldElement_SIZE;
+use amcl_wrapper::field_elem::FieldElement;
+use bbs::prelude::*;
+use std::collections::btree_map::Entry;
+use std::collections::BTreeMap;
+
+/// Encode an attribute value as a signature message.
+/// Canonical decimal integers are encoded as their value so they can be used with
+/// predicates like intervals, everything else is hashed. Issuers should sign attributes
+/// encoded with this function.
+pub fn encode_attribute(value: &str) -> SignatureMessage {
+    match value.parse::<u64>() {
+        Ok(v) if v.to_string() == value => {
+            let mut bytes = [0u8; FR_COMPRESSED_SIZE];
+            bytes[FR_COMPRESSED_SIZE - 8..].copy_from_slice(&v.to_be_bytes());
+            SignatureMessage::from(bytes)
+        }
+        _ => SignatureMessage::hash(value.as_bytes()),
+    }
+}
+
 pub struct Parser {}
 
-#[allow(unused_variables)]
 impl Parser {
-    pub fn parse(proof_spec: &spec::ProofSpec, witness: &Witness) -> Result<Proof, ZKLError> {
-        unimplemented!();
+    /// Create a proof that satisfies `proof_spec` using the secrets in `witness`
+    pub fn parse(proof_spec: &ProofSpec, witness: &Witness) -> Result<Proof, ZKLError> {
+        let disclosed = disclosed_messages(proof_spec)?;
+
+        // Every hidden attribute gets one blinding that is shared by all clauses using it.
+        // This results in equal responses for equal attributes.
+        let mut blindings: BTreeMap<u32, ProofNonce> = (0..proof_spec.attribute_count)
+            .filter(|i| !disclosed.contains_key(i))
+            .map(|i| (i, ProofNonce::random()))
+            .collect();
+        // Encrypted attributes are proven over the integers and need larger blindings
+        let mut integer_blindings = BTreeMap::new();
+        for clause in &proof_spec.clauses {
+            if let ProofSpecClauseType::VerifiableEncryption(c) = clause {
+                if let Entry::Vacant(e) = integer_blindings.entry(c.attr) {
+                    let (m_tilde, blinding) = verifiable_encryption::blinding()?;
+                    blindings.insert(c.attr, blinding);
+                    e.insert(m_tilde);
+                }
+            }
+        }
+
+        let mut committed = Vec::with_capacity(proof_spec.clauses.len());
+        let mut challenge_bytes = Vec::new();
+        for (i, clause) in proof_spec.clauses.iter().enumerate() {
+            match clause {
+                ProofSpecClauseType::Credential(c) => {
+                    let signature = witness
+                        .credentials
+                        .get(&i)
+                        .ok_or(ZKLError::InvalidWitness)?;
+                    let pok = credential::commit(
+                        c,
+                        signature,
+                        &witness.attributes,
+                        &disclosed,
+                        &blindings,
+                    )?;
+                    challenge_bytes.extend_from_slice(pok.to_bytes().as_slice());
+                    committed.push(CommittedClause::Credential(pok));
+                }
+                ProofSpecClauseType::Interval(c) => {
+                    let committed_interval = interval::commit(c, &witness.attributes, &blindings)?;
+                    challenge_bytes.append(&mut committed_interval.to_bytes());
+                    committed.push(CommittedClause::Interval(committed_interval));
+                }
+                ProofSpecClauseType::SetMembership(c) => {
+                    let committed_set = set_membership::commit(c, &witness.attributes, &blindings)?;
+                    challenge_bytes.append(&mut committed_set.to_bytes());
+                    committed.push(CommittedClause::SetMembership(committed_set));
+                }
+                ProofSpecClauseType::VerifiableEncryption(c) => {
+                    let committed_enc = verifiable_encryption::commit(
+                        c,
+                        &witness.attributes,
+                        &integer_blindings[&c.attr],
+                    )?;
+                    challenge_bytes.append(&mut committed_enc.to_bytes()?);
+                    committed.push(CommittedClause::VerifiableEncryption(committed_enc));
+                }
+                ProofSpecClauseType::Nym(c) => {
+                    let committed_nym = nym::commit(c, &witness.attributes, &blindings)?;
+                    challenge_bytes.append(&mut committed_nym.to_bytes());
+                    committed.push(CommittedClause::Nym(committed_nym));
+                }
+                ProofSpecClauseType::LessThan(c) => {
+                    let committed_lt = less_than::commit(c, &witness.attributes, &blindings)?;
+                    challenge_bytes.append(&mut committed_lt.to_bytes());
+                    committed.push(CommittedClause::LessThan(committed_lt));
+                }
+                ProofSpecClauseType::NotEqual(c) => {
+                    let committed_ne = not_equal::commit(c, &witness.attributes, &blindings)?;
+                    challenge_bytes.append(&mut committed_ne.to_bytes());
+                    committed.push(CommittedClause::NotEqual(committed_ne));
+                }
+            }
+        }
+        challenge_bytes.extend_from_slice(&proof_spec.nonce.to_bytes_uncompressed_form()[..]);
+        let challenge = ProofChallenge::hash(&challenge_bytes);
+
+        let mut clauses = Vec::with_capacity(committed.len());
+        for (c, clause) in committed.into_iter().zip(proof_spec.clauses.iter()) {
+            match (c, clause) {
+                (CommittedClause::Credential(pok), _) => clauses.push(ClauseProof::Credential(
+                    pok.gen_proof(&challenge)
+                        .map_err(|_| ZKLError::InvalidWitness)?,
+                )),
+                (CommittedClause::Interval(i), _) => {
+                    clauses.push(ClauseProof::Interval(i.gen_proof(&challenge)?))
+                }
+                (CommittedClause::SetMembership(m), _) => {
+                    clauses.push(ClauseProof::SetMembership(m.gen_proof(&challenge)?))
+                }
+                (
+                    CommittedClause::VerifiableEncryption(e),
+                    ProofSpecClauseType::VerifiableEncryption(c),
+                ) => clauses.push(ClauseProof::VerifiableEncryption(
+                    e.gen_proof(&challenge, c)?,
+                )),
+                (CommittedClause::Nym(n), _) => {
+                    clauses.push(ClauseProof::Nym(n.gen_proof(&challenge)?))
+                }
+                (CommittedClause::LessThan(l), _) => {
+                    clauses.push(ClauseProof::LessThan(l.gen_proof(&challenge)?))
+                }
+                (CommittedClause::NotEqual(n), _) => {
+                    clauses.push(ClauseProof::NotEqual(n.gen_proof(&challenge)?))
+                }
+                _ => return Err(ZKLError::InvalidProofSpec),
+            }
+        }
+        Ok(Proof { clauses })
     }
 }
 
-pub struct Witness {}
+/// The secrets used to create a proof: the attribute values and the credentials
+pub struct Witness {
+    attributes: BTreeMap<u32, SignatureMessage>,
+    credentials: BTreeMap<usize, Signature>,
+}
 
-pub struct WitnessBuilder {}
+pub struct WitnessBuilder {
+    attributes: BTreeMap<u32, SignatureMessage>,
+    credentials: BTreeMap<usize, Signature>,
+}
 
 impl WitnessBuilder {
     pub fn new() -> WitnessBuilder {
-        WitnessBuilder {}
+        WitnessBuilder {
+            attributes: BTreeMap::new(),
+            credentials: BTreeMap::new(),
+        }
+    }
+
+    /// Set the value of attribute `index`, encoded with `encode_attribute`
+    pub fn add_attribute(&mut self, index: u32, value: &str) {
+        self.attributes.insert(index, encode_attribute(value));
+    }
+
+    /// Set the value of attribute `index` when it is not a string, like a link secret
+    pub fn add_attribute_message(&mut self, index: u32, value: SignatureMessage) {
+        self.attributes.insert(index, value);
+    }
+
+    /// Set the signature for the credential clause at `clause_index` in the proof spec
+    pub fn add_credential(&mut self, clause_index: usize, signature: Signature) {
+        self.credentials.insert(clause_index, signature);
+    }
+
+    pub fn finalize(self) -> Witness {
+        Witness {
+            attributes: self.attributes,
+            credentials: self.credentials,
+        }
+    }
+}
+
+impl Default for WitnessBuilder {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
-pub struct Proof {}
+#[derive(Serialize, Deserialize)]
+pub struct Proof {
+    clauses: Vec<ClauseProof>,
+}
+
+/// The proof for each clause in the proof spec, in the same order
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", content = "proof", rename_all = "snake_case")]
+pub enum ClauseProof {
+    Credential(#[serde(with = "serialization::compressed")] PoKOfSignatureProof),
+    Interval(IntervalProof),
+    #[serde(rename = "set")]
+    SetMembership(SetMembershipProof),
+    #[serde(rename = "verifiable_enc")]
+    VerifiableEncryption(VerifiableEncryptionProof),
+    Nym(NymProof),
+    LessThan(LessThanProof),
+    NotEqual(NotEqualProof),
+}
+
+#[allow(clippy::large_enum_variant)]
+enum CommittedClause {
+    Credential(PoKOfSignature),
+    Interval(IntervalCommitted),
+    SetMembership(SetMembershipCommitted),
+    VerifiableEncryption(VerifiableEncryptionCommitted),
+    Nym(NymCommitted),
+    LessThan(LessThanCommitted),
+    NotEqual(NotEqualCommitted),
+}
 
-#[allow(unused_variables)]
 impl Proof {
-    pub fn verify(&self, proof_spec: &spec::ProofSpec) -> Result<bool, ZKLError> {
-        unimplemented!();
+    /// The proof for each clause, like the ciphertext of a verifiable encryption clause
+    pub fn clauses(&self) -> &[ClauseProof] {
+        &self.clauses
+    }
+
+    /// Check the proof against `proof_spec`.
+    /// A proof that was not created for `proof_spec` returns `InvalidProofSpec`
+    /// and a proof that fails verification returns `InvalidProof`.
+    pub fn verify(&self, proof_spec: &ProofSpec) -> Result<bool, ZKLError> {
+        let disclosed = disclosed_messages(proof_spec)?;
+        if self.clauses.len() != proof_spec.clauses.len() {
+            return Err(ZKLError::InvalidProofSpec);
+        }
+
+        let mut challenge_bytes = Vec::new();
+        for (clause, proof) in proof_spec.clauses.iter().zip(self.clauses.iter()) {
+            match (clause, proof) {
+                (ProofSpecClauseType::Credential(c), ClauseProof::Credential(p)) => {
+                    challenge_bytes.append(&mut credential::challenge_bytes(c, p, &disclosed))
+                }
+                (ProofSpecClauseType::Interval(_), ClauseProof::Interval(p)) => {
+                    challenge_bytes.append(&mut interval::challenge_bytes(p))
+                }
+                (ProofSpecClauseType::SetMembership(_), ClauseProof::SetMembership(p)) => {
+                    challenge_bytes.append(&mut set_membership::challenge_bytes(p))
+                }
+                (
+                    ProofSpecClauseType::VerifiableEncryption(_),
+                    ClauseProof::VerifiableEncryption(p),
+                ) => challenge_bytes.append(&mut verifiable_encryption::challenge_bytes(p)?),
+                (ProofSpecClauseType::Nym(_), ClauseProof::Nym(p)) => {
+                    challenge_bytes.append(&mut nym::challenge_bytes(p))
+                }
+                (ProofSpecClauseType::LessThan(_), ClauseProof::LessThan(p)) => {
+                    challenge_bytes.append(&mut less_than::challenge_bytes(p))
+                }
+                (ProofSpecClauseType::NotEqual(_), ClauseProof::NotEqual(p)) => {
+                    challenge_bytes.append(&mut not_equal::challenge_bytes(p))
+                }
+                _ => return Err(ZKLError::InvalidProofSpec),
+            }
+        }
+        challenge_bytes.extend_from_slice(&proof_spec.nonce.to_bytes_uncompressed_form()[..]);
+        let challenge = ProofChallenge::hash(&challenge_bytes);
+
+        let mut responses = BTreeMap::new();
+        for (clause, proof) in proof_spec.clauses.iter().zip(self.clauses.iter()) {
+            match (clause, proof) {
+                (ProofSpecClauseType::Credential(c), ClauseProof::Credential(p)) => {
+                    credential::verify(c, p, &disclosed, &challenge, &mut responses)?
+                }
+                (ProofSpecClauseType::Interval(c), ClauseProof::Interval(p)) => {
+                    interval::verify(c, p, &challenge, &mut responses)?
+                }
+                (ProofSpecClauseType::SetMembership(c), ClauseProof::SetMembership(p)) => {
+                    set_membership::verify(c, p, &challenge, &mut responses)?
+                }
+                (
+                    ProofSpecClauseType::VerifiableEncryption(c),
+                    ClauseProof::VerifiableEncryption(p),
+                ) => verifiable_encryption::verify(c, p, &challenge, &mut responses)?,
+                (ProofSpecClauseType::Nym(c), ClauseProof::Nym(p)) => {
+                    nym::verify(c, p, &challenge, &mut responses)?
+                }
+                (ProofSpecClauseType::LessThan(c), ClauseProof::LessThan(p)) => {
+                    less_than::verify(c, p, &challenge, &mut responses)?
+                }
+                (ProofSpecClauseType::NotEqual(c), ClauseProof::NotEqual(p)) => {
+                    not_equal::verify(c, p, &challenge, &mut responses)?
+                }
+                _ => return Err(ZKLError::InvalidProofSpec),
+            }
+        }
+        Ok(true)
     }
 }
 
 #[repr(i32)]
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum ZKLError {
     InvalidProofSpec = 1,
     InvalidWitness = 2,
+    InvalidProof = 3,
+}
+
+/// Check the proof spec is well formed and return the encoded disclosed attributes
+fn disclosed_messages(proof_spec: &ProofSpec) -> Result<BTreeMap<u32, SignatureMessage>, ZKLError> {
+    if proof_spec.attribute_count == 0 || proof_spec.clauses.is_empty() {
+        return Err(ZKLError::InvalidProofSpec);
+    }
+    let mut disclosed = BTreeMap::new();
+    for d in &proof_spec.disclosed_attributes {
+        if d.index >= proof_spec.attribute_count
+            || disclosed
+                .insert(d.index, encode_attribute(&d.value))
+                .is_some()
+        {
+            return Err(ZKLError::InvalidProofSpec);
+        }
+    }
+    for clause in &proof_spec.clauses {
+        match clause {
+            ProofSpecClauseType::Credential(c) => {
+                credential::validate(c, proof_spec.attribute_count)?
+            }
+            ProofSpecClauseType::Interval(c) => {
+                interval::validate(c, proof_spec.attribute_count, &disclosed)?
+            }
+            ProofSpecClauseType::SetMembership(c) => {
+                set_membership::validate(c, proof_spec.attribute_count, &disclosed)?
+            }
+            ProofSpecClauseType::VerifiableEncryption(c) => {
+                verifiable_encryption::validate(c, proof_spec.attribute_count, &disclosed)?
+            }
+            ProofSpecClauseType::Nym(c) => {
+                nym::validate(c, proof_spec.attribute_count, &disclosed)?
+            }
+            ProofSpecClauseType::LessThan(c) => {
+                less_than::validate(c, proof_spec.attribute_count, &disclosed)?
+            }
+            ProofSpecClauseType::NotEqual(c) => {
+                not_equal::validate(c, proof_spec.attribute_count, &disclosed)?
+            }
+        }
+    }
+    Ok(disclosed)
+}
+
+/// Hidden attributes with the same index must have the same response in every clause
+fn record_response(
+    responses: &mut BTreeMap<u32, SignatureMessage>,
+    index: u32,
+    response: SignatureMessage,
+) -> Result<(), ZKLError> {
+    match responses.insert(index, response) {
+        Some(r) if r != response => Err(ZKLError::InvalidProof),
+        _ => Ok(()),
+    }
+}
+
+/// Convert a BBS+ scalar to the same value in amcl so clauses built on amcl can share
+/// blindings and challenges with the credential clauses
+fn field_element(bytes: &[u8; FR_COMPRESSED_SIZE]) -> FieldElement {
+    let mut padded = [0u8; FieldElement_SIZE];
+    padded[FieldElement_SIZE - FR_COMPRESSED_SIZE..].copy_from_slice(&bytes[..]);
+    FieldElement::from_bytes(&padded[..]).unwrap()
+}
+
+/// Convert an amcl scalar to the same value as a BBS+ scalar
+fn signature_message(e: &FieldElement) -> SignatureMessage {
+    let bytes = e.to_bytes();
+    SignatureMessage::from(array_ref![
+        bytes,
+        FieldElement_SIZE - FR_COMPRESSED_SIZE,
+        FR_COMPRESSED_SIZE
+    ])
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use zkl::spec::ProofSpecBuilder;
+    use amcl_wrapper::group_elem::GroupElement;
+    use verifiable_encryption::bn::BigNumber;
+    use verifiable_encryption::cs_verifiable_encryption::{decrypt, CSEncPubkey, CSKeypair};
+    use zkl::spec::{
+        CredentialClause, IntervalClause, LessThanClause, NotEqualClause, NymClause,
+        ProofSpecBuilder, SetMembershipClause, VerifiableEncryptionClause,
+    };
+
+    // Attributes 0 and 1 are the holder's name and age, attribute 2 is a link secret
+    fn issue(attrs: &[u32], values: &[SignatureMessage]) -> (CredentialClause, Signature) {
+        let (pk, sk) = Issuer::new_keys(attrs.len()).unwrap();
+        let signature = Issuer::sign(values, &sk, &pk).unwrap();
+        (
+            CredentialClause {
+                attrs: attrs.to_vec(),
+                pk,
+            },
+            signature,
+        )
+    }
+
+    fn credential_spec_and_witness(disclosed_age: &str) -> (ProofSpec, Witness) {
+        let link_secret = Prover::new_link_secret();
+        let (clause, signature) = issue(
+            &[0, 1, 2],
+            &[
+                encode_attribute("Alice"),
+                encode_attribute("21"),
+                link_secret,
+            ],
+        );
+
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_disclosed_attribute(1, disclosed_age);
+        pb.add_clause(ProofSpecClauseType::Credential(clause));
+        pb.set_nonce(ProofNonce::hash(b"credential clause"));
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, "21");
+        wb.add_attribute_message(2, link_secret);
+        wb.add_credential(0, signature);
+        (pb.finalize(), wb.finalize())
+    }
+
+    #[test]
+    fn encode_attribute_values() {
+        let mut expected = [0u8; FR_COMPRESSED_SIZE];
+        expected[FR_COMPRESSED_SIZE - 1] = 21;
+        assert_eq!(encode_attribute("21"), SignatureMessage::from(expected));
+        assert_eq!(encode_attribute("021"), SignatureMessage::hash(b"021"));
+        assert_eq!(encode_attribute("Alice"), SignatureMessage::hash(b"Alice"));
+    }
 
     #[test]
-    #[ignore]
     fn parse_empty_clause() {
         let pb = ProofSpecBuilder::new();
         let proof_spec = pb.finalize();
-        let witness = Witness {};
+        let witness = WitnessBuilder::new().finalize();
         let result = Parser::parse(&proof_spec, &witness);
         assert!(result.is_err());
         if let Err(e) = result {
@@ -54,77 +474,801 @@ mod tests {
     }
 
     #[test]
-    #[ignore]
     fn parse_credential_clause() {
-        //TODO: Add credential clause to the proof_spec
-        let pb = ProofSpecBuilder::new();
-        let proof_spec = pb.finalize();
-        let witness = Witness {};
+        let (proof_spec, witness) = credential_spec_and_witness("21");
         assert!(Parser::parse(&proof_spec, &witness).is_ok());
+
+        // Missing signature
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, "21");
+        assert_eq!(
+            Parser::parse(&proof_spec, &wb.finalize()).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+
+        // The disclosed value is not the signed one
+        let (proof_spec, witness) = credential_spec_and_witness("22");
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+    }
+
+    fn interval_spec_and_witness(value: &str, min: u64, max: u64) -> (ProofSpec, Witness) {
+        let (clause, signature) = issue(
+            &[0, 1],
+            &[encode_attribute("Alice"), encode_attribute(value)],
+        );
+
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(2);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(clause));
+        pb.add_clause(ProofSpecClauseType::Interval(IntervalClause {
+            attr: 1,
+            min,
+            max,
+        }));
+        pb.set_nonce(ProofNonce::hash(b"interval clause"));
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, value);
+        wb.add_credential(0, signature);
+        (pb.finalize(), wb.finalize())
     }
 
     #[test]
-    #[ignore]
     fn parse_interval_clause() {
-        //TODO: Add interval clause to the proof_spec
-        let pb = ProofSpecBuilder::new();
-        let proof_spec = pb.finalize();
-        let witness = Witness {};
+        let (proof_spec, witness) = interval_spec_and_witness("21", 18, 150);
         assert!(Parser::parse(&proof_spec, &witness).is_ok());
+
+        let (proof_spec, witness) = interval_spec_and_witness("21", 21, 21);
+        assert!(Parser::parse(&proof_spec, &witness).is_ok());
+
+        // The attribute is out of range
+        let (proof_spec, witness) = interval_spec_and_witness("21", 25, 150);
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+
+        // Disclosed attributes can't be used
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(2);
+        pb.add_disclosed_attribute(1, "21");
+        pb.add_clause(ProofSpecClauseType::Interval(IntervalClause {
+            attr: 1,
+            min: 18,
+            max: 150,
+        }));
+        assert_eq!(
+            Parser::parse(&pb.finalize(), &witness).err(),
+            Some(ZKLError::InvalidProofSpec)
+        );
     }
 
     #[test]
-    #[ignore]
-    fn parse_set_membership_clause() {
-        //TODO: Add set membership clause to the proof_spec
-        let pb = ProofSpecBuilder::new();
+    fn proof_verify_interval() {
+        let (proof_spec, witness) = interval_spec_and_witness("21", 18, 150);
+        let proof = Parser::parse(&proof_spec, &witness).unwrap();
+        assert!(proof.verify(&proof_spec).unwrap());
+
+        // Different bounds
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(2);
+        pb.add_disclosed_attribute(0, "Alice");
+        for clause in proof_spec.clauses {
+            match clause {
+                ProofSpecClauseType::Interval(c) => {
+                    pb.add_clause(ProofSpecClauseType::Interval(IntervalClause {
+                        min: 22,
+                        ..c
+                    }))
+                }
+                c => pb.add_clause(c),
+            }
+        }
+        pb.set_nonce(proof_spec.nonce);
+        assert_eq!(proof.verify(&pb.finalize()), Err(ZKLError::InvalidProof));
+    }
+
+    #[test]
+    fn proof_verify_interval_large_values() {
+        // Values and bounds above 2^58 don't fit in one limb of the field element
+        for (value, min, max) in &[
+            (1u64 << 59, 0, 1u64 << 60),
+            ((1u64 << 63) + 5, 0, u64::max_value()),
+            (u64::max_value() - 1, 1u64 << 63, u64::max_value()),
+        ] {
+            let (proof_spec, witness) = interval_spec_and_witness(&value.to_string(), *min, *max);
+            let proof = Parser::parse(&proof_spec, &witness).unwrap();
+            assert!(proof.verify(&proof_spec).unwrap());
+        }
+
+        let (proof_spec, witness) =
+            interval_spec_and_witness(&u64::max_value().to_string(), 0, u64::max_value() - 1);
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+    }
+
+    #[test]
+    fn proof_verify_set_membership() {
+        for (set, non_member) in &[
+            (vec!["Norway", "Iceland", "Denmark"], false),
+            (vec!["Norway", "Denmark"], true),
+        ] {
+            let (proof_spec, witness) = set_membership_spec_and_witness(set, *non_member);
+            let proof = Parser::parse(&proof_spec, &witness).unwrap();
+            assert!(proof.verify(&proof_spec).unwrap());
+
+            // Different set of the same size
+            let mut pb = ProofSpecBuilder::new();
+            pb.set_attribute_count(2);
+            pb.add_disclosed_attribute(0, "Alice");
+            for clause in proof_spec.clauses {
+                match clause {
+                    ProofSpecClauseType::SetMembership(c) => {
+                        let mut set = c.set.clone();
+                        set[0] = "Sweden".to_string();
+                        pb.add_clause(ProofSpecClauseType::SetMembership(SetMembershipClause {
+                            set,
+                            ..c
+                        }))
+                    }
+                    c => pb.add_clause(c),
+                }
+            }
+            pb.set_nonce(proof_spec.nonce);
+            assert_eq!(proof.verify(&pb.finalize()), Err(ZKLError::InvalidProof));
+        }
+    }
+
+    #[test]
+    fn proof_verify_interval_fail_attribute_mismatch() {
+        // The bounded value is not the signed attribute
+        let (clause, signature) = issue(
+            &[0, 1],
+            &[encode_attribute("Alice"), encode_attribute("16")],
+        );
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(clause));
+        pb.add_clause(ProofSpecClauseType::Interval(IntervalClause {
+            attr: 2,
+            min: 18,
+            max: 150,
+        }));
         let proof_spec = pb.finalize();
-        let witness = Witness {};
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, "16");
+        wb.add_attribute(2, "21");
+        wb.add_credential(0, signature);
+        let proof = Parser::parse(&proof_spec, &wb.finalize()).unwrap();
+        assert!(proof.verify(&proof_spec).unwrap());
+
+        // Claiming the bound is on the signed attribute is caught by the verifier
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_disclosed_attribute(0, "Alice");
+        for clause in proof_spec.clauses {
+            match clause {
+                ProofSpecClauseType::Interval(c) => {
+                    pb.add_clause(ProofSpecClauseType::Interval(IntervalClause {
+                        attr: 1,
+                        ..c
+                    }))
+                }
+                c => pb.add_clause(c),
+            }
+        }
+        assert_eq!(proof.verify(&pb.finalize()), Err(ZKLError::InvalidProof));
+    }
+
+    // Attribute 1 is the holder's country
+    fn set_membership_spec_and_witness(set: &[&str], non_member: bool) -> (ProofSpec, Witness) {
+        let (clause, signature) = issue(
+            &[0, 1],
+            &[encode_attribute("Alice"), encode_attribute("Iceland")],
+        );
+
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(2);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(clause));
+        pb.add_clause(ProofSpecClauseType::SetMembership(SetMembershipClause {
+            attr: 1,
+            set: set.iter().map(|s| s.to_string()).collect(),
+            non_member,
+        }));
+        pb.set_nonce(ProofNonce::hash(b"set membership clause"));
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, "Iceland");
+        wb.add_credential(0, signature);
+        (pb.finalize(), wb.finalize())
+    }
+
+    #[test]
+    fn parse_set_membership_clause() {
+        let (proof_spec, witness) =
+            set_membership_spec_and_witness(&["Norway", "Iceland", "Denmark"], false);
+        assert!(Parser::parse(&proof_spec, &witness).is_ok());
+
+        let (proof_spec, witness) = set_membership_spec_and_witness(&["Norway", "Denmark"], true);
         assert!(Parser::parse(&proof_spec, &witness).is_ok());
+
+        // The attribute is not in the set
+        let (proof_spec, witness) = set_membership_spec_and_witness(&["Norway", "Denmark"], false);
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+
+        // The attribute is in the set
+        let (proof_spec, witness) = set_membership_spec_and_witness(&["Iceland"], true);
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+
+        // Empty set
+        let (proof_spec, witness) = set_membership_spec_and_witness(&[], true);
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidProofSpec)
+        );
+    }
+
+    // Attribute 1 is the holder's national id, attribute 2 is not signed
+    fn verifiable_encryption_spec_and_witness(
+        pk: CSEncPubkey,
+        encrypted_attr: u32,
+    ) -> (ProofSpec, Witness) {
+        let (clause, signature) = issue(
+            &[0, 1],
+            &[encode_attribute("Alice"), encode_attribute("AB123456")],
+        );
+
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(clause));
+        pb.add_clause(ProofSpecClauseType::VerifiableEncryption(
+            VerifiableEncryptionClause {
+                attr: encrypted_attr,
+                pk,
+                label: "escrow".to_string(),
+            },
+        ));
+        pb.set_nonce(ProofNonce::hash(b"verifiable encryption clause"));
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, "AB123456");
+        wb.add_attribute(2, "CD654321");
+        wb.add_credential(0, signature);
+        (pb.finalize(), wb.finalize())
     }
 
     #[test]
-    #[ignore]
     fn parse_verifiable_encryption_clause() {
-        //TODO: Add verifiable encryption clause to the proof_spec
-        let pb = ProofSpecBuilder::new();
-        let proof_spec = pb.finalize();
-        let witness = Witness {};
-        assert!(Parser::parse(&proof_spec, &witness).is_ok());
+        let CSKeypair { pri_key, pub_key } = CSKeypair::new(1).unwrap();
+        let (proof_spec, witness) = verifiable_encryption_spec_and_witness(pub_key, 1);
+        let proof = Parser::parse(&proof_spec, &witness).unwrap();
+
+        // The auditor recovers the signed attribute
+        let (clause, ciphertext) = match (&proof_spec.clauses[1], &proof.clauses()[1]) {
+            (
+                ProofSpecClauseType::VerifiableEncryption(c),
+                ClauseProof::VerifiableEncryption(p),
+            ) => (c, p.ciphertext()),
+            _ => unreachable!(),
+        };
+        let decrypted = decrypt(clause.label.as_bytes(), ciphertext, &clause.pk, &pri_key).unwrap();
+        let expected =
+            BigNumber::from_bytes(&encode_attribute("AB123456").to_bytes_compressed_form()[..])
+                .unwrap();
+        assert_eq!(decrypted, vec![expected]);
+        assert!(decrypt(b"another policy", ciphertext, &clause.pk, &pri_key).is_err());
+    }
+
+    #[test]
+    fn proof_verify_verifiable_encryption() {
+        // Encrypt the unsigned attribute
+        let (proof_spec, witness) =
+            verifiable_encryption_spec_and_witness(CSKeypair::new(1).unwrap().pub_key, 2);
+        let proof = Parser::parse(&proof_spec, &witness).unwrap();
+        assert!(proof.verify(&proof_spec).unwrap());
+
+        let rebuild = |proof_spec: ProofSpec, attr: u32, label: &str| {
+            let mut pb = ProofSpecBuilder::new();
+            pb.set_attribute_count(3);
+            pb.add_disclosed_attribute(0, "Alice");
+            for clause in proof_spec.clauses {
+                match clause {
+                    ProofSpecClauseType::VerifiableEncryption(c) => pb.add_clause(
+                        ProofSpecClauseType::VerifiableEncryption(VerifiableEncryptionClause {
+                            attr,
+                            label: label.to_string(),
+                            ..c
+                        }),
+                    ),
+                    c => pb.add_clause(c),
+                }
+            }
+            pb.set_nonce(proof_spec.nonce);
+            pb.finalize()
+        };
+
+        // Claiming the ciphertext holds the signed attribute is caught by the verifier
+        let proof_spec = rebuild(proof_spec, 1, "escrow");
+        assert_eq!(proof.verify(&proof_spec), Err(ZKLError::InvalidProof));
+
+        // Different decryption policy
+        let proof_spec = rebuild(proof_spec, 2, "another policy");
+        assert_eq!(proof.verify(&proof_spec), Err(ZKLError::InvalidProof));
+    }
+
+    // Attribute 1 is the link secret, attribute 2 is not signed
+    fn nym_spec_and_witness(
+        link_secret: SignatureMessage,
+        scope: &str,
+        nym_attr: u32,
+    ) -> (ProofSpec, Witness) {
+        let (clause, signature) = issue(&[0, 1], &[encode_attribute("Alice"), link_secret]);
+
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(clause));
+        pb.add_clause(ProofSpecClauseType::Nym(NymClause {
+            attr: nym_attr,
+            scope: scope.to_string(),
+        }));
+        pb.set_nonce(ProofNonce::random());
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute_message(1, link_secret);
+        wb.add_attribute_message(2, Prover::new_link_secret());
+        wb.add_credential(0, signature);
+        (pb.finalize(), wb.finalize())
+    }
+
+    fn pseudonym(proof: &Proof) -> Vec<u8> {
+        match &proof.clauses()[1] {
+            ClauseProof::Nym(p) => p.pseudonym().to_bytes(),
+            _ => unreachable!(),
+        }
     }
 
     #[test]
-    #[ignore]
     fn parse_nym_clause() {
-        //TODO: Add nym clause to the proof_spec
-        let pb = ProofSpecBuilder::new();
-        let proof_spec = pb.finalize();
-        let witness = Witness {};
+        let link_secret = Prover::new_link_secret();
+        let (proof_spec, witness) = nym_spec_and_witness(link_secret, "verifier 1", 1);
+        let proof_1 = Parser::parse(&proof_spec, &witness).unwrap();
+        let proof_2 = Parser::parse(&proof_spec, &witness).unwrap();
+        assert_eq!(pseudonym(&proof_1), pseudonym(&proof_2));
+
+        // Same holder with another verifier
+        let (proof_spec, witness) = nym_spec_and_witness(link_secret, "verifier 2", 1);
+        let proof_3 = Parser::parse(&proof_spec, &witness).unwrap();
+        assert_ne!(pseudonym(&proof_1), pseudonym(&proof_3));
+
+        // Another holder with the same verifier
+        let (proof_spec, witness) =
+            nym_spec_and_witness(Prover::new_link_secret(), "verifier 1", 1);
+        let proof_4 = Parser::parse(&proof_spec, &witness).unwrap();
+        assert_ne!(pseudonym(&proof_1), pseudonym(&proof_4));
+
+        // Empty scope
+        let (proof_spec, witness) = nym_spec_and_witness(link_secret, "", 1);
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidProofSpec)
+        );
+    }
+
+    #[test]
+    fn proof_verify_nym() {
+        let (proof_spec, witness) = nym_spec_and_witness(Prover::new_link_secret(), "verifier", 1);
+        let proof = Parser::parse(&proof_spec, &witness).unwrap();
+        assert!(proof.verify(&proof_spec).unwrap());
+
+        let rebuild = |proof_spec: ProofSpec, attr: u32, scope: &str| {
+            let mut pb = ProofSpecBuilder::new();
+            pb.set_attribute_count(3);
+            pb.add_disclosed_attribute(0, "Alice");
+            for clause in proof_spec.clauses {
+                match clause {
+                    ProofSpecClauseType::Nym(_) => {
+                        pb.add_clause(ProofSpecClauseType::Nym(NymClause {
+                            attr,
+                            scope: scope.to_string(),
+                        }))
+                    }
+                    c => pb.add_clause(c),
+                }
+            }
+            pb.set_nonce(proof_spec.nonce);
+            pb.finalize()
+        };
+
+        // Different scope
+        let proof_spec = rebuild(proof_spec, 1, "another verifier");
+        assert_eq!(proof.verify(&proof_spec), Err(ZKLError::InvalidProof));
+
+        // The pseudonym is derived from a secret that is not in the credential
+        let (proof_spec, witness) = nym_spec_and_witness(Prover::new_link_secret(), "verifier", 2);
+        let proof = Parser::parse(&proof_spec, &witness).unwrap();
+        assert!(proof.verify(&proof_spec).unwrap());
+        let proof_spec = rebuild(proof_spec, 1, "verifier");
+        assert_eq!(proof.verify(&proof_spec), Err(ZKLError::InvalidProof));
+    }
+
+    // Attributes 1 and 2 are the issue and expiry dates
+    fn less_than_spec_and_witness(issued: &str, expires: &str) -> (ProofSpec, Witness) {
+        let (clause, signature) = issue(
+            &[0, 1, 2],
+            &[
+                encode_attribute("Alice"),
+                encode_attribute(issued),
+                encode_attribute(expires),
+            ],
+        );
+
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(clause));
+        pb.add_clause(ProofSpecClauseType::LessThan(LessThanClause {
+            attr: 1,
+            other: 2,
+        }));
+        pb.set_nonce(ProofNonce::hash(b"less than clause"));
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, issued);
+        wb.add_attribute(2, expires);
+        wb.add_credential(0, signature);
+        (pb.finalize(), wb.finalize())
+    }
+
+    #[test]
+    fn parse_less_than_clause() {
+        let (proof_spec, witness) = less_than_spec_and_witness("20200101", "20250101");
+        assert!(Parser::parse(&proof_spec, &witness).is_ok());
+
+        // Equal values
+        let (proof_spec, witness) = less_than_spec_and_witness("20200101", "20200101");
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+
+        // Too large to be encoded as an integer
+        let (proof_spec, witness) = less_than_spec_and_witness("20200101", "18446744073709551616");
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+
+        // Not integers
+        let (proof_spec, witness) = less_than_spec_and_witness("2020-01-01", "2025-01-01");
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+
+        // Comparing an attribute with itself
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_clause(ProofSpecClauseType::LessThan(LessThanClause {
+            attr: 1,
+            other: 1,
+        }));
+        assert_eq!(
+            Parser::parse(&pb.finalize(), &witness).err(),
+            Some(ZKLError::InvalidProofSpec)
+        );
+    }
+
+    #[test]
+    fn proof_verify_less_than() {
+        let (proof_spec, witness) = less_than_spec_and_witness("20200101", "20250101");
+        let proof = Parser::parse(&proof_spec, &witness).unwrap();
+        assert!(proof.verify(&proof_spec).unwrap());
+
+        // Claiming the expiry date is before the issue date
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_disclosed_attribute(0, "Alice");
+        for clause in proof_spec.clauses {
+            match clause {
+                ProofSpecClauseType::LessThan(c) => {
+                    pb.add_clause(ProofSpecClauseType::LessThan(LessThanClause {
+                        attr: c.other,
+                        other: c.attr,
+                    }))
+                }
+                c => pb.add_clause(c),
+            }
+        }
+        pb.set_nonce(proof_spec.nonce);
+        assert_eq!(proof.verify(&pb.finalize()), Err(ZKLError::InvalidProof));
+    }
+
+    #[test]
+    fn proof_verify_less_than_large_values() {
+        let max = u64::MAX.to_string();
+        for (value, other) in [
+            (1u64 << 58, 1u64 << 59),
+            ((1u64 << 63) + 5, u64::MAX),
+            (u64::MAX - 1, u64::MAX),
+        ] {
+            let (proof_spec, witness) =
+                less_than_spec_and_witness(&value.to_string(), &other.to_string());
+            let proof = Parser::parse(&proof_spec, &witness).unwrap();
+            assert!(proof.verify(&proof_spec).unwrap());
+        }
+        let (proof_spec, witness) = less_than_spec_and_witness(&max, &max);
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+    }
+
+    // Attribute 1 is the holder's country
+    fn not_equal_spec_and_witness(value: &str) -> (ProofSpec, Witness) {
+        let (clause, signature) = issue(
+            &[0, 1],
+            &[encode_attribute("Alice"), encode_attribute("Iceland")],
+        );
+
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(2);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(clause));
+        pb.add_clause(ProofSpecClauseType::NotEqual(NotEqualClause {
+            attr: 1,
+            value: value.to_string(),
+        }));
+        pb.set_nonce(ProofNonce::hash(b"not equal clause"));
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, "Iceland");
+        wb.add_credential(0, signature);
+        (pb.finalize(), wb.finalize())
+    }
+
+    #[test]
+    fn parse_not_equal_clause() {
+        let (proof_spec, witness) = not_equal_spec_and_witness("Norway");
         assert!(Parser::parse(&proof_spec, &witness).is_ok());
+
+        // The attribute is the value
+        let (proof_spec, witness) = not_equal_spec_and_witness("Iceland");
+        assert_eq!(
+            Parser::parse(&proof_spec, &witness).err(),
+            Some(ZKLError::InvalidWitness)
+        );
+    }
+
+    #[test]
+    fn proof_verify_not_equal() {
+        let (proof_spec, witness) = not_equal_spec_and_witness("Norway");
+        let proof = Parser::parse(&proof_spec, &witness).unwrap();
+        assert!(proof.verify(&proof_spec).unwrap());
+
+        // Claiming the attribute is not the signed value
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(2);
+        pb.add_disclosed_attribute(0, "Alice");
+        for clause in proof_spec.clauses {
+            match clause {
+                ProofSpecClauseType::NotEqual(c) => {
+                    pb.add_clause(ProofSpecClauseType::NotEqual(NotEqualClause {
+                        value: "Iceland".to_string(),
+                        ..c
+                    }))
+                }
+                c => pb.add_clause(c),
+            }
+        }
+        pb.set_nonce(proof_spec.nonce);
+        assert_eq!(proof.verify(&pb.finalize()), Err(ZKLError::InvalidProof));
     }
 
     #[test]
-    #[ignore]
     fn proof_verify() {
-        let pb = ProofSpecBuilder::new();
-        let proof_spec = pb.finalize();
-        let witness = Witness {};
+        let (proof_spec, witness) = credential_spec_and_witness("21");
         let proof = Parser::parse(&proof_spec, &witness).unwrap();
 
         assert!(proof.verify(&proof_spec).is_ok());
     }
 
     #[test]
-    #[ignore]
     fn proof_verify_fail_attribute_mismatch() {
-        let pb = ProofSpecBuilder::new();
-        let proof_spec = pb.finalize();
-        let witness = Witness {};
+        let (proof_spec, witness) = credential_spec_and_witness("21");
         let proof = Parser::parse(&proof_spec, &witness).unwrap();
 
         //Different proof spec
-        let pb = ProofSpecBuilder::new();
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_disclosed_attribute(1, "22");
+        for clause in proof_spec.clauses {
+            pb.add_clause(clause);
+        }
+        pb.set_nonce(proof_spec.nonce);
         let proof_spec = pb.finalize();
         assert!(proof.verify(&proof_spec).is_err());
     }
+
+    #[test]
+    fn proof_verify_fail_nonce_mismatch() {
+        let (mut proof_spec, witness) = credential_spec_and_witness("21");
+        let proof = Parser::parse(&proof_spec, &witness).unwrap();
+
+        proof_spec.nonce = ProofNonce::hash(b"another verifier");
+        assert_eq!(proof.verify(&proof_spec), Err(ZKLError::InvalidProof));
+    }
+
+    #[test]
+    fn proof_verify_equal_hidden_attributes() {
+        // Two credentials from different issuers share the link secret, attribute 2
+        let link_secret = Prover::new_link_secret();
+        let (clause_1, signature_1) = issue(&[0, 2], &[encode_attribute("Alice"), link_secret]);
+        let (clause_2, signature_2) = issue(&[2, 1], &[link_secret, encode_attribute("21")]);
+
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(3);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(clause_1));
+        pb.add_clause(ProofSpecClauseType::Credential(clause_2));
+        let proof_spec = pb.finalize();
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, "21");
+        wb.add_attribute_message(2, link_secret);
+        wb.add_credential(0, signature_1.clone());
+        wb.add_credential(1, signature_2);
+        let proof = Parser::parse(&proof_spec, &wb.finalize()).unwrap();
+        assert!(proof.verify(&proof_spec).unwrap());
+
+        // The second credential was issued to a different link secret
+        let other_secret = Prover::new_link_secret();
+        let (clause_3, signature_3) = issue(&[3, 1], &[other_secret, encode_attribute("21")]);
+        let pk_1 = match &proof_spec.clauses[0] {
+            ProofSpecClauseType::Credential(c) => c.pk.clone(),
+            _ => unreachable!(),
+        };
+        let pk_3 = clause_3.pk.clone();
+
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(4);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(CredentialClause {
+            attrs: vec![0, 2],
+            pk: pk_1.clone(),
+        }));
+        pb.add_clause(ProofSpecClauseType::Credential(clause_3));
+        let proof_spec = pb.finalize();
+
+        let mut wb = WitnessBuilder::new();
+        wb.add_attribute(0, "Alice");
+        wb.add_attribute(1, "21");
+        wb.add_attribute_message(2, link_secret);
+        wb.add_attribute_message(3, other_secret);
+        wb.add_credential(0, signature_1);
+        wb.add_credential(1, signature_3);
+        let proof = Parser::parse(&proof_spec, &wb.finalize()).unwrap();
+        assert!(proof.verify(&proof_spec).unwrap());
+
+        // Claiming both credentials hold attribute 2 is caught by the verifier
+        let mut pb = ProofSpecBuilder::new();
+        pb.set_attribute_count(4);
+        pb.add_disclosed_attribute(0, "Alice");
+        pb.add_clause(ProofSpecClauseType::Credential(CredentialClause {
+            attrs: vec![0, 2],
+            pk: pk_1,
+        }));
+        pb.add_clause(ProofSpecClauseType::Credential(CredentialClause {
+            attrs: vec![2, 1],
+            pk: pk_3,
+        }));
+        assert_eq!(proof.verify(&pb.finalize()), Err(ZKLError::InvalidProof));
+    }
+
+    /// Encode and decode the proof spec and its proof in both formats and check they still verify
+    fn assert_round_trip(proof_spec: &ProofSpec, proof: &Proof) {
+        let json = proof_spec.to_json().unwrap();
+        let bytes = proof_spec.to_bytes().unwrap();
+        let from_json = ProofSpec::from_json(&json).unwrap();
+        let from_bytes = ProofSpec::from_bytes(&bytes).unwrap();
+        assert_eq!(from_json.to_json().unwrap(), json);
+        assert_eq!(from_bytes.to_bytes().unwrap(), bytes);
+
+        let proof_json = proof.to_json().unwrap();
+        let proof_bytes = proof.to_bytes().unwrap();
+        assert!(Proof::from_json(&proof_json)
+            .unwrap()
+            .verify(&from_json)
+            .unwrap());
+        assert!(Proof::from_bytes(&proof_bytes)
+            .unwrap()
+            .verify(&from_bytes)
+            .unwrap());
+    }
+
+    #[test]
+    fn serialize_proof_spec_and_proof() {
+        let specs = vec![
+            credential_spec_and_witness("21"),
+            interval_spec_and_witness("21", 18, 65),
+            set_membership_spec_and_witness(&["Iceland", "Norway"], false),
+            set_membership_spec_and_witness(&["Sweden", "Norway"], true),
+            verifiable_encryption_spec_and_witness(CSKeypair::new(1).unwrap().pub_key, 1),
+            nym_spec_and_witness(Prover::new_link_secret(), "verifier", 1),
+            less_than_spec_and_witness("20200101", "20250101"),
+            not_equal_spec_and_witness("Norway"),
+        ];
+        for (proof_spec, witness) in specs {
+            let proof = Parser::parse(&proof_spec, &witness).unwrap();
+            assert_round_trip(&proof_spec, &proof);
+        }
+    }
+
+    #[test]
+    fn serialize_proof_spec_json_format() {
+        let (proof_spec, _) = interval_spec_and_witness("21", 18, 65);
+        let json: serde_json::Value = serde_json::from_str(&proof_spec.to_json().unwrap()).unwrap();
+        assert_eq!(json["version"], FORMAT_VERSION);
+        let json = &json["proofSpec"];
+        assert_eq!(json["attributeCount"], 2);
+        assert_eq!(json["disclosed"][0]["value"], "Alice");
+        assert_eq!(json["clauses"][0]["type"], "credential");
+        assert!(json["clauses"][0]["clauseData"]["pk"].is_string());
+        assert_eq!(json["clauses"][1]["type"], "interval");
+        assert_eq!(json["clauses"][1]["clauseData"]["max"], 65);
+        assert_eq!(
+            json["nonce"],
+            hex::encode(&proof_spec.nonce.to_bytes_compressed_form()[..])
+        );
+    }
+
+    #[test]
+    fn deserialize_unknown_version() {
+        let (proof_spec, witness) = credential_spec_and_witness("21");
+        let proof = Parser::parse(&proof_spec, &witness).unwrap();
+
+        let mut json: serde_json::Value =
+            serde_json::from_str(&proof_spec.to_json().unwrap()).unwrap();
+        json["version"] = serde_json::Value::from(FORMAT_VERSION + 1);
+        assert_eq!(
+            ProofSpec::from_json(&json.to_string()).err(),
+            Some(ZKLError::InvalidProofSpec)
+        );
+
+        let mut json: serde_json::Value = serde_json::from_str(&proof.to_json().unwrap()).unwrap();
+        json["version"] = serde_json::Value::from(FORMAT_VERSION + 1);
+        assert_eq!(
+            Proof::from_json(&json.to_string()).err(),
+            Some(ZKLError::InvalidProof)
+        );
+        assert_eq!(
+            Proof::from_bytes(&[0xff, 0x00]).err(),
+            Some(ZKLError::InvalidProof)
+        );
+    }
 }
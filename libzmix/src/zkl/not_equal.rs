@@ -0,0 +1,138 @@
+// Not equal clause: a hidden attribute is not a public value.
+// The R1CS proof shows `attribute - value` is not zero. Its commitment uses the randomness of
+// the attribute commitment so the verifier gets the attribute commitment by adding g^value.
+
+use super::pedersen::{
+    commitment_bytes, opening_challenge_bytes, verify_opening, Generators, OpeningCommitted,
+};
+use super::{encode_attribute, field_element, ZKLError};
+use amcl_wrapper::field_elem::FieldElement;
+use amcl_wrapper::group_elem_g1::G1;
+use bbs::prelude::*;
+use bulletproofs::r1cs::gadgets::non_zero::{
+    gen_proof_of_non_zero_val, verify_proof_of_non_zero_val,
+};
+use bulletproofs::r1cs::R1CSProof;
+use commitments::pok_vc::ProofG1;
+use rand::rngs::ThreadRng;
+use std::collections::BTreeMap;
+use zkl::spec::NotEqualClause;
+
+const TRANSCRIPT_LABEL: &[u8] = b"zkl not equal clause";
+
+/// The gadget uses two multiplication gates
+const MULTIPLIERS: usize = 2;
+
+/// The non-zero proof for the committed difference with the proof of knowledge of the opening
+/// of the attribute commitment
+#[derive(Serialize, Deserialize)]
+pub struct NotEqualProof {
+    /// Commitments to `attribute - value` and its inverse
+    commitments: Vec<G1>,
+    pok: ProofG1,
+    proof: R1CSProof,
+}
+
+pub(crate) struct NotEqualCommitted {
+    commitments: Vec<G1>,
+    opening: OpeningCommitted,
+    proof: R1CSProof,
+}
+
+pub(crate) fn validate(
+    clause: &NotEqualClause,
+    attribute_count: u32,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    // Disclosed attributes can be checked by the verifier directly
+    if clause.attr >= attribute_count || disclosed.contains_key(&clause.attr) {
+        return Err(ZKLError::InvalidProofSpec);
+    }
+    Ok(())
+}
+
+pub(crate) fn commit(
+    clause: &NotEqualClause,
+    attributes: &BTreeMap<u32, SignatureMessage>,
+    blindings: &BTreeMap<u32, ProofNonce>,
+) -> Result<NotEqualCommitted, ZKLError> {
+    let message = attributes
+        .get(&clause.attr)
+        .ok_or(ZKLError::InvalidWitness)?;
+    let value = field_element(&message.to_bytes_compressed_form());
+    let difference = &value - &encode_value(clause);
+    if difference.is_zero() {
+        return Err(ZKLError::InvalidWitness);
+    }
+
+    let gens = Generators::new(MULTIPLIERS);
+    let r = FieldElement::random();
+    let (proof, commitments) = gen_proof_of_non_zero_val(
+        difference,
+        Some(r.clone()),
+        None::<&mut ThreadRng>,
+        TRANSCRIPT_LABEL,
+        &gens.g,
+        &gens.h,
+        &gens.big_g,
+        &gens.big_h,
+    )
+    .map_err(|_| ZKLError::InvalidWitness)?;
+
+    Ok(NotEqualCommitted {
+        commitments,
+        opening: OpeningCommitted::new(value, r, &blindings[&clause.attr]),
+        proof,
+    })
+}
+
+impl NotEqualCommitted {
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = commitment_bytes(&self.commitments);
+        bytes.append(&mut self.opening.to_bytes());
+        bytes
+    }
+
+    pub(crate) fn gen_proof(self, challenge: &ProofChallenge) -> Result<NotEqualProof, ZKLError> {
+        Ok(NotEqualProof {
+            commitments: self.commitments,
+            pok: self.opening.gen_proof(challenge)?,
+            proof: self.proof,
+        })
+    }
+}
+
+pub(crate) fn challenge_bytes(proof: &NotEqualProof) -> Vec<u8> {
+    let mut bytes = commitment_bytes(&proof.commitments);
+    bytes.append(&mut opening_challenge_bytes(&proof.pok));
+    bytes
+}
+
+pub(crate) fn verify(
+    clause: &NotEqualClause,
+    proof: &NotEqualProof,
+    challenge: &ProofChallenge,
+    responses: &mut BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    if proof.commitments.len() != 2 {
+        return Err(ZKLError::InvalidProof);
+    }
+    let gens = Generators::new(MULTIPLIERS);
+    let commitment = &proof.commitments[0] + &(&gens.g * &encode_value(clause));
+    verify_opening(&proof.pok, &commitment, challenge, clause.attr, responses)?;
+
+    verify_proof_of_non_zero_val(
+        proof.proof.clone(),
+        proof.commitments.clone(),
+        TRANSCRIPT_LABEL,
+        &gens.g,
+        &gens.h,
+        &gens.big_g,
+        &gens.big_h,
+    )
+    .map_err(|_| ZKLError::InvalidProof)
+}
+
+fn encode_value(clause: &NotEqualClause) -> FieldElement {
+    field_element(&encode_attribute(&clause.value).to_bytes_compressed_form())
+}
@@ -0,0 +1,131 @@
+// Nym clause: a scope-specific pseudonym derived from a hidden link secret with the
+// Dodis-Yampolskiy PRF, nym = g^{1/(s + x)} where x is derived from the scope.
+// The proof shows knowledge of s such that nym^s = g * nym^{-x}, using the link secret's
+// shared blinding so the verifier can check s is the attribute in the credential clauses.
+
+use super::{field_element, record_response, signature_message, ZKLError};
+use amcl_wrapper::field_elem::FieldElement;
+use amcl_wrapper::group_elem::GroupElement;
+use amcl_wrapper::group_elem_g1::G1;
+use bbs::prelude::*;
+use commitments::pok_vc::{ProofG1, ProverCommittedG1, ProverCommittingG1};
+use prf::dodis_yampolskiy::DodisYampolskiyPRF;
+use std::collections::BTreeMap;
+use zkl::spec::NymClause;
+
+/// The pseudonym and the proof it was derived from the link secret
+#[derive(Serialize, Deserialize)]
+pub struct NymProof {
+    nym: G1,
+    pok: ProofG1,
+}
+
+impl NymProof {
+    /// The pseudonym is the same for every proof from a holder with the same scope
+    pub fn pseudonym(&self) -> &G1 {
+        &self.nym
+    }
+}
+
+pub(crate) struct NymCommitted {
+    nym: G1,
+    pok: ProverCommittedG1,
+    secret: FieldElement,
+}
+
+pub(crate) fn validate(
+    clause: &NymClause,
+    attribute_count: u32,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    if clause.attr >= attribute_count
+        || clause.scope.is_empty()
+        || disclosed.contains_key(&clause.attr)
+    {
+        return Err(ZKLError::InvalidProofSpec);
+    }
+    Ok(())
+}
+
+pub(crate) fn commit(
+    clause: &NymClause,
+    attributes: &BTreeMap<u32, SignatureMessage>,
+    blindings: &BTreeMap<u32, ProofNonce>,
+) -> Result<NymCommitted, ZKLError> {
+    let link_secret = attributes
+        .get(&clause.attr)
+        .ok_or(ZKLError::InvalidWitness)?;
+    let secret = field_element(&link_secret.to_bytes_compressed_form());
+    let prf = DodisYampolskiyPRF::with_key(secret.clone(), None);
+    let nym = prf
+        .eval(&DodisYampolskiyPRF::input_from_bytes(
+            clause.scope.as_bytes(),
+        ))
+        .ok_or(ZKLError::InvalidWitness)?;
+
+    let mut committing = ProverCommittingG1::new();
+    committing.commit(
+        &nym,
+        Some(&field_element(
+            &blindings[&clause.attr].to_bytes_compressed_form(),
+        )),
+    );
+    Ok(NymCommitted {
+        nym,
+        pok: committing.finish(),
+        secret,
+    })
+}
+
+impl NymCommitted {
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.nym.to_bytes();
+        bytes.append(&mut self.pok.to_bytes());
+        bytes
+    }
+
+    pub(crate) fn gen_proof(self, challenge: &ProofChallenge) -> Result<NymProof, ZKLError> {
+        let challenge = field_element(&challenge.to_bytes_compressed_form());
+        let pok = self
+            .pok
+            .gen_proof(&challenge, &[self.secret])
+            .map_err(|_| ZKLError::InvalidWitness)?;
+        Ok(NymProof { nym: self.nym, pok })
+    }
+}
+
+pub(crate) fn challenge_bytes(proof: &NymProof) -> Vec<u8> {
+    // Same layout as `NymCommitted::to_bytes`
+    let mut bytes = proof.nym.to_bytes();
+    bytes.append(&mut proof.nym.to_bytes());
+    bytes.append(&mut proof.pok.commitment.to_bytes());
+    bytes
+}
+
+pub(crate) fn verify(
+    clause: &NymClause,
+    proof: &NymProof,
+    challenge: &ProofChallenge,
+    responses: &mut BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    if proof.pok.responses.len() != 1 || proof.nym.is_identity() {
+        return Err(ZKLError::InvalidProof);
+    }
+
+    // nym^s = g * nym^{-x}
+    let x = DodisYampolskiyPRF::input_from_bytes(clause.scope.as_bytes());
+    let y = G1::generator() - (&proof.nym * &x);
+    let c = field_element(&challenge.to_bytes_compressed_form());
+    let valid = proof
+        .pok
+        .verify(std::slice::from_ref(&proof.nym), &y, &c)
+        .map_err(|_| ZKLError::InvalidProof)?;
+    if !valid {
+        return Err(ZKLError::InvalidProof);
+    }
+    record_response(
+        responses,
+        clause.attr,
+        signature_message(&proof.pok.responses[0]),
+    )
+}
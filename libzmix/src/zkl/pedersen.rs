@@ -0,0 +1,113 @@
+// Pedersen commitments to hidden attributes for the clauses proven with bulletproofs.
+// A clause commits to its attribute as V = g^m * h^r and its R1CS proof is over V.
+// The proof of knowledge of the opening of V uses the attribute's shared blinding
+// so the response for m must equal the one from the credential clauses.
+
+use super::{field_element, record_response, signature_message, ZKLError};
+use amcl_wrapper::field_elem::FieldElement;
+use amcl_wrapper::group_elem::GroupElement;
+use amcl_wrapper::group_elem_g1::{G1Vector, G1};
+use bbs::prelude::*;
+use bulletproofs::utils::get_generators;
+use commitments::pok_vc::{ProofG1, ProverCommittedG1, ProverCommittingG1};
+use std::collections::BTreeMap;
+
+/// Pedersen generators for the attribute commitment and the vector generators for the R1CS proof
+pub(crate) struct Generators {
+    pub g: G1,
+    pub h: G1,
+    pub big_g: G1Vector,
+    pub big_h: G1Vector,
+}
+
+impl Generators {
+    /// Generators for a constraint system with `multipliers` multiplication gates
+    pub fn new(multipliers: usize) -> Self {
+        let count = multipliers.next_power_of_two();
+        let (g, h) = pedersen_generators();
+        Generators {
+            g,
+            h,
+            big_g: get_generators("zkl G", count).into(),
+            big_h: get_generators("zkl H", count).into(),
+        }
+    }
+}
+
+fn pedersen_generators() -> (G1, G1) {
+    (G1::from_msg_hash(b"zkl g"), G1::from_msg_hash(b"zkl h"))
+}
+
+/// Proof of knowledge of the opening of an attribute commitment
+pub(crate) struct OpeningCommitted {
+    pok: ProverCommittedG1,
+    secrets: [FieldElement; 2],
+}
+
+impl OpeningCommitted {
+    /// `value` and `r` open the commitment, `blinding` is the attribute's shared blinding
+    pub fn new(value: FieldElement, r: FieldElement, blinding: &ProofNonce) -> Self {
+        let (g, h) = pedersen_generators();
+        let mut committing = ProverCommittingG1::new();
+        committing.commit(
+            &g,
+            Some(&field_element(&blinding.to_bytes_compressed_form())),
+        );
+        committing.commit(&h, None);
+        OpeningCommitted {
+            pok: committing.finish(),
+            secrets: [value, r],
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.pok.to_bytes()
+    }
+
+    pub fn gen_proof(self, challenge: &ProofChallenge) -> Result<ProofG1, ZKLError> {
+        let challenge = field_element(&challenge.to_bytes_compressed_form());
+        self.pok
+            .gen_proof(&challenge, &self.secrets)
+            .map_err(|_| ZKLError::InvalidWitness)
+    }
+}
+
+/// Same layout as `OpeningCommitted::to_bytes`
+pub(crate) fn opening_challenge_bytes(pok: &ProofG1) -> Vec<u8> {
+    let (g, h) = pedersen_generators();
+    let mut bytes = g.to_bytes();
+    bytes.append(&mut h.to_bytes());
+    bytes.append(&mut pok.commitment.to_bytes());
+    bytes
+}
+
+/// Check the proof of knowledge of the opening of `commitment` and record the response
+/// for attribute `index`
+pub(crate) fn verify_opening(
+    pok: &ProofG1,
+    commitment: &G1,
+    challenge: &ProofChallenge,
+    index: u32,
+    responses: &mut BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    if pok.responses.len() != 2 {
+        return Err(ZKLError::InvalidProof);
+    }
+    let (g, h) = pedersen_generators();
+    let challenge = field_element(&challenge.to_bytes_compressed_form());
+    let valid = pok
+        .verify(&[g, h], commitment, &challenge)
+        .map_err(|_| ZKLError::InvalidProof)?;
+    if !valid {
+        return Err(ZKLError::InvalidProof);
+    }
+    record_response(responses, index, signature_message(&pok.responses[0]))
+}
+
+pub(crate) fn commitment_bytes(commitments: &[G1]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    for c in commitments {
+        bytes.append(&mut c.to_bytes());
+    }
+    bytes
+}
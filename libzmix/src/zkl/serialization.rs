@@ -0,0 +1,182 @@
+// Versioned encodings of proof specs and proofs for exchanging them across languages.
+// Both wrap the value with the format version, `{"version": 1, "proofSpec": {..}}`, and the
+// proof spec uses the field names from libzmix/docs/zklang_proof_spec.json.
+// JSON is meant for web APIs and the binary encoding is CBOR with the same structure.
+// BBS+ values are hex strings in JSON and byte strings in CBOR.
+
+use super::{Proof, ZKLError};
+use bbs::prelude::*;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_cbor;
+use serde_json;
+use std::fmt;
+use zkl::spec::ProofSpec;
+
+/// Incremented when an encoding changes in a way older readers can't handle
+pub const FORMAT_VERSION: u16 = 1;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ProofSpecEnvelope<T> {
+    version: u16,
+    proof_spec: T,
+}
+
+#[derive(Serialize, Deserialize)]
+struct ProofEnvelope<T> {
+    version: u16,
+    proof: T,
+}
+
+impl ProofSpec {
+    pub fn to_json(&self) -> Result<String, ZKLError> {
+        serde_json::to_string(&ProofSpecEnvelope {
+            version: FORMAT_VERSION,
+            proof_spec: self,
+        })
+        .map_err(|_| ZKLError::InvalidProofSpec)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ZKLError> {
+        let e: ProofSpecEnvelope<ProofSpec> =
+            serde_json::from_str(json).map_err(|_| ZKLError::InvalidProofSpec)?;
+        check_version(e.version).ok_or(ZKLError::InvalidProofSpec)?;
+        Ok(e.proof_spec)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ZKLError> {
+        serde_cbor::to_vec(&ProofSpecEnvelope {
+            version: FORMAT_VERSION,
+            proof_spec: self,
+        })
+        .map_err(|_| ZKLError::InvalidProofSpec)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZKLError> {
+        let e: ProofSpecEnvelope<ProofSpec> =
+            serde_cbor::from_slice(bytes).map_err(|_| ZKLError::InvalidProofSpec)?;
+        check_version(e.version).ok_or(ZKLError::InvalidProofSpec)?;
+        Ok(e.proof_spec)
+    }
+}
+
+impl Proof {
+    pub fn to_json(&self) -> Result<String, ZKLError> {
+        serde_json::to_string(&ProofEnvelope {
+            version: FORMAT_VERSION,
+            proof: self,
+        })
+        .map_err(|_| ZKLError::InvalidProof)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, ZKLError> {
+        let e: ProofEnvelope<Proof> =
+            serde_json::from_str(json).map_err(|_| ZKLError::InvalidProof)?;
+        check_version(e.version).ok_or(ZKLError::InvalidProof)?;
+        Ok(e.proof)
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, ZKLError> {
+        serde_cbor::to_vec(&ProofEnvelope {
+            version: FORMAT_VERSION,
+            proof: self,
+        })
+        .map_err(|_| ZKLError::InvalidProof)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, ZKLError> {
+        let e: ProofEnvelope<Proof> =
+            serde_cbor::from_slice(bytes).map_err(|_| ZKLError::InvalidProof)?;
+        check_version(e.version).ok_or(ZKLError::InvalidProof)?;
+        Ok(e.proof)
+    }
+}
+
+fn check_version(version: u16) -> Option<()> {
+    if version == FORMAT_VERSION {
+        Some(())
+    } else {
+        None
+    }
+}
+
+/// For `#[serde(with)]` on BBS+ types with variable length compressed forms like public keys
+pub(crate) mod compressed {
+    use super::*;
+
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ToVariableLengthBytes,
+        S: Serializer,
+    {
+        write_bytes(&value.to_bytes_compressed_form(), serializer)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: ToVariableLengthBytes<Output = T>,
+        D: Deserializer<'de>,
+    {
+        let bytes = read_bytes(deserializer)?;
+        T::from_bytes_compressed_form(bytes).map_err(|_| D::Error::custom("invalid BBS+ value"))
+    }
+}
+
+/// For `#[serde(with)]` on proof nonces
+pub(crate) mod nonce {
+    use super::*;
+    use std::convert::TryFrom;
+
+    pub fn serialize<S: Serializer>(nonce: &ProofNonce, serializer: S) -> Result<S::Ok, S::Error> {
+        write_bytes(&nonce.to_bytes_compressed_form()[..], serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<ProofNonce, D::Error> {
+        let bytes = read_bytes(deserializer)?;
+        ProofNonce::try_from(bytes.as_slice()).map_err(|_| D::Error::custom("invalid nonce"))
+    }
+}
+
+fn write_bytes<S: Serializer>(bytes: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        serializer.serialize_str(&hex::encode(bytes))
+    } else {
+        serializer.serialize_bytes(bytes)
+    }
+}
+
+fn read_bytes<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+    if deserializer.is_human_readable() {
+        let s = String::deserialize(deserializer)?;
+        hex::decode(s).map_err(D::Error::custom)
+    } else {
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a byte string")
+    }
+
+    fn visit_bytes<E: Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        Ok(v.to_vec())
+    }
+
+    fn visit_byte_buf<E: Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+        Ok(v)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = Vec::new();
+        while let Some(b) = seq.next_element()? {
+            bytes.push(b);
+        }
+        Ok(bytes)
+    }
+}
@@ -0,0 +1,194 @@
+// Set membership clause: a hidden attribute is, or is not, one of the values in a public set.
+// The R1CS set (non-)membership proof is over the attribute commitment. Both gadgets are
+// linear in the size of the set so this is meant for small lists.
+
+use super::pedersen::{
+    commitment_bytes, opening_challenge_bytes, verify_opening, Generators, OpeningCommitted,
+};
+use super::{encode_attribute, field_element, ZKLError};
+use amcl_wrapper::field_elem::FieldElement;
+use amcl_wrapper::group_elem_g1::G1;
+use bbs::prelude::*;
+use bulletproofs::r1cs::gadgets::set_membership::{
+    gen_proof_of_set_membership, verify_proof_of_set_membership,
+};
+use bulletproofs::r1cs::gadgets::set_non_membership::{
+    gen_proof_of_set_non_membership, verify_proof_of_set_non_membership,
+};
+use bulletproofs::r1cs::R1CSProof;
+use commitments::pok_vc::ProofG1;
+use rand::rngs::ThreadRng;
+use std::collections::BTreeMap;
+use zkl::spec::SetMembershipClause;
+
+const MEMBERSHIP_LABEL: &[u8] = b"zkl set membership clause";
+const NON_MEMBERSHIP_LABEL: &[u8] = b"zkl set non-membership clause";
+
+/// The set (non-)membership proof for the committed attribute with the proof of knowledge of its opening
+#[derive(Serialize, Deserialize)]
+pub struct SetMembershipProof {
+    /// Commitments to the attribute followed by the ones used by the gadget
+    commitments: Vec<G1>,
+    pok: ProofG1,
+    proof: R1CSProof,
+}
+
+pub(crate) struct SetMembershipCommitted {
+    commitments: Vec<G1>,
+    opening: OpeningCommitted,
+    proof: R1CSProof,
+}
+
+pub(crate) fn validate(
+    clause: &SetMembershipClause,
+    attribute_count: u32,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    // Disclosed attributes can be checked by the verifier directly
+    if clause.attr >= attribute_count
+        || clause.set.is_empty()
+        || disclosed.contains_key(&clause.attr)
+    {
+        return Err(ZKLError::InvalidProofSpec);
+    }
+    Ok(())
+}
+
+pub(crate) fn commit(
+    clause: &SetMembershipClause,
+    attributes: &BTreeMap<u32, SignatureMessage>,
+    blindings: &BTreeMap<u32, ProofNonce>,
+) -> Result<SetMembershipCommitted, ZKLError> {
+    let message = attributes
+        .get(&clause.attr)
+        .ok_or(ZKLError::InvalidWitness)?;
+    let value = field_element(&message.to_bytes_compressed_form());
+    let set = encode_set(clause);
+    if set.contains(&value) == clause.non_member {
+        return Err(ZKLError::InvalidWitness);
+    }
+
+    let gens = Generators::new(multipliers(clause));
+    let r = FieldElement::random();
+    let (proof, commitments) = if clause.non_member {
+        gen_proof_of_set_non_membership(
+            value.clone(),
+            Some(r.clone()),
+            set.as_slice(),
+            None::<&mut ThreadRng>,
+            NON_MEMBERSHIP_LABEL,
+            &gens.g,
+            &gens.h,
+            &gens.big_g,
+            &gens.big_h,
+        )
+    } else {
+        gen_proof_of_set_membership(
+            value.clone(),
+            Some(r.clone()),
+            set.as_slice(),
+            None::<&mut ThreadRng>,
+            MEMBERSHIP_LABEL,
+            &gens.g,
+            &gens.h,
+            &gens.big_g,
+            &gens.big_h,
+        )
+    }
+    .map_err(|_| ZKLError::InvalidWitness)?;
+
+    Ok(SetMembershipCommitted {
+        commitments,
+        opening: OpeningCommitted::new(value, r, &blindings[&clause.attr]),
+        proof,
+    })
+}
+
+impl SetMembershipCommitted {
+    pub(crate) fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = commitment_bytes(&self.commitments);
+        bytes.append(&mut self.opening.to_bytes());
+        bytes
+    }
+
+    pub(crate) fn gen_proof(
+        self,
+        challenge: &ProofChallenge,
+    ) -> Result<SetMembershipProof, ZKLError> {
+        Ok(SetMembershipProof {
+            commitments: self.commitments,
+            pok: self.opening.gen_proof(challenge)?,
+            proof: self.proof,
+        })
+    }
+}
+
+pub(crate) fn challenge_bytes(proof: &SetMembershipProof) -> Vec<u8> {
+    let mut bytes = commitment_bytes(&proof.commitments);
+    bytes.append(&mut opening_challenge_bytes(&proof.pok));
+    bytes
+}
+
+pub(crate) fn verify(
+    clause: &SetMembershipClause,
+    proof: &SetMembershipProof,
+    challenge: &ProofChallenge,
+    responses: &mut BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    // The attribute plus one commitment per set value, or two for non-membership
+    let per_value = if clause.non_member { 2 } else { 1 };
+    if proof.commitments.len() != 1 + per_value * clause.set.len() {
+        return Err(ZKLError::InvalidProof);
+    }
+    verify_opening(
+        &proof.pok,
+        &proof.commitments[0],
+        challenge,
+        clause.attr,
+        responses,
+    )?;
+
+    let set = encode_set(clause);
+    let gens = Generators::new(multipliers(clause));
+    if clause.non_member {
+        verify_proof_of_set_non_membership(
+            set.as_slice(),
+            proof.proof.clone(),
+            proof.commitments.clone(),
+            NON_MEMBERSHIP_LABEL,
+            &gens.g,
+            &gens.h,
+            &gens.big_g,
+            &gens.big_h,
+        )
+    } else {
+        verify_proof_of_set_membership(
+            set.as_slice(),
+            proof.proof.clone(),
+            proof.commitments.clone(),
+            MEMBERSHIP_LABEL,
+            &gens.g,
+            &gens.h,
+            &gens.big_g,
+            &gens.big_h,
+        )
+    }
+    .map_err(|_| ZKLError::InvalidProof)
+}
+
+fn encode_set(clause: &SetMembershipClause) -> Vec<FieldElement> {
+    clause
+        .set
+        .iter()
+        .map(|v| field_element(&encode_attribute(v).to_bytes_compressed_form()))
+        .collect()
+}
+
+/// Membership uses one multiplication gate per set value, non-membership uses two
+fn multipliers(clause: &SetMembershipClause) -> usize {
+    if clause.non_member {
+        2 * clause.set.len()
+    } else {
+        clause.set.len()
+    }
+}
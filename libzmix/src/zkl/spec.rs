@@ -1,7 +1,17 @@
+use bbs::prelude::{ProofNonce, PublicKey};
+use verifiable_encryption::cs_verifiable_encryption::CSEncPubkey;
+use zkl::serialization;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct ProofSpec {
     pub attribute_count: u32,
+    #[serde(rename = "disclosed", default)]
     pub disclosed_attributes: Vec<DisclosedAttribute>,
     pub clauses: Vec<ProofSpecClauseType>,
+    /// Fresh value from the verifier that is bound into the proof challenge
+    #[serde(with = "serialization::nonce")]
+    pub nonce: ProofNonce,
 }
 
 /// Used for crossing ffi boundary like 'C'
@@ -9,6 +19,7 @@ pub struct ProofSpecBuilder {
     attribute_count: u32,
     disclosed_attributes: Vec<DisclosedAttribute>,
     clauses: Vec<ProofSpecClauseType>,
+    nonce: ProofNonce,
 }
 
 impl ProofSpecBuilder {
@@ -17,6 +28,7 @@ impl ProofSpecBuilder {
             attribute_count: 0,
             disclosed_attributes: Vec::new(),
             clauses: Vec::new(),
+            nonce: ProofNonce::default(),
         }
     }
 
@@ -35,23 +47,119 @@ impl ProofSpecBuilder {
         self.attribute_count = attribute_count;
     }
 
+    pub fn set_nonce(&mut self, nonce: ProofNonce) {
+        self.nonce = nonce;
+    }
+
     pub fn finalize(self) -> ProofSpec {
         ProofSpec {
             attribute_count: self.attribute_count,
             disclosed_attributes: self.disclosed_attributes,
             clauses: self.clauses,
+            nonce: self.nonce,
         }
     }
 }
 
+impl Default for ProofSpecBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", content = "clauseData", rename_all = "snake_case")]
 pub enum ProofSpecClauseType {
-    Credential,
-    Interval,
-    SetMembership,
-    VerifiableEncryption,
-    Nym,
+    Credential(CredentialClause),
+    Interval(IntervalClause),
+    #[serde(rename = "set")]
+    SetMembership(SetMembershipClause),
+    #[serde(rename = "verifiable_enc")]
+    VerifiableEncryption(VerifiableEncryptionClause),
+    Nym(NymClause),
+    LessThan(LessThanClause),
+    NotEqual(NotEqualClause),
+}
+
+/// Proves knowledge of a BBS+ signature over some of the attributes in the proof spec.
+#[derive(Serialize, Deserialize)]
+pub struct CredentialClause {
+    /// The proof spec attribute index for each message in the signature, in signing order.
+    /// Using the same index in more than one clause proves that the values are equal.
+    pub attrs: Vec<u32>,
+    /// The issuer's public key
+    #[serde(with = "serialization::compressed")]
+    pub pk: PublicKey,
+}
+
+/// Proves that a hidden attribute encoded as an integer lies in [min, max]
+/// without revealing it. Put the same attribute index in a credential clause
+/// to prove the value is the one that was signed.
+#[derive(Serialize, Deserialize)]
+pub struct IntervalClause {
+    /// The proof spec attribute index
+    pub attr: u32,
+    pub min: u64,
+    pub max: u64,
+}
+
+/// Proves that a hidden attribute is one of the values in `set`, or that it is none of them,
+/// without revealing it. Proof size and time grow with the size of the set.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SetMembershipClause {
+    /// The proof spec attribute index
+    pub attr: u32,
+    /// The values to check against, encoded with `encode_attribute`
+    pub set: Vec<String>,
+    /// Prove that the attribute is not in `set` instead
+    #[serde(default)]
+    pub non_member: bool,
+}
+
+/// Encrypts a hidden attribute to an auditor with Camenisch-Shoup encryption and proves
+/// that the ciphertext holds the attribute without revealing it.
+#[derive(Serialize, Deserialize)]
+pub struct VerifiableEncryptionClause {
+    /// The proof spec attribute index
+    pub attr: u32,
+    /// The auditor's public key
+    pub pk: CSEncPubkey,
+    /// The decryption policy. The auditor must use the same label to decrypt.
+    pub label: String,
+}
+
+/// Derives a pseudonym for `scope` from a hidden link secret. The same link secret always gives
+/// the same pseudonym for a scope but pseudonyms for different scopes can't be linked.
+#[derive(Serialize, Deserialize)]
+pub struct NymClause {
+    /// The proof spec attribute index of the link secret
+    pub attr: u32,
+    /// Usually identifies the verifier or the context
+    pub scope: String,
+}
+
+/// Proves that a hidden attribute encoded as an integer is less than another hidden
+/// integer attribute without revealing either. Both values must be less than 2^64.
+#[derive(Serialize, Deserialize)]
+pub struct LessThanClause {
+    /// The proof spec attribute index of the smaller value
+    pub attr: u32,
+    /// The proof spec attribute index of the larger value
+    pub other: u32,
+}
+
+/// Proves that a hidden attribute is not `value` without revealing it
+#[derive(Serialize, Deserialize)]
+pub struct NotEqualClause {
+    /// The proof spec attribute index
+    pub attr: u32,
+    /// The value to check against, encoded with `encode_attribute`
+    pub value: String,
 }
 
+#[derive(Serialize, Deserialize)]
 pub struct DisclosedAttribute {
     pub index: u32,
     pub value: String,
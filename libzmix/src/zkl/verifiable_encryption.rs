@@ -0,0 +1,211 @@
+// Verifiable encryption clause: a hidden attribute is encrypted to an auditor with
+// Camenisch-Shoup encryption and the proof shows the ciphertext holds the attribute.
+// The encryption proof works over the integers so its response for the attribute is
+// m_tilde - c*m without reduction. The attribute's blinding m_tilde is chosen large enough
+// to hide c*m, and the BBS+ proof uses m_tilde mod q, so the verifier can reduce this
+// response mod q and compare it with the credential clauses.
+
+use super::{record_response, ZKLError};
+use bbs::prelude::*;
+use std::collections::BTreeMap;
+use verifiable_encryption::bn::BigNumber;
+use verifiable_encryption::cs_verifiable_encryption::{
+    encrypt_and_prove_phase_1, encrypt_and_prove_phase_2, reconstruct_blindings_ciphertext,
+    CSCiphertext,
+};
+use zkl::spec::VerifiableEncryptionClause;
+
+/// Order of the BLS12-381 scalar field
+const CURVE_ORDER: &str =
+    "52435875175126190479447740508185965837690552500527637822603658699938581184513";
+
+/// Bits in an attribute times the challenge plus 128 bits for statistical hiding
+const BLINDING_BITS: usize = 2 * 256 + 128;
+
+/// The ciphertext for the auditor and the proof that it encrypts the attribute
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiableEncryptionProof {
+    ciphertext: CSCiphertext,
+    /// Encryption of the blindings, the commitment of the sigma protocol
+    blindings_ciphertext: CSCiphertext,
+    r_hat: BigNumber,
+    m_hat: BigNumber,
+}
+
+impl VerifiableEncryptionProof {
+    /// The ciphertext the auditor can decrypt to recover the attribute
+    pub fn ciphertext(&self) -> &CSCiphertext {
+        &self.ciphertext
+    }
+}
+
+pub(crate) struct VerifiableEncryptionCommitted {
+    ciphertext: CSCiphertext,
+    blindings_ciphertext: CSCiphertext,
+    m: BigNumber,
+    m_tilde: BigNumber,
+    r: BigNumber,
+    r_tilde: BigNumber,
+}
+
+pub(crate) fn validate(
+    clause: &VerifiableEncryptionClause,
+    attribute_count: u32,
+    disclosed: &BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    if clause.attr >= attribute_count
+        || clause.pk.y1.is_empty()
+        || disclosed.contains_key(&clause.attr)
+    {
+        return Err(ZKLError::InvalidProofSpec);
+    }
+    Ok(())
+}
+
+/// Create a blinding for an encrypted attribute. The first value is used by this clause
+/// and the second, the same value mod q, by every other clause.
+pub(crate) fn blinding() -> Result<(BigNumber, ProofNonce), ZKLError> {
+    let m_tilde = BigNumber::rand(BLINDING_BITS).map_err(|_| ZKLError::InvalidWitness)?;
+    let reduced = reduce(&m_tilde).map_err(|_| ZKLError::InvalidWitness)?;
+    Ok((m_tilde, ProofNonce::from(reduced)))
+}
+
+pub(crate) fn commit(
+    clause: &VerifiableEncryptionClause,
+    attributes: &BTreeMap<u32, SignatureMessage>,
+    m_tilde: &BigNumber,
+) -> Result<VerifiableEncryptionCommitted, ZKLError> {
+    let message = attributes
+        .get(&clause.attr)
+        .ok_or(ZKLError::InvalidWitness)?;
+    let m = BigNumber::from_bytes(&message.to_bytes_compressed_form()[..])
+        .map_err(|_| ZKLError::InvalidWitness)?;
+    let m_tilde = m_tilde.try_clone().map_err(|_| ZKLError::InvalidWitness)?;
+    let messages = [m.try_clone().map_err(|_| ZKLError::InvalidWitness)?];
+    let blindings = [m_tilde.try_clone().map_err(|_| ZKLError::InvalidWitness)?];
+    let (ciphertext, blindings_ciphertext, r, r_tilde) =
+        encrypt_and_prove_phase_1(&messages, &blindings, clause.label.as_bytes(), &clause.pk)
+            .map_err(|_| ZKLError::InvalidWitness)?;
+    Ok(VerifiableEncryptionCommitted {
+        ciphertext,
+        blindings_ciphertext,
+        m,
+        m_tilde,
+        r,
+        r_tilde,
+    })
+}
+
+impl VerifiableEncryptionCommitted {
+    pub(crate) fn to_bytes(&self) -> Result<Vec<u8>, ZKLError> {
+        let mut bytes = ciphertext_bytes(&self.ciphertext).map_err(|_| ZKLError::InvalidWitness)?;
+        bytes.append(
+            &mut ciphertext_bytes(&self.blindings_ciphertext)
+                .map_err(|_| ZKLError::InvalidWitness)?,
+        );
+        Ok(bytes)
+    }
+
+    pub(crate) fn gen_proof(
+        self,
+        challenge: &ProofChallenge,
+        clause: &VerifiableEncryptionClause,
+    ) -> Result<VerifiableEncryptionProof, ZKLError> {
+        let c = BigNumber::from_bytes(&challenge.to_bytes_compressed_form()[..])
+            .map_err(|_| ZKLError::InvalidWitness)?;
+        let r_hat = encrypt_and_prove_phase_2(&self.r, &self.r_tilde, &c, &clause.pk, None)
+            .map_err(|_| ZKLError::InvalidWitness)?;
+        // Over the integers, not mod q
+        let m_hat = self
+            .m
+            .mul(&c, None)
+            .and_then(|cm| self.m_tilde.sub(&cm))
+            .map_err(|_| ZKLError::InvalidWitness)?;
+        Ok(VerifiableEncryptionProof {
+            ciphertext: self.ciphertext,
+            blindings_ciphertext: self.blindings_ciphertext,
+            r_hat,
+            m_hat,
+        })
+    }
+}
+
+pub(crate) fn challenge_bytes(proof: &VerifiableEncryptionProof) -> Result<Vec<u8>, ZKLError> {
+    // Same layout as `VerifiableEncryptionCommitted::to_bytes`
+    let mut bytes = ciphertext_bytes(&proof.ciphertext).map_err(|_| ZKLError::InvalidProof)?;
+    bytes.append(
+        &mut ciphertext_bytes(&proof.blindings_ciphertext).map_err(|_| ZKLError::InvalidProof)?,
+    );
+    Ok(bytes)
+}
+
+pub(crate) fn verify(
+    clause: &VerifiableEncryptionClause,
+    proof: &VerifiableEncryptionProof,
+    challenge: &ProofChallenge,
+    responses: &mut BTreeMap<u32, SignatureMessage>,
+) -> Result<(), ZKLError> {
+    if proof.ciphertext.e.len() != 1 || proof.blindings_ciphertext.e.len() != 1 {
+        return Err(ZKLError::InvalidProof);
+    }
+    // An honest response is never larger than the blinding
+    let m_hat_bits = proof.m_hat.num_bits().map_err(|_| ZKLError::InvalidProof)?;
+    if m_hat_bits as usize > BLINDING_BITS + 1 {
+        return Err(ZKLError::InvalidProof);
+    }
+
+    let c = BigNumber::from_bytes(&challenge.to_bytes_compressed_form()[..])
+        .map_err(|_| ZKLError::InvalidProof)?;
+    let m_hat = proof
+        .m_hat
+        .try_clone()
+        .map_err(|_| ZKLError::InvalidProof)?;
+    let blindings_ciphertext = reconstruct_blindings_ciphertext(
+        &proof.ciphertext,
+        &[m_hat],
+        &proof.r_hat,
+        &c,
+        clause.label.as_bytes(),
+        &clause.pk,
+    )
+    .map_err(|_| ZKLError::InvalidProof)?;
+    if blindings_ciphertext.u != proof.blindings_ciphertext.u
+        || blindings_ciphertext.e != proof.blindings_ciphertext.e
+        || blindings_ciphertext.v != proof.blindings_ciphertext.v
+    {
+        return Err(ZKLError::InvalidProof);
+    }
+
+    let response = reduce(&proof.m_hat).map_err(|_| ZKLError::InvalidProof)?;
+    record_response(responses, clause.attr, SignatureMessage::from(response))
+}
+
+/// `n` mod q as the bytes of a BBS+ scalar
+fn reduce(n: &BigNumber) -> Result<[u8; FR_COMPRESSED_SIZE], ZKLError> {
+    let q = BigNumber::from_dec(CURVE_ORDER).map_err(|_| ZKLError::InvalidProof)?;
+    let bytes = n
+        .modulus(&q, None)
+        .and_then(|r| r.to_bytes())
+        .map_err(|_| ZKLError::InvalidProof)?;
+    let mut out = [0u8; FR_COMPRESSED_SIZE];
+    out[FR_COMPRESSED_SIZE - bytes.len()..].copy_from_slice(bytes.as_slice());
+    Ok(out)
+}
+
+fn ciphertext_bytes(ciphertext: &CSCiphertext) -> Result<Vec<u8>, ZKLError> {
+    let mut bytes = ciphertext
+        .u
+        .to_bytes()
+        .map_err(|_| ZKLError::InvalidProof)?;
+    for e in &ciphertext.e {
+        bytes.append(&mut e.to_bytes().map_err(|_| ZKLError::InvalidProof)?);
+    }
+    bytes.append(
+        &mut ciphertext
+            .v
+            .to_bytes()
+            .map_err(|_| ZKLError::InvalidProof)?,
+    );
+    Ok(bytes)
+}